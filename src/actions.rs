@@ -1,47 +1,251 @@
-use crate::tools::{self, InstallMethod, Tool};
-use crate::versions::{check_latest_versions, is_newer_version};
+use crate::exit::{self, Outcome};
+use crate::http;
+use crate::journal;
+use crate::logging;
+use crate::node::{self, Orphan};
+use crate::notify::{self, Event};
+use crate::output;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+use crate::tools::{self, InstallMethod, Tool, ToolVersion, installed_versions};
+use crate::versions::{
+    self, ai_cli_version, check_latest_versions, is_newer_version, print_version,
+};
 use anyhow::{Context, Result};
 use colored::*;
 use inquire::MultiSelect;
-use std::{
-    fs,
-    io::{self, Write},
-    path::{Path, PathBuf},
-    process::Command,
-};
+use std::{fs, process::Command};
+
+/// Detect installed versions and look up the latest release of every tool
+pub(crate) async fn collect_versions() -> Vec<ToolVersion> {
+    let spinner = output::spinner("Checking installed tools...");
+
+    let mut tools = installed_versions();
+    spinner.finish_and_clear();
+
+    check_latest_versions(&mut tools).await;
+    tools
+}
+
+fn column_widths(tools: &[ToolVersion]) -> (usize, usize) {
+    let label_width = tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    let id_width = tools
+        .iter()
+        .map(|t| t.identifier.as_ref().map(|id| id.len()).unwrap_or(0))
+        .max()
+        .unwrap_or(0);
+    (label_width, id_width)
+}
+
+/// Handle `apps` / `apps list` command
+pub async fn handle_list_command() {
+    let mut tools = collect_versions().await;
+    tools.insert(0, ai_cli_version().await);
+    let editors = tools::ide::installed_versions();
+    let (label_width, id_width) = column_widths(&[tools.as_slice(), &editors].concat());
+    let installed: Vec<_> = tools.iter().filter(|t| t.installed.is_some()).collect();
+    let not_installed: Vec<_> = tools.iter().filter(|t| t.installed.is_none()).collect();
+
+    let all_up_to_date = installed.iter().all(|t| {
+        if let (Some(installed_ver), Some(latest_ver)) = (&t.installed, &t.latest) {
+            installed_ver.contains(latest_ver) || latest_ver.contains(installed_ver)
+        } else {
+            true
+        }
+    });
+
+    if !installed.is_empty() {
+        println!("{}", "Installed:".success().bold());
+        for tool in &installed {
+            print_version(tool, true, label_width, id_width);
+        }
+        if all_up_to_date {
+            println!("\n{}", "✓ All tools are up to date".success());
+        }
+    }
+
+    if !not_installed.is_empty() {
+        if !installed.is_empty() {
+            println!();
+        }
+        println!("{}", "Not Installed:".muted().bold());
+        for tool in &not_installed {
+            print_version(tool, true, label_width, id_width);
+        }
+    }
+
+    if !editors.is_empty() {
+        println!();
+        println!("{}", "Editors and Extensions:".info().bold());
+        for editor in &editors {
+            print_version(editor, false, label_width, id_width);
+        }
+    }
+}
 
-pub async fn handle_install_command(tool_name: Option<&str>) -> Result<()> {
+/// Handle `apps check` command
+pub async fn handle_check_command() -> Outcome {
+    let tools = collect_versions().await;
+    let (label_width, id_width) = column_widths(&tools);
+    println!();
+    for tool in &tools {
+        print_version(tool, true, label_width, id_width);
+    }
+
+    let outdated: Vec<String> = tools
+        .iter()
+        .filter_map(|t| match (&t.installed, &t.latest) {
+            (Some(installed), Some(latest)) if is_newer_version(latest, installed) => {
+                Some(format!("{} {} → {}", t.name, installed, latest))
+            }
+            _ => None,
+        })
+        .collect();
+    // Offline: nothing is known about updates, so don't clear the last report
+    if tools.iter().any(|t| t.latest.is_some()) {
+        notify::send(Event::ToolOutdated, "apps check", &outdated).await;
+    }
+    if !outdated.is_empty() {
+        Outcome::UpdatesAvailable
+    } else {
+        Outcome::Success
+    }
+}
+
+/// `1234567` as `1,234,567`
+pub(crate) fn thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Handle `apps info <tool>` command
+pub async fn handle_info_command(tool_name: &str) -> Result<Outcome> {
+    let tools = tools::catalog();
+    let tool = find_tool(&tools, tool_name).ok_or_else(|| {
+        exit::usage(format!(
+            "Tool '{}' not found. Available tools: {}",
+            tool_name,
+            format_available_tools(&tools)
+        ))
+    })?;
+    fn row(label: &str, value: impl std::fmt::Display) {
+        println!("  {:<18} {}", label, value);
+    }
+
+    println!("{}", tool.name.bold());
+    let installed = installed_versions()
+        .into_iter()
+        .find(|v| v.name == tool.name)
+        .and_then(|v| v.installed);
+    match &installed {
+        Some(version) => row("Installed", version),
+        None => row("Installed", "not installed".muted()),
+    }
+    let method = match &tool.install_method {
+        InstallMethod::Npm(package) => format!("npm: {}", package),
+        InstallMethod::Bootstrap(url) | InstallMethod::Amp(url) => format!("installer: {}", url),
+    };
+    row("Install method", method);
+    if let Some(repo) = &tool.release_repo {
+        row(
+            "Release notes",
+            format!("https://github.com/{}/releases", repo),
+        );
+    }
+    println!();
+
+    let Some(package) = tool.npm_packages().first().copied() else {
+        println!(
+            "{}",
+            "Not published on npm; no download or publish data".muted()
+        );
+        return Ok(Outcome::Success);
+    };
+    let spinner = output::spinner("Fetching package metadata...");
+    let health = versions::get_npm_health(package).await;
+    spinner.finish_and_clear();
+    let health = match health {
+        Ok(health) => health,
+        Err(e) => {
+            println!(
+                "{} Could not fetch {} from npm: {:#}",
+                "✗".error(),
+                package,
+                e
+            );
+            return Ok(Outcome::Failure);
+        }
+    };
+
+    row("npm package", package);
+    let latest = match installed.as_deref() {
+        Some(current) if is_newer_version(&health.latest, current) => {
+            format!("{} (update available)", health.latest.warning())
+        }
+        _ => health.latest,
+    };
+    row("Latest", latest);
+    if let Some(published) = &health.published
+        && let Ok(when) = chrono::DateTime::parse_from_rfc3339(published)
+    {
+        let days = (chrono::Utc::now() - when.to_utc()).num_days();
+        row(
+            "Last published",
+            format!("{} ({} days ago)", when.format("%Y-%m-%d"), days),
+        );
+    }
+    if let Some(downloads) = health.weekly_downloads {
+        row("Weekly downloads", thousands(downloads));
+    }
+    match &health.deprecated {
+        Some(message) => row("Deprecated", format!("yes: {}", message).error()),
+        None => row("Deprecated", "no".success()),
+    }
+    Ok(Outcome::Success)
+}
+
+pub async fn handle_install_command(tool_name: Option<&str>) -> Result<Outcome> {
     let tools = tools::catalog();
 
     if let Some(name) = tool_name {
-        let tool = find_tool(&tools, name).with_context(|| {
-            format!(
+        let tool = find_tool(&tools, name).ok_or_else(|| {
+            exit::usage(format!(
                 "Tool '{}' not found. Available tools: {}",
                 name,
                 format_available_tools(&tools)
-            )
+            ))
         })?;
 
         if tool.is_installed() {
-            println!("{} {} is already installed!", "✓".green(), tool.name);
-            return Ok(());
+            println!("{} {} is already installed!", "✓".success(), tool.name);
+            return Ok(Outcome::NothingToDo);
         }
 
         install_tool(tool).await?;
-        return Ok(());
+        return Ok(Outcome::Success);
     }
 
     let mut uninstalled_tools: Vec<&Tool> = tools.iter().filter(|t| !t.is_installed()).collect();
     let installed_tools: Vec<&Tool> = tools.iter().filter(|t| t.is_installed()).collect();
 
     if uninstalled_tools.is_empty() {
-        println!("{}", "All tools are already installed! ✓".green());
-        return Ok(());
+        println!("{}", "All tools are already installed! ✓".success());
+        return Ok(Outcome::NothingToDo);
     }
 
     uninstalled_tools.sort_by(|a, b| a.name.cmp(&b.name));
 
-    println!("{}", "\nSelect tools to install:".bright_cyan().bold());
+    prompt::require_interactive("pass a tool name, e.g. `ai-cli apps install claude`")?;
+
+    println!("{}", "\nSelect tools to install:".accent().bold());
 
     let options: Vec<String> = uninstalled_tools
         .iter()
@@ -59,9 +263,9 @@ pub async fn handle_install_command(tool_name: Option<&str>) -> Result<()> {
         .collect();
 
     if !installed_tools.is_empty() {
-        println!("\n{}", "Already installed:".bright_black());
+        println!("\n{}", "Already installed:".muted());
         for tool in &installed_tools {
-            println!("  {} {}", "✓".green(), tool.name.bright_black());
+            println!("  {} {}", "✓".success(), tool.name.muted());
         }
         println!();
     }
@@ -72,62 +276,71 @@ pub async fn handle_install_command(tool_name: Option<&str>) -> Result<()> {
 
     match selected {
         Ok(selections) if !selections.is_empty() => {
-            println!("\n{}", "Starting installation...".bright_cyan());
+            println!("\n{}", "Starting installation...".accent());
 
+            let mut failed = 0;
             for selection in selections {
                 if let Some(tool) = uninstalled_tools
                     .iter()
                     .find(|t| selection.starts_with(&t.name))
                     && let Err(e) = install_tool(tool).await
                 {
-                    println!("{} Failed to install {}: {}", "✗".red(), tool.name, e);
+                    println!("{} Failed to install {}: {}", "✗".error(), tool.name, e);
+                    failed += 1;
                 }
             }
 
-            println!("\n{}", "Installation complete!".green().bold());
+            println!("\n{}", "Installation complete!".success().bold());
+            Ok(Outcome::from_failures(failed))
+        }
+        Ok(_) => {
+            println!("{}", "No tools selected.".warning());
+            Ok(Outcome::NothingToDo)
+        }
+        Err(e) => {
+            println!("{} Selection cancelled: {}", "✗".error(), e);
+            Ok(Outcome::NothingToDo)
         }
-        Ok(_) => println!("{}", "No tools selected.".yellow()),
-        Err(e) => println!("{} Selection cancelled: {}", "✗".red(), e),
     }
-
-    Ok(())
 }
 
 pub async fn handle_uninstall_command(
     tool_name: Option<&str>,
     remove_config: bool,
     force: bool,
-) -> Result<()> {
+) -> Result<Outcome> {
     let tools = tools::catalog();
 
     if let Some(name) = tool_name {
-        let tool = find_tool(&tools, name).with_context(|| {
-            format!(
+        let tool = find_tool(&tools, name).ok_or_else(|| {
+            exit::usage(format!(
                 "Tool '{}' not found. Available tools: {}",
                 name,
                 format_available_tools(&tools)
-            )
+            ))
         })?;
 
         if !tool.is_installed() {
-            println!("{} {} is not installed!", "!".yellow(), tool.name);
-            return Ok(());
+            println!("{} {} is not installed!", "!".warning(), tool.name);
+            return Ok(Outcome::NothingToDo);
         }
 
         uninstall_tool(tool, remove_config, force).await?;
-        return Ok(());
+        return Ok(Outcome::Success);
     }
 
     let mut installed_tools: Vec<&Tool> = tools.iter().filter(|t| t.is_installed()).collect();
 
     if installed_tools.is_empty() {
-        println!("{}", "No tools are currently installed.".yellow());
-        return Ok(());
+        println!("{}", "No tools are currently installed.".warning());
+        return Ok(Outcome::NothingToDo);
     }
 
     installed_tools.sort_by(|a, b| a.name.cmp(&b.name));
 
-    println!("{}", "\nSelect tools to uninstall:".bright_cyan().bold());
+    prompt::require_interactive("pass a tool name, e.g. `ai-cli apps uninstall claude`")?;
+
+    println!("{}", "\nSelect tools to uninstall:".accent().bold());
 
     let options: Vec<String> = installed_tools.iter().map(|t| t.name.clone()).collect();
 
@@ -137,26 +350,33 @@ pub async fn handle_uninstall_command(
 
     match selected {
         Ok(selections) if !selections.is_empty() => {
-            println!("\n{}", "Starting uninstallation...".bright_cyan());
+            println!("\n{}", "Starting uninstallation...".accent());
 
+            let mut failed = 0;
             for selection in selections {
                 if let Some(tool) = installed_tools.iter().find(|t| t.name == selection)
                     && let Err(e) = uninstall_tool(tool, remove_config, force).await
                 {
-                    println!("{} Failed to uninstall {}: {}", "✗".red(), tool.name, e);
+                    println!("{} Failed to uninstall {}: {}", "✗".error(), tool.name, e);
+                    failed += 1;
                 }
             }
 
-            println!("\n{}", "Uninstallation complete!".green().bold());
+            println!("\n{}", "Uninstallation complete!".success().bold());
+            Ok(Outcome::from_failures(failed))
+        }
+        Ok(_) => {
+            println!("{}", "No tools selected.".warning());
+            Ok(Outcome::NothingToDo)
+        }
+        Err(e) => {
+            println!("{} Selection cancelled: {}", "✗".error(), e);
+            Ok(Outcome::NothingToDo)
         }
-        Ok(_) => println!("{}", "No tools selected.".yellow()),
-        Err(e) => println!("{} Selection cancelled: {}", "✗".red(), e),
     }
-
-    Ok(())
 }
 
-pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<()> {
+pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<Outcome> {
     let tools = tools::catalog();
 
     let Some(name) = tool_name else {
@@ -181,8 +401,8 @@ pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<()> {
             .collect();
 
         if updates_available.is_empty() {
-            println!("{} All tools are up to date!", "✓".green());
-            return Ok(());
+            println!("{} All tools are up to date!", "✓".success());
+            return Ok(Outcome::NothingToDo);
         }
 
         // List tools with updates
@@ -190,18 +410,17 @@ pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<()> {
         for (tool, installed, latest) in &updates_available {
             println!(
                 "  {} {} {} → {}",
-                "•".cyan(),
+                "•".info(),
                 tool.name,
-                installed.yellow(),
-                latest.bright_blue()
+                installed.warning(),
+                latest.highlight()
             );
         }
         println!();
 
         // Ask for confirmation
-        print!(
-            "{} Update {} {}? [y/N] ",
-            "?".yellow(),
+        let question = format!(
+            "Update {} {}?",
             updates_available.len(),
             if updates_available.len() == 1 {
                 "tool"
@@ -209,14 +428,10 @@ pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<()> {
                 "tools"
             }
         );
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
 
-        if !input.trim().eq_ignore_ascii_case("y") {
+        if !prompt::confirm(&question)? {
             println!("Cancelled.");
-            return Ok(());
+            return Ok(Outcome::NothingToDo);
         }
 
         // Update tools with available updates
@@ -226,51 +441,52 @@ pub async fn handle_upgrade_command(tool_name: Option<&str>) -> Result<()> {
             println!();
         }
 
-        println!("{} All updates complete!", "✓".green());
-        return Ok(());
+        println!("{} All updates complete!", "✓".success());
+        return Ok(Outcome::Success);
     };
 
-    let tool = find_tool(&tools, name).with_context(|| {
-        format!(
+    let tool = find_tool(&tools, name).ok_or_else(|| {
+        exit::usage(format!(
             "Tool '{}' not found. Available tools: {}",
             name,
             format_available_tools(&tools)
-        )
+        ))
     })?;
 
     if !tool.is_installed() {
         println!(
             "{} {} is not installed. Run `ai-cli apps install {}` first.",
-            "!".yellow(),
+            "!".warning(),
             tool.name,
             name
         );
-        return Ok(());
+        return Ok(Outcome::NothingToDo);
     }
 
-    upgrade_tool(tool).await
+    upgrade_tool(tool).await?;
+    Ok(Outcome::Success)
 }
 
-async fn install_tool(tool: &Tool) -> Result<()> {
-    println!("Installing {}...", tool.name.bright_cyan());
+/// Install `tool` with its install method (npm, bootstrap script, ...)
+pub async fn install_tool(tool: &Tool) -> Result<()> {
+    println!("Installing {}...", tool.name.accent());
 
     match &tool.install_method {
         InstallMethod::Bootstrap(url) => {
             run_install_script(url, "bootstrap.sh", "bootstrap script").await?;
-            println!("{} {} installed successfully!", "✓".green(), tool.name);
+            println!("{} {} installed successfully!", "✓".success(), tool.name);
         }
         InstallMethod::Amp(url) => {
             run_install_script(url, "amp_install.sh", "Amp installer").await?;
-            println!("{} {} installed successfully!", "✓".green(), tool.name);
+            println!("{} {} installed successfully!", "✓".success(), tool.name);
         }
         InstallMethod::Npm(package) => {
-            let status = Command::new("npm")
-                .args(["install", "-g", package])
-                .status()
+            let status = logging::run(node::npm_global("install").arg(package))
                 .context("Failed to run npm install")?;
 
             if status.success() {
-                println!("{} {} installed successfully!", "✓".green(), tool.name);
+                journal::record_npm_install(package);
+                println!("{} {} installed successfully!", "✓".success(), tool.name);
             } else {
                 anyhow::bail!("npm install failed for {}", tool.name);
             }
@@ -281,30 +497,24 @@ async fn install_tool(tool: &Tool) -> Result<()> {
 }
 
 async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result<()> {
-    println!("Uninstalling {}...", tool.name.bright_cyan());
+    println!("Uninstalling {}...", tool.name.accent());
 
     match &tool.install_method {
         InstallMethod::Bootstrap(_) => {
-            let home = std::env::var("HOME").context("HOME environment variable not set")?;
+            let home = paths::home();
             let binary_name = tool.binary_name.as_deref().unwrap_or(tool.name.as_str());
 
-            let symlink_path = Path::new(&home)
-                .join(".local")
-                .join("bin")
-                .join(binary_name);
+            let symlink_path = paths::local_bin().join(binary_name);
 
-            let versions_path = Path::new(&home)
+            let versions_path = home
                 .join(".local")
                 .join("share")
                 .join(binary_name)
                 .join("versions");
             let config_dirs: Vec<_> = if tool.config_dirs.is_empty() {
-                vec![Path::new(&home).join(format!(".{}", binary_name))]
+                vec![home.join(format!(".{}", binary_name))]
             } else {
-                tool.config_dirs
-                    .iter()
-                    .map(|dir| Path::new(&home).join(dir))
-                    .collect()
+                tool.config_dirs.iter().map(|dir| home.join(dir)).collect()
             };
             let mut existing_configs: Vec<_> = config_dirs
                 .into_iter()
@@ -313,11 +523,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
 
             let mut removed_items = Vec::new();
             let mut binary_paths = vec![symlink_path];
-            binary_paths.extend(
-                tool.extra_binary_paths
-                    .iter()
-                    .map(|extra| Path::new(&home).join(extra)),
-            );
+            binary_paths.extend(tool.extra_binary_paths.iter().map(|extra| home.join(extra)));
 
             for binary_path in binary_paths {
                 if binary_path.exists() {
@@ -339,28 +545,21 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                 if existing_configs.len() == 1 {
                     println!(
                         "{} Config directory found at: {}",
-                        "→".cyan(),
+                        "→".info(),
                         existing_configs[0].display()
                     );
                 } else {
-                    println!("{} Config directories found:", "→".cyan());
+                    println!("{} Config directories found:", "→".info());
                     for path in &existing_configs {
                         println!("  - {}", path.display());
                     }
                 }
 
                 if remove_config {
-                    let should_remove = if force {
-                        true
-                    } else {
-                        println!(
-                            "{} Remove config directories? (contains settings and history) [y/N]",
-                            "?".yellow()
-                        );
-                        let mut input = String::new();
-                        io::stdin().read_line(&mut input)?;
-                        input.trim().eq_ignore_ascii_case("y")
-                    };
+                    let should_remove = force
+                        || prompt::confirm(
+                            "Remove config directories? (contains settings and history)",
+                        )?;
 
                     if should_remove {
                         for path in existing_configs.drain(..) {
@@ -370,7 +569,7 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                             removed_items.push(format!("config: {}", path.display()));
                         }
                     } else {
-                        println!("{} Keeping config directories", "→".cyan());
+                        println!("{} Keeping config directories", "→".info());
                     }
                 } else {
                     let suffix = if existing_configs.len() > 1 {
@@ -380,29 +579,25 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                     };
                     println!(
                         "{} Keeping config {} (use --remove-config to remove it)",
-                        "→".cyan(),
+                        "→".info(),
                         suffix
                     );
                 }
             }
 
             if removed_items.is_empty() {
-                println!("{} {} not found on system", "!".yellow(), tool.name);
+                println!("{} {} not found on system", "!".warning(), tool.name);
             } else {
-                println!("{} {} uninstalled successfully!", "✓".green(), tool.name);
-                println!("{} Removed:", "→".cyan());
+                println!("{} {} uninstalled successfully!", "✓".success(), tool.name);
+                println!("{} Removed:", "→".info());
                 for item in removed_items {
                     println!("  - {}", item);
                 }
             }
         }
         InstallMethod::Amp(_) => {
-            let home = std::env::var("HOME")
-                .or_else(|_| std::env::var("USERPROFILE"))
-                .context("HOME environment variable not set")?;
-            let home_path = Path::new(&home);
-            let amp_home = home_path.join(".amp");
-            let local_bin = home_path.join(".local").join("bin");
+            let amp_home = paths::home().join(".amp");
+            let local_bin = paths::local_bin();
             let mut removed_items = Vec::new();
 
             for shim in ["amp", "amp.bat"] {
@@ -419,28 +614,13 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                 removed_items.push(format!("AMP_HOME: {}", amp_home.display()));
             }
 
-            let config_home = std::env::var("XDG_CONFIG_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home_path.join(".config"));
-            let data_home = std::env::var("XDG_DATA_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home_path.join(".local").join("share"));
-            let cache_home = std::env::var("XDG_CACHE_HOME")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| home_path.join(".cache"));
+            let config_home = paths::config_home();
+            let data_home = paths::data_home();
+            let cache_home = paths::cache_home();
 
             if remove_config {
-                let should_remove = if force {
-                    true
-                } else {
-                    println!(
-                        "{} Remove Amp config/cache directories? [y/N]",
-                        "?".yellow()
-                    );
-                    let mut input = String::new();
-                    io::stdin().read_line(&mut input)?;
-                    input.trim().eq_ignore_ascii_case("y")
-                };
+                let should_remove =
+                    force || prompt::confirm("Remove Amp config/cache directories?")?;
 
                 if should_remove {
                     for path in [
@@ -463,74 +643,160 @@ async fn uninstall_tool(tool: &Tool, remove_config: bool, force: bool) -> Result
                         }
                     }
                 } else {
-                    println!("{} Keeping Amp config/cache directories", "→".cyan());
+                    println!("{} Keeping Amp config/cache directories", "→".info());
                 }
             } else {
                 println!(
                     "{} Keeping Amp config/cache directories (use --remove-config to delete them)",
-                    "→".cyan()
+                    "→".info()
                 );
             }
 
             if removed_items.is_empty() {
-                println!("{} Amp files not found on system", "!".yellow());
+                println!("{} Amp files not found on system", "!".warning());
             } else {
-                println!("{} {} uninstalled successfully!", "✓".green(), tool.name);
-                println!("{} Removed:", "→".cyan());
+                println!("{} {} uninstalled successfully!", "✓".success(), tool.name);
+                println!("{} Removed:", "→".info());
                 for item in removed_items {
                     println!("  - {}", item);
                 }
                 println!(
                     "{} Remove any PATH entries for ~/.local/bin/amp in your shell rc files.",
-                    "→".cyan()
+                    "→".info()
                 );
             }
         }
         InstallMethod::Npm(package) => {
-            let status = Command::new("npm")
-                .args(["uninstall", "-g", package])
-                .status()
+            let status = logging::run(node::npm_global("uninstall").arg(package))
                 .context("Failed to run npm uninstall")?;
 
             if status.success() {
-                println!("{} {} uninstalled successfully!", "✓".green(), tool.name);
+                println!("{} {} uninstalled successfully!", "✓".success(), tool.name);
             } else {
                 anyhow::bail!("npm uninstall failed for {}", tool.name);
             }
         }
     }
 
+    if let Err(e) = remove_leftover_packages(tool, force) {
+        println!("{} {:#}", "!".warning(), e);
+    }
     Ok(())
 }
 
+/// List `orphans` and remove them with `npm uninstall -g` after asking;
+/// whether they were removed
+fn remove_orphans(orphans: &[Orphan], force: bool) -> Result<bool> {
+    println!("{} Global npm packages no tool uses:", "→".info());
+    for orphan in orphans {
+        println!(
+            "  - {} {}",
+            orphan.package,
+            format!("({}: {})", orphan.tool, orphan.reason).muted()
+        );
+    }
+    if !force && !prompt::confirm("Remove them with `npm uninstall -g`?")? {
+        println!("{} Keeping them", "→".info());
+        return Ok(false);
+    }
+
+    let packages = orphans.iter().map(|o| o.package.as_str());
+    let status = logging::run(node::npm_global("uninstall").args(packages))
+        .context("Failed to run npm uninstall")?;
+    if !status.success() {
+        anyhow::bail!("npm uninstall failed - see output above for details");
+    }
+    println!("{} Removed {} npm package(s)", "✓".success(), orphans.len());
+    Ok(true)
+}
+
+/// Offer to remove npm copies of `tool` left from before it switched installers
+fn remove_leftover_packages(tool: &Tool, force: bool) -> Result<()> {
+    if tool.legacy_npm_packages.is_empty() {
+        return Ok(());
+    }
+    let installed = node::global_packages();
+    let leftovers: Vec<Orphan> = tool
+        .legacy_npm_packages
+        .iter()
+        .filter(|package| installed.contains(package))
+        .map(|package| Orphan {
+            package: package.clone(),
+            tool: tool.name.clone(),
+            reason: "left from an earlier npm install",
+        })
+        .collect();
+    if !leftovers.is_empty() {
+        remove_orphans(&leftovers, force)?;
+    }
+    Ok(())
+}
+
+/// Handle `apps cleanup` command: global npm packages of removed or
+/// reinstalled tools
+pub fn handle_cleanup_command(dry_run: bool) -> Result<Outcome> {
+    let spinner = output::spinner("Checking global npm packages...");
+    let installed = node::global_packages();
+    let tools: Vec<(Tool, bool)> = tools::catalog()
+        .into_iter()
+        .filter(|tool| {
+            tool.npm_packages()
+                .iter()
+                .any(|package| installed.iter().any(|p| p == package))
+        })
+        .map(|tool| {
+            let current = tool.has_current_install();
+            (tool, current)
+        })
+        .collect();
+    let orphans = node::orphaned_packages(&tools, &installed);
+    spinner.finish_and_clear();
+
+    if orphans.is_empty() {
+        println!("{} No leftover global npm packages", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+    if dry_run {
+        for orphan in &orphans {
+            println!(
+                "  - {} {}",
+                orphan.package,
+                format!("({}: {})", orphan.tool, orphan.reason).muted()
+            );
+        }
+        println!("{}", "Dry run: nothing removed".muted());
+        return Ok(Outcome::Success);
+    }
+    Ok(if remove_orphans(&orphans, false)? {
+        Outcome::Success
+    } else {
+        Outcome::NothingToDo
+    })
+}
+
 async fn upgrade_tool(tool: &Tool) -> Result<()> {
-    println!("Upgrading {}...", tool.name.bright_cyan());
+    println!("Upgrading {}...", tool.name.accent());
 
     match &tool.install_method {
         InstallMethod::Amp(_) => {
-            println!("{} Running `amp update`...", "→".cyan());
-            let status = Command::new("amp")
-                .arg("update")
-                .status()
+            println!("{} Running `amp update`...", "→".info());
+            let status = logging::run(Command::new("amp").arg("update"))
                 .context("Failed to run `amp update`")?;
 
             if status.success() {
-                println!("{} {} upgraded successfully!", "✓".green(), tool.name);
+                println!("{} {} upgraded successfully!", "✓".success(), tool.name);
                 Ok(())
             } else {
                 anyhow::bail!("`amp update` failed - see output above for details");
             }
         }
         InstallMethod::Npm(package) => {
-            println!("{} Running `npm install -g {}`...", "→".cyan(), package);
-            let status = Command::new("npm")
-                .args(["install", "-g"])
-                .arg(package)
-                .status()
+            println!("{} Running `npm install -g {}`...", "→".info(), package);
+            let status = logging::run(node::npm_global("install").arg(package))
                 .context("Failed to run npm install")?;
 
             if status.success() {
-                println!("{} {} upgraded successfully!", "✓".green(), tool.name);
+                println!("{} {} upgraded successfully!", "✓".success(), tool.name);
                 Ok(())
             } else {
                 anyhow::bail!("npm install failed for {}", tool.name);
@@ -540,47 +806,41 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
             let binary_name = tool.binary_name.as_deref().unwrap_or("");
 
             if binary_name == "claude" {
-                println!("{} Running `claude update`...", "→".cyan());
-                let status = Command::new("claude")
-                    .arg("update")
-                    .status()
+                println!("{} Running `claude update`...", "→".info());
+                let status = logging::run(Command::new("claude").arg("update"))
                     .context("Failed to run claude update")?;
 
                 if status.success() {
-                    println!("{} {} upgraded successfully!", "✓".green(), tool.name);
+                    println!("{} {} upgraded successfully!", "✓".success(), tool.name);
                     Ok(())
                 } else {
                     anyhow::bail!("claude update failed");
                 }
             } else if binary_name == "cursor-agent" {
-                println!("{} Running `cursor-agent upgrade`...", "→".cyan());
-                let status = Command::new("cursor-agent")
-                    .arg("upgrade")
-                    .status()
+                println!("{} Running `cursor-agent upgrade`...", "→".info());
+                let status = logging::run(Command::new("cursor-agent").arg("upgrade"))
                     .context("Failed to run cursor-agent upgrade")?;
 
                 if status.success() {
-                    println!("{} {} upgraded successfully!", "✓".green(), tool.name);
+                    println!("{} {} upgraded successfully!", "✓".success(), tool.name);
                     Ok(())
                 } else {
                     anyhow::bail!("cursor-agent upgrade failed");
                 }
             } else if binary_name == "opencode" {
-                println!("{} Running `opencode upgrade`...", "→".cyan());
-                let status = Command::new("opencode")
-                    .arg("upgrade")
-                    .status()
+                println!("{} Running `opencode upgrade`...", "→".info());
+                let status = logging::run(Command::new("opencode").arg("upgrade"))
                     .context("Failed to run opencode upgrade")?;
 
                 if status.success() {
-                    println!("{} {} upgraded successfully!", "✓".green(), tool.name);
+                    println!("{} {} upgraded successfully!", "✓".success(), tool.name);
                     Ok(())
                 } else {
                     anyhow::bail!("opencode upgrade failed");
                 }
             } else {
                 run_install_script(url, "bootstrap_upgrade.sh", "bootstrap script").await?;
-                println!("{} {} upgraded successfully!", "✓".green(), tool.name);
+                println!("{} {} upgraded successfully!", "✓".success(), tool.name);
                 Ok(())
             }
         }
@@ -588,11 +848,13 @@ async fn upgrade_tool(tool: &Tool) -> Result<()> {
 }
 
 async fn run_install_script(url: &str, temp_filename: &str, description: &str) -> Result<()> {
-    println!("{} Downloading {}...", "→".cyan(), description);
+    println!("{} Downloading {}...", "→".info(), description);
 
-    let script = reqwest::get(url)
+    let script = http::send(http::client().get(url))
         .await
         .with_context(|| format!("Failed to download {}", description))?
+        .error_for_status()
+        .with_context(|| format!("Failed to download {}", description))?
         .text()
         .await
         .with_context(|| format!("Failed to read {}", description))?;
@@ -609,12 +871,10 @@ async fn run_install_script(url: &str, temp_filename: &str, description: &str) -
         fs::set_permissions(&script_path, perms)?;
     }
 
-    println!("{} Running {}...", "→".cyan(), description);
+    println!("{} Running {}...", "→".info(), description);
     println!();
 
-    let status = Command::new("bash")
-        .arg(&script_path)
-        .status()
+    let status = logging::run(Command::new("bash").arg(&script_path))
         .context("Failed to run install script")?;
 
     let _ = fs::remove_file(&script_path);
@@ -641,7 +901,8 @@ fn format_available_tools(tools: &[Tool]) -> String {
         .join(", ")
 }
 
-fn find_tool<'a>(tools: &'a [Tool], name: &str) -> Option<&'a Tool> {
+/// Find a tool by display name or binary name, case-insensitively
+pub fn find_tool<'a>(tools: &'a [Tool], name: &str) -> Option<&'a Tool> {
     tools.iter().find(|t| {
         t.name.eq_ignore_ascii_case(name)
             || t.binary_name
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+
+use crate::cli;
+use crate::config;
+use crate::paths;
+
+/// Global options that take a value, so the value isn't mistaken for the command
+const VALUE_OPTIONS: &[&str] = &["--log-file", "--config-root"];
+
+/// Expand a user-defined alias from the config file in `args` (including the binary name).
+///
+/// Built-in commands always win over aliases, and expansion happens once so
+/// aliases can't recurse.
+pub fn expand_from_config(args: Vec<String>) -> Vec<String> {
+    let Some(index) = command_index(&args) else {
+        return args;
+    };
+    if cli::builtin_commands().contains(&args[index]) {
+        return args;
+    }
+
+    // The alias table lives under the config root, which isn't parsed yet
+    let root = option_value(&args[..index], "--config-root")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("AI_CLI_HOME").map(PathBuf::from));
+    if paths::set_config_root(root).is_err() {
+        return args;
+    }
+    match config::load() {
+        Ok(config) => expand(args, index, &config.aliases),
+        Err(_) => args,
+    }
+}
+
+fn expand(args: Vec<String>, index: usize, aliases: &BTreeMap<String, String>) -> Vec<String> {
+    let Some(expansion) = aliases.get(&args[index]) else {
+        return args;
+    };
+    let mut expanded = args[..index].to_vec();
+    expanded.extend(expansion.split_whitespace().map(str::to_string));
+    expanded.extend_from_slice(&args[index + 1..]);
+    expanded
+}
+
+/// Position of the first argument that isn't a global option or its value
+fn command_index(args: &[String]) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if VALUE_OPTIONS.contains(&arg.as_str()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
+        } else {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn option_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == name {
+            args.get(i + 1).map(String::as_str)
+        } else {
+            arg.strip_prefix(name)?.strip_prefix('=')
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn finds_command_after_global_options() {
+        assert_eq!(command_index(&args("ai-cli mcp ls")), Some(1));
+        assert_eq!(
+            command_index(&args("ai-cli -y --log-file x.log up claude")),
+            Some(4)
+        );
+        assert_eq!(command_index(&args("ai-cli --yes")), None);
+    }
+
+    #[test]
+    fn expands_alias_in_place() {
+        let aliases = BTreeMap::from([("up".to_string(), "apps update".to_string())]);
+        assert_eq!(
+            expand(args("ai-cli -y up claude"), 2, &aliases),
+            args("ai-cli -y apps update claude")
+        );
+        assert_eq!(
+            expand(args("ai-cli unknown"), 1, &aliases),
+            args("ai-cli unknown")
+        );
+    }
+
+    #[test]
+    fn reads_config_root_option() {
+        assert_eq!(
+            option_value(&args("ai-cli --config-root /tmp/x"), "--config-root"),
+            Some("/tmp/x")
+        );
+        assert_eq!(
+            option_value(&args("ai-cli --config-root=/tmp/y"), "--config-root"),
+            Some("/tmp/y")
+        );
+    }
+}
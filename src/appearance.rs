@@ -0,0 +1,365 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde_json::{Value, json};
+
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::paths;
+use crate::settings::read_json;
+use crate::theme::Themed;
+
+/// A Claude Code statusline script from the built-in catalog
+///
+/// Claude Code pipes session JSON to the script on stdin and shows the first
+/// line it prints.
+#[derive(Debug, Clone)]
+pub struct Statusline {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub script: &'static str,
+}
+
+const MINIMAL_SCRIPT: &str = r#"#!/usr/bin/env bash
+input=$(cat)
+model=$(echo "$input" | jq -r '.model.display_name')
+dir=$(echo "$input" | jq -r '.workspace.current_dir')
+printf '%s · %s' "$model" "${dir##*/}"
+"#;
+
+const GIT_SCRIPT: &str = r#"#!/usr/bin/env bash
+input=$(cat)
+model=$(echo "$input" | jq -r '.model.display_name')
+dir=$(echo "$input" | jq -r '.workspace.current_dir')
+branch=$(git -C "$dir" branch --show-current 2>/dev/null)
+dirty=""
+if [ -n "$branch" ] && [ -n "$(git -C "$dir" status --porcelain 2>/dev/null)" ]; then
+  dirty="*"
+fi
+printf '%s · %s' "$model" "${dir##*/}"
+[ -n "$branch" ] && printf ' · %s%s' "$branch" "$dirty"
+"#;
+
+const COST_SCRIPT: &str = r#"#!/usr/bin/env bash
+input=$(cat)
+model=$(echo "$input" | jq -r '.model.display_name')
+cost=$(echo "$input" | jq -r '.cost.total_cost_usd // 0')
+added=$(echo "$input" | jq -r '.cost.total_lines_added // 0')
+removed=$(echo "$input" | jq -r '.cost.total_lines_removed // 0')
+printf '%s · $%.2f · +%s -%s' "$model" "$cost" "$added" "$removed"
+"#;
+
+/// Returns all built-in statuslines
+pub fn catalog() -> Vec<Statusline> {
+    vec![
+        Statusline {
+            name: "minimal",
+            description: "model and directory",
+            script: MINIMAL_SCRIPT,
+        },
+        Statusline {
+            name: "git",
+            description: "model, directory and git branch",
+            script: GIT_SCRIPT,
+        },
+        Statusline {
+            name: "cost",
+            description: "model, session cost and lines changed",
+            script: COST_SCRIPT,
+        },
+    ]
+}
+
+fn claude_settings_path() -> PathBuf {
+    paths::home().join(".claude/settings.json")
+}
+
+fn script_path(name: &str) -> PathBuf {
+    paths::home()
+        .join(".claude/statusline")
+        .join(format!("{}.sh", name))
+}
+
+/// Color scheme applied to every agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Dark,
+    Light,
+}
+
+impl Scheme {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Scheme::Dark),
+            "light" => Some(Scheme::Light),
+            _ => None,
+        }
+    }
+}
+
+/// An agent's theme setting and the values it uses for each scheme
+#[derive(Debug, Clone)]
+pub struct ThemeSetting {
+    pub agent: &'static str,
+    /// Exists when the agent is installed
+    pub config_dir: PathBuf,
+    pub path: PathBuf,
+    /// JSON pointer of the setting
+    pub pointer: &'static str,
+    pub dark: &'static str,
+    pub light: &'static str,
+}
+
+impl ThemeSetting {
+    fn value(&self, scheme: Scheme) -> &'static str {
+        match scheme {
+            Scheme::Dark => self.dark,
+            Scheme::Light => self.light,
+        }
+    }
+}
+
+/// Returns the theme settings of agents that have one
+pub fn theme_settings() -> Vec<ThemeSetting> {
+    let home = paths::home();
+    vec![
+        ThemeSetting {
+            agent: "Claude Code",
+            config_dir: home.join(".claude"),
+            path: home.join(".claude.json"),
+            pointer: "/theme",
+            dark: "dark",
+            light: "light",
+        },
+        ThemeSetting {
+            agent: "Gemini CLI",
+            config_dir: home.join(".gemini"),
+            path: home.join(".gemini/settings.json"),
+            pointer: "/ui/theme",
+            dark: "Default",
+            light: "Default Light",
+        },
+    ]
+}
+
+/// Apply `edit` to a JSON settings file, creating it if needed
+fn edit_json(path: &Path, edit: impl FnOnce(&mut Value)) -> Result<()> {
+    let before = fs::read_to_string(path).unwrap_or_default();
+    let mut json = read_json(path)?;
+    edit(&mut json);
+    let after = format!("{}\n", serde_json::to_string_pretty(&json)?);
+
+    logging::write_config(path, &before, &after)
+}
+
+/// Set a string at a JSON pointer, creating intermediate objects
+fn set_pointer(json: &mut Value, pointer: &str, value: &str) {
+    let mut current = json;
+    for part in pointer.trim_start_matches('/').split('/') {
+        if !current.is_object() {
+            *current = json!({});
+        }
+        current = current
+            .as_object_mut()
+            .unwrap()
+            .entry(part)
+            .or_insert(Value::Null);
+    }
+    *current = Value::String(value.to_string());
+}
+
+/// Name of the catalog statusline a command points at
+fn statusline_name(command: &str) -> Option<&'static str> {
+    catalog()
+        .into_iter()
+        .find(|s| script_path(s.name).display().to_string() == command)
+        .map(|s| s.name)
+}
+
+/// Handle `appearance` command: show the current statusline and themes
+pub fn handle_show() -> Result<()> {
+    let settings = read_json(&claude_settings_path())?;
+    let statusline = match settings["statusLine"]["command"].as_str() {
+        Some(command) => match statusline_name(command) {
+            Some(name) => name.bold().to_string(),
+            None => format!("custom {}", command.muted()),
+        },
+        None => "(none)".muted().to_string(),
+    };
+    println!("{} {}", "Claude Code statusline:".bold(), statusline);
+    println!();
+
+    println!("{}", "Themes:".bold());
+    for setting in theme_settings() {
+        if !setting.config_dir.exists() {
+            println!("  {:<12} {}", setting.agent, "not installed".muted());
+            continue;
+        }
+        let json = read_json(&setting.path)?;
+        let value = json
+            .pointer(setting.pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| "(default)".muted().to_string());
+        println!("  {:<12} {}", setting.agent, value);
+    }
+    Ok(())
+}
+
+/// Handle `appearance list` command
+pub fn handle_list() -> Result<()> {
+    println!("{}", "Claude Code statuslines:".bold());
+    for statusline in catalog() {
+        println!(
+            "  {} {:<8} {}",
+            "-".info(),
+            statusline.name,
+            statusline.description.muted()
+        );
+    }
+    println!();
+    println!(
+        "{} Install one with `ai-cli appearance statusline <name>`",
+        "→".info()
+    );
+    Ok(())
+}
+
+/// Handle `appearance statusline <name|off>` command
+pub fn handle_statusline(name: &str) -> Result<Outcome> {
+    let settings_path = claude_settings_path();
+    if !settings_path.parent().is_some_and(|p| p.exists()) {
+        anyhow::bail!("Claude Code is not installed (no ~/.claude directory)");
+    }
+
+    if name == "off" {
+        edit_json(&settings_path, |json| {
+            if let Some(object) = json.as_object_mut() {
+                object.remove("statusLine");
+            }
+        })?;
+        println!("{} Statusline removed", "✓".success());
+        return Ok(Outcome::Success);
+    }
+
+    let statusline = catalog()
+        .into_iter()
+        .find(|s| s.name == name)
+        .ok_or_else(|| {
+            let names: Vec<_> = catalog().iter().map(|s| s.name).collect();
+            exit::usage(format!(
+                "Unknown statusline '{}'. Available: {}, off",
+                name,
+                names.join(", ")
+            ))
+        })?;
+
+    let script = script_path(statusline.name);
+    if let Some(parent) = script.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&script, statusline.script)
+        .with_context(|| format!("Failed to write {}", script.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", script.display()))?;
+    }
+    logging::line(&format!("installed statusline script {}", script.display()));
+
+    edit_json(&settings_path, |json| {
+        if !json.is_object() {
+            *json = json!({});
+        }
+        json["statusLine"] = json!({
+            "type": "command",
+            "command": script.display().to_string(),
+            "padding": 0,
+        });
+    })?;
+
+    println!(
+        "{} Statusline '{}' installed {}",
+        "✓".success(),
+        statusline.name,
+        format!("({})", script.display()).muted()
+    );
+    let has_jq = Command::new("which")
+        .arg("jq")
+        .output()
+        .is_ok_and(|o| o.status.success());
+    if !has_jq {
+        println!(
+            "  {} The script needs `jq`; install it to see the statusline",
+            "!".warning()
+        );
+    }
+    Ok(Outcome::Success)
+}
+
+/// Handle `appearance theme <dark|light>` command
+pub fn handle_theme(scheme: &str) -> Result<Outcome> {
+    let scheme = Scheme::parse(scheme)
+        .ok_or_else(|| exit::usage(format!("Unknown theme '{}'. Use dark or light", scheme)))?;
+
+    let mut failed = 0;
+    let mut changed = 0;
+    for setting in theme_settings() {
+        if !setting.config_dir.exists() {
+            continue;
+        }
+        let value = setting.value(scheme);
+        let result = edit_json(&setting.path, |json| {
+            set_pointer(json, setting.pointer, value)
+        });
+        match result {
+            Ok(()) => {
+                println!("{} {} → {}", "✓".success(), setting.agent, value);
+                changed += 1;
+            }
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), setting.agent, e);
+                failed += 1;
+            }
+        }
+    }
+    if changed == 0 && failed == 0 {
+        println!("No agents with a theme setting are installed");
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_nested_theme_keeping_other_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        fs::write(&path, r#"{"ui":{"hideBanner":true},"mcpServers":{}}"#).unwrap();
+
+        edit_json(&path, |json| {
+            set_pointer(json, "/ui/theme", "Default Light")
+        })
+        .unwrap();
+        let json = read_json(&path).unwrap();
+        assert_eq!(json["ui"]["theme"], "Default Light");
+        assert_eq!(json["ui"]["hideBanner"], true);
+        assert!(json["mcpServers"].is_object());
+    }
+
+    #[test]
+    fn catalog_scripts_read_session_json() {
+        for statusline in catalog() {
+            assert!(statusline.script.starts_with("#!/usr/bin/env bash\n"));
+            assert!(statusline.script.contains(".model.display_name"));
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! `backup`: tar archives of every config file ai-cli knows about.
+//!
+//! Archives hold paths relative to the home directory, so restoring extracts
+//! them back over `~`. Encryption shells out to `age`, like git and npm
+//! elsewhere.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use colored::*;
+
+use crate::commands;
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::keys::{self, KeyFile};
+use crate::logging;
+use crate::mcp::targets;
+use crate::memory::{self, MemoryKind};
+use crate::output_styles;
+use crate::paths;
+use crate::prompt;
+use crate::prompts;
+use crate::prune::format_size;
+use crate::rules;
+use crate::sandbox;
+use crate::skills::agents;
+use crate::theme::Themed;
+
+/// Backups kept when neither `--keep` nor `[backup] keep` is set
+const DEFAULT_KEEP: usize = 10;
+const EXTENSION: &str = ".tar.gz";
+const ENCRYPTED_EXTENSION: &str = ".tar.gz.age";
+
+/// A backup archive
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    /// Creation time, `YYYYMMDD-HHMMSS` (with a `-N` suffix when taken in the same second)
+    pub id: String,
+    pub path: PathBuf,
+    pub encrypted: bool,
+}
+
+pub fn backups_dir() -> PathBuf {
+    paths::data_home().join("ai-cli").join("backups")
+}
+
+/// Config files and directories from every catalog: tool settings, MCP
+/// files, skills, instructions, credentials and ai-cli's own config
+fn config_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = targets::catalog()
+        .iter()
+        .map(|t| t.config_path().to_path_buf())
+        .collect();
+    paths.extend(agents::catalog().into_iter().map(|a| a.skills_path));
+    paths.extend(Scope::Global.targets().into_iter().map(|t| t.path));
+    paths.extend(sandbox::targets(&Scope::Global).into_iter().map(|t| t.path));
+    paths.extend(keys::locations().into_iter().map(|l| match l.file {
+        KeyFile::Json { path, .. } | KeyFile::DotEnv { path, .. } => path,
+    }));
+    paths.extend(memory::catalog().into_iter().filter_map(|m| match m.kind {
+        MemoryKind::Section { path, .. } => Some(path),
+        MemoryKind::ProjectFiles { .. } => None,
+    }));
+    paths.push(commands::source_dir(&Scope::Global));
+    paths.push(output_styles::styles_dir(&Scope::Global));
+    paths.push(prompts::library_dir());
+    paths.push(rules::canonical_dir(&Scope::Global));
+    paths.push(config::config_dir());
+    paths
+}
+
+/// Existing paths under `home`, relative to it, without paths already
+/// covered by a parent directory in the list
+fn archive_entries(home: &Path, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut relative: Vec<PathBuf> = paths
+        .into_iter()
+        .filter(|p| p.exists())
+        .filter_map(|p| p.strip_prefix(home).ok().map(Path::to_path_buf))
+        .filter(|p| !p.as_os_str().is_empty())
+        .collect();
+    relative.sort();
+    relative.dedup();
+    let mut entries: Vec<PathBuf> = Vec::new();
+    for path in relative {
+        if !entries.iter().any(|parent| path.starts_with(parent)) {
+            entries.push(path);
+        }
+    }
+    entries
+}
+
+/// Backups in `dir`, oldest first
+pub fn list_backups(dir: &Path) -> Vec<Backup> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (id, encrypted) = match name.strip_suffix(ENCRYPTED_EXTENSION) {
+                Some(id) => (id.to_string(), true),
+                None => (name.strip_suffix(EXTENSION)?.to_string(), false),
+            };
+            Some(Backup {
+                id,
+                path: entry.path(),
+                encrypted,
+            })
+        })
+        .collect();
+    backups.sort_by_cached_key(|b| {
+        (
+            fs::metadata(&b.path).and_then(|m| m.modified()).ok(),
+            b.id.clone(),
+        )
+    });
+    backups
+}
+
+/// Backups beyond the newest `keep`; `keep = 0` keeps everything
+fn expired(backups: &[Backup], keep: usize) -> &[Backup] {
+    if keep == 0 || backups.len() <= keep {
+        return &[];
+    }
+    &backups[..backups.len() - keep]
+}
+
+fn run(command: &mut Command, what: &str) -> Result<()> {
+    let status = logging::run(command).with_context(|| format!("Failed to run {}", what))?;
+    if !status.success() {
+        bail!("{} failed", what);
+    }
+    Ok(())
+}
+
+/// Archive `entries` (relative to `home`) into `dir`, encrypting to
+/// `recipient` with age when given
+fn create_archive(
+    home: &Path,
+    entries: &[PathBuf],
+    dir: &Path,
+    recipient: Option<&str>,
+) -> Result<Backup> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    let existing = list_backups(dir);
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut id = timestamp.clone();
+    // Two backups within a second (e.g. restore's safety backup right after create)
+    for n in 2.. {
+        if !existing.iter().any(|b| b.id == id) {
+            break;
+        }
+        id = format!("{}-{}", timestamp, n);
+    }
+
+    let tarball = dir.join(format!("{}{}", id, EXTENSION));
+    run(
+        Command::new("tar")
+            .arg("-czf")
+            .arg(&tarball)
+            .arg("-C")
+            .arg(home)
+            .args(entries),
+        "tar",
+    )?;
+    let Some(recipient) = recipient else {
+        return Ok(Backup {
+            id,
+            path: tarball,
+            encrypted: false,
+        });
+    };
+
+    let encrypted = dir.join(format!("{}{}", id, ENCRYPTED_EXTENSION));
+    let result = run(
+        Command::new("age")
+            .args(["-r", recipient, "-o"])
+            .arg(&encrypted)
+            .arg(&tarball),
+        "age (is it installed?)",
+    );
+    fs::remove_file(&tarball).with_context(|| format!("Failed to remove {}", tarball.display()))?;
+    result?;
+    Ok(Backup {
+        id,
+        path: encrypted,
+        encrypted: true,
+    })
+}
+
+/// Extract `backup` over `home`, decrypting with the age `identity` file
+fn extract_archive(backup: &Backup, home: &Path, identity: Option<&Path>) -> Result<()> {
+    if !backup.encrypted {
+        return run(
+            Command::new("tar")
+                .arg("-xzf")
+                .arg(&backup.path)
+                .arg("-C")
+                .arg(home),
+            "tar",
+        );
+    }
+    let identity = identity.ok_or_else(|| {
+        exit::usage(format!(
+            "Backup {} is encrypted; pass --identity <FILE>",
+            backup.id
+        ))
+    })?;
+    let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let tarball = temp_dir.path().join(format!("{}{}", backup.id, EXTENSION));
+    run(
+        Command::new("age")
+            .arg("-d")
+            .arg("-i")
+            .arg(identity)
+            .arg("-o")
+            .arg(&tarball)
+            .arg(&backup.path),
+        "age (is it installed?)",
+    )?;
+    run(
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(&tarball)
+            .arg("-C")
+            .arg(home),
+        "tar",
+    )
+}
+
+fn size(path: &Path) -> String {
+    fs::metadata(path)
+        .map(|m| format_size(m.len()))
+        .unwrap_or_default()
+}
+
+/// Handle `backup create` command
+pub fn handle_create(encrypt: Option<&str>, keep: Option<usize>) -> Result<Outcome> {
+    let settings = config::load()?.backup;
+    let recipient = encrypt.or(settings.encrypt_to.as_deref());
+    let keep = keep.or(settings.keep).unwrap_or(DEFAULT_KEEP);
+
+    let home = paths::home();
+    let entries = archive_entries(&home, config_paths());
+    if entries.is_empty() {
+        println!("No AI tool configs found to back up");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let dir = backups_dir();
+    let backup = create_archive(&home, &entries, &dir, recipient)?;
+    logging::line(&format!("created backup {}", backup.path.display()));
+    for entry in &entries {
+        println!("  {} ~/{}", "+".success(), entry.display());
+    }
+    println!();
+    println!(
+        "{} Backup {} ({}){}",
+        "✓".success(),
+        backup.id.bold(),
+        size(&backup.path),
+        if backup.encrypted { ", encrypted" } else { "" }
+    );
+    if !backup.encrypted {
+        println!(
+            "{} It includes API keys and tokens; pass --encrypt <age recipient> to encrypt it",
+            "!".warning()
+        );
+    }
+
+    for old in expired(&list_backups(&dir), keep) {
+        fs::remove_file(&old.path)
+            .with_context(|| format!("Failed to remove {}", old.path.display()))?;
+        logging::line(&format!("removed backup {}", old.path.display()));
+        println!("  {} Removed old backup {}", "-".muted(), old.id);
+    }
+    Ok(Outcome::Success)
+}
+
+/// Handle `backup list` command
+pub fn handle_list() -> Result<()> {
+    let dir = backups_dir();
+    let backups = list_backups(&dir);
+    println!("{} {}", "Backups".bold(), dir.display().to_string().muted());
+    if backups.is_empty() {
+        println!(
+            "  {}",
+            "(no backups yet; create one with `ai-cli backup create`)".muted()
+        );
+        return Ok(());
+    }
+    for backup in backups.iter().rev() {
+        print!(
+            "  {} {:<18} {:>9}",
+            "-".info(),
+            backup.id,
+            size(&backup.path)
+        );
+        if backup.encrypted {
+            print!(" {}", "encrypted".accent());
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Handle `backup restore [id]` command
+pub fn handle_restore(id: Option<&str>, identity: Option<&Path>) -> Result<Outcome> {
+    let dir = backups_dir();
+    let backups = list_backups(&dir);
+    let backup = match id {
+        Some(id) => backups.iter().find(|b| b.id == id),
+        None => backups.last(),
+    }
+    .ok_or_else(|| match id {
+        Some(id) => exit::usage(format!("Backup '{}' not found in {}", id, dir.display())),
+        None => exit::usage("No backups to restore; create one with `ai-cli backup create`"),
+    })?;
+    if backup.encrypted && identity.is_none() {
+        return Err(exit::usage(format!(
+            "Backup {} is encrypted; pass --identity <FILE>",
+            backup.id
+        )));
+    }
+
+    if !prompt::confirm(&format!(
+        "Overwrite your current AI configs with backup {}?",
+        backup.id
+    ))? {
+        println!("Cancelled");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    // Keep what's there now, in case the restore was a mistake
+    let home = paths::home();
+    let entries = archive_entries(&home, config_paths());
+    if !entries.is_empty() {
+        let current = create_archive(&home, &entries, &dir, None)?;
+        println!(
+            "{} Saved the current configs as backup {}",
+            "→".info(),
+            current.id
+        );
+    }
+
+    extract_archive(backup, &home, identity)?;
+    logging::line(&format!("restored backup {}", backup.path.display()));
+    println!("{} Restored backup {}", "✓".success(), backup.id.bold());
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archives_round_trip_relative_to_home() {
+        let home = tempfile::tempdir().unwrap();
+        let backups = tempfile::tempdir().unwrap();
+        fs::create_dir_all(home.path().join(".claude/skills/pdf")).unwrap();
+        fs::write(home.path().join(".claude/skills/pdf/SKILL.md"), "# pdf").unwrap();
+        fs::write(home.path().join(".claude.json"), "{}").unwrap();
+
+        let entries = archive_entries(
+            home.path(),
+            vec![
+                home.path().join(".claude/skills/pdf"),
+                home.path().join(".claude/skills"),
+                home.path().join(".claude.json"),
+                home.path().join(".codex/config.toml"),
+                PathBuf::from("/etc/hosts"),
+            ],
+        );
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from(".claude/skills"),
+                PathBuf::from(".claude.json")
+            ]
+        );
+
+        let backup = create_archive(home.path(), &entries, backups.path(), None).unwrap();
+        assert_eq!(list_backups(backups.path()), vec![backup.clone()]);
+
+        fs::remove_dir_all(home.path().join(".claude")).unwrap();
+        extract_archive(&backup, home.path(), None).unwrap();
+        assert_eq!(
+            fs::read_to_string(home.path().join(".claude/skills/pdf/SKILL.md")).unwrap(),
+            "# pdf"
+        );
+    }
+
+    #[test]
+    fn expires_all_but_the_newest() {
+        let backups: Vec<Backup> = ["20261001-090000", "20261008-090000", "20261015-090000"]
+            .iter()
+            .map(|id| Backup {
+                id: id.to_string(),
+                path: PathBuf::from(format!("{}{}", id, EXTENSION)),
+                encrypted: false,
+            })
+            .collect();
+        assert_eq!(expired(&backups, 2), &backups[..1]);
+        assert!(expired(&backups, 3).is_empty());
+        assert!(expired(&backups, 0).is_empty());
+    }
+}
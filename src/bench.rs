@@ -0,0 +1,245 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::actions::find_tool;
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::keys::keychain;
+use crate::logging;
+use crate::output;
+use crate::run;
+use crate::theme::Themed;
+use crate::tools::{self, Tool};
+
+/// Arguments that run each tool non-interactively; the prompt goes last
+const HEADLESS: &[(&str, &[&str])] = &[
+    ("claude", &["-p"]),
+    ("amp", &["-x"]),
+    ("codex", &["exec"]),
+    ("cursor-agent", &["-p"]),
+    ("copilot", &["-p"]),
+    ("gemini", &["-p"]),
+    ("opencode", &["run"]),
+    ("droid", &["exec"]),
+    ("vibe", &["--prompt"]),
+];
+
+/// Lines of each tool's output shown in the comparison
+const PREVIEW_LINES: usize = 20;
+
+/// Arguments running `binary` non-interactively with `prompt`
+fn headless_args(binary: &str, prompt: &str) -> Option<Vec<String>> {
+    let (_, args) = HEADLESS.iter().find(|(b, _)| *b == binary)?;
+    let mut args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    args.push(prompt.to_string());
+    Some(args)
+}
+
+/// How one tool did
+#[derive(Debug)]
+struct Run {
+    tool: String,
+    binary: String,
+    elapsed: Duration,
+    /// `None` when the tool was stopped at the timeout
+    success: Option<bool>,
+    output: String,
+}
+
+async fn run_tool(tool: &Tool, binary: &str, prompt: &str, timeout: Duration) -> Result<Run> {
+    let config = config::load()?;
+    let profile = config
+        .active_profile
+        .as_ref()
+        .and_then(|name| config.profiles.get(name));
+    let args = headless_args(binary, prompt).unwrap_or_default();
+    let launch = run::prepare(
+        tool,
+        profile,
+        None,
+        &args,
+        |var| env::var(var).ok().filter(|v| !v.is_empty()),
+        keychain::get,
+    )?;
+    logging::line(&format!("bench {} {}", binary, launch.args.join(" ")));
+
+    let child = tokio::process::Command::new(binary)
+        .args(&launch.args)
+        .envs(&launch.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run {}", binary))?;
+    let started = Instant::now();
+    let result = tokio::time::timeout(timeout, child.wait_with_output()).await;
+    let elapsed = started.elapsed();
+
+    let (success, output) = match result {
+        Ok(output) => {
+            let output = output.with_context(|| format!("Failed to run {}", binary))?;
+            let mut text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                text = format!("{}\n{}", text, stderr.trim()).trim().to_string();
+            }
+            (Some(output.status.success()), text)
+        }
+        Err(_) => (None, String::new()),
+    };
+    Ok(Run {
+        tool: tool.name.clone(),
+        binary: binary.to_string(),
+        elapsed,
+        success,
+        output,
+    })
+}
+
+/// Installed tools with a headless mode, limited to `names` when given
+fn select_tools(names: &[String]) -> Result<Vec<(Tool, String)>> {
+    let catalog = tools::catalog();
+    let supported = |tool: &Tool| {
+        let binary = tool.binary_name.clone()?;
+        HEADLESS
+            .iter()
+            .any(|(b, _)| *b == binary)
+            .then_some((tool.clone(), binary))
+    };
+    if names.is_empty() {
+        return Ok(catalog
+            .iter()
+            .filter(|t| t.is_installed())
+            .filter_map(supported)
+            .collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            let tool = find_tool(&catalog, name)
+                .ok_or_else(|| exit::usage(format!("Unknown tool '{}'", name)))?;
+            let selected = supported(tool)
+                .ok_or_else(|| exit::usage(format!("{} has no non-interactive mode", tool.name)))?;
+            if !tool.is_installed() {
+                return Err(exit::usage(format!("{} is not installed", tool.name)));
+            }
+            Ok(selected)
+        })
+        .collect()
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        format!("{}m {:02}s", elapsed.as_secs() / 60, elapsed.as_secs() % 60)
+    }
+}
+
+/// Handle `bench <prompt>` command
+pub async fn handle_bench(
+    prompt: &str,
+    names: &[String],
+    timeout: u64,
+    save: Option<&Path>,
+) -> Result<Outcome> {
+    let selected = select_tools(names)?;
+    if selected.is_empty() {
+        return Err(exit::usage(
+            "No installed tools with a non-interactive mode; see `ai-cli apps install`",
+        ));
+    }
+
+    // One at a time, so the tools don't compete for the machine
+    let mut runs = Vec::new();
+    for (tool, binary) in &selected {
+        let spinner = output::spinner(&format!("Running {}...", tool.name));
+        let run = run_tool(tool, binary, prompt, Duration::from_secs(timeout)).await;
+        spinner.finish_and_clear();
+        runs.push(run?);
+    }
+
+    let width = runs.iter().map(|r| r.tool.len()).max().unwrap_or(0);
+    println!(
+        "{}",
+        format!("{:<width$}  {:>8}  {:<9}  Output", "Tool", "Time", "Result").bold()
+    );
+    for run in &runs {
+        let result = match run.success {
+            Some(true) => format!("{:<9}", "ok").success(),
+            Some(false) => format!("{:<9}", "failed").error(),
+            None => format!("{:<9}", "timed out").warning(),
+        };
+        println!(
+            "{:<width$}  {:>8}  {}  {} lines, {} chars",
+            run.tool,
+            format_elapsed(run.elapsed),
+            result,
+            run.output.lines().count(),
+            run.output.chars().count()
+        );
+    }
+
+    for run in &runs {
+        println!();
+        println!(
+            "{} {}",
+            format!("── {} ", run.tool).accent(),
+            format_elapsed(run.elapsed).muted()
+        );
+        let lines: Vec<&str> = run.output.lines().collect();
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("  {}", line);
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!(
+                "  {}",
+                format!("... {} more lines", lines.len() - PREVIEW_LINES).muted()
+            );
+        }
+    }
+
+    if let Some(dir) = save {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        for run in &runs {
+            let path = dir.join(format!("{}.txt", run.binary));
+            fs::write(&path, format!("{}\n", run.output))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        println!();
+        println!("{} Full outputs saved to {}", "→".info(), dir.display());
+    }
+
+    let failed = runs.iter().filter(|r| r.success != Some(true)).count();
+    Ok(if failed == runs.len() {
+        Outcome::Failure
+    } else {
+        Outcome::from_failures(failed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puts_the_prompt_after_the_headless_flags() {
+        assert_eq!(
+            headless_args("codex", "explain main.rs").unwrap(),
+            vec!["exec", "explain main.rs"]
+        );
+        assert_eq!(headless_args("claude", "hi").unwrap(), vec!["-p", "hi"]);
+        assert_eq!(headless_args("cline", "hi"), None);
+        assert_eq!(format_elapsed(Duration::from_millis(12_340)), "12.3s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m 05s");
+    }
+}
@@ -0,0 +1,244 @@
+//! `apps changelog`: release notes between the installed and latest version.
+//!
+//! Notes come from the GitHub releases of each tool's `release_repo`, through
+//! the HTTP cache. Tools without a public repository have no notes to show.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{collect_versions, find_tool};
+use crate::exit::{self, Outcome};
+use crate::http;
+use crate::theme::Themed;
+use crate::tools::{self, Tool, ToolVersion};
+use crate::versions::is_newer_version;
+
+/// Release notes don't change once published; new releases show up within the hour
+const RELEASES_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+impl Release {
+    /// Version in the tag, without prefixes like `v` or `rust-v`
+    pub fn version(&self) -> &str {
+        let start = self
+            .tag_name
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(0);
+        &self.tag_name[start..]
+    }
+}
+
+/// Latest page of releases of `repo` (`owner/name`)
+pub async fn fetch_releases(repo: &str) -> Result<Vec<Release>> {
+    let url = format!(
+        "https://api.github.com/repos/{}/releases?per_page=100",
+        repo
+    );
+    http::get_json(&url, RELEASES_TTL).await
+}
+
+/// Stable releases newer than `installed` up to `latest`, newest first
+pub fn between<'a>(releases: &'a [Release], installed: &str, latest: &str) -> Vec<&'a Release> {
+    let mut found: Vec<&Release> = releases
+        .iter()
+        .filter(|r| !r.draft && !r.prerelease && !r.version().contains('-'))
+        .filter(|r| is_newer_version(r.version(), installed))
+        .filter(|r| !is_newer_version(r.version(), latest))
+        .collect();
+    found.sort_by(|a, b| {
+        if is_newer_version(a.version(), b.version()) {
+            std::cmp::Ordering::Less
+        } else if is_newer_version(b.version(), a.version()) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+    found
+}
+
+/// Print the notes from `installed` to `latest`; returns false when they
+/// couldn't be fetched
+async fn print_notes(tool: &Tool, installed: &str, latest: &str) -> bool {
+    println!(
+        "{} {} → {}",
+        tool.name.bold(),
+        installed.muted(),
+        latest.success()
+    );
+    let Some(repo) = &tool.release_repo else {
+        println!(
+            "  {}",
+            "No public release notes; see the tool's website".muted()
+        );
+        println!();
+        return true;
+    };
+    let releases = match fetch_releases(repo).await {
+        Ok(releases) => releases,
+        Err(e) => {
+            println!("  {} {:#}", "✗".error(), e);
+            println!();
+            return false;
+        }
+    };
+
+    let notes = between(&releases, installed, latest);
+    if notes.is_empty() {
+        println!(
+            "  {}",
+            format!(
+                "No release notes found on https://github.com/{}/releases",
+                repo
+            )
+            .muted()
+        );
+    }
+    for release in &notes {
+        let date = release
+            .published_at
+            .as_deref()
+            .and_then(|d| d.get(..10))
+            .unwrap_or_default();
+        println!();
+        println!("  {} {}", release.version().accent().bold(), date.muted());
+        let body = release.body.as_deref().unwrap_or_default().trim();
+        if body.is_empty() {
+            println!("    {}", "(no notes)".muted());
+        }
+        for line in body.lines() {
+            println!("    {}", line.trim_end());
+        }
+    }
+    // Only the latest page is fetched; say so when it doesn't reach back far enough
+    if releases.len() >= 100
+        && releases
+            .iter()
+            .all(|r| is_newer_version(r.version(), installed))
+    {
+        println!();
+        println!(
+            "  {}",
+            format!("Older releases: https://github.com/{}/releases", repo).muted()
+        );
+    }
+    println!();
+    true
+}
+
+fn outdated(version: &ToolVersion) -> Option<(&str, &str)> {
+    match (&version.installed, &version.latest) {
+        (Some(installed), Some(latest)) if is_newer_version(latest, installed) => {
+            Some((installed, latest))
+        }
+        _ => None,
+    }
+}
+
+/// Handle `apps changelog [<tool>] [--all]` command
+pub async fn handle_changelog(tool: Option<&str>, all: bool) -> Result<Outcome> {
+    let catalog = tools::catalog();
+    let wanted = match (tool, all) {
+        (Some(name), false) => Some(
+            find_tool(&catalog, name)
+                .ok_or_else(|| exit::usage(format!("Unknown tool: {}", name)))?
+                .name
+                .clone(),
+        ),
+        (None, true) => None,
+        _ => return Err(exit::usage("Name a tool or pass --all")),
+    };
+
+    let versions = collect_versions().await;
+    let mut shown = 0;
+    let mut failures = 0;
+    for version in &versions {
+        if wanted.as_ref().is_some_and(|name| *name != version.name) {
+            continue;
+        }
+        let Some(tool) = catalog.iter().find(|t| t.name == version.name) else {
+            continue;
+        };
+        let Some((installed, latest)) = outdated(version) else {
+            if wanted.is_some() {
+                match (&version.installed, &version.latest) {
+                    (None, _) => println!("{} is not installed", version.name),
+                    (_, None) => {
+                        println!(
+                            "{} Could not look up the latest {} version",
+                            "✗".error(),
+                            version.name
+                        );
+                        return Ok(Outcome::Failure);
+                    }
+                    (Some(installed), _) => {
+                        println!("{} {} is up to date", version.name, installed)
+                    }
+                }
+            }
+            continue;
+        };
+        shown += 1;
+        if !print_notes(tool, installed, latest).await {
+            failures += 1;
+        }
+    }
+
+    if shown == 0 {
+        if wanted.is_none() {
+            println!("{} Every installed tool is up to date", "✓".success());
+        }
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(failures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            body: Some(format!("Notes for {}", tag)),
+            published_at: None,
+            prerelease,
+            draft: false,
+        }
+    }
+
+    #[test]
+    fn selects_stable_releases_after_installed_newest_first() {
+        let releases = [
+            release("rust-v0.52.0", false),
+            release("rust-v0.51.0-alpha.2", true),
+            release("rust-v0.50.1", false),
+            release("rust-v0.51.0", false),
+            release("rust-v0.50.0", false),
+            release("rust-v0.49.0", false),
+        ];
+
+        let tags: Vec<&str> = between(&releases, "0.50.0", "0.51.0")
+            .iter()
+            .map(|r| r.tag_name.as_str())
+            .collect();
+        assert_eq!(tags, ["rust-v0.51.0", "rust-v0.50.1"]);
+        assert_eq!(releases[0].version(), "0.52.0");
+        assert!(between(&releases, "0.52.0", "0.52.0").is_empty());
+    }
+}
@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Command, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
 
 #[derive(Parser)]
 #[command(name = "ai-cli")]
@@ -10,29 +13,339 @@ pub struct Cli {
     #[arg(short = 'v', long, action = clap::ArgAction::Version)]
     version: Option<bool>,
 
+    /// Check whether a newer ai-cli release is available
+    #[arg(long)]
+    pub check_update: bool,
+
+    /// Answer yes to all prompts and fail when other input would be required
+    #[arg(
+        short = 'y',
+        long,
+        global = true,
+        env = "AI_CLI_NONINTERACTIVE",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub yes: bool,
+
+    /// Plain line-oriented output without colors, spinners or header art
+    /// (default when stdout isn't a terminal)
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    /// Also write the log to this file (default: ~/.local/state/ai-cli/ai-cli.log)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Use this directory instead of the home directory for all configs and state
+    #[arg(long, global = true, env = "AI_CLI_HOME", value_name = "DIR")]
+    pub config_root: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// The clap command with unambiguous prefixes (`mcp en` for `mcp enable`) accepted at every level
+pub fn command() -> Command {
+    infer_subcommands(Cli::command())
+}
+
+fn infer_subcommands(cmd: Command) -> Command {
+    let names: Vec<String> = cmd
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    names.iter().fold(cmd.infer_subcommands(true), |cmd, name| {
+        cmd.mut_subcommand(name, infer_subcommands)
+    })
+}
+
+/// Parse `args` (including the binary name), exiting with clap's message on errors
+pub fn parse_from(args: Vec<String>) -> Cli {
+    let matches = command().get_matches_from(args);
+    Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+}
+
+/// Names and aliases of the top-level commands
+pub fn builtin_commands() -> Vec<String> {
+    let cmd = Cli::command();
+    let mut names = vec!["help".to_string()];
+    for sub in cmd.get_subcommands() {
+        names.push(sub.get_name().to_string());
+        names.extend(sub.get_all_aliases().map(str::to_string));
+    }
+    names
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Manage AI CLI tools (install, update, uninstall)
-    #[command(arg_required_else_help = false)]
+    #[command(arg_required_else_help = false, visible_alias = "a")]
     Apps {
         #[command(subcommand)]
         command: Option<AppsCommands>,
     },
     /// Manage MCP servers across AI CLI tools
-    #[command(arg_required_else_help = false)]
+    #[command(arg_required_else_help = false, visible_alias = "m")]
     Mcp {
         #[command(subcommand)]
         command: Option<McpCommands>,
     },
     /// Manage skills across AI CLI tools
-    #[command(arg_required_else_help = false)]
+    #[command(arg_required_else_help = false, visible_alias = "s")]
     Skills {
         #[command(subcommand)]
         command: Option<SkillsCommands>,
+        /// Token for cloning private repositories over HTTPS (e.g., in CI)
+        #[arg(long, global = true, value_name = "TOKEN")]
+        token: Option<String>,
+    },
+    /// Diagnose installed tools, configs, PATH and network
+    Doctor,
+    /// Guided setup: pick MCP servers, starter skills and write the config
+    Onboard,
+    /// Print an environment report for bug reports
+    Env {
+        /// List the API key, base URL and proxy variables each tool reads, and conflicts
+        #[arg(long)]
+        keys: bool,
+    },
+    /// Export installed tools, enabled MCP servers and skills to a file
+    Export {
+        /// Snapshot file to write
+        #[arg(default_value = "ai-cli-snapshot.json")]
+        file: PathBuf,
+    },
+    /// Install tools, MCP servers and skills from an exported snapshot
+    Import {
+        /// Snapshot file to read
+        file: PathBuf,
+    },
+    /// Keep CLAUDE.md / AGENTS.md / GEMINI.md in sync with one canonical document
+    Instructions {
+        #[command(subcommand)]
+        command: InstructionsCommands,
+    },
+    /// Inspect and rotate provider API keys used by the tools
+    Keys {
+        #[command(subcommand)]
+        command: Option<KeysCommands>,
+    },
+    /// Search tools, MCP servers (built-in and registry) and skills in one query
+    Search {
+        /// Text to look for in names and descriptions
+        term: String,
+    },
+    /// Print shell functions that launch tools through `ai-cli run`
+    ///
+    /// Add `eval "$(ai-cli shell-init)"` to ~/.zshrc or ~/.bashrc, or
+    /// `ai-cli shell-init fish | source` to ~/.config/fish/config.fish.
+    ShellInit {
+        /// Shell to generate functions for (default: from $SHELL)
+        #[arg(value_enum)]
+        shell: Option<Shell>,
+    },
+    /// Turn anonymous usage statistics on or off (off by default)
+    Telemetry {
+        #[command(subcommand)]
+        command: Option<TelemetryCommands>,
+    },
+    /// Run the same prompt through each installed agent and compare time and output
+    Bench {
+        /// Prompt to send to every tool
+        prompt: String,
+        /// Only run these tools (repeatable; default: every installed tool with a headless mode)
+        #[arg(long = "tool", value_name = "TOOL")]
+        tools: Vec<String>,
+        /// Seconds to wait for each tool before stopping it
+        #[arg(long, default_value_t = 600)]
+        timeout: u64,
+        /// Directory to save each tool's full output to
+        #[arg(long, value_name = "DIR")]
+        save: Option<PathBuf>,
+    },
+    /// Install and configure tools, MCP servers, skills and instructions from a manifest
+    Setup {
+        /// Setup manifest (TOML)
+        #[arg(default_value = "ai-cli-setup.toml")]
+        file: PathBuf,
+    },
+    /// Move MCP servers, instructions, rules and ignored files from one tool to another
+    Migrate {
+        /// Tool to migrate from (claude, codex, gemini, amp, cursor, copilot)
+        #[arg(long)]
+        from: String,
+        /// Tool to migrate to
+        #[arg(long)]
+        to: String,
+        /// Show what would be migrated without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Back up and restore every AI tool config: settings, MCP files, skills, instructions
+    Backup {
+        #[command(subcommand)]
+        command: Option<BackupCommands>,
+    },
+    /// Report remaining quota and rate limits for each configured provider credential
+    Quota,
+    /// Compare installed tools: version freshness, MCP, skills, project files and sandboxing
+    Compare,
+    /// Install, list and remove Claude Code output styles (~/.claude/output-styles)
+    OutputStyles {
+        #[command(subcommand)]
+        command: Option<OutputStylesCommands>,
+    },
+    /// Generate project files that set up AI tooling (e.g. a devcontainer)
+    Init {
+        /// Write .devcontainer/ with this machine's tools, MCP servers and skills
+        #[arg(long)]
+        devcontainer: bool,
+        /// Tool to install (repeatable; default: the tools installed here)
+        #[arg(long = "tool", value_name = "NAME")]
+        tools: Vec<String>,
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+    /// Create git worktrees pre-wired with the project's MCP config, skills and instructions
+    Workspace {
+        #[command(subcommand)]
+        command: Option<WorkspaceCommands>,
+    },
+    /// Show a tool's latest log with normalized timestamps and level/keyword filters
+    Logs {
+        /// Tool id ('claude', 'codex', 'copilot' or 'ai-cli')
+        tool: String,
+        /// Keep printing new entries as they are written
+        #[arg(short, long)]
+        follow: bool,
+        /// Show this level and more severe ones (error, warn, info, debug, trace)
+        #[arg(long)]
+        level: Option<String>,
+        /// Show only entries containing this text (case-insensitive)
+        #[arg(long, value_name = "TEXT")]
+        grep: Option<String>,
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+    },
+    /// Report what tools accumulate on disk and delete old transcripts, caches and logs
+    Prune {
+        /// Stores to prune (default: all), e.g. 'claude-transcripts', 'npx'
+        stores: Vec<String>,
+        /// Delete entries not modified for this many days
+        #[arg(long, value_name = "DAYS", default_value_t = 30)]
+        older_than: u64,
+        /// Report what would be deleted without deleting
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Scan tool configs for plaintext secrets, unknown MCP servers, skill scripts and open permissions
+    Audit,
+    /// Revert the changes of the most recent ai-cli command(s)
+    Undo {
+        /// Number of operations to undo, newest first
+        #[arg(long, default_value_t = 1)]
+        last: usize,
+        /// Also revert files edited since the operation
+        #[arg(long)]
+        force: bool,
+    },
+    /// Overview of installed tools, MCP servers, skills and pending drift
+    Status {
+        /// Check the status pages of Anthropic, OpenAI, Google and GitHub for incidents instead
+        #[arg(long)]
+        providers: bool,
+    },
+    /// Launch a tool with a profile's environment, keychain API keys and model
+    Run {
+        /// Tool name or binary (e.g. 'claude', 'codex')
+        tool: String,
+        /// Profile to use (default: the active profile)
+        #[arg(long)]
+        profile: Option<String>,
+        /// Model to pass as --model (overrides the profile's model)
+        #[arg(long)]
+        model: Option<String>,
+        /// Check for a newer version of the tool before launching
+        #[arg(long)]
+        update_check: bool,
+        /// Arguments passed to the tool
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Switch Claude Code and Codex CLI between sandbox presets (read-only, safe, auto, yolo)
+    Sandbox {
+        #[command(subcommand)]
+        command: Option<SandboxCommands>,
+    },
+    /// Manage Claude Code statuslines and agent color themes
+    Appearance {
+        #[command(subcommand)]
+        command: Option<AppearanceCommands>,
+    },
+    /// Apply one permission policy to every agent and audit deviations
+    Settings {
+        #[command(subcommand)]
+        command: Option<SettingsCommands>,
+    },
+    /// Inspect, prune and wipe what agents remember between sessions
+    Memory {
+        #[command(subcommand)]
+        command: Option<MemoryCommands>,
+    },
+    /// Write one canonical ruleset as Cursor and Windsurf rules
+    Rules {
+        #[command(subcommand)]
+        command: RulesCommands,
+    },
+    /// Manage slash commands (~/.claude/commands) and mirror them to other agents
+    Commands {
+        #[command(subcommand)]
+        command: Option<CommandsCommands>,
+    },
+    /// Keep a central prompt library and install it as commands in each agent
+    Prompts {
+        #[command(subcommand)]
+        command: Option<PromptsCommands>,
+    },
+    /// Show and change the default model of each tool
+    Models {
+        #[command(subcommand)]
+        command: Option<ModelsCommands>,
+    },
+    /// Switch between named profiles of MCP servers, skills and env vars
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+    /// Browse and search conversation transcripts across tools
+    Sessions {
+        #[command(subcommand)]
+        command: Option<SessionsCommands>,
+    },
+    /// Report tokens and estimated cost per tool per day
+    Usage {
+        /// Number of days to include, counting today
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+    },
+    /// Sync managed state across machines through a git repository
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommands,
+    },
+    /// Generate shell completion scripts
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Generate man pages for all commands
+    Manpages {
+        /// Directory to write the man pages into
+        #[arg(long, default_value = "man")]
+        out_dir: PathBuf,
     },
 }
 
@@ -83,49 +396,692 @@ pub enum AppsCommands {
         force: bool,
     },
     /// List installed AI CLI tools (alias for default command)
+    #[command(visible_alias = "ls")]
     List,
+    /// Show a tool's install method, latest release, npm downloads and deprecation status
+    Info {
+        /// Tool name (e.g., 'codex')
+        tool: String,
+    },
+    /// Show the release notes between the installed and latest version of a tool
+    Changelog {
+        /// Tool name (e.g., 'codex')
+        tool: Option<String>,
+        /// Show the notes of every outdated tool
+        #[arg(long)]
+        all: bool,
+    },
+    /// Remove global npm packages left behind by removed or reinstalled tools
+    Cleanup {
+        /// List the packages without removing them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum McpCommands {
     /// List MCP servers and their status across tools
+    #[command(visible_alias = "ls")]
     List,
     /// Enable an MCP server across all installed tools
     Enable {
         /// Server to enable (e.g., 'linear', 'playwright', or 'all')
         server: String,
+        /// Only change these tools (e.g., 'claude', 'cursor'); repeatable
+        #[arg(long = "tool", value_delimiter = ',', value_name = "TOOL")]
+        tools: Vec<String>,
+        /// Keep secrets in the OS keychain instead of the tools' config files
+        #[arg(long)]
+        keychain: bool,
     },
     /// Disable an MCP server across all installed tools
     Disable {
         /// Server to disable (e.g., 'linear', 'playwright', or 'all')
         server: String,
+        /// Only change these tools (e.g., 'claude', 'cursor'); repeatable
+        #[arg(long = "tool", value_delimiter = ',', value_name = "TOOL")]
+        tools: Vec<String>,
+    },
+    /// Define a server from a registry manifest, e.g. `--from smithery/exa`
+    Add {
+        /// Where to import from: smithery/<name>
+        #[arg(long)]
+        from: String,
+        /// Id to register the server under (default: derived from the name)
+        #[arg(long)]
+        id: Option<String>,
+    },
+    /// Make every tool's servers match a manifest (default: ~/.config/ai-cli/mcp.yaml)
+    Apply {
+        /// Manifest declaring servers and the tools that get them
+        file: Option<PathBuf>,
+        /// Show the plan without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Show installed tools and their config paths
     Doctor,
+    /// Run one MCP endpoint that proxies to several servers
+    Gateway {
+        #[command(subcommand)]
+        command: Option<GatewayCommands>,
+    },
+    /// Start a server with its secrets from the keychain (run by the tools)
+    #[command(hide = true)]
+    Exec {
+        server: String,
+        /// Environment variable to set from the keychain
+        #[arg(long = "secret", value_name = "VAR")]
+        secrets: Vec<String>,
+        /// Secret whose `${KEY}` placeholder in the arguments is filled from the keychain
+        #[arg(long = "secret-arg", value_name = "KEY")]
+        secret_args: Vec<String>,
+        /// The server's command and arguments
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GatewayCommands {
+    /// Show the gateway's servers and the tools using it
+    Status,
+    /// Proxy a catalog server through the gateway
+    Enable {
+        /// Server to enable (e.g., 'linear', 'playwright')
+        server: String,
+    },
+    /// Stop proxying a server
+    Disable {
+        /// Server to disable (e.g., 'linear', 'playwright')
+        server: String,
+    },
+    /// Point installed tools at the gateway instead of individual servers
+    Install,
+    /// Configure the gateway's servers directly in each tool again
+    Uninstall,
+    /// Speak MCP on stdin/stdout (started by the tools)
+    Serve,
+}
+
+#[derive(Subcommand)]
+pub enum InstructionsCommands {
+    /// Show which agent instruction files match the canonical document
+    Status {
+        /// Use the current project instead of the global files
+        #[arg(long)]
+        project: bool,
+    },
+    /// Write the canonical document to every agent's instructions file
+    Sync {
+        /// Use the current project instead of the global files
+        #[arg(long)]
+        project: bool,
+    },
+    /// Create the canonical document (from an existing agent file if there is one)
+    Init {
+        /// Use the current project instead of the global files
+        #[arg(long)]
+        project: bool,
+        /// Seed the canonical document from this file
+        #[arg(long, value_name = "FILE")]
+        from: Option<PathBuf>,
+    },
+    /// Open the canonical document in $EDITOR
+    Edit {
+        /// Use the current project instead of the global files
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum KeysCommands {
+    /// Show where each provider's key is stored (env, keychain, config files)
+    #[command(visible_alias = "ls")]
+    List,
+    /// Store a key in the keychain and rotate it in every file that holds it
+    Set {
+        /// Provider id (e.g., 'anthropic', 'openai', 'google')
+        provider: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TelemetryCommands {
+    /// Send anonymous command counts, OS and failure categories
+    On,
+    /// Stop sending and delete unsent events
+    Off,
+    /// Show whether statistics are sent and what is collected
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum BackupCommands {
+    /// Archive every config file ai-cli knows about
+    Create {
+        /// Encrypt the archive to this age recipient (default: `[backup] encrypt_to`)
+        #[arg(long, value_name = "RECIPIENT")]
+        encrypt: Option<String>,
+        /// Backups to keep, deleting older ones (default: `[backup] keep` or 10, 0 keeps all)
+        #[arg(long)]
+        keep: Option<usize>,
+    },
+    /// List backups, newest first
+    #[command(visible_alias = "ls")]
+    List,
+    /// Restore a backup over the current configs (default: the newest)
+    Restore {
+        /// Backup ID from `backup list`
+        id: Option<String>,
+        /// age identity file for encrypted backups
+        #[arg(long, value_name = "FILE")]
+        identity: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OutputStylesCommands {
+    /// List installed output styles and the active one
+    #[command(visible_alias = "ls")]
+    List {
+        /// Use the current project's .claude/output-styles instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Install a style pack from a repository (e.g., 'owner/repo')
+    Install {
+        /// Repository with an output-styles/ or .claude/output-styles/ directory
+        repo: String,
+        /// Install into the current project instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Remove an output style
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Style name or file name
+        name: String,
+        /// Remove from the current project instead
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceCommands {
+    /// Create a worktree for a branch and copy untracked agent files into it
+    New {
+        /// Branch to check out (created when it doesn't exist)
+        branch: String,
+        /// Where to create the worktree (default: ../<repo>-<branch>)
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Start a new branch from this ref (default: HEAD)
+        #[arg(long, value_name = "REF")]
+        from: Option<String>,
+    },
+    /// List the repository's worktrees
+    #[command(visible_alias = "ls")]
+    List,
+    /// Remove a worktree by branch or path
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Branch or path of the worktree
+        name: String,
+        /// Remove even with uncommitted changes
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SandboxCommands {
+    /// Show each agent's sandbox settings and the preset they match
+    Show {
+        /// Show this project's settings instead of the global ones
+        #[arg(long)]
+        project: bool,
+    },
+    /// List the presets and the settings they write
+    #[command(visible_alias = "ls")]
+    List,
+    /// Switch every agent to a preset
+    Use {
+        /// Preset name ('read-only', 'safe', 'auto' or 'yolo')
+        preset: String,
+        /// Only for the current project (.claude/settings.local.json)
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AppearanceCommands {
+    /// Show the current statusline and each agent's theme
+    Show,
+    /// List the built-in statuslines
+    #[command(visible_alias = "ls")]
+    List,
+    /// Install a statusline for Claude Code ('off' removes it)
+    Statusline {
+        /// Statusline name (e.g., 'minimal', 'git', 'cost') or 'off'
+        name: String,
+    },
+    /// Switch Claude Code and Gemini CLI to a dark or light theme
+    Theme {
+        /// 'dark' or 'light'
+        scheme: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SettingsCommands {
+    /// Show the policy from config.toml (or the built-in hardened default)
+    Show,
+    /// Report agent settings that differ from the policy
+    Audit,
+    /// Write the policy into Claude Code, Codex CLI and Copilot CLI settings
+    Apply,
+}
+
+#[derive(Subcommand)]
+pub enum MemoryCommands {
+    /// Show where each agent keeps its memory and how big it is
+    #[command(visible_alias = "ls")]
+    List,
+    /// Print an agent's remembered entries
+    Show {
+        /// Agent id ('claude' or 'gemini')
+        agent: String,
+    },
+    /// Remove selected entries from an agent's memory
+    Prune {
+        /// Agent id ('claude' or 'gemini')
+        agent: String,
+        /// Remove entries containing this text instead of picking from a list
+        #[arg(long = "match", value_name = "TEXT")]
+        pattern: Option<String>,
+    },
+    /// Remove everything an agent remembers
+    Wipe {
+        /// Agent id ('claude' or 'gemini')
+        agent: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RulesCommands {
+    /// List the canonical rules and the state of each generated file
+    #[command(visible_alias = "ls")]
+    List {
+        /// Use the current project's ruleset (.ai-cli/rules)
+        #[arg(long)]
+        project: bool,
+    },
+    /// Write the rules to .cursor/rules, .windsurf/rules or Windsurf's global rules
+    Sync {
+        /// Use the current project's ruleset (.ai-cli/rules)
+        #[arg(long)]
+        project: bool,
+    },
+    /// Create the canonical ruleset from existing Cursor or Windsurf project rules
+    Import {
+        /// Import into the current project's ruleset (.ai-cli/rules)
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CommandsCommands {
+    /// List Claude Code slash commands and which agents mirror them
+    #[command(visible_alias = "ls")]
+    List {
+        /// Use the current project's .claude/commands instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Install a command pack from a repository (e.g., 'owner/repo')
+    Install {
+        /// Repository with a commands/ or .claude/commands/ directory
+        repo: String,
+        /// Install into the current project instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Copy Claude Code's commands to Codex CLI, Gemini CLI and OpenCode
+    Sync {
+        /// Use the current project's commands instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Remove a command from every agent
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Command name (e.g., 'review' or 'frontend:component')
+        name: String,
+        /// Remove from the current project instead
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PromptsCommands {
+    /// List library prompts and where they are installed
+    #[command(visible_alias = "ls")]
+    List,
+    /// Add a prompt to the library (from a file, stdin or a template)
+    Add {
+        /// Prompt name, used as the command name (e.g., 'review' for /review)
+        name: String,
+        /// Markdown file to copy into the library
+        #[arg(long, value_name = "FILE")]
+        from: Option<PathBuf>,
+    },
+    /// Write every library prompt to Claude Code, Codex CLI, Gemini CLI and OpenCode
+    Sync,
+    /// Remove a prompt from the library and from every agent
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Prompt name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ModelsCommands {
+    /// Show the default model configured in each tool
+    #[command(visible_alias = "ls")]
+    List,
+    /// Set the default model in every tool that can run it
+    Set {
+        /// Model id (e.g., 'claude-opus-4-5', 'gpt-5', 'gemini-2.5-pro')
+        model: String,
+        /// Only change this tool (e.g., 'claude', 'codex', 'opencode')
+        #[arg(long)]
+        tool: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// List profiles defined in the config file
+    #[command(visible_alias = "ls")]
+    List,
+    /// Show what a profile enables
+    Show {
+        /// Profile name
+        name: String,
+    },
+    /// Apply a profile across all installed tools
+    Use {
+        /// Profile name
+        name: String,
+    },
+    /// Print `export` lines for a profile's env vars (default: active profile)
+    Env {
+        /// Profile name
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SessionsCommands {
+    /// List recent sessions of Claude Code, Codex CLI and Gemini CLI
+    #[command(visible_alias = "ls")]
+    List {
+        /// Only sessions of this tool (e.g., 'claude', 'codex', 'gemini')
+        #[arg(long)]
+        tool: Option<String>,
+        /// Number of sessions to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Print the conversation of a session
+    Show {
+        /// Session id or a unique prefix of it
+        id: String,
+    },
+    /// Find sessions whose messages mention some text
+    Search {
+        /// Text to look for (case-insensitive)
+        query: String,
+        /// Only sessions of this tool (e.g., 'claude', 'codex', 'gemini')
+        #[arg(long)]
+        tool: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SyncCommands {
+    /// Set up the sync repository (clones the remote or starts a new one)
+    Init {
+        /// Git remote URL (e.g., 'git@github.com:me/ai-cli-state.git') or 's3://bucket/prefix'
+        remote: String,
+    },
+    /// Commit this machine's state and push it
+    Push,
+    /// Pull the synced state and apply it to this machine
+    Pull {
+        /// Apply the remote state even if this machine has unpushed changes
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show the sync repository and whether local state has changed
+    Status,
+    /// Keep converging this machine with the synced state, reporting what differs
+    Daemon {
+        /// Minutes between rounds (1 to 1440)
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..=1440))]
+        interval: u64,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum SkillsCommands {
     /// List installed skills per agent
+    #[command(visible_alias = "ls")]
     List {
         /// Filter by specific agent (e.g., 'claude', 'gemini')
         #[arg(short, long)]
         agent: Option<String>,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Show a skill's frontmatter, source and where it is installed
+    Info {
+        /// Skill name
+        skill: String,
     },
     /// Install skill(s) from a git repository
     Install {
-        /// Repository (owner/repo or full URL)
-        repo: String,
+        /// Repository (owner/repo or full URL, optionally owner/repo#ref)
+        #[arg(required_unless_present = "locked")]
+        repo: Option<String>,
+        /// Branch, tag or commit to install from (default: the default branch)
+        #[arg(long = "ref", value_name = "REF")]
+        git_ref: Option<String>,
+        /// Install the versions in ~/.ai-cli/skills.lock (only the repository's, if given)
+        #[arg(long, conflicts_with_all = ["git_ref", "only", "exclude", "project"])]
+        locked: bool,
         /// Target specific agent (e.g., 'claude', 'gemini')
         #[arg(short, long)]
         agent: Option<String>,
+        /// Install only these skills (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',', value_name = "NAME")]
+        only: Vec<String>,
+        /// Skip these skills (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',', value_name = "NAME")]
+        exclude: Vec<String>,
+        /// Install every skill without asking which (the default without a terminal)
+        #[arg(long, conflicts_with = "only")]
+        all: bool,
+        /// Copy the skills into each agent instead of linking them to ~/.ai-cli/skills
+        #[arg(long)]
+        copy: bool,
+        /// Replace installed skills of the same name from another source without asking
+        #[arg(long)]
+        force: bool,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Link a skill you're writing into agents, so edits show up without reinstalling
+    Dev {
+        /// Directory holding the skill's SKILL.md
+        path: String,
+        /// Only link into these agents (comma-separated or repeated; default: all installed)
+        #[arg(short, long, value_delimiter = ',', value_name = "AGENT")]
+        agent: Vec<String>,
+        /// Keep running and validate the skill again whenever it changes
+        #[arg(long)]
+        watch: bool,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Write an installed skill as a Cursor rule, Claude Code command or Copilot instructions
+    Convert {
+        /// Skill name
+        skill: String,
+        /// Format to convert to
+        #[arg(long, value_parser = ["cursor-rule", "claude-command", "copilot-instructions"])]
+        to: String,
+        /// Write the Claude Code command into the current project instead of ~/.claude/commands
+        #[arg(long)]
+        project: bool,
+    },
+    /// Turn a skill off without deleting it (moved to .disabled/ in the skills directory)
+    Disable {
+        /// Skill name
+        skill: String,
+        /// Only for this agent (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Turn a disabled skill back on
+    Enable {
+        /// Skill name
+        skill: String,
+        /// Only for this agent (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
     },
     /// Remove installed skill(s)
+    #[command(visible_alias = "rm")]
     Remove {
         /// Skill name to remove
-        skill: String,
+        #[arg(required_unless_present_any = ["repo", "all"], conflicts_with_all = ["repo", "all"])]
+        skill: Option<String>,
+        /// Remove every skill installed from this repository (asks first)
+        #[arg(long, conflicts_with_all = ["all", "project"])]
+        repo: Option<String>,
+        /// Remove every skill of the agent, or of all agents (asks first)
+        #[arg(long)]
+        all: bool,
+        /// Target specific agent (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Find broken skill installs (missing SKILL.md, dangling links, name mismatches, ...)
+    Doctor {
+        /// Only check this agent (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Use the skills directories of the current project (its git root) instead
+        #[arg(long)]
+        project: bool,
+    },
+    /// Copy installed skills from one agent to others
+    Sync {
+        /// Agent to take the skills from (e.g., 'claude')
+        #[arg(long, value_name = "AGENT")]
+        from: String,
+        /// Agent to bring them to, or 'all' for every other installed agent
+        #[arg(long, value_name = "AGENT")]
+        to: String,
+    },
+    /// Show files of installed skills edited since they were installed (exits 1 if any)
+    Diff {
+        /// Only check this skill
+        skill: Option<String>,
+    },
+    /// List installed skills whose source repository has new commits (exits 6 if any)
+    Outdated,
+    /// Refresh skills whose source repository changed and install new ones from subscriptions
+    Update {
+        /// Only update this skill
+        skill: Option<String>,
+    },
+    /// Install a repository's skills and, on `skills update`, ones added to it later
+    Subscribe {
+        /// Repository (owner/repo or full URL)
+        repo: String,
+        /// Target specific agent (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Copy the skills into each agent instead of linking them to ~/.ai-cli/skills
+        #[arg(long)]
+        copy: bool,
+    },
+    /// Stop installing new skills from a repository
+    Unsubscribe {
+        /// Repository as given to `skills subscribe`
+        repo: String,
+    },
+    /// Write the installed skills and their sources to a bundle file to share
+    Export {
+        /// Bundle file to write
+        #[arg(default_value = "skills-bundle.json")]
+        file: PathBuf,
+    },
+    /// Install the skills listed in a bundle file
+    Import {
+        /// Bundle file to read
+        file: PathBuf,
         /// Target specific agent (e.g., 'claude', 'gemini')
         #[arg(short, long)]
         agent: Option<String>,
     },
+    /// Find skill repositories on GitHub or in the configured registry
+    Search {
+        /// Text to look for in repository names, descriptions and topics
+        query: String,
+    },
+    /// Search installed skills' names, descriptions and SKILL.md text
+    Grep {
+        /// Text to look for (case-insensitive)
+        query: String,
+        /// Only search this agent's skills (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+    },
+    /// Check SKILL.md files against the skill spec (exits 1 on problems)
+    Validate {
+        /// SKILL.md, directory to search, or installed skill name (default: current directory)
+        target: Option<String>,
+    },
+    /// Remove installed skills that ai-cli didn't install (not in the manifest)
+    Prune {
+        /// Only prune this agent's skills (e.g., 'claude', 'gemini')
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// List what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
@@ -0,0 +1,217 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use super::discovery::{self, CommandFile};
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::journal;
+use crate::logging;
+use crate::prompt;
+use crate::prompts::{Prompt, PromptTarget, TargetStatus};
+use crate::skills::actions::clone_repo;
+use crate::theme::Themed;
+
+fn mirror_status(target: &PromptTarget, command: &CommandFile, content: &str) -> TargetStatus {
+    match fs::read_to_string(target.file_for(&command.name)) {
+        Ok(existing) if existing == super::render_for(target, &command.name, content) => {
+            TargetStatus::InSync
+        }
+        Ok(_) => TargetStatus::Drifted,
+        Err(_) => TargetStatus::Missing,
+    }
+}
+
+/// Handle `commands list` command
+pub fn handle_list(scope: &Scope) -> Result<()> {
+    let source = super::source_dir(scope);
+    let commands = discovery::list_commands(&source);
+    println!(
+        "{} {}",
+        "Claude Code".bold(),
+        source.display().to_string().muted()
+    );
+    if commands.is_empty() {
+        println!("  {}", "(no commands installed)".muted());
+        return Ok(());
+    }
+
+    let mirrors = super::mirrors(scope);
+    for command in &commands {
+        let content = fs::read_to_string(&command.path)
+            .with_context(|| format!("Failed to read {}", command.path.display()))?;
+        let description = Prompt::parse(&command.name, &content).description;
+        print!("  {} /{}", "-".info(), command.name.replace('/', ":"));
+        if let Some(description) = description {
+            print!(" - {}", description.muted());
+        }
+        println!();
+
+        if !mirrors.is_empty() {
+            let statuses: Vec<String> = mirrors
+                .iter()
+                .map(|t| match mirror_status(t, command, &content) {
+                    TargetStatus::InSync => format!("{} {}", "✓".success(), t.agent),
+                    TargetStatus::Drifted => format!("{} {}", "!".warning(), t.agent),
+                    TargetStatus::Missing => format!("{} {}", "-".muted(), t.agent.muted()),
+                })
+                .collect();
+            println!("      {}", statuses.join("  "));
+        }
+    }
+    Ok(())
+}
+
+/// Handle `commands install <repo>` command
+pub fn handle_install(repo: &str, scope: &Scope) -> Result<Outcome> {
+    let temp_dir = clone_repo(repo)?;
+    let commands = discovery::discover_commands(temp_dir.path());
+    if commands.is_empty() {
+        anyhow::bail!(
+            "No commands found in repository (expected .md files in commands/ or .claude/commands/)"
+        );
+    }
+
+    println!("{} Found {} command(s):", "->".info(), commands.len());
+    for command in &commands {
+        println!("  {} /{}", "-".info(), command.name.replace('/', ":"));
+    }
+    println!();
+
+    let source = super::source_dir(scope);
+    let mirrors = super::mirrors(scope);
+    let mut failed = 0;
+    for command in &commands {
+        let result = fs::read_to_string(&command.path)
+            .with_context(|| format!("Failed to read {}", command.path.display()))
+            .and_then(|content| {
+                logging::write_file(&source.join(format!("{}.md", command.name)), &content)?;
+                for target in &mirrors {
+                    logging::write_file(
+                        &target.file_for(&command.name),
+                        &super::render_for(target, &command.name, &content),
+                    )?;
+                }
+                Ok(())
+            });
+        match result {
+            Ok(()) => println!("  {} /{}", "✓".success(), command.name.replace('/', ":")),
+            Err(e) => {
+                println!("  {} /{}: {:#}", "✗".error(), command.name, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    let agents: Vec<_> = std::iter::once("Claude Code")
+        .chain(mirrors.iter().map(|t| t.agent))
+        .collect();
+    println!(
+        "{}",
+        format!("Installed for {}", agents.join(", ")).success()
+    );
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `commands sync` command: mirror Claude Code's commands to the other agents
+pub fn handle_sync(scope: &Scope) -> Result<Outcome> {
+    let commands = discovery::list_commands(&super::source_dir(scope));
+    if commands.is_empty() {
+        println!("No commands in {}", super::source_dir(scope).display());
+        return Ok(Outcome::NothingToDo);
+    }
+    let mirrors = super::mirrors(scope);
+    if mirrors.is_empty() {
+        println!("No other agents with custom commands are installed");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut stale = Vec::new();
+    for command in &commands {
+        let content = fs::read_to_string(&command.path)
+            .with_context(|| format!("Failed to read {}", command.path.display()))?;
+        for target in &mirrors {
+            let status = mirror_status(target, command, &content);
+            if status != TargetStatus::InSync {
+                stale.push((target, command, content.clone(), status));
+            }
+        }
+    }
+    if stale.is_empty() {
+        println!("{} All commands are in sync", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let drifted: Vec<_> = stale
+        .iter()
+        .filter(|(_, _, _, status)| *status == TargetStatus::Drifted)
+        .collect();
+    if !drifted.is_empty() {
+        println!("{}", "These commands differ from Claude Code's:".bold());
+        for (target, command, _, _) in &drifted {
+            println!(
+                "  {} {}",
+                "!".warning(),
+                target.file_for(&command.name).display()
+            );
+        }
+        if !prompt::confirm("Overwrite them?")? {
+            println!("Cancelled.");
+            return Ok(Outcome::NothingToDo);
+        }
+    }
+
+    let mut failed = 0;
+    for (target, command, content, _) in stale {
+        let path = target.file_for(&command.name);
+        match logging::write_file(&path, &super::render_for(target, &command.name, &content)) {
+            Ok(()) => println!(
+                "{} /{} → {} {}",
+                "✓".success(),
+                command.name.replace('/', ":"),
+                target.agent,
+                format!("({})", path.display()).muted()
+            ),
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `commands remove <name>` command
+pub fn handle_remove(name: &str, scope: &Scope) -> Result<Outcome> {
+    // Accept the `/ns:name` form shown by `commands list`
+    let name = name.trim_start_matches('/').replace(':', "/");
+    let mut paths = vec![super::source_dir(scope).join(format!("{}.md", name))];
+    paths.extend(super::mirrors(scope).iter().map(|t| t.file_for(&name)));
+
+    let existing: Vec<_> = paths.into_iter().filter(|p| p.exists()).collect();
+    if existing.is_empty() {
+        return Err(exit::usage(format!(
+            "Command '{}' not found in any agent",
+            name
+        )));
+    }
+
+    println!("{}", format!("Removing command '/{}':", name).bold());
+    let mut failed = 0;
+    for path in existing {
+        journal::record_file(&path);
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                logging::line(&format!("removed command {}", path.display()));
+                println!("  {} {}", "✓".success(), path.display());
+            }
+            Err(e) => {
+                println!("  {} {}: {}", "✗".error(), path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
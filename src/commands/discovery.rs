@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+/// A slash command file (`<name>.md`)
+#[derive(Debug, Clone)]
+pub struct CommandFile {
+    /// Command name relative to the commands directory, e.g. "review" or
+    /// "frontend/component" for namespaced commands
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Where command packs keep their commands, in priority order
+const DISCOVERY_PATHS: &[&str] = &[".claude/commands", "commands"];
+
+/// Discover command files in a cloned repository
+pub fn discover_commands(repo_path: &Path) -> Vec<CommandFile> {
+    DISCOVERY_PATHS
+        .iter()
+        .map(|subpath| list_commands(&repo_path.join(subpath)))
+        .find(|commands| !commands.is_empty())
+        .unwrap_or_default()
+}
+
+/// List the commands in a commands directory, including namespaced subdirectories
+pub fn list_commands(dir: &Path) -> Vec<CommandFile> {
+    let mut commands = Vec::new();
+    collect(dir, dir, &mut commands);
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+fn collect(root: &Path, dir: &Path, commands: &mut Vec<CommandFile>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect(root, &path, commands);
+        } else if path.extension().is_some_and(|e| e == "md")
+            && !path
+                .file_name()
+                .is_some_and(|n| n.eq_ignore_ascii_case("README.md"))
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            let name = relative
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/");
+            commands.push(CommandFile { name, path });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_namespaced_commands_and_skips_readme() {
+        let repo = tempfile::tempdir().unwrap();
+        let commands = repo.path().join("commands");
+        std::fs::create_dir_all(commands.join("frontend")).unwrap();
+        std::fs::write(commands.join("review.md"), "Review").unwrap();
+        std::fs::write(commands.join("README.md"), "Docs").unwrap();
+        std::fs::write(commands.join("frontend/component.md"), "Build").unwrap();
+
+        let names: Vec<_> = discover_commands(repo.path())
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["frontend/component", "review"]);
+    }
+}
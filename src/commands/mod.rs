@@ -0,0 +1,44 @@
+pub mod actions;
+pub mod discovery;
+
+pub use actions::{handle_install, handle_list, handle_remove, handle_sync};
+
+use std::path::PathBuf;
+
+use crate::instructions::Scope;
+use crate::paths;
+use crate::prompts::{self, PromptTarget};
+
+/// Claude Code's commands directory, the source of truth for slash commands
+pub fn source_dir(scope: &Scope) -> PathBuf {
+    match scope {
+        Scope::Global => paths::home().join(".claude/commands"),
+        Scope::Project(root) => root.join(".claude/commands"),
+    }
+}
+
+/// Other agents' command directories that mirror Claude Code's, for agents
+/// installed on this machine
+pub fn mirrors(scope: &Scope) -> Vec<PromptTarget> {
+    prompts::targets()
+        .into_iter()
+        .filter(|t| t.agent != "Claude Code" && t.is_installed())
+        .filter_map(|t| match scope {
+            Scope::Global => Some(t),
+            Scope::Project(root) => {
+                let dir = match t.agent {
+                    "Gemini CLI" => root.join(".gemini/commands"),
+                    "OpenCode" => root.join(".opencode/command"),
+                    // Codex CLI only reads prompts from the home directory
+                    _ => return None,
+                };
+                Some(PromptTarget { dir, ..t })
+            }
+        })
+        .collect()
+}
+
+/// Render a Claude Code command file for another agent
+pub fn render_for(target: &PromptTarget, name: &str, content: &str) -> String {
+    prompts::render(&prompts::Prompt::parse(name, content), target.format)
+}
@@ -0,0 +1,214 @@
+use anyhow::Result;
+use colored::*;
+
+use crate::instructions::Scope;
+use crate::mcp::targets;
+use crate::output;
+use crate::sandbox;
+use crate::skills::{agents, discovery};
+use crate::theme::Themed;
+use crate::tools::{self, installed_versions};
+use crate::versions::{fetch_latest_versions, is_newer_version};
+
+/// How a cell is colored
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tone {
+    Good,
+    Warn,
+    None,
+    Plain,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Cell {
+    text: String,
+    tone: Tone,
+}
+
+impl Cell {
+    fn new(text: impl Into<String>, tone: Tone) -> Self {
+        Self {
+            text: text.into(),
+            tone,
+        }
+    }
+
+    fn none() -> Self {
+        Self::new("-", Tone::None)
+    }
+
+    fn render(&self, width: usize) -> String {
+        let padded = format!("{:<width$}", self.text, width = width);
+        match self.tone {
+            Tone::Good => padded.success().to_string(),
+            Tone::Warn => padded.warning().to_string(),
+            Tone::None => padded.muted().to_string(),
+            Tone::Plain => padded,
+        }
+    }
+}
+
+const HEADERS: [&str; 6] = [
+    "Tool",
+    "Version",
+    "MCP",
+    "Skills",
+    "Project file",
+    "Sandbox",
+];
+
+fn version_cell(installed: &str, latest: Option<&str>) -> Cell {
+    match latest {
+        Some(latest) if is_newer_version(latest, installed) => {
+            Cell::new(format!("{} → {}", installed, latest), Tone::Warn)
+        }
+        Some(_) => Cell::new(format!("{} ✓", installed), Tone::Good),
+        None => Cell::new(installed, Tone::Plain),
+    }
+}
+
+/// One row per installed tool, built from the tool, MCP, skills,
+/// instructions and sandbox catalogs
+fn rows(versions: &[(String, String, Option<String>)]) -> Vec<[Cell; 6]> {
+    let catalog = tools::catalog();
+    let mcp_targets = targets::catalog();
+    let skill_agents = agents::catalog();
+    let project_files = Scope::Project(Default::default()).targets();
+    let sandboxes = sandbox::targets(&Scope::Global);
+
+    versions
+        .iter()
+        .filter_map(|(name, installed, latest)| {
+            let tool = catalog.iter().find(|t| &t.name == name)?;
+            let binary = tool.binary_name.as_deref().unwrap_or_default();
+
+            let mcp = match mcp_targets.iter().find(|t| t.binary_name == binary) {
+                Some(target) => match target.configured_servers() {
+                    Ok(servers) => {
+                        Cell::new(format!("yes ({} servers)", servers.len()), Tone::Good)
+                    }
+                    Err(_) => Cell::new("yes (unreadable)", Tone::Warn),
+                },
+                None => Cell::none(),
+            };
+
+            let skills = match skill_agents.iter().find(|a| a.binary_name == binary) {
+                Some(agent) => {
+                    let count = discovery::list_installed_skills(&agent.skills_path)
+                        .map(|s| s.len())
+                        .unwrap_or(0);
+                    Cell::new(format!("yes ({} installed)", count), Tone::Good)
+                }
+                None => Cell::none(),
+            };
+
+            let project = project_files
+                .iter()
+                .find(|t| t.agent.split(", ").any(|a| a == tool.name))
+                .and_then(|t| t.path.file_name())
+                .map(|f| Cell::new(f.to_string_lossy(), Tone::Plain))
+                .unwrap_or_else(Cell::none);
+
+            let sandbox = match sandboxes.iter().find(|s| s.agent == tool.name) {
+                Some(target) => match target.matching_preset() {
+                    Ok(Some(preset)) => Cell::new(format!("presets ({})", preset.name), Tone::Good),
+                    Ok(None) => Cell::new("presets (custom)", Tone::Warn),
+                    Err(_) => Cell::new("presets", Tone::Warn),
+                },
+                None => Cell::none(),
+            };
+
+            Some([
+                Cell::new(tool.name.clone(), Tone::Plain),
+                version_cell(installed, latest.as_deref()),
+                mcp,
+                skills,
+                project,
+                sandbox,
+            ])
+        })
+        .collect()
+}
+
+/// Handle `compare` command: a capability matrix of the installed tools
+pub async fn handle_compare() -> Result<()> {
+    let spinner = output::spinner("Checking installed tools and latest versions...");
+    let (installed, latest) = tokio::join!(
+        tokio::task::spawn_blocking(installed_versions),
+        fetch_latest_versions()
+    );
+    spinner.finish_and_clear();
+
+    let versions: Vec<(String, String, Option<String>)> = installed?
+        .into_iter()
+        .filter_map(|v| {
+            // "2.0.14 (Claude Code)" -> "2.0.14"
+            let installed = v.installed?.split_whitespace().next()?.to_string();
+            let latest = latest.get(v.name.as_str()).cloned().flatten();
+            Some((v.name, installed, latest))
+        })
+        .collect();
+    if versions.is_empty() {
+        println!("No AI tools installed; see `ai-cli apps install`");
+        return Ok(());
+    }
+
+    let rows = rows(&versions);
+    // The last column isn't padded
+    let widths: Vec<usize> = (0..HEADERS.len())
+        .map(|i| {
+            if i == HEADERS.len() - 1 {
+                return 0;
+            }
+            rows.iter()
+                .map(|row| row[i].text.chars().count())
+                .chain([HEADERS[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let header: Vec<String> = HEADERS
+        .iter()
+        .zip(&widths)
+        .map(|(h, w)| format!("{:<w$}", h, w = w).bold().to_string())
+        .collect();
+    println!("{}", header.join("  "));
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| cell.render(*w))
+            .collect();
+        println!("{}", cells.join("  "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_catalogs_by_tool() {
+        let versions = vec![
+            (
+                "Claude Code".to_string(),
+                "2.0.1".to_string(),
+                Some("2.0.5".to_string()),
+            ),
+            (
+                "Kilo Code CLI".to_string(),
+                "1.0.0".to_string(),
+                Some("1.0.0".to_string()),
+            ),
+        ];
+        let rows = rows(&versions);
+        assert_eq!(rows[0][1], Cell::new("2.0.1 → 2.0.5", Tone::Warn));
+        assert_eq!(rows[0][4], Cell::new("CLAUDE.md", Tone::Plain));
+        assert!(rows[0][2].text.starts_with("yes"));
+        assert!(rows[0][5].text.starts_with("presets"));
+        assert_eq!(rows[1][1], Cell::new("1.0.0 ✓", Tone::Good));
+        assert_eq!(rows[1][2], Cell::none());
+    }
+}
@@ -0,0 +1,78 @@
+use std::io;
+
+use anyhow::Result;
+use clap::builder::PossibleValuesParser;
+use clap::{Command, CommandFactory};
+use clap_complete::Shell;
+
+use crate::cli::Cli;
+use crate::mcp::servers;
+use crate::skills::{agents, discovery};
+use crate::tools;
+
+/// Print a completion script for `shell` to stdout
+pub fn handle_completions(shell: Shell) -> Result<()> {
+    let mut cmd = with_dynamic_values(Cli::command());
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut io::stdout());
+    Ok(())
+}
+
+/// Attach catalog-derived candidates to free-form arguments.
+///
+/// Candidates are baked into the generated script, so they reflect the
+/// catalogs and installed skills at the time `completions` was run.
+fn with_dynamic_values(cmd: Command) -> Command {
+    let tool_names: Vec<String> = tools::catalog()
+        .iter()
+        .map(|t| t.binary_name.clone().unwrap_or_else(|| t.name.clone()))
+        .collect();
+
+    let mut server_ids: Vec<String> = servers::catalog()
+        .iter()
+        .map(|s| s.id.to_string())
+        .collect();
+    server_ids.push("all".to_string());
+
+    let agent_ids: Vec<String> = agents::catalog().iter().map(|a| a.id.to_string()).collect();
+    let skill_names = installed_skill_names();
+
+    cmd.mut_subcommand("apps", |apps| {
+        ["upgrade", "update", "install", "add", "uninstall", "remove"]
+            .into_iter()
+            .fold(apps, |apps, sub| {
+                apps.mut_subcommand(sub, |c| c.mut_arg("tool", |a| candidates(a, &tool_names)))
+            })
+    })
+    .mut_subcommand("mcp", |mcp| {
+        ["enable", "disable"].into_iter().fold(mcp, |mcp, sub| {
+            mcp.mut_subcommand(sub, |c| c.mut_arg("server", |a| candidates(a, &server_ids)))
+        })
+    })
+    .mut_subcommand("skills", |skills| {
+        ["list", "install", "remove"]
+            .into_iter()
+            .fold(skills, |skills, sub| {
+                skills.mut_subcommand(sub, |c| c.mut_arg("agent", |a| candidates(a, &agent_ids)))
+            })
+            .mut_subcommand("remove", |c| {
+                c.mut_arg("skill", |a| candidates(a, &skill_names))
+            })
+    })
+}
+
+fn candidates(arg: clap::Arg, values: &[String]) -> clap::Arg {
+    arg.value_parser(PossibleValuesParser::new(values.to_vec()))
+}
+
+fn installed_skill_names() -> Vec<String> {
+    let mut names: Vec<String> = agents::catalog()
+        .iter()
+        .filter_map(|a| discovery::list_installed_skills(&a.skills_path).ok())
+        .flatten()
+        .map(|s| s.name)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use toml_edit::{DocumentMut, value};
+
+use crate::mcp::servers::{Placement, Transport};
+use crate::notify::Event;
+use crate::paths;
+use crate::settings::Policy;
+use crate::theme::ThemeConfig;
+
+/// User configuration from `~/.config/ai-cli/config.toml`
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Name of the profile last applied with `profile use`
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Check for updates in the background and print a hint (default: true)
+    #[serde(default)]
+    pub update_check: Option<bool>,
+    /// Check edited configs against their tools' JSON schemas (default: true)
+    #[serde(default)]
+    pub schema_check: Option<bool>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    /// User-defined command aliases, e.g. `up = "apps update"`
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    /// Permission policy applied with `settings apply`
+    #[serde(default)]
+    pub policy: Option<Policy>,
+    #[serde(default)]
+    pub gateway: GatewayConfig,
+    /// Organization policy checked by `doctor` and enforced by `sync pull`
+    #[serde(default)]
+    pub managed_config_url: Option<String>,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    /// Where npm-installed tools go
+    #[serde(default)]
+    pub npm: NpmConfig,
+    /// MCP servers defined outside the built-in catalog, by id
+    #[serde(default)]
+    pub mcp_servers: BTreeMap<String, CustomServer>,
+    #[serde(default)]
+    pub skills: SkillsConfig,
+}
+
+/// An MCP server defined by the user, e.g. imported with `mcp add --from`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CustomServer {
+    /// Display name (default: the id)
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// `http` or `sse` for a remote server (default: `http` when `url` is set)
+    #[serde(default, skip_serializing_if = "is_stdio")]
+    pub transport: Transport,
+    /// Endpoint of a remote server, which has no command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Environment of the server process
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Where the definition was imported from, e.g. `smithery/exa`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Values the server needs to work, e.g. an API key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub settings: Vec<ServerSetting>,
+}
+
+fn is_stdio(transport: &Transport) -> bool {
+    *transport == Transport::Stdio
+}
+
+/// A configuration value an MCP server asks for
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ServerSetting {
+    pub key: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+    /// How the value reaches the server (default: as an environment variable)
+    #[serde(default)]
+    pub placement: Placement,
+}
+
+/// Webhook that `apps check`, `mcp doctor` and `doctor` report findings to
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct NotifyConfig {
+    /// URL events are POSTed to, e.g. a Slack incoming webhook
+    #[serde(default)]
+    pub webhook: Option<String>,
+    /// Events to send (default: all)
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+/// Anonymous usage statistics, off unless turned on with `ai-cli telemetry on`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct TelemetryConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where batches are sent (default: the ai-cli project's collector)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+/// Settings for `ai-cli backup create`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct BackupConfig {
+    /// Number of backups to keep (default 10, 0 keeps all)
+    #[serde(default)]
+    pub keep: Option<usize>,
+    /// age recipient backups are encrypted to
+    #[serde(default)]
+    pub encrypt_to: Option<String>,
+}
+
+/// Skill installs and `skills search`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct SkillsConfig {
+    /// URL or path of a registry index searched instead of GitHub
+    #[serde(default)]
+    pub registry: Option<String>,
+    /// Copy skills into every agent instead of linking them to
+    /// `~/.ai-cli/skills/`, as if `--copy` was always given
+    #[serde(default)]
+    pub copy: bool,
+    /// Warn when a skill to install is larger than this many MB (default 10)
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+}
+
+/// Global npm installs
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct NpmConfig {
+    /// Install into `~/.local/share/ai-cli/npm` instead of npm's global prefix,
+    /// which nvm, fnm and mise replace with every Node version
+    #[serde(default)]
+    pub stable_prefix: bool,
+}
+
+/// Backends of the MCP gateway (`ai-cli mcp gateway`)
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct GatewayConfig {
+    /// Catalog server ids the gateway proxies to
+    #[serde(default)]
+    pub servers: Vec<String>,
+}
+
+/// A named set of MCP servers, skills and environment variables
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Profile {
+    /// MCP server ids to enable; every other catalog server gets disabled
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    /// Skill names (installed from their recorded source) or `owner/repo` sources
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Environment variables injected when launching tools with this profile
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Model passed as `--model` by `ai-cli run`
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Directory holding ai-cli's own configuration files
+pub fn config_dir() -> PathBuf {
+    paths::config_home().join("ai-cli")
+}
+
+pub fn config_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// MCP server definitions kept apart from config.toml, e.g. a company's
+/// internal servers shared as one file
+pub fn mcp_servers_path() -> PathBuf {
+    config_dir().join("mcp-servers.toml")
+}
+
+/// Load the servers in `mcp-servers.toml` by id, none when it doesn't exist
+pub fn load_mcp_servers() -> Result<BTreeMap<String, CustomServer>> {
+    let path = mcp_servers_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse TOML in {}", path.display()))
+}
+
+/// Load the config file, returning defaults when it doesn't exist
+pub fn load() -> Result<Config> {
+    let path = config_path();
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse TOML in {}", path.display()))
+}
+
+/// Set a top-level string key, keeping the rest of the file (and its comments) intact
+pub fn set_value(key: &str, new_value: &str) -> Result<()> {
+    edit(|doc| doc[key] = value(new_value))
+}
+
+/// Set a string list in `[table]`, keeping the rest of the file intact
+pub fn set_list(table: &str, key: &str, values: &[String]) -> Result<()> {
+    edit(|doc| {
+        if !doc.contains_table(table) {
+            doc[table] = toml_edit::table();
+        }
+        doc[table][key] = value(values.iter().collect::<toml_edit::Array>());
+    })
+}
+
+/// Set a boolean in `[table]`, keeping the rest of the file intact
+pub fn set_flag(table: &str, key: &str, enabled: bool) -> Result<()> {
+    edit(|doc| {
+        if !doc.contains_table(table) {
+            doc[table] = toml_edit::table();
+        }
+        doc[table][key] = value(enabled);
+    })
+}
+
+/// Set `[table.key]` to `item`, keeping the rest of the file intact
+pub fn set_table<T: Serialize>(table: &str, key: &str, item: &T) -> Result<()> {
+    let item: DocumentMut = toml::to_string(item)?
+        .parse()
+        .context("Failed to convert to TOML")?;
+    edit(|doc| {
+        if !doc.contains_table(table) {
+            let mut parent = toml_edit::Table::new();
+            parent.set_implicit(true);
+            doc[table] = toml_edit::Item::Table(parent);
+        }
+        doc[table][key] = toml_edit::Item::Table(item.as_table().clone());
+    })
+}
+
+fn edit(change: impl FnOnce(&mut DocumentMut)) -> Result<()> {
+    let path = config_path();
+    let content = if path.exists() {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
+    change(&mut doc);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(&path, doc.to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profiles() {
+        let config: Config = toml::from_str(
+            r#"
+            active_profile = "work"
+
+            [profiles.work]
+            mcp_servers = ["linear"]
+            skills = ["pdf", "acme/skills"]
+
+            [profiles.work.env]
+            ANTHROPIC_BASE_URL = "https://proxy.example.com"
+
+            [profiles.personal]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.active_profile.as_deref(), Some("work"));
+        let work = &config.profiles["work"];
+        assert_eq!(work.mcp_servers, vec!["linear"]);
+        assert_eq!(work.skills, vec!["pdf", "acme/skills"]);
+        assert_eq!(work.env["ANTHROPIC_BASE_URL"], "https://proxy.example.com");
+        assert!(config.profiles["personal"].mcp_servers.is_empty());
+    }
+
+    #[test]
+    fn parses_mcp_servers_file() {
+        let servers: BTreeMap<String, CustomServer> = toml::from_str(
+            r#"
+            [acme-search]
+            command = "acme-mcp"
+            args = ["--stdio"]
+            description = "Search the intranet"
+
+            [acme-search.env]
+            ACME_REGION = "eu"
+
+            [acme-wiki]
+            transport = "sse"
+            url = "https://wiki.acme.example/sse"
+            "#,
+        )
+        .unwrap();
+        let search = &servers["acme-search"];
+        assert_eq!(search.name, "");
+        assert_eq!(search.args, ["--stdio"]);
+        assert_eq!(search.env["ACME_REGION"], "eu");
+        assert!(search.settings.is_empty());
+        assert_eq!(search.transport, Transport::Stdio);
+        let wiki = &servers["acme-wiki"];
+        assert_eq!(wiki.transport, Transport::Sse);
+        assert_eq!(wiki.command, "");
+    }
+}
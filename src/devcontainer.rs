@@ -0,0 +1,237 @@
+//! `init --devcontainer`: a container definition with the same AI tooling as
+//! this machine.
+//!
+//! The Dockerfile installs the tools and then runs `ai-cli import` on a
+//! snapshot of this machine, so MCP servers and skills are set up at build time.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde_json::{Map, Value, json};
+
+use crate::actions::find_tool;
+use crate::exit::{self, Outcome};
+use crate::keys;
+use crate::mcp::targets;
+use crate::snapshot::{self, Snapshot, ToolEntry};
+use crate::theme::Themed;
+use crate::tools::{self, InstallMethod, Tool};
+
+const BASE_IMAGE: &str = "mcr.microsoft.com/devcontainers/javascript-node:22-bookworm";
+const AI_CLI_REPO: &str = "https://github.com/mikko-kohtala/ai-cli";
+const SNAPSHOT_FILE: &str = "ai-cli.json";
+
+/// The Dockerfile installing `tools` (with the version to pin, if known)
+pub fn render_dockerfile(tools: &[(Tool, Option<String>)]) -> String {
+    let mut out = format!(
+        "FROM rust:1-bookworm AS ai-cli\n\
+         RUN cargo install --locked --git {}\n\
+         \n\
+         FROM {}\n\
+         COPY --from=ai-cli /usr/local/cargo/bin/ai-cli /usr/local/bin/ai-cli\n\
+         USER node\n\
+         ENV PATH=\"/home/node/.local/bin:/home/node/.amp/bin:${{PATH}}\"\n",
+        AI_CLI_REPO, BASE_IMAGE
+    );
+
+    let packages: Vec<String> = tools
+        .iter()
+        .filter_map(|(tool, version)| match &tool.install_method {
+            InstallMethod::Npm(package) => Some(match version {
+                Some(version) => format!("{}@{}", package, version),
+                None => package.clone(),
+            }),
+            _ => None,
+        })
+        .collect();
+    if !packages.is_empty() {
+        out.push_str(&format!(
+            "\nRUN npm install -g \\\n    {}\n",
+            packages.join(" \\\n    ")
+        ));
+    }
+
+    for (tool, version) in tools {
+        let url = match &tool.install_method {
+            InstallMethod::Bootstrap(url) | InstallMethod::Amp(url) => url,
+            InstallMethod::Npm(_) => continue,
+        };
+        out.push('\n');
+        if let Some(version) = version {
+            out.push_str(&format!(
+                "# {} {} on the host; the installer script installs the latest release\n",
+                tool.name, version
+            ));
+        }
+        out.push_str(&format!("RUN curl -fsSL {} | bash\n", url));
+    }
+
+    out.push_str(&format!(
+        "\n# MCP servers and skills from the host\n\
+         COPY {file} /tmp/{file}\n\
+         RUN ai-cli --yes import /tmp/{file}\n",
+        file = SNAPSHOT_FILE
+    ));
+    out
+}
+
+/// devcontainer.json forwarding the API keys the tools use from the host
+pub fn render_devcontainer_json(name: &str, tools: &[(Tool, Option<String>)]) -> Value {
+    let names: Vec<&str> = tools.iter().map(|(t, _)| t.name.as_str()).collect();
+    let mut remote_env = Map::new();
+    for provider in keys::catalog()
+        .into_iter()
+        .filter(|p| p.tools.iter().any(|t| names.contains(t)))
+    {
+        let var = provider.env_vars[0];
+        remote_env.insert(
+            var.to_string(),
+            Value::String(format!("${{localEnv:{}}}", var)),
+        );
+    }
+    json!({
+        "name": name,
+        "build": { "dockerfile": "Dockerfile" },
+        "remoteUser": "node",
+        "remoteEnv": remote_env,
+    })
+}
+
+/// Tools to install: the named ones, or everything installed here
+fn select_tools(names: &[String], snapshot: &Snapshot) -> Result<Vec<(Tool, Option<String>)>> {
+    let catalog = tools::catalog();
+    let installed = |tool: &Tool| {
+        snapshot
+            .tools
+            .iter()
+            .find(|t| t.name == tool.name)
+            .and_then(|t| t.version.as_deref())
+            // "2.0.14 (Claude Code)" -> "2.0.14"
+            .and_then(|v| v.split_whitespace().next())
+            .map(str::to_string)
+    };
+    if names.is_empty() {
+        return Ok(catalog
+            .iter()
+            .filter_map(|tool| installed(tool).map(|v| (tool.clone(), Some(v))))
+            .collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            let tool = find_tool(&catalog, name)
+                .ok_or_else(|| exit::usage(format!("Unknown tool '{}'", name)))?;
+            Ok((tool.clone(), installed(tool)))
+        })
+        .collect()
+}
+
+/// Handle `init --devcontainer` command
+pub fn handle_devcontainer(names: &[String], force: bool) -> Result<Outcome> {
+    let root = env::current_dir().context("Failed to get current directory")?;
+    let dir = root.join(".devcontainer");
+    let files = ["devcontainer.json", "Dockerfile", SNAPSHOT_FILE];
+    if !force && files.iter().any(|f| dir.join(f).exists()) {
+        return Err(exit::usage(format!(
+            "{} already has a devcontainer; pass --force to overwrite",
+            dir.display()
+        )));
+    }
+
+    let mut snapshot = snapshot::capture()?;
+    let selected = select_tools(names, &snapshot)?;
+    if selected.is_empty() {
+        return Err(exit::usage(
+            "No AI tools installed; choose some with --tool <name>",
+        ));
+    }
+
+    // Only configure MCP servers for the tools going into the container
+    let binaries: Vec<&str> = selected
+        .iter()
+        .filter_map(|(t, _)| t.binary_name.as_deref())
+        .collect();
+    let mcp_binaries: Vec<(String, &str)> = targets::catalog()
+        .into_iter()
+        .map(|t| (t.name.to_string(), t.binary_name))
+        .collect();
+    snapshot.mcp.retain(|target, _| {
+        mcp_binaries
+            .iter()
+            .any(|(name, binary)| name == target && binaries.contains(binary))
+    });
+    snapshot.tools = selected
+        .iter()
+        .map(|(tool, version)| ToolEntry {
+            name: tool.name.clone(),
+            version: version.clone(),
+        })
+        .collect();
+
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "ai-cli".to_string());
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    write(
+        &dir.join("devcontainer.json"),
+        &serde_json::to_string_pretty(&render_devcontainer_json(&name, &selected))?,
+    )?;
+    write(&dir.join("Dockerfile"), &render_dockerfile(&selected))?;
+    snapshot.save(&dir.join(SNAPSHOT_FILE))?;
+
+    println!(
+        "{} Wrote {} with:",
+        "✓".success(),
+        dir.display().to_string().bold()
+    );
+    for (tool, version) in &selected {
+        println!(
+            "  {} {} {}",
+            "+".success(),
+            tool.name,
+            version.as_deref().unwrap_or("latest").muted()
+        );
+    }
+    let servers: usize = snapshot.mcp.values().map(Vec::len).sum();
+    println!(
+        "  {} {} MCP server entries, {} skills",
+        "+".success(),
+        servers,
+        snapshot.skills.len()
+    );
+    Ok(Outcome::Success)
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, format!("{}\n", content.trim_end()))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pins_npm_tools_and_runs_installers() {
+        let selected = vec![
+            (tools::codex_tool(), Some("0.50.0".to_string())),
+            (tools::gemini_tool(), None),
+            (tools::claude_tool(), Some("2.0.14".to_string())),
+        ];
+        let dockerfile = render_dockerfile(&selected);
+        assert!(dockerfile.contains("    @openai/codex@0.50.0 \\\n    @google/gemini-cli\n"));
+        assert!(dockerfile.contains("RUN curl -fsSL https://claude.ai/install.sh | bash"));
+        assert!(dockerfile.ends_with("RUN ai-cli --yes import /tmp/ai-cli.json\n"));
+
+        let json = render_devcontainer_json("app", &selected);
+        assert_eq!(
+            json["remoteEnv"]["ANTHROPIC_API_KEY"],
+            "${localEnv:ANTHROPIC_API_KEY}"
+        );
+    }
+}
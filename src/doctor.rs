@@ -0,0 +1,342 @@
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+
+use crate::config;
+use crate::http;
+use crate::managed;
+use crate::mcp::{self, ConfigHealth, servers, targets};
+use crate::node;
+use crate::notify::{self, Event};
+use crate::paths;
+use crate::skills::doctor as skill_doctor;
+use crate::skills::{agents, discovery};
+use crate::theme::Themed;
+use crate::tools::{self, InstallMethod};
+use crate::versions::{check_latest_versions, is_newer_version};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug)]
+struct Finding {
+    severity: Severity,
+    message: String,
+    fix: Option<String>,
+}
+
+impl Finding {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn warning(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn error(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Handle top-level `doctor` command. Returns `false` when errors were found.
+pub async fn handle_doctor() -> Result<bool> {
+    let mut sections = vec![
+        ("Apps", check_apps().await),
+        ("PATH", check_path()),
+        ("MCP configs", check_mcp()),
+        ("Skills", check_skills()),
+    ];
+    if let Some(findings) = check_managed().await {
+        sections.push(("Managed policy", findings));
+    }
+    sections.push(("Network", check_network().await));
+
+    let mut warnings = 0;
+    let mut errors = 0;
+
+    for (title, findings) in &sections {
+        println!("{}", title.bold());
+        for finding in findings {
+            let marker = match finding.severity {
+                Severity::Ok => "✓".success(),
+                Severity::Warning => {
+                    warnings += 1;
+                    "!".warning()
+                }
+                Severity::Error => {
+                    errors += 1;
+                    "✗".error()
+                }
+            };
+            println!("  {} {}", marker, finding.message);
+            if let Some(fix) = &finding.fix {
+                println!("    {} {}", "→".info(), fix.muted());
+            }
+        }
+        println!();
+    }
+
+    if errors == 0 && warnings == 0 {
+        println!("{}", "✓ Everything looks good".success());
+    } else {
+        println!(
+            "{}",
+            format!("Found {} error(s) and {} warning(s)", errors, warnings).warning()
+        );
+    }
+
+    Ok(errors == 0)
+}
+
+async fn check_apps() -> Vec<Finding> {
+    let mut versions = tools::installed_versions();
+    check_latest_versions(&mut versions).await;
+
+    let mut findings = Vec::new();
+    let installed: Vec<_> = versions.iter().filter(|v| v.installed.is_some()).collect();
+
+    if installed.is_empty() {
+        findings.push(Finding::warning(
+            "No AI CLI tools installed",
+            "Run `ai-cli apps install` to pick tools to install",
+        ));
+        return findings;
+    }
+
+    for version in installed {
+        let id = version
+            .identifier
+            .as_deref()
+            .unwrap_or(version.name.as_str());
+        match (&version.installed, &version.latest) {
+            (Some(installed), Some(latest)) if is_newer_version(latest, installed) => {
+                findings.push(Finding::warning(
+                    format!(
+                        "{} {} is outdated (latest {})",
+                        version.name, installed, latest
+                    ),
+                    format!("Run `ai-cli apps upgrade {}`", id),
+                ));
+            }
+            (Some(installed), _) => {
+                findings.push(Finding::ok(format!("{} {}", version.name, installed)));
+            }
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+fn check_path() -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let path_dirs: Vec<PathBuf> = env::var_os("PATH")
+        .map(|p| env::split_paths(&p).collect())
+        .unwrap_or_default();
+
+    let catalog = tools::catalog();
+    let needs_local_bin = catalog.iter().any(|t| {
+        matches!(
+            t.install_method,
+            InstallMethod::Bootstrap(_) | InstallMethod::Amp(_)
+        ) && t.is_installed()
+    });
+
+    if needs_local_bin {
+        let local_bin = paths::local_bin();
+        if path_dirs.iter().any(|d| d == &local_bin) {
+            findings.push(Finding::ok(format!("{} is on PATH", local_bin.display())));
+        } else {
+            findings.push(Finding::warning(
+                format!("{} is not on PATH", local_bin.display()),
+                "Add `export PATH=\"$HOME/.local/bin:$PATH\"` to your shell rc file",
+            ));
+        }
+    }
+
+    let stable = config::load().is_ok_and(|c| c.npm.stable_prefix);
+    let prefix = if stable {
+        Some(node::stable_prefix())
+    } else {
+        node::global_prefix()
+    };
+    if !stable && let Some(manager) = prefix.as_deref().and_then(node::manager_of) {
+        findings.push(Finding::warning(
+            format!(
+                "npm global prefix belongs to the active {} Node version",
+                manager.name()
+            ),
+            "Set `[npm] stable_prefix = true` in config.toml so tools survive Node upgrades",
+        ));
+    }
+    match prefix {
+        Some(prefix) => {
+            let npm_bin = prefix.join("bin");
+            if path_dirs.iter().any(|d| d == &npm_bin) {
+                findings.push(Finding::ok(format!(
+                    "npm global bin {} is on PATH",
+                    npm_bin.display()
+                )));
+            } else {
+                findings.push(Finding::warning(
+                    format!("npm global bin {} is not on PATH", npm_bin.display()),
+                    "Add the npm global bin directory to PATH so npm-installed tools are found",
+                ));
+            }
+        }
+        None => findings.push(Finding::warning(
+            "npm not found",
+            "Install Node.js to manage npm-based tools (Codex CLI, Gemini CLI, ...)",
+        )),
+    }
+
+    findings
+}
+
+/// The checks of `mcp doctor`, for every installed tool
+fn check_mcp() -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let servers = servers::catalog();
+    let mut checked = Vec::new();
+
+    for target in targets::catalog() {
+        if !target.is_installed() {
+            continue;
+        }
+
+        let path = target.config_path();
+        let health = mcp::inspect(&target, &servers);
+        findings.push(match &health {
+            ConfigHealth::Missing => {
+                Finding::ok(format!("{}: config not created yet", target.name))
+            }
+            ConfigHealth::Broken(e) => Finding::error(
+                format!("{}: {:#}", target.name, e),
+                format!("Fix or remove {}", path.display()),
+            ),
+            ConfigHealth::Enabled(_) => Finding::ok(format!("{}: {}", target.name, path.display())),
+        });
+        checked.push((target.name, health));
+    }
+
+    for drifted in mcp::config_drift(&servers, &checked) {
+        findings.push(Finding::warning(
+            drifted,
+            "Run `ai-cli mcp enable <server>` to enable it in every tool",
+        ));
+    }
+
+    findings
+}
+
+/// The checks of `skills doctor`, for every installed agent
+fn check_skills() -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for agent in agents::catalog() {
+        if !agent.is_installed() || !agent.skills_path.exists() {
+            continue;
+        }
+
+        let problems = skill_doctor::inspect(&agent.skills_path);
+        let healthy = discovery::list_installed_skills(&agent.skills_path)
+            .map(|skills| {
+                skills
+                    .iter()
+                    .filter(|skill| !problems.iter().any(|p| skill.path.ends_with(&p.skill)))
+                    .count()
+            })
+            .unwrap_or(0);
+        for problem in problems {
+            findings.push(Finding::warning(
+                format!("{}: {}: {}", agent.name, problem.skill, problem.message),
+                problem.fix,
+            ));
+        }
+
+        findings.push(Finding::ok(format!(
+            "{}: {} healthy skill(s) in {}",
+            agent.name,
+            healthy,
+            agent.skills_path.display()
+        )));
+    }
+
+    if findings.is_empty() {
+        findings.push(Finding::ok("No skills directories found"));
+    }
+
+    findings
+}
+
+/// Deviations from the organization policy, when `managed_config_url` is set
+async fn check_managed() -> Option<Vec<Finding>> {
+    let policy = match managed::load().await {
+        Ok(policy) => policy?,
+        Err(e) => {
+            return Some(vec![Finding::error(
+                format!("Failed to load the managed policy: {:#}", e),
+                "Check managed_config_url in config.toml and your network",
+            )]);
+        }
+    };
+    let deviations = managed::check(&policy);
+    let messages: Vec<String> = deviations.iter().map(|d| d.message()).collect();
+    notify::send(Event::PolicyViolation, "doctor", &messages).await;
+    if deviations.is_empty() {
+        return Some(vec![Finding::ok("Matches the organization's policy")]);
+    }
+    Some(
+        deviations
+            .iter()
+            .map(|d| Finding::error(d.message(), d.fix()))
+            .collect(),
+    )
+}
+
+async fn check_network() -> Vec<Finding> {
+    let hosts = [
+        "https://registry.npmjs.org",
+        "https://pypi.org",
+        "https://github.com",
+    ];
+
+    // Sent directly rather than through `http::send`, so hosts are probed
+    // even when earlier requests in this run failed
+    let checks = hosts.iter().map(|url| async move {
+        let request = http::client().head(*url).timeout(Duration::from_secs(5));
+        (*url, request.send().await)
+    });
+
+    futures::future::join_all(checks)
+        .await
+        .into_iter()
+        .map(|(url, result)| match result {
+            Ok(_) => Finding::ok(format!("{} reachable", url)),
+            Err(e) => Finding::error(
+                format!("{} unreachable: {}", url, e),
+                "Check your internet connection or proxy settings (HTTPS_PROXY)",
+            ),
+        })
+        .collect()
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+
+/// Exit codes reported by ai-cli. Keep in sync with the README.
+pub const SUCCESS: u8 = 0;
+/// Unexpected error
+pub const FAILURE: u8 = 1;
+/// Invalid arguments, unknown tool/server/agent names
+pub const USAGE: u8 = 2;
+/// A network request failed
+pub const NETWORK: u8 = 3;
+/// Some items succeeded and some failed
+pub const PARTIAL_FAILURE: u8 = 4;
+/// The command had nothing to do (already installed, up to date, ...)
+pub const NOTHING_TO_DO: u8 = 5;
+/// `apps check` found tools with newer versions
+pub const UPDATES_AVAILABLE: u8 = 6;
+
+/// Result of a command that completed without an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    NothingToDo,
+    UpdatesAvailable,
+    PartialFailure,
+    /// The command ran but found problems (e.g. `doctor`)
+    Failure,
+}
+
+impl Outcome {
+    pub fn code(self) -> u8 {
+        match self {
+            Outcome::Success => SUCCESS,
+            Outcome::NothingToDo => NOTHING_TO_DO,
+            Outcome::UpdatesAvailable => UPDATES_AVAILABLE,
+            Outcome::PartialFailure => PARTIAL_FAILURE,
+            Outcome::Failure => FAILURE,
+        }
+    }
+
+    /// Outcome for a batch where `failed` of the items went wrong
+    pub fn from_failures(failed: usize) -> Self {
+        if failed == 0 {
+            Outcome::Success
+        } else {
+            Outcome::PartialFailure
+        }
+    }
+}
+
+/// Error caused by invalid user input rather than by the environment
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// Shorthand for returning a [`UsageError`] from a function returning `anyhow::Result`
+pub fn usage(message: impl Into<String>) -> anyhow::Error {
+    UsageError(message.into()).into()
+}
+
+/// Map an error onto an exit code by inspecting its cause chain
+pub fn code_for_error(err: &anyhow::Error) -> u8 {
+    for cause in err.chain() {
+        if cause.is::<UsageError>() {
+            return USAGE;
+        }
+        if cause.is::<reqwest::Error>() || cause.is::<crate::http::Offline>() {
+            return NETWORK;
+        }
+    }
+    FAILURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn usage_errors_map_to_usage_code() {
+        let err = usage("Unknown server: foo");
+        assert_eq!(code_for_error(&err), USAGE);
+
+        let wrapped = Err::<(), _>(err).context("while enabling").unwrap_err();
+        assert_eq!(code_for_error(&wrapped), USAGE);
+    }
+
+    #[test]
+    fn other_errors_map_to_failure() {
+        let err = anyhow::anyhow!("npm install failed");
+        assert_eq!(code_for_error(&err), FAILURE);
+    }
+
+    #[test]
+    fn batch_outcome_reflects_failures() {
+        assert_eq!(Outcome::from_failures(0), Outcome::Success);
+        assert_eq!(Outcome::from_failures(2).code(), PARTIAL_FAILURE);
+    }
+}
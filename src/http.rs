@@ -0,0 +1,294 @@
+//! Shared HTTP access for every module.
+//!
+//! One client for all requests, requests to the same host spaced out, hosts
+//! that failed to connect skipped for the rest of the run, and GET responses
+//! cached on disk so repeated version checks and searches don't hit the
+//! registries again. With `AI_CLI_OFFLINE` set, nothing is sent and cached
+//! responses are used regardless of their age.
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::logging;
+use crate::paths;
+
+/// Used unless a request sets its own timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Minimum gap between two requests to the same host
+const HOST_INTERVAL: Duration = Duration::from_millis(200);
+/// Cached responses older than this are removed
+const CACHE_MAX_AGE_SECS: i64 = 7 * 24 * 60 * 60;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(DEFAULT_TIMEOUT)
+        .user_agent(concat!("ai-cli/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .unwrap_or_default()
+});
+
+/// When the next request to each host may start
+static NEXT_SLOT: LazyLock<Mutex<HashMap<String, Instant>>> = LazyLock::new(Default::default);
+
+/// Hosts that could not be reached during this run
+static UNREACHABLE: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(Default::default);
+
+/// A request that was not sent because the network is known to be unavailable
+#[derive(Debug)]
+pub struct Offline(String);
+
+impl fmt::Display for Offline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Offline {}
+
+/// The shared client; send requests built with it through [`send`]
+pub fn client() -> &'static reqwest::Client {
+    &CLIENT
+}
+
+/// Whether `AI_CLI_OFFLINE` asks for no network access
+pub fn offline() -> bool {
+    env::var_os("AI_CLI_OFFLINE").is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Wait until `host` may be asked again
+async fn pace(host: &str) {
+    let wait = {
+        let mut slots = NEXT_SLOT.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let start = slots.get(host).copied().filter(|t| *t > now).unwrap_or(now);
+        slots.insert(host.to_string(), start + HOST_INTERVAL);
+        start - now
+    };
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Send `request` unless the network or its host is known to be unavailable
+///
+/// Adds `GITHUB_TOKEN` to GitHub API requests that don't carry credentials.
+pub async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+    let (client, request) = request.build_split();
+    let mut request = request.context("Failed to build HTTP request")?;
+    let url = request.url().clone();
+    let host = url.host_str().unwrap_or_default().to_string();
+
+    if offline() {
+        return Err(Offline(format!("Not fetching {} (AI_CLI_OFFLINE is set)", url)).into());
+    }
+    if UNREACHABLE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(&host)
+    {
+        return Err(Offline(format!("Not fetching {} ({} is unreachable)", url, host)).into());
+    }
+    if host == "api.github.com"
+        && !request
+            .headers()
+            .contains_key(reqwest::header::AUTHORIZATION)
+        && let Some(token) = env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty())
+        && let Ok(value) = format!("Bearer {}", token).parse()
+    {
+        request
+            .headers_mut()
+            .insert(reqwest::header::AUTHORIZATION, value);
+    }
+
+    pace(&host).await;
+    match client.execute(request).await {
+        Ok(response) => Ok(response),
+        Err(e) => {
+            if e.is_connect() || e.is_timeout() {
+                UNREACHABLE
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(host);
+            }
+            Err(e).with_context(|| format!("Failed to reach {}", url))
+        }
+    }
+}
+
+/// A cached GET response
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    fetched_at: i64,
+    body: Value,
+}
+
+fn cache_dir() -> PathBuf {
+    paths::cache_home().join("ai-cli").join("http")
+}
+
+/// Named after a hash of `url` that stays the same across Rust releases
+fn cache_path(url: &str) -> PathBuf {
+    cache_dir().join(format!("{:x}.json", Sha256::digest(url.as_bytes())))
+}
+
+fn load(url: &str) -> Option<Entry> {
+    let content = fs::read_to_string(cache_path(url)).ok()?;
+    let entry: Entry = serde_json::from_str(&content).ok()?;
+    (entry.url == url).then_some(entry)
+}
+
+fn store(url: &str, body: &Value) -> Result<()> {
+    let dir = cache_dir();
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    let now = chrono::Utc::now().timestamp();
+    let entry = Entry {
+        url: url.to_string(),
+        fetched_at: now,
+        body: body.clone(),
+    };
+    let path = cache_path(url);
+    fs::write(&path, serde_json::to_string(&entry)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // Entries for URLs that are no longer asked for
+    for old in fs::read_dir(&dir).into_iter().flatten().flatten() {
+        let expired = old.metadata().and_then(|m| m.modified()).is_ok_and(|t| {
+            now - chrono::DateTime::<chrono::Utc>::from(t).timestamp() > CACHE_MAX_AGE_SECS
+        });
+        if expired {
+            let _ = fs::remove_file(old.path());
+        }
+    }
+    Ok(())
+}
+
+/// GET `url` through the cache: a response younger than `ttl` is reused, and
+/// an older one is used when the request fails
+async fn get_cached<F, Fut>(url: &str, ttl: Duration, fetch: F) -> Result<Value>
+where
+    F: FnOnce(reqwest::Response) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let cached = load(url);
+    let now = chrono::Utc::now().timestamp();
+    if let Some(entry) = &cached
+        && (now - entry.fetched_at < ttl.as_secs() as i64 || offline())
+    {
+        return Ok(entry.body.clone());
+    }
+
+    let result = async {
+        let response = send(client().get(url))
+            .await?
+            .error_for_status()
+            .with_context(|| format!("Failed to fetch {}", url))?;
+        fetch(response).await
+    }
+    .await;
+    match (result, cached) {
+        (Ok(body), _) => {
+            if let Err(e) = store(url, &body) {
+                logging::line(&format!("http cache: {:#}", e));
+            }
+            Ok(body)
+        }
+        (Err(e), Some(entry)) => {
+            logging::line(&format!("http: using cached {}: {:#}", url, e));
+            Ok(entry.body)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+/// GET and parse a JSON document, cached for `ttl`
+///
+/// The parsed `T` is what's cached, so large documents only cost the fields
+/// the caller reads.
+pub async fn get_json<T: DeserializeOwned + Serialize>(url: &str, ttl: Duration) -> Result<T> {
+    let body = get_cached(url, ttl, |response| async move {
+        let parsed: T = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse the response from {}", url))?;
+        Ok(serde_json::to_value(parsed)?)
+    })
+    .await?;
+    serde_json::from_value(body).with_context(|| format!("Unexpected cached response for {}", url))
+}
+
+/// GET a text document, cached for `ttl`
+pub async fn get_text(url: &str, ttl: Duration) -> Result<String> {
+    let body = get_cached(url, ttl, |response| async move {
+        let text = response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read the response from {}", url))?;
+        Ok(Value::String(text))
+    })
+    .await?;
+    Ok(body.as_str().unwrap_or_default().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn serves_fresh_responses_from_the_cache() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/pkg");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"version":"1.2.3","readme":"long"}"#);
+            })
+            .await;
+
+        #[derive(Serialize, Deserialize)]
+        struct Package {
+            version: String,
+        }
+        let url = server.url("/pkg");
+        for _ in 0..2 {
+            let package: Package = get_json(&url, Duration::from_secs(60)).await.unwrap();
+            assert_eq!(package.version, "1.2.3");
+        }
+        mock.assert_calls_async(1).await;
+        assert!(cache_path(&url).starts_with(paths::home()));
+    }
+
+    #[test]
+    fn cache_names_are_stable() {
+        assert_eq!(
+            cache_path("https://registry.npmjs.org/@openai/codex")
+                .file_name()
+                .unwrap(),
+            "24b03fd2c080e6bd2d342d35ba08aa38216274ea82d7aa2677d10f2d1f9c13ed.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_hosts_that_were_unreachable() {
+        // Nothing listens on port 9
+        let url = "http://127.0.0.9:9/down";
+        let first = send(client().get(url)).await.unwrap_err();
+        assert!(first.chain().any(|c| c.is::<reqwest::Error>()));
+        let second = send(client().get(url)).await.unwrap_err();
+        assert!(second.is::<Offline>());
+    }
+}
@@ -0,0 +1,316 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// File an agent reads its instructions from
+#[derive(Debug, Clone)]
+pub struct InstructionsTarget {
+    pub agent: &'static str,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetStatus {
+    InSync,
+    Drifted,
+    Missing,
+}
+
+/// Which set of instruction files to work on
+#[derive(Debug, Clone)]
+pub enum Scope {
+    /// Per-user files in each agent's config directory
+    Global,
+    /// Files in a project directory
+    Project(PathBuf),
+}
+
+impl Scope {
+    pub fn from_flag(project: bool) -> Result<Self> {
+        if project {
+            Ok(Scope::Project(
+                env::current_dir().context("Failed to get current directory")?,
+            ))
+        } else {
+            Ok(Scope::Global)
+        }
+    }
+
+    /// The single canonical document the targets are generated from
+    pub fn canonical_path(&self) -> PathBuf {
+        match self {
+            Scope::Global => config::config_dir().join("instructions.md"),
+            Scope::Project(root) => root.join(".ai-cli").join("instructions.md"),
+        }
+    }
+
+    pub fn targets(&self) -> Vec<InstructionsTarget> {
+        match self {
+            Scope::Global => {
+                let home = paths::home();
+                let config_home = paths::config_home();
+                vec![
+                    target("Claude Code", home.join(".claude/CLAUDE.md")),
+                    target("Codex CLI", home.join(".codex/AGENTS.md")),
+                    target("Gemini CLI", home.join(".gemini/GEMINI.md")),
+                    target("Amp", config_home.join("amp/AGENTS.md")),
+                    target("OpenCode", config_home.join("opencode/AGENTS.md")),
+                ]
+            }
+            Scope::Project(root) => vec![
+                target("Claude Code", root.join("CLAUDE.md")),
+                target("Codex CLI, Amp, OpenCode, Cursor", root.join("AGENTS.md")),
+                target("Gemini CLI", root.join("GEMINI.md")),
+                target(
+                    "GitHub Copilot",
+                    root.join(".github/copilot-instructions.md"),
+                ),
+            ],
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Scope::Global => "global".to_string(),
+            Scope::Project(root) => format!("project {}", root.display()),
+        }
+    }
+}
+
+fn target(agent: &'static str, path: PathBuf) -> InstructionsTarget {
+    InstructionsTarget { agent, path }
+}
+
+/// Compare a target file with the canonical content
+pub fn status_of(target: &InstructionsTarget, canonical: &str) -> TargetStatus {
+    match fs::read_to_string(&target.path) {
+        Ok(content) if content == canonical => TargetStatus::InSync,
+        Ok(_) => TargetStatus::Drifted,
+        Err(_) => TargetStatus::Missing,
+    }
+}
+
+fn read_canonical(scope: &Scope) -> Result<String> {
+    let path = scope.canonical_path();
+    if !path.exists() {
+        return Err(exit::usage(format!(
+            "No canonical instructions at {} (run `ai-cli instructions init{}` first)",
+            path.display(),
+            if matches!(scope, Scope::Project(_)) {
+                " --project"
+            } else {
+                ""
+            }
+        )));
+    }
+    fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Handle `instructions status` command. Returns `false` when any target drifted.
+pub fn handle_status(scope: &Scope) -> Result<bool> {
+    let canonical = read_canonical(scope)?;
+    println!(
+        "{} {} ({})",
+        "Canonical:".bold(),
+        scope.canonical_path().display(),
+        scope.label()
+    );
+    println!();
+
+    let mut drifted = 0;
+    for target in scope.targets() {
+        let (marker, state) = match status_of(&target, &canonical) {
+            TargetStatus::InSync => ("✓".success(), "in sync".success()),
+            TargetStatus::Drifted => {
+                drifted += 1;
+                ("!".warning(), "drifted".warning())
+            }
+            TargetStatus::Missing => ("-".muted(), "missing".muted()),
+        };
+        println!(
+            "  {} {:<34} {} {}",
+            marker,
+            target.agent,
+            state,
+            target.path.display().to_string().muted()
+        );
+    }
+
+    if drifted > 0 {
+        println!();
+        println!(
+            "{} {} file(s) were edited outside ai-cli; `ai-cli instructions sync` overwrites them",
+            "→".info(),
+            drifted
+        );
+    }
+    Ok(drifted == 0)
+}
+
+/// Handle `instructions sync` command
+pub fn handle_sync(scope: &Scope) -> Result<Outcome> {
+    let canonical = read_canonical(scope)?;
+    let targets = scope.targets();
+
+    let stale: Vec<_> = targets
+        .iter()
+        .map(|t| (t, status_of(t, &canonical)))
+        .filter(|(_, status)| *status != TargetStatus::InSync)
+        // Don't create config directories for agents that were never set up
+        .filter(|(t, status)| {
+            !(matches!(scope, Scope::Global)
+                && *status == TargetStatus::Missing
+                && t.path.parent().is_some_and(|p| !p.exists()))
+        })
+        .collect();
+    if stale.is_empty() {
+        println!("{} All instruction files are in sync", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let drifted: Vec<_> = stale
+        .iter()
+        .filter(|(_, status)| *status == TargetStatus::Drifted)
+        .collect();
+    if !drifted.is_empty() {
+        println!(
+            "{}",
+            "These files differ from the canonical instructions:".bold()
+        );
+        for (target, _) in &drifted {
+            println!("  {} {}", "!".warning(), target.path.display());
+        }
+        if !prompt::confirm("Overwrite them?")? {
+            println!("Cancelled.");
+            return Ok(Outcome::NothingToDo);
+        }
+    }
+
+    let mut failed = 0;
+    for (target, _) in stale {
+        match logging::write_file(&target.path, &canonical) {
+            Ok(()) => println!(
+                "{} {} ({})",
+                "✓".success(),
+                target.path.display(),
+                target.agent
+            ),
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), target.path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `instructions init` command: create the canonical document,
+/// seeded from `from` or the first existing target file
+pub fn handle_init(scope: &Scope, from: Option<&Path>) -> Result<()> {
+    let canonical_path = scope.canonical_path();
+    if canonical_path.exists() {
+        return Err(exit::usage(format!(
+            "Canonical instructions already exist at {}",
+            canonical_path.display()
+        )));
+    }
+
+    let source = match from {
+        Some(path) => Some(path.to_path_buf()),
+        None => scope
+            .targets()
+            .into_iter()
+            .map(|t| t.path)
+            .find(|p| p.exists()),
+    };
+    let content = match &source {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+        None => "# Instructions\n".to_string(),
+    };
+
+    logging::write_file(&canonical_path, &content)?;
+    match source {
+        Some(path) => println!(
+            "{} Created {} from {}",
+            "✓".success(),
+            canonical_path.display(),
+            path.display()
+        ),
+        None => println!("{} Created {}", "✓".success(), canonical_path.display()),
+    }
+    println!(
+        "  {} Edit it with `ai-cli instructions edit`, then run `ai-cli instructions sync`",
+        "→".info()
+    );
+    Ok(())
+}
+
+/// Handle `instructions edit` command: open the canonical document in $EDITOR
+pub fn handle_edit(scope: &Scope) -> Result<()> {
+    prompt::require_interactive("edit the canonical instructions file directly")?;
+    let path = scope.canonical_path();
+    read_canonical(scope)?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new(&editor)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to run editor '{}'", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    println!(
+        "{} Run `ai-cli instructions sync` to update the agent files",
+        "→".info()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_drift_against_canonical() {
+        let root = tempfile::tempdir().unwrap();
+        let scope = Scope::Project(root.path().to_path_buf());
+        let targets = scope.targets();
+        let canonical = "# Rules\n";
+
+        fs::write(root.path().join("CLAUDE.md"), canonical).unwrap();
+        fs::write(root.path().join("AGENTS.md"), "# Old rules\n").unwrap();
+
+        let statuses: Vec<_> = targets.iter().map(|t| status_of(t, canonical)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                TargetStatus::InSync,
+                TargetStatus::Drifted,
+                TargetStatus::Missing,
+                TargetStatus::Missing
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_nested_targets() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join(".github/copilot-instructions.md");
+        logging::write_file(&path, "# Rules\n").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "# Rules\n");
+    }
+}
@@ -0,0 +1,446 @@
+//! Journal of what each command changed, for `ai-cli undo`.
+//!
+//! Every run that changes something becomes one operation in
+//! `~/.local/state/ai-cli/journal/<id>/`: the content config files had before
+//! they were written, copies of directories (skills) that were replaced or
+//! removed, and npm packages that were installed. `undo` restores the files and
+//! directories and uninstalls the packages, newest operation first. A file
+//! edited by hand since the operation is left alone unless `--force` is given.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::exit::Outcome;
+use crate::logging;
+use crate::node;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// Operations kept; older ones are dropped
+const KEEP_OPERATIONS: usize = 50;
+
+/// One thing an operation changed, with what is needed to put it back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Change {
+    /// A file written; `None` content means it didn't exist
+    File {
+        path: PathBuf,
+        before: Option<String>,
+        after: Option<String>,
+        /// `sha256:` of the content after, kept instead of `after` when it
+        /// holds a secret
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        after_checksum: Option<String>,
+    },
+    /// A directory created, replaced or removed; `backup` is the copy of what
+    /// was there before, relative to the operation's directory
+    Dir {
+        path: PathBuf,
+        backup: Option<String>,
+    },
+    /// A global npm package installed
+    NpmPackage { package: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Operation {
+    pub id: String,
+    /// Command line of the run, e.g. `mcp enable linear`
+    pub command: String,
+    /// Unix timestamp the run started at
+    pub started_at: i64,
+    pub changes: Vec<Change>,
+}
+
+static CURRENT: Mutex<Option<Operation>> = Mutex::new(None);
+
+fn journal_dir() -> PathBuf {
+    paths::state_home().join("ai-cli").join("journal")
+}
+
+/// Start recording the changes of this run
+pub fn begin(command: &str) {
+    let now = chrono::Local::now();
+    *CURRENT.lock().unwrap() = Some(Operation {
+        id: now.format("%Y%m%d-%H%M%S-%3f").to_string(),
+        command: command.to_string(),
+        started_at: now.timestamp(),
+        changes: Vec::new(),
+    });
+}
+
+fn record(change: impl FnOnce(&Operation) -> Option<Change>) {
+    let mut guard = CURRENT.lock().unwrap();
+    let Some(operation) = guard.as_mut() else {
+        return;
+    };
+    if let Some(change) = change(operation) {
+        operation.changes.push(change);
+    }
+}
+
+fn already_recorded(operation: &Operation, target: &Path) -> bool {
+    operation.changes.iter().any(|change| match change {
+        Change::File { path, .. } | Change::Dir { path, .. } => path == target,
+        Change::NpmPackage { .. } => false,
+    })
+}
+
+/// Remember the content of `path` before it is written for the first time in
+/// this run
+pub fn record_file(path: &Path) {
+    record(|operation| {
+        if already_recorded(operation, path) {
+            return None;
+        }
+        Some(Change::File {
+            path: path.to_path_buf(),
+            before: fs::read_to_string(path).ok(),
+            after: None,
+            after_checksum: None,
+        })
+    });
+}
+
+/// Keep a copy of the directory at `path` before it is replaced or removed
+pub fn record_dir(path: &Path) {
+    record(|operation| {
+        if already_recorded(operation, path) {
+            return None;
+        }
+        let backup = if path.is_dir() {
+            let name = format!("dirs/{}", operation.changes.len());
+            let copy = journal_dir().join(&operation.id).join(&name);
+            match copy_dir(path, &copy) {
+                Ok(()) => Some(name),
+                Err(e) => {
+                    logging::line(&format!(
+                        "journal: can't back up {}: {:#}",
+                        path.display(),
+                        e
+                    ));
+                    return None;
+                }
+            }
+        } else {
+            None
+        };
+        Some(Change::Dir {
+            path: path.to_path_buf(),
+            backup,
+        })
+    });
+}
+
+/// Remember that `package` was installed globally with npm
+pub fn record_npm_install(package: &str) {
+    record(|_| {
+        Some(Change::NpmPackage {
+            package: package.to_string(),
+        })
+    });
+}
+
+/// Save the operation of this run, if it changed anything
+pub fn finish() {
+    let Some(mut operation) = CURRENT.lock().unwrap().take() else {
+        return;
+    };
+    if operation.changes.is_empty() {
+        return;
+    }
+    for change in &mut operation.changes {
+        if let Change::File {
+            path,
+            after,
+            after_checksum,
+            ..
+        } = change
+        {
+            *after = fs::read_to_string(&*path).ok();
+            if after.as_deref().is_some_and(logging::holds_secret) {
+                *after_checksum = after.take().map(|content| checksum(&content));
+            }
+        }
+    }
+    // A secret that was in a file before can't be kept for restoring it
+    operation.changes.retain(|change| match change {
+        Change::File {
+            path,
+            before: Some(before),
+            ..
+        } if logging::holds_secret(before) => {
+            logging::line(&format!(
+                "journal: not keeping {}, it holds a secret",
+                path.display()
+            ));
+            false
+        }
+        _ => true,
+    });
+    if operation.changes.is_empty() {
+        return;
+    }
+    if let Err(e) = save(&operation) {
+        logging::line(&format!("journal: {:#}", e));
+    }
+    prune();
+}
+
+fn checksum(content: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn save(operation: &Operation) -> Result<()> {
+    let dir = journal_dir().join(&operation.id);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    let path = dir.join("operation.json");
+    fs::write(&path, serde_json::to_string_pretty(operation)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Recorded operations, newest first
+pub fn operations() -> Vec<Operation> {
+    let Ok(entries) = fs::read_dir(journal_dir()) else {
+        return Vec::new();
+    };
+    let mut operations: Vec<Operation> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path().join("operation.json")).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    operations.sort_by(|a, b| b.id.cmp(&a.id));
+    operations
+}
+
+fn prune() {
+    for operation in operations().iter().skip(KEEP_OPERATIONS) {
+        let _ = fs::remove_dir_all(journal_dir().join(&operation.id));
+    }
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)
+        .with_context(|| format!("Failed to create directory {}", dst.display()))?;
+    for entry in fs::read_dir(src).with_context(|| format!("Failed to read {}", src.display()))? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn describe(change: &Change) -> String {
+    match change {
+        Change::File { path, before, .. } => match before {
+            Some(_) => format!("restore {}", path.display()),
+            None => format!("delete {}", path.display()),
+        },
+        Change::Dir { path, backup } => match backup {
+            Some(_) => format!("restore {}/", path.display()),
+            None => format!("delete {}/", path.display()),
+        },
+        Change::NpmPackage { package } => format!("npm uninstall -g {}", package),
+    }
+}
+
+/// Put back what `change` changed; `Ok(false)` when a file was edited since
+/// and `force` isn't set
+fn revert(operation_dir: &Path, change: &Change, force: bool) -> Result<bool> {
+    match change {
+        Change::File {
+            path,
+            before,
+            after,
+            after_checksum,
+        } => {
+            let current = fs::read_to_string(path).ok();
+            let unchanged = match after_checksum {
+                Some(expected) => current.as_deref().map(checksum).as_ref() == Some(expected),
+                None => current == *after,
+            };
+            if !unchanged && !force {
+                return Ok(false);
+            }
+            match before {
+                Some(content) => {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent).with_context(|| {
+                            format!("Failed to create directory {}", parent.display())
+                        })?;
+                    }
+                    fs::write(path, content)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                }
+                None if current.is_some() => fs::remove_file(path)
+                    .with_context(|| format!("Failed to delete {}", path.display()))?,
+                None => {}
+            }
+        }
+        Change::Dir { path, backup } => {
+            if path.exists() {
+                fs::remove_dir_all(path)
+                    .with_context(|| format!("Failed to delete {}", path.display()))?;
+            }
+            if let Some(backup) = backup {
+                copy_dir(&operation_dir.join(backup), path)?;
+            }
+        }
+        Change::NpmPackage { package } => {
+            let status = logging::run(node::npm_global("uninstall").arg(package))
+                .context("Failed to run npm uninstall")?;
+            if !status.success() {
+                anyhow::bail!("npm uninstall -g {} failed", package);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Revert every change of `operation`, newest first; returns whether all of
+/// them were reverted
+fn revert_operation(operation: &Operation, force: bool) -> bool {
+    let dir = journal_dir().join(&operation.id);
+    let mut complete = true;
+    for change in operation.changes.iter().rev() {
+        match revert(&dir, change, force) {
+            Ok(true) => println!("  {} {}", "✓".success(), describe(change)),
+            Ok(false) => {
+                complete = false;
+                println!(
+                    "  {} {} {}",
+                    "!".warning(),
+                    describe(change),
+                    "(changed since; use --force to overwrite)".muted()
+                );
+            }
+            Err(e) => {
+                complete = false;
+                println!("  {} {}: {:#}", "✗".error(), describe(change), e);
+            }
+        }
+    }
+    complete
+}
+
+/// Handle `undo [--last N] [--force]` command
+pub fn handle_undo(last: usize, force: bool) -> Result<Outcome> {
+    let operations: Vec<Operation> = operations().into_iter().take(last).collect();
+    if operations.is_empty() {
+        println!("Nothing to undo");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    for operation in &operations {
+        let when = chrono::DateTime::from_timestamp(operation.started_at, 0)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        println!(
+            "{} {}",
+            format!("ai-cli {}", operation.command).bold(),
+            when.muted()
+        );
+        for change in operation.changes.iter().rev() {
+            println!("  {}", describe(change));
+        }
+    }
+    println!();
+    if !prompt::confirm(&format!("Undo {} operation(s)?", operations.len()))? {
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut undone = 0;
+    for operation in &operations {
+        println!("{}", format!("ai-cli {}", operation.command).bold());
+        if !revert_operation(operation, force) {
+            // Older operations build on this one; stop here
+            println!();
+            println!(
+                "{} Kept ai-cli {} in the journal; older operations weren't undone",
+                "!".warning(),
+                operation.command
+            );
+            return Ok(if undone == 0 {
+                Outcome::Failure
+            } else {
+                Outcome::PartialFailure
+            });
+        }
+        let _ = fs::remove_dir_all(journal_dir().join(&operation.id));
+        undone += 1;
+    }
+    println!();
+    println!("{} Undid {} operation(s)", "✓".success(), undone);
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn restores_files_and_directories_unless_edited_since() {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("settings.json");
+        let created = dir.path().join("new.json");
+        let skill = dir.path().join("skills/pdf");
+        fs::write(&config, "{\"theme\":\"light\"}").unwrap();
+        fs::write(&created, "{}").unwrap();
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "new").unwrap();
+        let backup = dir.path().join("op/dirs/2");
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(backup.join("SKILL.md"), "old").unwrap();
+
+        let edited = Change::File {
+            path: config.clone(),
+            before: Some("{}".to_string()),
+            after: Some("{\"theme\":\"dark\"}".to_string()),
+            after_checksum: None,
+        };
+        assert!(!revert(&dir.path().join("op"), &edited, false).unwrap());
+        assert_eq!(
+            fs::read_to_string(&config).unwrap(),
+            "{\"theme\":\"light\"}"
+        );
+        assert!(revert(&dir.path().join("op"), &edited, true).unwrap());
+        assert_eq!(fs::read_to_string(&config).unwrap(), "{}");
+
+        let new_file = Change::File {
+            path: created.clone(),
+            before: None,
+            after: None,
+            after_checksum: Some(checksum("{}")),
+        };
+        assert!(revert(&dir.path().join("op"), &new_file, false).unwrap());
+        assert!(!created.exists());
+
+        let replaced = Change::Dir {
+            path: skill.clone(),
+            backup: Some("dirs/2".to_string()),
+        };
+        assert!(revert(&dir.path().join("op"), &replaced, false).unwrap());
+        assert_eq!(fs::read_to_string(skill.join("SKILL.md")).unwrap(), "old");
+    }
+}
@@ -0,0 +1,450 @@
+use std::fs;
+use std::io::{BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use colored::*;
+use inquire::Password;
+use serde_json::Value;
+
+use super::{KeyFile, KeyLocation, Provider, keychain};
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// Handle `keys list` command
+pub fn handle_list() -> Result<()> {
+    let locations = super::locations();
+    let rc_files = super::shell_rc_files();
+    let keychain_backend = keychain::backend();
+    let mut plaintext = 0;
+
+    for provider in super::catalog() {
+        println!(
+            "{} {}",
+            provider.name.bold(),
+            format!("({})", provider.tools.join(", ")).muted()
+        );
+        let mut found = false;
+
+        for var in provider.env_vars {
+            if let Ok(value) = std::env::var(var)
+                && !value.is_empty()
+            {
+                println!("  {} env {} {}", "✓".success(), var, mask(&value).muted());
+                found = true;
+            }
+        }
+
+        if keychain_backend.is_some()
+            && let Some(secret) = keychain::get(provider.id)
+        {
+            println!(
+                "  {} keychain ({}) {}",
+                "✓".success(),
+                keychain::SERVICE,
+                mask(&secret).muted()
+            );
+            found = true;
+        }
+
+        for location in locations.iter().filter(|l| l.provider == provider.id) {
+            if let Some(secret) = read_key(location) {
+                plaintext += 1;
+                found = true;
+                println!(
+                    "  {} plaintext {} ({}) {}{}",
+                    "!".warning(),
+                    location.path().display(),
+                    location.tool,
+                    mask(&secret).muted(),
+                    permission_note(location.path())
+                );
+            }
+        }
+
+        for rc in &rc_files {
+            let Ok(content) = fs::read_to_string(rc) else {
+                continue;
+            };
+            for var in provider.env_vars {
+                if rc_sets_var(&content, var) {
+                    plaintext += 1;
+                    found = true;
+                    println!(
+                        "  {} plaintext {} (sets {}){}",
+                        "!".warning(),
+                        rc.display(),
+                        var,
+                        permission_note(rc)
+                    );
+                }
+            }
+        }
+
+        if !found {
+            println!("  {} not configured", "-".muted());
+        }
+        println!();
+    }
+
+    if plaintext > 0 {
+        println!(
+            "{} {} key(s) stored in plaintext files; `ai-cli keys set <provider>` can move them to the keychain",
+            "→".info(),
+            plaintext
+        );
+    }
+    if keychain_backend.is_none() {
+        println!(
+            "{} No keychain found (needs `security` on macOS or `secret-tool` on Linux)",
+            "!".warning()
+        );
+    }
+    Ok(())
+}
+
+/// A place `keys set` writes the new key to
+enum Destination<'a> {
+    Keychain,
+    File(&'a KeyLocation),
+    ShellRc(PathBuf, &'static str),
+}
+
+/// Handle `keys set <provider>` command: store or rotate a key everywhere it is used
+pub fn handle_set(provider_id: &str) -> Result<Outcome> {
+    let provider = super::find(provider_id).ok_or_else(|| {
+        let ids: Vec<_> = super::catalog().iter().map(|p| p.id).collect();
+        exit::usage(format!(
+            "Unknown provider '{}'. Available providers: {}",
+            provider_id,
+            ids.join(", ")
+        ))
+    })?;
+
+    let locations = super::locations();
+    let mut destinations = Vec::new();
+    if keychain::backend().is_some() {
+        destinations.push(Destination::Keychain);
+    }
+    for location in locations
+        .iter()
+        .filter(|l| l.provider == provider.id && l.rotatable)
+    {
+        if read_key(location).is_some() {
+            destinations.push(Destination::File(location));
+        }
+    }
+    for rc in super::shell_rc_files() {
+        let Ok(content) = fs::read_to_string(&rc) else {
+            continue;
+        };
+        for var in provider.env_vars {
+            if rc_sets_var(&content, var) {
+                destinations.push(Destination::ShellRc(rc.clone(), var));
+            }
+        }
+    }
+
+    if destinations.is_empty() {
+        bail!(
+            "Nowhere to store the {} key: no keychain and no existing config. Export {} in your shell instead.",
+            provider.name,
+            provider.env_vars[0]
+        );
+    }
+
+    let key = read_new_key(&provider)?;
+
+    println!(
+        "{}",
+        format!("{} key will be written to:", provider.name).bold()
+    );
+    for destination in &destinations {
+        match destination {
+            Destination::Keychain => {
+                println!("  {} keychain ({})", "+".success(), keychain::SERVICE)
+            }
+            Destination::File(location) => println!(
+                "  {} {} ({})",
+                "~".warning(),
+                location.path().display(),
+                location.tool
+            ),
+            Destination::ShellRc(path, var) => {
+                println!("  {} {} ({})", "~".warning(), path.display(), var)
+            }
+        }
+    }
+    println!();
+    if !prompt::confirm("Write the key?")? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+    for destination in &destinations {
+        let (label, result) = match destination {
+            Destination::Keychain => (
+                format!("keychain ({})", keychain::SERVICE),
+                keychain::set(provider.id, &key),
+            ),
+            Destination::File(location) => (
+                location.path().display().to_string(),
+                write_key(location, &key),
+            ),
+            Destination::ShellRc(path, var) => {
+                (path.display().to_string(), write_rc_key(path, var, &key))
+            }
+        };
+        match result {
+            Ok(()) => {
+                // Never log the key itself
+                logging::line(&format!("updated {} key in {}", provider.id, label));
+                println!("{} Updated {}", "✓".success(), label);
+            }
+            Err(e) => {
+                println!("{} Failed to update {}: {:#}", "✗".error(), label, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if destinations
+        .iter()
+        .any(|d| matches!(d, Destination::ShellRc(..)))
+    {
+        println!(
+            "{} Open a new shell (or re-source your rc file) to pick up the new key",
+            "→".info()
+        );
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Read the new key from a hidden prompt, or from stdin when piped
+fn read_new_key(provider: &Provider) -> Result<String> {
+    let key = if std::io::stdin().is_terminal() {
+        prompt::require_interactive("pipe the key on stdin")?;
+        Password::new(&format!("{} API key:", provider.name))
+            .without_confirmation()
+            .prompt()
+            .context("No key entered")?
+    } else {
+        let mut line = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .context("Failed to read key from stdin")?;
+        line
+    };
+
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Err(exit::usage("Empty key"));
+    }
+    Ok(key)
+}
+
+/// The key stored at `location`, if the file exists and holds one
+pub fn read_key(location: &KeyLocation) -> Option<String> {
+    let content = fs::read_to_string(location.path()).ok()?;
+    match &location.file {
+        KeyFile::Json { pointer, .. } => {
+            let json: Value = serde_json::from_str(&content).ok()?;
+            json.pointer(pointer)?
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        }
+        KeyFile::DotEnv { var, .. } => dotenv_value(&content, var),
+    }
+}
+
+fn write_key(location: &KeyLocation, key: &str) -> Result<()> {
+    let path = location.path();
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let updated = match &location.file {
+        KeyFile::Json { pointer, .. } => {
+            let mut json: Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse JSON in {}", path.display()))?;
+            let field = json
+                .pointer_mut(pointer)
+                .with_context(|| format!("No {} in {}", pointer, path.display()))?;
+            *field = Value::String(key.to_string());
+            format!("{}\n", serde_json::to_string_pretty(&json)?)
+        }
+        KeyFile::DotEnv { var, .. } => replace_dotenv_value(&content, var, key),
+    };
+    fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn write_rc_key(path: &Path, var: &str, key: &str) -> Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    fs::write(path, replace_rc_value(&content, var, key))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Show only enough of a secret to tell keys apart
+fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
+
+#[cfg(unix)]
+fn permission_note(path: &Path) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    match fs::metadata(path) {
+        Ok(meta) if meta.permissions().mode() & 0o044 != 0 => format!(
+            " {}",
+            format!(
+                "mode {:o}, readable by other users",
+                meta.permissions().mode() & 0o777
+            )
+            .error()
+        ),
+        _ => String::new(),
+    }
+}
+
+#[cfg(not(unix))]
+fn permission_note(_path: &Path) -> String {
+    String::new()
+}
+
+/// `VAR=value` or `export VAR=value` in a dotenv file
+fn dotenv_value(content: &str, var: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let value = line.strip_prefix(var)?.strip_prefix('=')?;
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+fn replace_dotenv_value(content: &str, var: &str, key: &str) -> String {
+    map_lines(content, |line| {
+        let trimmed = line.trim_start();
+        let export = trimmed.starts_with("export ");
+        let rest = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        rest.strip_prefix(var)
+            .filter(|r| r.starts_with('='))
+            .map(|_| format!("{}{}=\"{}\"", if export { "export " } else { "" }, var, key))
+    })
+}
+
+/// Whether a shell rc file assigns `var` (sh-style export or fish `set -x`)
+pub(crate) fn rc_sets_var(content: &str, var: &str) -> bool {
+    content
+        .lines()
+        .any(|line| rc_line_sets(line.trim_start(), var))
+}
+
+fn rc_line_sets(line: &str, var: &str) -> bool {
+    if let Some(rest) = line.strip_prefix("export ") {
+        return rest.strip_prefix(var).is_some_and(|r| r.starts_with('='));
+    }
+    if line.starts_with("set ") {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        return words.iter().any(|w| w.starts_with('-') && w.contains('x'))
+            && words.iter().skip(1).find(|w| !w.starts_with('-')) == Some(&var);
+    }
+    false
+}
+
+fn replace_rc_value(content: &str, var: &str, key: &str) -> String {
+    map_lines(content, |line| {
+        let trimmed = line.trim_start();
+        if !rc_line_sets(trimmed, var) {
+            return None;
+        }
+        let indent = &line[..line.len() - trimmed.len()];
+        Some(if trimmed.starts_with("set ") {
+            format!("{}set -gx {} \"{}\"", indent, var, key)
+        } else {
+            format!("{}export {}=\"{}\"", indent, var, key)
+        })
+    })
+}
+
+/// Replace lines for which `f` returns a new line, keeping the trailing newline
+fn map_lines(content: &str, f: impl Fn(&str) -> Option<String>) -> String {
+    let mut out: Vec<String> = content
+        .lines()
+        .map(|line| f(line).unwrap_or_else(|| line.to_string()))
+        .collect();
+    if content.ends_with('\n') {
+        out.push(String::new());
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_secrets() {
+        assert_eq!(mask("sk-ant-api03-abcdefgh1234"), "****1234");
+        assert_eq!(mask("short"), "****");
+    }
+
+    #[test]
+    fn reads_and_rotates_dotenv_keys() {
+        let content = "# gemini\nexport GEMINI_API_KEY='old-key'\nOTHER=1\n";
+        assert_eq!(
+            dotenv_value(content, "GEMINI_API_KEY").as_deref(),
+            Some("old-key")
+        );
+        assert_eq!(
+            replace_dotenv_value(content, "GEMINI_API_KEY", "new-key"),
+            "# gemini\nexport GEMINI_API_KEY=\"new-key\"\nOTHER=1\n"
+        );
+    }
+
+    #[test]
+    fn rotates_shell_rc_exports() {
+        let zshrc = "export PATH=$HOME/bin:$PATH\n  export OPENAI_API_KEY=sk-old\n";
+        assert!(rc_sets_var(zshrc, "OPENAI_API_KEY"));
+        assert!(!rc_sets_var(zshrc, "OPENAI_API_KEY_2"));
+        assert_eq!(
+            replace_rc_value(zshrc, "OPENAI_API_KEY", "sk-new"),
+            "export PATH=$HOME/bin:$PATH\n  export OPENAI_API_KEY=\"sk-new\"\n"
+        );
+
+        let fish = "set -gx OPENAI_API_KEY sk-old\n";
+        assert!(rc_sets_var(fish, "OPENAI_API_KEY"));
+        assert_eq!(
+            replace_rc_value(fish, "OPENAI_API_KEY", "sk-new"),
+            "set -gx OPENAI_API_KEY \"sk-new\"\n"
+        );
+    }
+
+    #[test]
+    fn rotates_json_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.json");
+        fs::write(&path, r#"{"OPENAI_API_KEY":"sk-old","tokens":null}"#).unwrap();
+        let location = KeyLocation {
+            provider: "openai",
+            tool: "Codex CLI",
+            file: KeyFile::Json {
+                path: path.clone(),
+                pointer: "/OPENAI_API_KEY",
+            },
+            rotatable: true,
+        };
+
+        assert_eq!(read_key(&location).as_deref(), Some("sk-old"));
+        write_key(&location, "sk-new").unwrap();
+        assert_eq!(read_key(&location).as_deref(), Some("sk-new"));
+    }
+}
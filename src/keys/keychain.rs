@@ -0,0 +1,122 @@
+//! Secrets in the OS credential store: the macOS keychain via `security`,
+//! or the Secret Service (GNOME Keyring, KWallet) via `secret-tool` on Linux.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+/// Service name ai-cli stores its secrets under
+pub const SERVICE: &str = "ai-cli";
+
+/// Name of the backend available on this machine, if any
+pub fn backend() -> Option<&'static str> {
+    let binary = if cfg!(target_os = "macos") {
+        "security"
+    } else {
+        "secret-tool"
+    };
+    Command::new("which")
+        .arg(binary)
+        .output()
+        .is_ok_and(|o| o.status.success())
+        .then_some(binary)
+}
+
+/// Read the secret stored for `account`
+pub fn get(account: &str) -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", SERVICE, "-a", account, "-w"])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", SERVICE, "account", account])
+            .output()
+    }
+    .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let secret = String::from_utf8(output.stdout).ok()?;
+    let secret = secret.trim_end_matches('\n').to_string();
+    (!secret.is_empty()).then_some(secret)
+}
+
+/// Store `secret` for `account`, replacing any previous value
+pub fn set(account: &str, secret: &str) -> Result<()> {
+    if backend().is_none() {
+        bail!("No keychain available (needs `security` on macOS or `secret-tool` on Linux)");
+    }
+
+    // Both read the secret from stdin, keeping it out of the process list
+    let status = if cfg!(target_os = "macos") {
+        // `security -i` takes its commands from stdin
+        let mut child = Command::new("security")
+            .arg("-i")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .context("Failed to run security")?;
+        child
+            .stdin
+            .take()
+            .context("Failed to open security stdin")?
+            .write_all(
+                format!(
+                    "add-generic-password -U -s {} -a {} -w {}\n",
+                    quote(SERVICE),
+                    quote(account),
+                    quote(secret)
+                )
+                .as_bytes(),
+            )?;
+        let status = child.wait().context("Failed to wait for security")?;
+        // Its exit status doesn't reflect the command's, so read the secret back
+        if status.success() && get(account).as_deref() != Some(secret) {
+            bail!("Failed to store secret for {} in the keychain", account);
+        }
+        status
+    } else {
+        let mut child = Command::new("secret-tool")
+            .args([
+                "store",
+                "--label",
+                &format!("{} {}", SERVICE, account),
+                "service",
+                SERVICE,
+                "account",
+                account,
+            ])
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Failed to run secret-tool")?;
+        child
+            .stdin
+            .take()
+            .context("Failed to open secret-tool stdin")?
+            .write_all(secret.as_bytes())?;
+        child.wait().context("Failed to wait for secret-tool")?
+    };
+
+    if !status.success() {
+        bail!("Failed to store secret for {} in the keychain", account);
+    }
+    Ok(())
+}
+
+/// `value` as one double-quoted word for `security -i`
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_words_for_security() {
+        assert_eq!(quote(r#"a"b\c d"#), r#""a\"b\\c d""#);
+    }
+}
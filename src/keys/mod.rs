@@ -0,0 +1,174 @@
+pub mod actions;
+pub mod keychain;
+
+use std::path::PathBuf;
+
+use crate::paths;
+
+pub use actions::{handle_list, handle_set};
+
+/// A credential provider and the environment variables tools read it from
+#[derive(Debug, Clone)]
+pub struct Provider {
+    /// Identifier used on the command line
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Variables holding the key, in the order tools check them
+    pub env_vars: &'static [&'static str],
+    /// Tools using this provider
+    pub tools: &'static [&'static str],
+}
+
+/// Where a tool keeps a credential on disk
+#[derive(Debug, Clone)]
+pub enum KeyFile {
+    /// A string field in a JSON file, addressed by JSON pointer
+    Json {
+        path: PathBuf,
+        pointer: &'static str,
+    },
+    /// A `VAR=value` line in a dotenv file
+    DotEnv { path: PathBuf, var: &'static str },
+}
+
+/// A credential file used by one tool
+#[derive(Debug, Clone)]
+pub struct KeyLocation {
+    pub provider: &'static str,
+    pub tool: &'static str,
+    pub file: KeyFile,
+    /// Holds an API key that `keys set` can replace (as opposed to OAuth tokens)
+    pub rotatable: bool,
+}
+
+impl KeyLocation {
+    pub fn path(&self) -> &PathBuf {
+        match &self.file {
+            KeyFile::Json { path, .. } | KeyFile::DotEnv { path, .. } => path,
+        }
+    }
+}
+
+/// Returns all supported providers
+pub fn catalog() -> Vec<Provider> {
+    vec![
+        Provider {
+            id: "anthropic",
+            name: "Anthropic",
+            env_vars: &["ANTHROPIC_API_KEY"],
+            tools: &["Claude Code", "OpenCode", "Cline CLI"],
+        },
+        Provider {
+            id: "openai",
+            name: "OpenAI",
+            env_vars: &["OPENAI_API_KEY"],
+            tools: &["Codex CLI", "OpenCode"],
+        },
+        Provider {
+            id: "google",
+            name: "Google Gemini",
+            env_vars: &["GEMINI_API_KEY", "GOOGLE_API_KEY"],
+            tools: &["Gemini CLI"],
+        },
+        Provider {
+            id: "mistral",
+            name: "Mistral",
+            env_vars: &["MISTRAL_API_KEY"],
+            tools: &["Mistral Vibe"],
+        },
+        Provider {
+            id: "amp",
+            name: "Amp",
+            env_vars: &["AMP_API_KEY"],
+            tools: &["Amp"],
+        },
+        Provider {
+            id: "factory",
+            name: "Factory",
+            env_vars: &["FACTORY_API_KEY"],
+            tools: &["Factory CLI"],
+        },
+        Provider {
+            id: "github",
+            name: "GitHub",
+            env_vars: &["GITHUB_TOKEN", "GH_TOKEN"],
+            tools: &["Copilot CLI"],
+        },
+    ]
+}
+
+/// Find a provider by ID
+pub fn find(id: &str) -> Option<Provider> {
+    catalog()
+        .into_iter()
+        .find(|p| p.id.eq_ignore_ascii_case(id))
+}
+
+/// Credential files tools write themselves
+pub fn locations() -> Vec<KeyLocation> {
+    let home = paths::home();
+    vec![
+        KeyLocation {
+            provider: "anthropic",
+            tool: "Claude Code",
+            file: KeyFile::Json {
+                path: home.join(".claude.json"),
+                pointer: "/primaryApiKey",
+            },
+            rotatable: true,
+        },
+        KeyLocation {
+            provider: "anthropic",
+            tool: "Claude Code",
+            file: KeyFile::Json {
+                path: home.join(".claude/.credentials.json"),
+                pointer: "/claudeAiOauth/accessToken",
+            },
+            rotatable: false,
+        },
+        KeyLocation {
+            provider: "openai",
+            tool: "Codex CLI",
+            file: KeyFile::Json {
+                path: home.join(".codex/auth.json"),
+                pointer: "/OPENAI_API_KEY",
+            },
+            rotatable: true,
+        },
+        KeyLocation {
+            provider: "google",
+            tool: "Gemini CLI",
+            file: KeyFile::DotEnv {
+                path: home.join(".gemini/.env"),
+                var: "GEMINI_API_KEY",
+            },
+            rotatable: true,
+        },
+        KeyLocation {
+            provider: "mistral",
+            tool: "Mistral Vibe",
+            file: KeyFile::DotEnv {
+                path: home.join(".vibe/.env"),
+                var: "MISTRAL_API_KEY",
+            },
+            rotatable: true,
+        },
+    ]
+}
+
+/// Shell startup files scanned for `export VAR=...` lines
+pub fn shell_rc_files() -> Vec<PathBuf> {
+    let home = paths::home();
+    [
+        ".zshrc",
+        ".zshenv",
+        ".zprofile",
+        ".bashrc",
+        ".bash_profile",
+        ".profile",
+        ".config/fish/config.fish",
+    ]
+    .iter()
+    .map(|name| home.join(name))
+    .collect()
+}
@@ -0,0 +1,74 @@
+//! Manage AI CLI tools, their MCP server configuration and skills.
+//!
+//! The `ai-cli` binary is a thin wrapper around this crate. The main entry
+//! points for reuse are:
+//!
+//! - [`tools::catalog`] and [`tools::installed_versions`]: supported tools and
+//!   what is installed locally
+//! - [`versions::check_latest_versions`]: latest published release per tool
+//! - [`mcp::targets::catalog`] and [`mcp::servers::catalog`]: editing MCP
+//!   server entries in each tool's config file
+//! - [`skills::actions::install_from_repo`] and [`skills::discovery`]:
+//!   installing and inspecting skills
+//! - [`snapshot`]: capturing and applying the managed state of a machine
+
+pub mod actions;
+pub mod aliases;
+pub mod appearance;
+pub mod audit;
+pub mod backup;
+pub mod bench;
+pub mod changelog;
+pub mod cli;
+pub mod commands;
+pub mod compare;
+pub mod completions;
+pub mod config;
+pub mod devcontainer;
+pub mod doctor;
+pub mod environment;
+pub mod exit;
+pub mod http;
+pub mod instructions;
+pub mod journal;
+pub mod keys;
+pub mod logging;
+pub mod logs;
+pub mod managed;
+pub mod manpages;
+pub mod mcp;
+pub mod memory;
+pub mod migrate;
+pub mod models;
+pub mod node;
+pub mod notify;
+pub mod onboarding;
+pub mod output;
+pub mod output_styles;
+pub mod overview;
+pub mod paths;
+pub mod profiles;
+pub mod prompt;
+pub mod prompts;
+pub mod prune;
+pub mod quota;
+pub mod rules;
+pub mod run;
+pub mod sandbox;
+pub mod schema;
+pub mod search;
+pub mod sessions;
+pub mod settings;
+pub mod setup;
+pub mod shell_init;
+pub mod skills;
+pub mod snapshot;
+pub mod status;
+pub mod sync;
+pub mod telemetry;
+pub mod theme;
+pub mod tools;
+pub mod update_notice;
+pub mod usage;
+pub mod versions;
+pub mod workspace;
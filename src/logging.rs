@@ -0,0 +1,279 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+use std::thread;
+
+use anyhow::{Context, Result};
+
+use crate::journal;
+use crate::paths;
+use crate::schema;
+
+static LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+/// Values kept out of the log and the journal, e.g. tokens entered for MCP servers
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Rotate the default log once it grows past this size
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+/// Number of rotated logs to keep (ai-cli.log.1 .. ai-cli.log.3)
+const KEEP_ROTATED: usize = 3;
+
+/// Default log location: `$XDG_STATE_HOME/ai-cli/ai-cli.log` or `~/.local/state/ai-cli/ai-cli.log`
+pub fn default_log_path() -> PathBuf {
+    paths::state_home().join("ai-cli").join("ai-cli.log")
+}
+
+/// Start logging to `path`, or to the rotating default log when `None`
+pub fn init(path: Option<&Path>) -> Result<()> {
+    let (path, rotate) = match path {
+        Some(path) => (path.to_path_buf(), false),
+        None => (default_log_path(), true),
+    };
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    if rotate {
+        rotate_if_needed(&path)?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+
+    *LOG_FILE.lock().unwrap() = Some(file);
+    Ok(())
+}
+
+fn rotate_if_needed(path: &Path) -> Result<()> {
+    let Ok(metadata) = fs::metadata(path) else {
+        return Ok(());
+    };
+    if metadata.len() < MAX_LOG_SIZE {
+        return Ok(());
+    }
+
+    let rotated = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+    let _ = fs::remove_file(rotated(KEEP_ROTATED));
+    for n in (1..KEEP_ROTATED).rev() {
+        let _ = fs::rename(rotated(n), rotated(n + 1));
+    }
+    fs::rename(path, rotated(1))
+        .with_context(|| format!("Failed to rotate log file {}", path.display()))?;
+    Ok(())
+}
+
+/// Keep `secret` out of the log and the journal for the rest of the run
+pub fn redact(secret: &str) {
+    let mut secrets = SECRETS.lock().unwrap();
+    if !secret.is_empty() && !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+    }
+}
+
+/// Whether `text` holds a value passed to [`redact`]
+pub fn holds_secret(text: &str) -> bool {
+    SECRETS.lock().unwrap().iter().any(|s| text.contains(s))
+}
+
+/// `text` with every value passed to [`redact`] masked
+fn scrub(text: &str) -> String {
+    let mut scrubbed = text.to_string();
+    for secret in SECRETS.lock().unwrap().iter() {
+        scrubbed = scrubbed.replace(secret, "***");
+    }
+    scrubbed
+}
+
+/// Append a timestamped message to the log, one entry per line
+pub fn line(message: &str) {
+    let mut guard = LOG_FILE.lock().unwrap();
+    let Some(file) = guard.as_mut() else {
+        return;
+    };
+
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    for text in scrub(message).lines() {
+        let _ = writeln!(file, "{} {}", timestamp, text);
+    }
+}
+
+/// Log the difference between the old and new content of a config file,
+/// which is about to be written, and journal it for `undo`
+pub fn config_diff(path: &Path, before: &str, after: &str) {
+    if before == after {
+        return;
+    }
+    journal::record_file(path);
+
+    let name = path.display().to_string();
+    let diff = similar::TextDiff::from_lines(before, after)
+        .unified_diff()
+        .header(&name, &name)
+        .to_string();
+    line(&format!("config change: {}\n{}", name, diff));
+}
+
+/// Write `content` to `path`, creating its directory, with the change logged
+/// and journaled like [`write_config`]
+pub fn write_file(path: &Path, content: &str) -> Result<()> {
+    let before = fs::read_to_string(path).unwrap_or_default();
+    write_config(path, &before, content)
+}
+
+/// Replace the config file `path` holding `before` with `after`: creates its
+/// directory, refuses content that breaks the tool's schema and logs the diff
+pub fn write_config(path: &Path, before: &str, after: &str) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    schema::check(path, before, after)?;
+    config_diff(path, before, after);
+    fs::write(path, after).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Run a command like `Command::status`, copying its stdout/stderr into the log
+///
+/// On a terminal the command keeps stdout, so installers can still draw
+/// progress bars and ask questions there; its stderr, where errors go, is
+/// still copied into the log.
+pub fn run(cmd: &mut Command) -> io::Result<ExitStatus> {
+    log_command(cmd);
+
+    if LOG_FILE.lock().unwrap().is_none() {
+        return cmd.status();
+    }
+    run_teed(cmd, io::stdout().is_terminal()).map(|(status, _)| status)
+}
+
+/// Like [`run`], also returning what the command wrote to stderr
+pub fn run_capturing_stderr(cmd: &mut Command) -> io::Result<(ExitStatus, String)> {
+    log_command(cmd);
+    run_teed(cmd, false)
+}
+
+/// The program and its arguments; environment variables are left out as
+/// they may carry credentials
+fn log_command(cmd: &Command) {
+    let mut words = vec![format!("{:?}", cmd.get_program())];
+    words.extend(cmd.get_args().map(|arg| format!("{:?}", arg)));
+    line(&format!("$ {}", words.join(" ")));
+}
+
+/// Run `cmd` with its stderr, and its stdout unless `keep_stdout`, copied to
+/// the terminal and the log
+fn run_teed(cmd: &mut Command, keep_stdout: bool) -> io::Result<(ExitStatus, String)> {
+    if !keep_stdout {
+        cmd.stdout(Stdio::piped());
+    }
+    let mut child = cmd.stderr(Stdio::piped()).spawn()?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let out = thread::spawn(move || {
+        if let Some(pipe) = stdout {
+            tee(pipe, io::stdout());
+        }
+    });
+    let err = thread::spawn(move || stderr.map(|pipe| tee(pipe, io::stderr())));
+
+    let status = child.wait()?;
+    let _ = out.join();
+    let stderr = err.join().ok().flatten().unwrap_or_default();
+
+    line(&format!("exit status: {}", status));
+    Ok((status, stderr))
+}
+
+/// Forward output to the terminal as it arrives and log it line by line;
+/// returns all of it
+fn tee(mut source: impl Read, mut terminal: impl Write) -> String {
+    let mut buffer = [0u8; 8192];
+    let mut pending = Vec::new();
+    let mut all = Vec::new();
+
+    while let Ok(n) = source.read(&mut buffer) {
+        if n == 0 {
+            break;
+        }
+        let _ = terminal.write_all(&buffer[..n]);
+        let _ = terminal.flush();
+        all.extend_from_slice(&buffer[..n]);
+
+        pending.extend_from_slice(&buffer[..n]);
+        while let Some(pos) = pending.iter().position(|&b| b == b'\n') {
+            let text: Vec<u8> = pending.drain(..=pos).collect();
+            line(&format!(
+                "  | {}",
+                String::from_utf8_lossy(&text).trim_end()
+            ));
+        }
+    }
+
+    if !pending.is_empty() {
+        line(&format!(
+            "  | {}",
+            String::from_utf8_lossy(&pending).trim_end()
+        ));
+    }
+    String::from_utf8_lossy(&all).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rotates_oversized_log() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("ai-cli.log");
+        fs::write(&path, vec![b'x'; MAX_LOG_SIZE as usize]).unwrap();
+        fs::write(dir.path().join("ai-cli.log.1"), "older").unwrap();
+
+        rotate_if_needed(&path).unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(
+            fs::metadata(dir.path().join("ai-cli.log.1")).unwrap().len(),
+            MAX_LOG_SIZE
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("ai-cli.log.2")).unwrap(),
+            "older"
+        );
+    }
+
+    #[test]
+    fn masks_redacted_values() {
+        redact("ghp_logging_test_token");
+        assert!(holds_secret("{\"TOKEN\": \"ghp_logging_test_token\"}"));
+        assert_eq!(
+            scrub("+ \"TOKEN\": \"ghp_logging_test_token\""),
+            "+ \"TOKEN\": \"***\""
+        );
+        assert!(!holds_secret("ghp_other"));
+    }
+
+    #[test]
+    fn keeps_small_log_in_place() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("ai-cli.log");
+        fs::write(&path, "small").unwrap();
+
+        rotate_if_needed(&path).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "small");
+    }
+}
@@ -0,0 +1,322 @@
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use colored::*;
+
+use crate::exit;
+use crate::logging;
+use crate::paths;
+use crate::theme::Themed;
+
+/// How often `--follow` checks the log for new lines
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Line layout of a log file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    /// `2025-10-01T12:00:00.123Z [DEBUG] message` (Claude Code, Copilot CLI)
+    Bracketed,
+    /// `2025-10-01T12:00:00.123456Z  INFO target: message` (Rust tracing, Codex CLI)
+    Tracing,
+    /// `2025-10-01 12:00:00 message` in local time (ai-cli)
+    Plain,
+}
+
+/// Where a tool writes its logs
+#[derive(Debug, Clone)]
+pub struct LogSource {
+    pub id: &'static str,
+    pub tool: &'static str,
+    pub dir: PathBuf,
+    /// Extension of the log files; the most recently written one is shown
+    pub extension: &'static str,
+    pub format: LogFormat,
+}
+
+/// Returns the log locations of all supported tools
+pub fn catalog() -> Vec<LogSource> {
+    let home = paths::home();
+    vec![
+        LogSource {
+            id: "claude",
+            tool: "Claude Code",
+            dir: home.join(".claude/debug"),
+            extension: "txt",
+            format: LogFormat::Bracketed,
+        },
+        LogSource {
+            id: "codex",
+            tool: "Codex CLI",
+            dir: home.join(".codex/log"),
+            extension: "log",
+            format: LogFormat::Tracing,
+        },
+        LogSource {
+            id: "copilot",
+            tool: "Copilot CLI",
+            dir: home.join(".copilot/logs"),
+            extension: "log",
+            format: LogFormat::Bracketed,
+        },
+        LogSource {
+            id: "ai-cli",
+            tool: "AI CLI",
+            dir: logging::default_log_path()
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            extension: "log",
+            format: LogFormat::Plain,
+        },
+    ]
+}
+
+impl LogSource {
+    /// The most recently written log file
+    pub fn latest_file(&self) -> Option<PathBuf> {
+        fs::read_dir(&self.dir)
+            .ok()?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == self.extension))
+            .max_by_key(|p| fs::metadata(p).and_then(|m| m.modified()).ok())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Level> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" | "warning" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> ColoredString {
+        match self {
+            Level::Error => "ERROR".error(),
+            Level::Warn => "WARN ".warning(),
+            Level::Info => "INFO ".info(),
+            Level::Debug => "DEBUG".muted(),
+            Level::Trace => "TRACE".muted(),
+        }
+    }
+}
+
+/// One log entry, with continuation lines folded into the message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub message: String,
+}
+
+fn parse_rfc3339(text: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|t| t.with_timezone(&Local))
+}
+
+/// Parse the first line of an entry, or `None` for a continuation line
+fn parse_line(line: &str, format: LogFormat) -> Option<Entry> {
+    match format {
+        LogFormat::Bracketed => {
+            let (time, rest) = line.split_once(' ')?;
+            let rest = rest.strip_prefix('[')?;
+            let (level, message) = rest.split_once(']')?;
+            Some(Entry {
+                time: parse_rfc3339(time)?,
+                level: Level::parse(level)?,
+                message: message.trim_start().to_string(),
+            })
+        }
+        LogFormat::Tracing => {
+            let (time, rest) = line.split_once(' ')?;
+            let rest = rest.trim_start();
+            let (level, message) = rest.split_once(' ').unwrap_or((rest, ""));
+            Some(Entry {
+                time: parse_rfc3339(time)?,
+                level: Level::parse(level)?,
+                message: message.trim_start().to_string(),
+            })
+        }
+        LogFormat::Plain => {
+            let time = NaiveDateTime::parse_from_str(line.get(..19)?, "%Y-%m-%d %H:%M:%S").ok()?;
+            let message = line[19..].trim_start();
+            let level = if message.contains("error") || message.contains("failed") {
+                Level::Error
+            } else {
+                Level::Info
+            };
+            Some(Entry {
+                time: Local.from_local_datetime(&time).single()?,
+                level,
+                message: message.to_string(),
+            })
+        }
+    }
+}
+
+/// Split log text into entries; lines before the first entry are dropped
+pub fn parse(content: &str, format: LogFormat) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for line in content.lines() {
+        match parse_line(line, format) {
+            Some(entry) => entries.push(entry),
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Which entries to show
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// Show this level and more severe ones
+    pub level: Option<Level>,
+    /// Case-insensitive text the message must contain
+    pub keyword: Option<String>,
+}
+
+impl Filter {
+    fn matches(&self, entry: &Entry) -> bool {
+        self.level.is_none_or(|level| entry.level <= level)
+            && self
+                .keyword
+                .as_ref()
+                .is_none_or(|k| entry.message.to_lowercase().contains(&k.to_lowercase()))
+    }
+}
+
+fn print_entry(entry: &Entry) {
+    println!(
+        "{} {} {}",
+        entry.time.format("%Y-%m-%d %H:%M:%S").to_string().muted(),
+        entry.level.label(),
+        entry.message
+    );
+}
+
+/// Print lines appended to `path` until interrupted
+fn follow(path: &Path, format: LogFormat, filter: &Filter, mut offset: u64) -> Result<()> {
+    let mut pending = String::new();
+    loop {
+        thread::sleep(FOLLOW_INTERVAL);
+        let mut file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let len = file.metadata()?.len();
+        if len < offset {
+            // Rotated or truncated: start over
+            offset = 0;
+        }
+        if len == offset {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut chunk = String::new();
+        file.read_to_string(&mut chunk)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        offset = len;
+
+        pending.push_str(&chunk);
+        // Keep a partial last line for the next round
+        let complete = match pending.rfind('\n') {
+            Some(end) => pending.drain(..=end).collect::<String>(),
+            None => continue,
+        };
+        for entry in parse(&complete, format) {
+            if filter.matches(&entry) {
+                print_entry(&entry);
+            }
+        }
+    }
+}
+
+/// Handle `logs <tool>` command
+pub fn handle_logs(id: &str, lines: usize, filter: &Filter, follow_log: bool) -> Result<bool> {
+    let sources = catalog();
+    let source = sources.iter().find(|s| s.id == id).ok_or_else(|| {
+        let ids: Vec<_> = sources.iter().map(|s| s.id).collect();
+        exit::usage(format!(
+            "Unknown tool '{}'. Tools with logs: {}",
+            id,
+            ids.join(", ")
+        ))
+    })?;
+    let Some(path) = source.latest_file() else {
+        println!(
+            "{} No {} logs in {}",
+            "!".warning(),
+            source.tool,
+            source.dir.display()
+        );
+        return Ok(false);
+    };
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    println!("{}", path.display().to_string().muted());
+    let entries: Vec<Entry> = parse(&content, source.format)
+        .into_iter()
+        .filter(|e| filter.matches(e))
+        .collect();
+    for entry in &entries[entries.len().saturating_sub(lines)..] {
+        print_entry(entry);
+    }
+
+    if follow_log {
+        follow(&path, source.format, filter, content.len() as u64)?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_claude_and_codex_logs_into_entries() {
+        let claude = "2026-10-16T09:04:01.418Z [DEBUG] MDM settings loaded\n\
+                      2026-10-16T09:04:02.000Z [ERROR] Request failed\n\
+                      Error: 529 overloaded\n";
+        let entries = parse(claude, LogFormat::Bracketed);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, Level::Debug);
+        assert_eq!(entries[1].message, "Request failed\nError: 529 overloaded");
+
+        let codex = "2026-10-16T09:04:01.123456Z  INFO codex_core::client: sending request\n\
+                     2026-10-16T09:04:02.123456Z  WARN codex_core::exec: sandbox denied\n";
+        let entries = parse(codex, LogFormat::Tracing);
+        assert_eq!(entries[0].message, "codex_core::client: sending request");
+
+        let filter = Filter {
+            level: Some(Level::Warn),
+            keyword: Some("SANDBOX".to_string()),
+        };
+        let shown: Vec<_> = entries.iter().filter(|e| filter.matches(e)).collect();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].level, Level::Warn);
+    }
+}
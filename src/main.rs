@@ -1,113 +1,170 @@
-mod actions;
-mod cli;
-mod mcp;
-mod skills;
-mod tools;
-mod versions;
-
-use actions::{handle_install_command, handle_uninstall_command, handle_upgrade_command};
+use ai_cli::actions::{
+    handle_check_command, handle_cleanup_command, handle_info_command, handle_install_command,
+    handle_list_command, handle_uninstall_command, handle_upgrade_command,
+};
+use ai_cli::cli::{
+    self, AppearanceCommands, AppsCommands, BackupCommands, Cli, Commands, CommandsCommands,
+    GatewayCommands, InstructionsCommands, KeysCommands, McpCommands, MemoryCommands,
+    ModelsCommands, OutputStylesCommands, ProfileCommands, PromptsCommands, RulesCommands,
+    SandboxCommands, SessionsCommands, SettingsCommands, SkillsCommands, SyncCommands,
+    TelemetryCommands, WorkspaceCommands,
+};
+use ai_cli::exit::{self, Outcome};
+use ai_cli::instructions::Scope;
+use ai_cli::theme::Themed;
+use ai_cli::{
+    aliases, appearance, audit, backup, bench, changelog, commands, compare, completions,
+    devcontainer, doctor, environment, instructions, journal, keys, logging, logs, manpages, mcp,
+    memory, migrate, models, onboarding, output, output_styles, overview, paths, profiles, prompt,
+    prompts, prune, quota, rules, run, sandbox, search, sessions, settings, setup, shell_init,
+    skills, snapshot, status, sync, telemetry, theme, update_notice, usage, versions, workspace,
+};
 use anyhow::Result;
-use clap::Parser;
-use cli::{AppsCommands, Cli, Commands, McpCommands, SkillsCommands};
-use colored::*;
-use indicatif::{ProgressBar, ProgressStyle};
-use tools::installed_versions;
-use versions::{check_latest_versions, print_version};
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() -> ExitCode {
+    let expanded = aliases::expand_from_config(std::env::args().collect());
+    let cli = cli::parse_from(expanded.clone());
+    // Global flags alone get past clap's `arg_required_else_help`
+    if cli.command.is_none() && !cli.check_update {
+        eprint!("{}", cli::command().render_help());
+        return ExitCode::from(exit::USAGE);
+    }
+    prompt::set_non_interactive(cli.yes);
+    output::init(cli.plain);
+    if let Err(e) = paths::set_config_root(cli.config_root.clone()) {
+        eprintln!("Error: {:?}", e);
+        return ExitCode::from(exit::USAGE);
+    }
+    if let Err(e) = theme::init_from_config() {
+        eprintln!("{} Using the default theme: {:#}", "!".warning(), e);
+    }
 
-    match cli.command {
-        Some(Commands::Apps { command }) => {
-            println!("\n{}", "🤖 AI CLI - Tools".bright_cyan().bold());
-            println!("{}\n", "=".repeat(17).bright_cyan());
+    // The default log is best effort; an explicit --log-file must work
+    if let Err(e) = logging::init(cli.log_file.as_deref())
+        && cli.log_file.is_some()
+    {
+        eprintln!("Error: {:?}", e);
+        return ExitCode::from(exit::FAILURE);
+    }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    logging::line(&format!("ai-cli {}", args.join(" ")));
+    if !matches!(cli.command, Some(Commands::Undo { .. })) {
+        journal::begin(&args.join(" "));
+    }
 
-            match command {
-                None | Some(AppsCommands::List) => {
-                    let spinner = ProgressBar::new_spinner();
-                    spinner.set_style(
-                        ProgressStyle::default_spinner()
-                            .template("{spinner:.cyan} {msg}")
-                            .unwrap(),
-                    );
-                    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-                    spinner.set_message("Checking installed tools...");
-
-                    let mut tools = installed_versions();
-                    spinner.finish_and_clear();
-
-                    check_latest_versions(&mut tools).await;
-
-                    let label_width = tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
-                    let id_width = tools
-                        .iter()
-                        .map(|t| t.identifier.as_ref().map(|id| id.len()).unwrap_or(0))
-                        .max()
-                        .unwrap_or(0);
-                    let installed: Vec<_> =
-                        tools.iter().filter(|t| t.installed.is_some()).collect();
-                    let not_installed: Vec<_> =
-                        tools.iter().filter(|t| t.installed.is_none()).collect();
-
-                    let all_up_to_date = installed.iter().all(|t| {
-                        if let (Some(installed_ver), Some(latest_ver)) = (&t.installed, &t.latest) {
-                            installed_ver.contains(latest_ver) || latest_ver.contains(installed_ver)
-                        } else {
-                            true
-                        }
-                    });
+    if onboarding::is_first_run()
+        && !machine_output(&cli.command)
+        && !matches!(cli.command, Some(Commands::Onboard))
+        && let Err(e) = onboarding::offer()
+    {
+        eprintln!("{} Setup failed: {:#}", "!".warning(), e);
+    }
 
-                    if !installed.is_empty() {
-                        println!("{}", "Installed:".bright_green().bold());
-                        for tool in &installed {
-                            print_version(tool, true, label_width, id_width);
-                        }
-                        if all_up_to_date {
-                            println!("\n{}", "✓ All tools are up to date".green());
-                        }
-                    }
+    let show_update_hint = wants_update_hint(&cli.command) && update_notice::enabled();
+    let update_check = if show_update_hint {
+        update_notice::spawn()
+    } else {
+        None
+    };
+    if changes_tools(&cli.command) {
+        update_notice::invalidate();
+    }
 
-                    if !not_installed.is_empty() {
-                        if !installed.is_empty() {
-                            println!();
-                        }
-                        println!("{}", "Not Installed:".bright_black().bold());
-                        for tool in &not_installed {
-                            print_version(tool, true, label_width, id_width);
-                        }
-                    }
+    let code = match run(cli).await {
+        Ok(outcome) => outcome.code(),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            logging::line(&format!("error: {:#}", e));
+            exit::code_for_error(&e)
+        }
+    };
+    journal::finish();
+
+    if show_update_hint {
+        update_notice::finish(update_check).await;
+    }
+
+    if telemetry::enabled() {
+        telemetry::record(&expanded, code).await;
+    }
+
+    logging::line(&format!("exit code {}", code));
+    ExitCode::from(code)
+}
+
+/// Commands whose output is meant for machines, or that already show versions, get no hint
+fn wants_update_hint(command: &Option<Commands>) -> bool {
+    !matches!(command, Some(Commands::Apps { .. } | Commands::Run { .. }))
+        && !machine_output(command)
+}
+
+/// Commands whose output is parsed, saved or pasted rather than read
+fn machine_output(command: &Option<Commands>) -> bool {
+    matches!(
+        command,
+        None | Some(
+            Commands::Env { .. }
+                | Commands::Export { .. }
+                | Commands::Completions { .. }
+                | Commands::ShellInit { .. }
+                | Commands::Manpages { .. }
+                | Commands::Profile {
+                    command: ProfileCommands::Env { .. },
                 }
-                Some(AppsCommands::Check) => {
-                    let spinner = ProgressBar::new_spinner();
-                    spinner.set_style(
-                        ProgressStyle::default_spinner()
-                            .template("{spinner:.cyan} {msg}")
-                            .unwrap(),
-                    );
-                    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-                    spinner.set_message("Checking installed tools...");
-
-                    let mut tools = installed_versions();
-                    spinner.finish_and_clear();
-
-                    check_latest_versions(&mut tools).await;
-                    let label_width = tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
-                    let id_width = tools
-                        .iter()
-                        .map(|t| t.identifier.as_ref().map(|id| id.len()).unwrap_or(0))
-                        .max()
-                        .unwrap_or(0);
-                    println!();
-                    for tool in &tools {
-                        print_version(tool, true, label_width, id_width);
-                    }
+                | Commands::Mcp {
+                    command: Some(
+                        McpCommands::Gateway {
+                            command: Some(GatewayCommands::Serve),
+                        } | McpCommands::Exec { .. }
+                    ),
                 }
+        )
+    )
+}
+
+fn changes_tools(command: &Option<Commands>) -> bool {
+    matches!(
+        command,
+        Some(Commands::Apps {
+            command: Some(
+                AppsCommands::Install { .. }
+                    | AppsCommands::Add { .. }
+                    | AppsCommands::Upgrade { .. }
+                    | AppsCommands::Update { .. }
+                    | AppsCommands::Uninstall { .. }
+                    | AppsCommands::Remove { .. }
+            ),
+        })
+    )
+}
+
+async fn run(cli: Cli) -> Result<Outcome> {
+    if cli.check_update {
+        return Ok(versions::handle_check_update().await);
+    }
+
+    let outcome = match cli.command {
+        Some(Commands::Apps { command }) => {
+            output::header("🤖", "AI CLI - Tools");
+
+            let outcome = match command {
+                None | Some(AppsCommands::List) => {
+                    handle_list_command().await;
+                    Outcome::Success
+                }
+                Some(AppsCommands::Check) => handle_check_command().await,
+                Some(AppsCommands::Info { tool }) => handle_info_command(&tool).await?,
+                Some(AppsCommands::Changelog { tool, all }) => {
+                    changelog::handle_changelog(tool.as_deref(), all).await?
+                }
+                Some(AppsCommands::Cleanup { dry_run }) => handle_cleanup_command(dry_run)?,
                 Some(AppsCommands::Upgrade { tool }) | Some(AppsCommands::Update { tool }) => {
-                    handle_upgrade_command(tool.as_deref()).await?;
+                    handle_upgrade_command(tool.as_deref()).await?
                 }
                 Some(AppsCommands::Install { tool }) | Some(AppsCommands::Add { tool }) => {
-                    handle_install_command(tool.as_deref()).await?;
+                    handle_install_command(tool.as_deref()).await?
                 }
                 Some(AppsCommands::Uninstall {
                     tool,
@@ -118,60 +175,721 @@ async fn main() -> Result<()> {
                     tool,
                     remove_config,
                     force,
-                }) => {
-                    handle_uninstall_command(tool.as_deref(), remove_config, force).await?;
-                }
-            }
+                }) => handle_uninstall_command(tool.as_deref(), remove_config, force).await?,
+            };
 
             println!();
+            outcome
         }
+        Some(Commands::Mcp {
+            command:
+                Some(McpCommands::Gateway {
+                    command: Some(GatewayCommands::Serve),
+                }),
+        }) => {
+            // stdout carries the MCP protocol
+            mcp::gateway::handle_serve()?;
+            Outcome::Success
+        }
+        Some(Commands::Mcp {
+            command:
+                Some(McpCommands::Exec {
+                    server,
+                    secrets,
+                    secret_args,
+                    command,
+                }),
+        }) => mcp::secrets::handle_exec(&server, &secrets, &secret_args, &command)?,
         Some(Commands::Mcp { command }) => {
-            println!("\n{}", "🔌 AI CLI - MCP Servers".bright_cyan().bold());
-            println!("{}\n", "=".repeat(23).bright_cyan());
+            output::header("🔌", "AI CLI - MCP Servers");
 
-            match command {
+            let outcome = match command {
                 None | Some(McpCommands::List) => {
                     mcp::handle_list()?;
+                    Outcome::Success
+                }
+                Some(McpCommands::Enable {
+                    server,
+                    tools,
+                    keychain,
+                }) => mcp::handle_enable(&server, &tools, keychain)?,
+                Some(McpCommands::Disable { server, tools }) => {
+                    mcp::handle_disable(&server, &tools)?
                 }
-                Some(McpCommands::Enable { server }) => {
-                    mcp::handle_enable(&server)?;
+                Some(McpCommands::Add { from, id }) => {
+                    mcp::import::handle_add(&from, id.as_deref()).await?
                 }
-                Some(McpCommands::Disable { server }) => {
-                    mcp::handle_disable(&server)?;
+                Some(McpCommands::Apply { file, dry_run }) => {
+                    mcp::apply::handle_apply(file.as_deref(), dry_run)?
                 }
                 Some(McpCommands::Doctor) => {
-                    mcp::handle_doctor()?;
+                    mcp::handle_doctor().await?;
+                    Outcome::Success
                 }
-            }
+                Some(McpCommands::Gateway { command }) => {
+                    match command.unwrap_or(GatewayCommands::Status) {
+                        GatewayCommands::Status => {
+                            mcp::gateway::handle_status()?;
+                            Outcome::Success
+                        }
+                        GatewayCommands::Enable { server } => {
+                            mcp::gateway::handle_toggle(&server, true)?
+                        }
+                        GatewayCommands::Disable { server } => {
+                            mcp::gateway::handle_toggle(&server, false)?
+                        }
+                        GatewayCommands::Install => mcp::gateway::handle_install()?,
+                        GatewayCommands::Uninstall => mcp::gateway::handle_uninstall()?,
+                        GatewayCommands::Serve => unreachable!("handled before the header"),
+                    }
+                }
+                Some(McpCommands::Exec { .. }) => unreachable!("handled before the header"),
+            };
 
             println!();
+            outcome
         }
-        Some(Commands::Skills { command }) => {
-            println!("\n{}", "📚 AI CLI - Skills".bright_cyan().bold());
-            println!("{}\n", "=".repeat(18).bright_cyan());
+        Some(Commands::Skills { command, token }) => {
+            output::header("📚", "AI CLI - Skills");
+            if let Some(token) = token {
+                skills::auth::set_token(token);
+            }
 
-            match command {
+            let outcome = match command {
                 None => {
-                    skills::handle_list(None)?;
+                    skills::handle_list(None, &Scope::Global)?;
+                    Outcome::Success
+                }
+                Some(SkillsCommands::List { agent, project }) => {
+                    skills::handle_list(agent.as_deref(), &skills::agents::scope(project)?)?;
+                    Outcome::Success
+                }
+                Some(SkillsCommands::Info { skill }) => skills::handle_info(&skill)?,
+                Some(SkillsCommands::Install {
+                    repo,
+                    git_ref,
+                    locked,
+                    agent,
+                    only,
+                    exclude,
+                    all,
+                    copy,
+                    force,
+                    project,
+                }) => match repo {
+                    _ if locked => {
+                        skills::handle_install_locked(repo.as_deref(), agent.as_deref(), copy)?
+                    }
+                    Some(repo) => skills::handle_install(
+                        &repo,
+                        git_ref.as_deref(),
+                        agent.as_deref(),
+                        &skills::SkillSelection { only, exclude, all },
+                        copy,
+                        force,
+                        &skills::agents::scope(project)?,
+                    )?,
+                    None => unreachable!("clap requires a repository without --locked"),
+                },
+                Some(SkillsCommands::Remove {
+                    skill,
+                    repo,
+                    all,
+                    agent,
+                    project,
+                }) => {
+                    let scope = skills::agents::scope(project)?;
+                    match skill {
+                        Some(skill) => skills::handle_remove(&skill, agent.as_deref(), &scope)?,
+                        None => skills::handle_remove_many(
+                            repo.as_deref(),
+                            all,
+                            agent.as_deref(),
+                            &scope,
+                        )?,
+                    }
+                }
+                Some(SkillsCommands::Dev {
+                    path,
+                    agent,
+                    watch,
+                    project,
+                }) => skills::handle_dev(&path, &agent, watch, &skills::agents::scope(project)?)?,
+                Some(SkillsCommands::Convert { skill, to, project }) => {
+                    skills::handle_convert(&skill, &to, project)?
+                }
+                Some(SkillsCommands::Disable {
+                    skill,
+                    agent,
+                    project,
+                }) => skills::handle_disable(
+                    &skill,
+                    agent.as_deref(),
+                    &skills::agents::scope(project)?,
+                )?,
+                Some(SkillsCommands::Enable {
+                    skill,
+                    agent,
+                    project,
+                }) => skills::handle_enable(
+                    &skill,
+                    agent.as_deref(),
+                    &skills::agents::scope(project)?,
+                )?,
+                Some(SkillsCommands::Doctor { agent, project }) => {
+                    skills::handle_doctor(agent.as_deref(), &skills::agents::scope(project)?)?
+                }
+                Some(SkillsCommands::Sync { from, to }) => skills::handle_sync(&from, &to)?,
+                Some(SkillsCommands::Diff { skill }) => skills::handle_diff(skill.as_deref())?,
+                Some(SkillsCommands::Outdated) => skills::handle_outdated()?,
+                Some(SkillsCommands::Update { skill }) => {
+                    skills::handle_update(skill.as_deref()).await?
                 }
-                Some(SkillsCommands::List { agent }) => {
-                    skills::handle_list(agent.as_deref())?;
+                Some(SkillsCommands::Subscribe { repo, agent, copy }) => {
+                    skills::handle_subscribe(&repo, agent.as_deref(), copy)?
                 }
-                Some(SkillsCommands::Install { repo, agent }) => {
-                    skills::handle_install(&repo, agent.as_deref())?;
+                Some(SkillsCommands::Unsubscribe { repo }) => skills::handle_unsubscribe(&repo)?,
+                Some(SkillsCommands::Export { file }) => skills::handle_export(&file)?,
+                Some(SkillsCommands::Import { file, agent }) => {
+                    skills::handle_import(&file, agent.as_deref())?
                 }
-                Some(SkillsCommands::Remove { skill, agent }) => {
-                    skills::handle_remove(&skill, agent.as_deref())?;
+                Some(SkillsCommands::Search { query }) => skills::handle_search(&query).await?,
+                Some(SkillsCommands::Grep { query, agent }) => {
+                    skills::handle_grep(&query, agent.as_deref())?
                 }
+                Some(SkillsCommands::Validate { target }) => {
+                    skills::handle_validate(target.as_deref())?
+                }
+                Some(SkillsCommands::Prune { agent, dry_run }) => {
+                    skills::handle_prune(agent.as_deref(), dry_run)?
+                }
+            };
+
+            println!();
+            outcome
+        }
+        Some(Commands::Doctor) => {
+            output::header("🩺", "AI CLI - Doctor");
+
+            if doctor::handle_doctor().await? {
+                Outcome::Success
+            } else {
+                Outcome::Failure
             }
+        }
+        Some(Commands::Onboard) => {
+            onboarding::handle_onboard()?;
+            Outcome::Success
+        }
+        Some(Commands::Env { keys: false }) => {
+            environment::handle_env()?;
+            Outcome::Success
+        }
+        Some(Commands::Env { keys: true }) => {
+            if environment::handle_keys()? {
+                Outcome::Success
+            } else {
+                Outcome::Failure
+            }
+        }
+        Some(Commands::Export { file }) => {
+            snapshot::handle_export(&file)?;
+            Outcome::Success
+        }
+        Some(Commands::Import { file }) => {
+            output::header("📦", "AI CLI - Import");
+
+            let outcome = snapshot::handle_import(&file).await?;
+            println!();
+            outcome
+        }
+        Some(Commands::Instructions { command }) => {
+            output::header("📝", "AI CLI - Instructions");
+
+            let outcome = match command {
+                InstructionsCommands::Status { project } => {
+                    if instructions::handle_status(&Scope::from_flag(project)?)? {
+                        Outcome::Success
+                    } else {
+                        Outcome::Failure
+                    }
+                }
+                InstructionsCommands::Sync { project } => {
+                    instructions::handle_sync(&Scope::from_flag(project)?)?
+                }
+                InstructionsCommands::Init { project, from } => {
+                    instructions::handle_init(&Scope::from_flag(project)?, from.as_deref())?;
+                    Outcome::Success
+                }
+                InstructionsCommands::Edit { project } => {
+                    instructions::handle_edit(&Scope::from_flag(project)?)?;
+                    Outcome::Success
+                }
+            };
 
             println!();
+            outcome
         }
-        None => {
-            // This won't happen due to arg_required_else_help = true
-            unreachable!()
+        Some(Commands::Keys { command }) => {
+            output::header("🔑", "AI CLI - Keys");
+
+            let outcome = match command.unwrap_or(KeysCommands::List) {
+                KeysCommands::List => {
+                    keys::handle_list()?;
+                    Outcome::Success
+                }
+                KeysCommands::Set { provider } => keys::handle_set(&provider)?,
+            };
+            println!();
+            outcome
         }
-    }
+        Some(Commands::Search { term }) => {
+            output::header("🔭", "AI CLI - Search");
+
+            let outcome = search::handle_search(&term).await?;
+            println!();
+            outcome
+        }
+        Some(Commands::ShellInit { shell }) => {
+            shell_init::handle_shell_init(shell)?;
+            Outcome::Success
+        }
+        Some(Commands::Telemetry { command }) => {
+            output::header("📈", "AI CLI - Telemetry");
+
+            let outcome = match command.unwrap_or(TelemetryCommands::Status) {
+                TelemetryCommands::On => telemetry::handle_on()?,
+                TelemetryCommands::Off => telemetry::handle_off()?,
+                TelemetryCommands::Status => {
+                    telemetry::handle_status()?;
+                    Outcome::Success
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Bench {
+            prompt,
+            tools,
+            timeout,
+            save,
+        }) => {
+            output::header("⏱", "AI CLI - Bench");
+
+            let outcome = bench::handle_bench(&prompt, &tools, timeout, save.as_deref()).await?;
+            println!();
+            outcome
+        }
+        Some(Commands::Setup { file }) => {
+            output::header("🛠", "AI CLI - Setup");
+
+            let outcome = setup::handle_setup(&file).await?;
+            println!();
+            outcome
+        }
+        Some(Commands::Migrate { from, to, dry_run }) => {
+            output::header("🚚", "AI CLI - Migrate");
+
+            let outcome = migrate::handle_migrate(&from, &to, dry_run)?;
+            println!();
+            outcome
+        }
+        Some(Commands::Backup { command }) => {
+            output::header("💾", "AI CLI - Backup");
+
+            let outcome = match command.unwrap_or(BackupCommands::List) {
+                BackupCommands::Create { encrypt, keep } => {
+                    backup::handle_create(encrypt.as_deref(), keep)?
+                }
+                BackupCommands::List => {
+                    backup::handle_list()?;
+                    Outcome::Success
+                }
+                BackupCommands::Restore { id, identity } => {
+                    backup::handle_restore(id.as_deref(), identity.as_deref())?
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Quota) => {
+            output::header("⛽", "AI CLI - Quota");
+
+            let outcome = if quota::handle_quota().await? {
+                Outcome::Success
+            } else {
+                Outcome::Failure
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Compare) => {
+            output::header("📋", "AI CLI - Compare");
+            compare::handle_compare().await?;
+            println!();
+            Outcome::Success
+        }
+        Some(Commands::OutputStyles { command }) => {
+            output::header("🖋", "AI CLI - Output Styles");
+
+            let outcome = match command.unwrap_or(OutputStylesCommands::List { project: false }) {
+                OutputStylesCommands::List { project } => {
+                    output_styles::handle_list(&Scope::from_flag(project)?)?;
+                    Outcome::Success
+                }
+                OutputStylesCommands::Install { repo, project } => {
+                    output_styles::handle_install(&repo, &Scope::from_flag(project)?)?
+                }
+                OutputStylesCommands::Remove { name, project } => {
+                    output_styles::handle_remove(&name, &Scope::from_flag(project)?)?
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Init {
+            devcontainer,
+            tools,
+            force,
+        }) => {
+            if !devcontainer {
+                return Err(exit::usage("Nothing to generate; pass --devcontainer"));
+            }
+            output::header("🐳", "AI CLI - Init");
+
+            let outcome = devcontainer::handle_devcontainer(&tools, force)?;
+            println!();
+            outcome
+        }
+        Some(Commands::Workspace { command }) => {
+            output::header("🌳", "AI CLI - Workspace");
+
+            let outcome = match command.unwrap_or(WorkspaceCommands::List) {
+                WorkspaceCommands::New { branch, path, from } => {
+                    workspace::handle_new(&branch, path.as_deref(), from.as_deref())?
+                }
+                WorkspaceCommands::List => {
+                    workspace::handle_list()?;
+                    Outcome::Success
+                }
+                WorkspaceCommands::Remove { name, force } => {
+                    workspace::handle_remove(&name, force)?
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Logs {
+            tool,
+            follow,
+            level,
+            grep,
+            lines,
+        }) => {
+            let level = match level.as_deref() {
+                Some(name) => Some(logs::Level::parse(name).ok_or_else(|| {
+                    exit::usage(format!(
+                        "Unknown level '{}'. Levels: error, warn, info, debug, trace",
+                        name
+                    ))
+                })?),
+                None => None,
+            };
+            let filter = logs::Filter {
+                level,
+                keyword: grep,
+            };
+            output::header("📜", "AI CLI - Logs");
+
+            let outcome = if logs::handle_logs(&tool, lines, &filter, follow)? {
+                Outcome::Success
+            } else {
+                Outcome::Failure
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Prune {
+            stores,
+            older_than,
+            dry_run,
+        }) => {
+            output::header("🧹", "AI CLI - Prune");
+
+            let outcome = prune::handle_prune(&stores, older_than, dry_run)?;
+            println!();
+            outcome
+        }
+        Some(Commands::Audit) => {
+            output::header("🔍", "AI CLI - Audit");
+
+            let outcome = if audit::handle_audit()? {
+                Outcome::Success
+            } else {
+                Outcome::Failure
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Undo { last, force }) => {
+            output::header("↩️", "AI CLI - Undo");
+            let outcome = journal::handle_undo(last, force)?;
+            println!();
+            outcome
+        }
+        Some(Commands::Status { providers: false }) => {
+            output::header("📊", "AI CLI - Status");
+            overview::handle_overview().await?
+        }
+        Some(Commands::Status { providers: true }) => {
+            output::header("📡", "AI CLI - Provider Status");
+
+            let outcome = if status::handle_providers().await? {
+                Outcome::Success
+            } else {
+                Outcome::Failure
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Run {
+            tool,
+            profile,
+            model,
+            update_check,
+            args,
+        }) => {
+            run::handle_run(
+                &tool,
+                profile.as_deref(),
+                model.as_deref(),
+                update_check,
+                &args,
+            )
+            .await?
+        }
+        Some(Commands::Sandbox { command }) => {
+            output::header("🔒", "AI CLI - Sandbox");
+
+            let outcome = match command.unwrap_or(SandboxCommands::Show { project: false }) {
+                SandboxCommands::Show { project } => {
+                    sandbox::handle_show(&Scope::from_flag(project)?)?;
+                    Outcome::Success
+                }
+                SandboxCommands::List => {
+                    sandbox::handle_list()?;
+                    Outcome::Success
+                }
+                SandboxCommands::Use { preset, project } => {
+                    sandbox::handle_use(&preset, &Scope::from_flag(project)?)?
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Appearance { command }) => {
+            output::header("🎨", "AI CLI - Appearance");
+
+            let outcome = match command.unwrap_or(AppearanceCommands::Show) {
+                AppearanceCommands::Show => {
+                    appearance::handle_show()?;
+                    Outcome::Success
+                }
+                AppearanceCommands::List => {
+                    appearance::handle_list()?;
+                    Outcome::Success
+                }
+                AppearanceCommands::Statusline { name } => appearance::handle_statusline(&name)?,
+                AppearanceCommands::Theme { scheme } => appearance::handle_theme(&scheme)?,
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Settings { command }) => {
+            output::header("🛡️", "AI CLI - Settings");
+
+            let outcome = match command.unwrap_or(SettingsCommands::Audit) {
+                SettingsCommands::Show => {
+                    settings::handle_show()?;
+                    Outcome::Success
+                }
+                SettingsCommands::Audit => {
+                    if settings::handle_audit()? {
+                        Outcome::Success
+                    } else {
+                        Outcome::Failure
+                    }
+                }
+                SettingsCommands::Apply => settings::handle_apply()?,
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Memory { command }) => {
+            output::header("🧩", "AI CLI - Memory");
+
+            let outcome = match command.unwrap_or(MemoryCommands::List) {
+                MemoryCommands::List => {
+                    memory::handle_list()?;
+                    Outcome::Success
+                }
+                MemoryCommands::Show { agent } => {
+                    memory::handle_show(&agent)?;
+                    Outcome::Success
+                }
+                MemoryCommands::Prune { agent, pattern } => {
+                    memory::handle_prune(&agent, pattern.as_deref())?
+                }
+                MemoryCommands::Wipe { agent } => memory::handle_wipe(&agent)?,
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Rules { command }) => {
+            output::header("📏", "AI CLI - Rules");
+
+            let outcome = match command {
+                RulesCommands::List { project } => {
+                    rules::handle_list(&Scope::from_flag(project)?)?;
+                    Outcome::Success
+                }
+                RulesCommands::Sync { project } => rules::handle_sync(&Scope::from_flag(project)?)?,
+                RulesCommands::Import { project } => {
+                    rules::handle_import(&Scope::from_flag(project)?)?
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Commands { command }) => {
+            output::header("⌘", "AI CLI - Commands");
+
+            let outcome = match command.unwrap_or(CommandsCommands::List { project: false }) {
+                CommandsCommands::List { project } => {
+                    commands::handle_list(&Scope::from_flag(project)?)?;
+                    Outcome::Success
+                }
+                CommandsCommands::Install { repo, project } => {
+                    commands::handle_install(&repo, &Scope::from_flag(project)?)?
+                }
+                CommandsCommands::Sync { project } => {
+                    commands::handle_sync(&Scope::from_flag(project)?)?
+                }
+                CommandsCommands::Remove { name, project } => {
+                    commands::handle_remove(&name, &Scope::from_flag(project)?)?
+                }
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Prompts { command }) => {
+            output::header("💬", "AI CLI - Prompts");
+
+            let outcome = match command.unwrap_or(PromptsCommands::List) {
+                PromptsCommands::List => {
+                    prompts::handle_list()?;
+                    Outcome::Success
+                }
+                PromptsCommands::Add { name, from } => {
+                    prompts::handle_add(&name, from.as_deref())?;
+                    Outcome::Success
+                }
+                PromptsCommands::Sync => prompts::handle_sync()?,
+                PromptsCommands::Remove { name } => prompts::handle_remove(&name)?,
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Models { command }) => {
+            output::header("🧠", "AI CLI - Models");
+
+            let outcome = match command.unwrap_or(ModelsCommands::List) {
+                ModelsCommands::List => {
+                    models::handle_list()?;
+                    Outcome::Success
+                }
+                ModelsCommands::Set { model, tool } => models::handle_set(&model, tool.as_deref())?,
+            };
+            println!();
+            outcome
+        }
+        Some(Commands::Profile { command }) => match command {
+            ProfileCommands::List => {
+                profiles::handle_list()?;
+                Outcome::Success
+            }
+            ProfileCommands::Show { name } => {
+                profiles::handle_show(&name)?;
+                Outcome::Success
+            }
+            ProfileCommands::Use { name } => {
+                output::header("👤", "AI CLI - Profile");
+
+                let outcome = profiles::handle_use(&name)?;
+                println!();
+                outcome
+            }
+            ProfileCommands::Env { name } => {
+                profiles::handle_env(name.as_deref())?;
+                Outcome::Success
+            }
+        },
+        Some(Commands::Sessions { command }) => match command.unwrap_or(SessionsCommands::List {
+            tool: None,
+            limit: 20,
+        }) {
+            SessionsCommands::List { tool, limit } => {
+                sessions::handle_list(tool.as_deref(), limit)?;
+                Outcome::Success
+            }
+            SessionsCommands::Show { id } => {
+                sessions::handle_show(&id)?;
+                Outcome::Success
+            }
+            SessionsCommands::Search { query, tool } => {
+                if sessions::handle_search(&query, tool.as_deref())? {
+                    Outcome::Success
+                } else {
+                    Outcome::NothingToDo
+                }
+            }
+        },
+        Some(Commands::Usage { days }) => {
+            output::header("📊", "AI CLI - Usage");
+
+            usage::handle_usage(days)?;
+            Outcome::Success
+        }
+        Some(Commands::Sync { command }) => {
+            output::header("🔄", "AI CLI - Sync");
+
+            let outcome = match command {
+                SyncCommands::Init { remote } => {
+                    sync::handle_init(&remote)?;
+                    Outcome::Success
+                }
+                SyncCommands::Push => sync::handle_push()?,
+                SyncCommands::Pull { force } => sync::handle_pull(force).await?,
+                SyncCommands::Status => {
+                    sync::handle_status()?;
+                    Outcome::Success
+                }
+                SyncCommands::Daemon { interval } => sync::handle_daemon(interval).await?,
+            };
+
+            println!();
+            outcome
+        }
+        Some(Commands::Completions { shell }) => {
+            completions::handle_completions(shell)?;
+            Outcome::Success
+        }
+        Some(Commands::Manpages { out_dir }) => {
+            manpages::handle_manpages(&out_dir)?;
+            Outcome::Success
+        }
+        None => return Err(exit::usage("No command given; see `ai-cli --help`")),
+    };
 
-    Ok(())
+    Ok(outcome)
 }
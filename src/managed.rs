@@ -0,0 +1,377 @@
+//! Organization defaults published at `managed_config_url`.
+//!
+//! The policy is fetched at most once a day and cached; `doctor` reports
+//! deviations and `sync pull` remediates them.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{find_tool, install_tool};
+use crate::config;
+use crate::exit::Outcome;
+use crate::http;
+use crate::logging;
+use crate::mcp::{servers, targets};
+use crate::node;
+use crate::paths;
+use crate::prompt;
+use crate::skills::actions::install_from_repo;
+use crate::skills::{agents, discovery};
+use crate::theme::Themed;
+use crate::tools::{self, InstallMethod};
+
+/// Refetch the policy when the cached copy is older than this
+const REFRESH_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// Defaults an organization publishes for every machine
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManagedPolicy {
+    /// Tools that must be installed (names or binaries, e.g. "claude")
+    #[serde(default)]
+    pub required_tools: Vec<String>,
+    /// MCP servers tools may have enabled; unset allows any
+    #[serde(default)]
+    pub approved_mcp_servers: Option<Vec<String>>,
+    /// Skills every installed agent must have
+    #[serde(default)]
+    pub skills: Vec<ManagedSkill>,
+    /// Exact versions, keyed by tool name or binary
+    #[serde(default)]
+    pub pinned_versions: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManagedSkill {
+    pub name: String,
+    /// Repository to install it from (`owner/repo` or git URL)
+    pub source: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    url: String,
+    fetched_at: i64,
+    policy: ManagedPolicy,
+}
+
+fn cache_path() -> PathBuf {
+    paths::state_home()
+        .join("ai-cli")
+        .join("managed-policy.json")
+}
+
+fn load_cache(url: &str) -> Option<Cache> {
+    let content = std::fs::read_to_string(cache_path()).ok()?;
+    let cache: Cache = serde_json::from_str(&content).ok()?;
+    (cache.url == url).then_some(cache)
+}
+
+fn save_cache(cache: &Cache) -> Result<()> {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(cache)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+async fn fetch(url: &str) -> Result<ManagedPolicy> {
+    http::send(http::client().get(url))
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse the managed policy from {}", url))
+}
+
+/// The organization policy, or `None` when no `managed_config_url` is set
+///
+/// Uses the cached copy while it is fresh, and falls back to it when the URL
+/// can't be reached.
+pub async fn load() -> Result<Option<ManagedPolicy>> {
+    let Some(url) = config::load()?.managed_config_url else {
+        return Ok(None);
+    };
+    let now = chrono::Utc::now().timestamp();
+    let cached = load_cache(&url);
+    if let Some(cache) = &cached
+        && now - cache.fetched_at < REFRESH_INTERVAL_SECS
+    {
+        return Ok(Some(cache.policy.clone()));
+    }
+
+    match fetch(&url).await {
+        Ok(policy) => {
+            let _ = save_cache(&Cache {
+                url,
+                fetched_at: now,
+                policy: policy.clone(),
+            });
+            Ok(Some(policy))
+        }
+        Err(e) => match cached {
+            Some(cache) => {
+                logging::line(&format!("managed policy: using cached copy: {:#}", e));
+                Ok(Some(cache.policy))
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Something on this machine that differs from the managed policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum Deviation {
+    MissingTool {
+        tool: String,
+    },
+    WrongVersion {
+        tool: String,
+        pinned: String,
+        installed: String,
+    },
+    UnapprovedServer {
+        target: String,
+        server: String,
+    },
+    MissingSkill {
+        skill: ManagedSkill,
+        agents: Vec<String>,
+    },
+}
+
+impl Deviation {
+    pub fn message(&self) -> String {
+        match self {
+            Deviation::MissingTool { tool } => format!("{} is required but not installed", tool),
+            Deviation::WrongVersion {
+                tool,
+                pinned,
+                installed,
+            } => format!("{} {} installed, {} is pinned", tool, installed, pinned),
+            Deviation::UnapprovedServer { target, server } => {
+                format!("{} has unapproved MCP server {}", target, server)
+            }
+            Deviation::MissingSkill { skill, agents } => {
+                format!(
+                    "Required skill {} missing for {}",
+                    skill.name,
+                    agents.join(", ")
+                )
+            }
+        }
+    }
+
+    pub fn fix(&self) -> String {
+        match self {
+            Deviation::MissingTool { tool } => {
+                let catalog = tools::catalog();
+                let binary = find_tool(&catalog, tool).and_then(|t| t.binary_name.clone());
+                format!("ai-cli apps install {}", binary.as_deref().unwrap_or(tool))
+            }
+            Deviation::WrongVersion { .. } => {
+                "ai-cli sync pull installs pinned versions".to_string()
+            }
+            Deviation::UnapprovedServer { server, .. } => format!("ai-cli mcp disable {}", server),
+            Deviation::MissingSkill { skill, .. } => {
+                format!("ai-cli skills install {}", skill.source)
+            }
+        }
+    }
+}
+
+/// Compare installed tools, MCP servers and skills with `policy`
+pub fn check(policy: &ManagedPolicy) -> Vec<Deviation> {
+    let mut deviations = Vec::new();
+
+    let catalog = tools::catalog();
+    let versions = tools::installed_versions();
+    let installed_version = |name: &str| -> Option<(String, Option<String>)> {
+        let tool = find_tool(&catalog, name)?;
+        let version = versions
+            .iter()
+            .find(|v| v.name == tool.name)
+            .and_then(|v| v.installed.clone());
+        Some((tool.name.clone(), version))
+    };
+
+    for name in &policy.required_tools {
+        match installed_version(name) {
+            Some((tool, None)) => deviations.push(Deviation::MissingTool { tool }),
+            Some(_) => {}
+            None => deviations.push(Deviation::MissingTool { tool: name.clone() }),
+        }
+    }
+    for (name, pinned) in &policy.pinned_versions {
+        if let Some((tool, Some(installed))) = installed_version(name)
+            && installed.trim_start_matches('v') != pinned.trim_start_matches('v')
+        {
+            deviations.push(Deviation::WrongVersion {
+                tool,
+                pinned: pinned.clone(),
+                installed,
+            });
+        }
+    }
+
+    if let Some(approved) = &policy.approved_mcp_servers {
+        let servers = servers::catalog();
+        for target in targets::catalog().into_iter().filter(|t| t.is_installed()) {
+            for server in &servers {
+                if !approved.contains(&server.id)
+                    && target.is_server_enabled(server).unwrap_or(false)
+                {
+                    deviations.push(Deviation::UnapprovedServer {
+                        target: target.name.to_string(),
+                        server: server.id.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    let installed_agents: Vec<_> = agents::catalog()
+        .into_iter()
+        .filter(|a| a.is_installed())
+        .collect();
+    for skill in &policy.skills {
+        let missing: Vec<String> = installed_agents
+            .iter()
+            .filter(|agent| {
+                !discovery::list_installed_skills(&agent.skills_path)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|s| s.name == skill.name)
+            })
+            .map(|agent| agent.id.to_string())
+            .collect();
+        if !missing.is_empty() {
+            deviations.push(Deviation::MissingSkill {
+                skill: skill.clone(),
+                agents: missing,
+            });
+        }
+    }
+
+    deviations
+}
+
+/// Install `version` of an npm-installed tool
+pub(crate) fn install_pinned(tool: &tools::Tool, version: &str) -> Result<()> {
+    let InstallMethod::Npm(package) = &tool.install_method else {
+        anyhow::bail!(
+            "{} can't be pinned automatically; install {} by hand",
+            tool.name,
+            version
+        );
+    };
+    let status = logging::run(node::npm_global("install").arg(format!("{}@{}", package, version)))
+        .context("Failed to run npm install")?;
+    if !status.success() {
+        anyhow::bail!("npm install failed for {}@{}", package, version);
+    }
+    Ok(())
+}
+
+/// Fix every deviation from the managed policy, after confirmation
+pub async fn remediate(policy: &ManagedPolicy) -> Result<Outcome> {
+    let deviations = check(policy);
+    if deviations.is_empty() {
+        println!(
+            "{} Matches the organization's managed policy",
+            "✓".success()
+        );
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!("{}", "Managed policy deviations:".bold());
+    for deviation in &deviations {
+        println!("  {} {}", "!".warning(), deviation.message());
+    }
+    println!();
+    if !prompt::confirm("Remediate these deviations?")? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let catalog = tools::catalog();
+    let mut failed = 0;
+    for deviation in &deviations {
+        let result = match deviation {
+            Deviation::MissingTool { tool } => match find_tool(&catalog, tool) {
+                Some(tool) => install_tool(tool).await,
+                None => Err(anyhow::anyhow!(
+                    "Unknown tool '{}' in the managed policy",
+                    tool
+                )),
+            },
+            Deviation::WrongVersion { tool, pinned, .. } => match find_tool(&catalog, tool) {
+                Some(tool) => install_pinned(tool, pinned),
+                None => Ok(()),
+            },
+            Deviation::UnapprovedServer { target, server } => {
+                let target = targets::catalog().into_iter().find(|t| t.name == target);
+                match (target, servers::find(server)) {
+                    (Some(target), Some(server)) => target.disable_server(&server).map(|_| ()),
+                    _ => Ok(()),
+                }
+            }
+            Deviation::MissingSkill { skill, agents } => {
+                let agents: Vec<_> = agents.iter().filter_map(|id| agents::find(id)).collect();
+                install_from_repo(
+                    &skill.source,
+                    &agents,
+                    Some(std::slice::from_ref(&skill.name)),
+                )
+            }
+        };
+        match result {
+            Ok(()) => println!("{} {}", "✓".success(), deviation.fix().muted()),
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), deviation.message(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn fetches_the_published_policy() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/ai-cli.json");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        r#"{"required_tools":["claude"],"approved_mcp_servers":["linear"],
+                            "skills":[{"name":"pdf","source":"acme/skills"}],
+                            "pinned_versions":{"codex":"0.50.0"}}"#,
+                    );
+            })
+            .await;
+
+        let policy = fetch(&format!("{}/ai-cli.json", server.base_url()))
+            .await
+            .unwrap();
+        assert_eq!(policy.required_tools, vec!["claude"]);
+        assert_eq!(
+            policy.approved_mcp_servers.as_deref(),
+            Some(&["linear".to_string()][..])
+        );
+        assert_eq!(policy.skills[0].source, "acme/skills");
+        assert_eq!(policy.pinned_versions["codex"], "0.50.0");
+    }
+}
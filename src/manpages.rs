@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::{Command, CommandFactory};
+
+use crate::cli::Cli;
+use crate::theme::Themed;
+
+/// Render man pages for ai-cli and every subcommand into `out_dir`
+pub fn handle_manpages(out_dir: &Path) -> Result<()> {
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create directory {}", out_dir.display()))?;
+
+    let mut cmd = Cli::command();
+    cmd.build();
+
+    let mut written = Vec::new();
+    render_recursive(&cmd, cmd.get_name(), out_dir, &mut written)?;
+
+    println!(
+        "{} Wrote {} man page(s) to {}",
+        "✓".success(),
+        written.len(),
+        out_dir.display()
+    );
+    Ok(())
+}
+
+fn render_recursive(
+    cmd: &Command,
+    page_name: &str,
+    out_dir: &Path,
+    written: &mut Vec<String>,
+) -> Result<()> {
+    // Subcommand pages are named like git's: ai-cli-mcp-enable.1
+    let page = cmd.clone().name(page_name.to_string());
+    let mut buffer = Vec::new();
+    clap_mangen::Man::new(page)
+        .render(&mut buffer)
+        .with_context(|| format!("Failed to render man page for {}", page_name))?;
+
+    let path = out_dir.join(format!("{}.1", page_name));
+    fs::write(&path, buffer).with_context(|| format!("Failed to write {}", path.display()))?;
+    written.push(page_name.to_string());
+
+    for sub in cmd.get_subcommands() {
+        if sub.get_name() == "help" || sub.is_hide_set() {
+            continue;
+        }
+        let sub_name = format!("{}-{}", page_name, sub.get_name());
+        render_recursive(sub, &sub_name, out_dir, written)?;
+    }
+
+    Ok(())
+}
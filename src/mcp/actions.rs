@@ -1,16 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use inquire::validator::Validation;
+use inquire::{Password, Text};
 
-use super::servers::{self, McpServer};
+use super::secrets;
+use super::servers::{self, Input, McpServer, Placement};
 use super::targets::{self, McpTarget};
+use crate::exit::{self, Outcome};
+use crate::keys;
+use crate::logging;
+use crate::notify::{self, Event};
+use crate::prompt;
+use crate::theme::Themed;
 
 #[derive(Clone, Debug)]
 enum ServerStatus {
     Enabled,
+    /// Enabled by an entry ai-cli didn't write
+    UserAdded,
     Disabled,
     Unknown,
     NotInstalled,
@@ -22,7 +34,7 @@ pub fn handle_list() -> Result<()> {
 
     println!("{}", "Available Servers:".bold());
     for server in &servers {
-        println!("  {}  {}", server.id.cyan(), server.description.dimmed());
+        println!("  {}  {}", server.id.info(), server.description.muted());
     }
     println!();
 
@@ -34,16 +46,16 @@ pub fn handle_list() -> Result<()> {
     println!();
 
     // Header
-    print!("  {:<16}", "Tool".dimmed());
+    print!("  {:<16}", "Tool".muted());
     for server in &servers {
-        print!("  {:<12}", server.id.dimmed());
+        print!("  {:<12}", server.id.muted());
     }
     println!();
 
     // Separator
-    print!("  {}", "-".repeat(16).dimmed());
+    print!("  {}", "-".repeat(16).muted());
     for _ in &servers {
-        print!("  {}", "-".repeat(12).dimmed());
+        print!("  {}", "-".repeat(12).muted());
     }
     println!();
 
@@ -52,15 +64,16 @@ pub fn handle_list() -> Result<()> {
         print!("  {:<16}", target.name);
 
         for server in &servers {
-            let key = (target.name, server.id);
+            let key = (target.name, server.id.clone());
             let status = statuses.get(&key).cloned().unwrap_or(ServerStatus::Unknown);
             let status_str = match status {
-                ServerStatus::Enabled => format!("{:<12}", "enabled").green().to_string(),
-                ServerStatus::Disabled => format!("{:<12}", "disabled").yellow().to_string(),
+                ServerStatus::Enabled => format!("{:<12}", "enabled").success().to_string(),
+                ServerStatus::UserAdded => format!("{:<12}", "user-added").info().to_string(),
+                ServerStatus::Disabled => format!("{:<12}", "disabled").warning().to_string(),
                 ServerStatus::NotInstalled => {
-                    format!("{:<12}", "not installed").dimmed().to_string()
+                    format!("{:<12}", "not installed").muted().to_string()
                 }
-                ServerStatus::Unknown => format!("{:<12}", "unknown").dimmed().to_string(),
+                ServerStatus::Unknown => format!("{:<12}", "unknown").muted().to_string(),
             };
             print!("  {}", status_str);
         }
@@ -73,8 +86,8 @@ pub fn handle_list() -> Result<()> {
 fn check_statuses_parallel(
     targets: &[McpTarget],
     servers: &[McpServer],
-) -> HashMap<(&'static str, &'static str), ServerStatus> {
-    let results: Arc<Mutex<HashMap<(&'static str, &'static str), ServerStatus>>> =
+) -> HashMap<(&'static str, String), ServerStatus> {
+    let results: Arc<Mutex<HashMap<(&'static str, String), ServerStatus>>> =
         Arc::new(Mutex::new(HashMap::new()));
 
     let mut handles = vec![];
@@ -92,6 +105,9 @@ fn check_statuses_parallel(
                     ServerStatus::NotInstalled
                 } else {
                     match target.is_server_enabled(&server) {
+                        Ok(true) if !target.owns_server(&server).unwrap_or(true) => {
+                            ServerStatus::UserAdded
+                        }
                         Ok(true) => ServerStatus::Enabled,
                         Ok(false) => ServerStatus::Disabled,
                         Err(_) => ServerStatus::Unknown,
@@ -113,16 +129,141 @@ fn check_statuses_parallel(
     Arc::try_unwrap(results).unwrap().into_inner().unwrap()
 }
 
-pub fn handle_enable(server_name: &str) -> Result<()> {
+/// Read one input, re-asking until it validates
+fn ask(server: &McpServer, input: &Input) -> Result<String> {
+    let label = format!("{} {}:", server.name, input.key);
+    let help = if input.required {
+        input.description.clone()
+    } else {
+        format!("{} (optional, Enter to skip)", input.description)
+    };
+    let check = input.clone();
+    let validator = move |value: &str| {
+        Ok(match check.validate(value.trim()) {
+            Ok(()) => Validation::Valid,
+            Err(e) => Validation::Invalid(e.into()),
+        })
+    };
+    let value = if input.secret {
+        Password::new(&label)
+            .without_confirmation()
+            .with_help_message(&help)
+            .with_validator(validator)
+            .prompt()
+    } else {
+        Text::new(&label)
+            .with_help_message(&help)
+            .with_validator(validator)
+            .prompt()
+    };
+    Ok(value.context("No value entered")?.trim().to_string())
+}
+
+/// `server` with the values it needs filled in, asking for them as needed
+///
+/// Environment inputs already set in the shell are taken from there; other
+/// missing required values fail non-interactive runs.
+pub fn configure(server: &McpServer) -> Result<McpServer> {
+    Ok(server.with_values(&values(server)?))
+}
+
+/// [`configure`], with secret environment values kept in the keychain
+fn configure_with_keychain(server: &McpServer) -> Result<McpServer> {
+    let mut values = values(server)?;
+    let stored = secrets::store(server, &mut values)?;
+    let configured = server.with_values(&values);
+    if stored.is_empty() {
+        return Ok(configured);
+    }
+    println!(
+        "  {} {} stored in the keychain",
+        "✓".success(),
+        stored.keys().join(", ")
+    );
+    Ok(secrets::wrap(&configured, &stored))
+}
+
+/// Values for `server`'s inputs, by key
+fn values(server: &McpServer) -> Result<BTreeMap<String, String>> {
+    let mut values = BTreeMap::new();
+    for input in &server.inputs {
+        let from_env = (input.placement == Placement::Env)
+            .then(|| env::var(&input.key).ok())
+            .flatten()
+            .filter(|v| !v.is_empty());
+        let value = match from_env {
+            Some(value) => {
+                input
+                    .validate(&value)
+                    .map_err(|e| exit::usage(format!("${}: {}", input.key, e)))?;
+                println!("  {} {} from the environment", "✓".success(), input.key);
+                value
+            }
+            None if prompt::is_non_interactive() => {
+                if input.required {
+                    let hint = match input.placement {
+                        Placement::Env => format!("export {} to enable {}", input.key, server.id),
+                        _ => format!("enable {} interactively", server.id),
+                    };
+                    prompt::require_interactive(&hint)?;
+                }
+                continue;
+            }
+            None => ask(server, input)?,
+        };
+        if !value.is_empty() {
+            if input.secret {
+                logging::redact(&value);
+            }
+            values.insert(input.key.clone(), value);
+        }
+    }
+    Ok(values)
+}
+
+/// [`configure`] each distinct server once, by id; servers that couldn't be
+/// configured are reported and left out
+pub fn configure_all<'a>(
+    servers: impl IntoIterator<Item = &'a McpServer>,
+) -> BTreeMap<String, McpServer> {
+    let mut configured = BTreeMap::new();
+    let mut failed = Vec::new();
+    for server in servers {
+        if configured.contains_key(&server.id) || failed.contains(&server.id) {
+            continue;
+        }
+        match configure(server) {
+            Ok(ready) => {
+                configured.insert(server.id.clone(), ready);
+            }
+            Err(e) => {
+                println!("{} Skipping {}: {:#}", "!".warning(), server.id, e);
+                failed.push(server.id.clone());
+            }
+        }
+    }
+    configured
+}
+
+/// What `tools` covers, for progress messages
+fn tools_label(tools: &[String]) -> String {
+    if tools.is_empty() {
+        "across installed tools".to_string()
+    } else {
+        format!("for {}", tools.join(", "))
+    }
+}
+
+pub fn handle_enable(server_name: &str, tools: &[String], keychain: bool) -> Result<Outcome> {
     let servers_to_enable = if server_name == "all" {
         servers::catalog()
     } else {
         vec![
             servers::find(server_name)
-                .with_context(|| format!("Unknown server: {}", server_name))?,
+                .ok_or_else(|| exit::usage(format!("Unknown server: {}", server_name)))?,
         ]
     };
-    let targets = targets::catalog();
+    let targets = targets::select(tools)?;
 
     let label = if server_name == "all" {
         "all servers".to_string()
@@ -130,20 +271,47 @@ pub fn handle_enable(server_name: &str) -> Result<()> {
         server_name.to_string()
     };
 
+    if keychain && keys::keychain::backend().is_none() {
+        return Err(exit::usage(
+            "No keychain available (needs `security` on macOS or `secret-tool` on Linux)",
+        ));
+    }
+
+    let mut configured = Vec::new();
+    for server in &servers_to_enable {
+        if !server.inputs.is_empty() {
+            println!("{}", format!("{} needs configuration", server.name).bold());
+        }
+        let ready = if keychain {
+            configure_with_keychain(server)
+        } else {
+            configure(server)
+        };
+        match ready {
+            Ok(server) => configured.push(server),
+            Err(e) if server_name == "all" => {
+                println!("{} Skipping {}: {:#}", "!".warning(), server.id, e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let servers_to_enable = configured;
+
     println!(
         "{}",
-        format!("Enabling {} across installed tools...", label).bold()
+        format!("Enabling {} {}...", label, tools_label(tools)).bold()
     );
     println!();
 
     let mut success_count = 0;
     let mut skip_count = 0;
+    let mut fail_count = 0;
 
     for target in &targets {
         print!("  {:<16}", target.name);
 
         if !target.is_installed() {
-            println!("{}", "[SKIP] Not installed".dimmed());
+            println!("{}", "[SKIP] Not installed".muted());
             skip_count += 1;
             continue;
         }
@@ -154,15 +322,17 @@ pub fn handle_enable(server_name: &str) -> Result<()> {
                 Ok(_) => {}
                 Err(e) => {
                     if target_ok {
-                        println!("{} {}", "[FAIL]".red(), e);
+                        println!("{} {}", "[FAIL]".error(), e);
                         target_ok = false;
                     }
                 }
             }
         }
         if target_ok {
-            println!("{}", "[OK]".green());
+            println!("{}", "[OK]".success());
             success_count += 1;
+        } else {
+            fail_count += 1;
         }
     }
 
@@ -173,27 +343,30 @@ pub fn handle_enable(server_name: &str) -> Result<()> {
             "Done! Enabled {} in {} tool(s), skipped {}.",
             label, success_count, skip_count
         )
-        .green()
+        .success()
     );
     println!();
     println!(
         "{}",
-        "Note: You may need to restart your CLI tools for changes to take effect.".dimmed()
+        "Note: You may need to restart your CLI tools for changes to take effect.".muted()
     );
 
-    Ok(())
+    if success_count == 0 && fail_count == 0 {
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(fail_count))
 }
 
-pub fn handle_disable(server_name: &str) -> Result<()> {
+pub fn handle_disable(server_name: &str, tools: &[String]) -> Result<Outcome> {
     let servers_to_disable = if server_name == "all" {
         servers::catalog()
     } else {
         vec![
             servers::find(server_name)
-                .with_context(|| format!("Unknown server: {}", server_name))?,
+                .ok_or_else(|| exit::usage(format!("Unknown server: {}", server_name)))?,
         ]
     };
-    let targets = targets::catalog();
+    let targets = targets::select(tools)?;
 
     let label = if server_name == "all" {
         "all servers".to_string()
@@ -203,37 +376,51 @@ pub fn handle_disable(server_name: &str) -> Result<()> {
 
     println!(
         "{}",
-        format!("Disabling {} across installed tools...", label).bold()
+        format!("Disabling {} {}...", label, tools_label(tools)).bold()
     );
     println!();
 
     let mut success_count = 0;
     let mut skip_count = 0;
+    let mut fail_count = 0;
 
     for target in &targets {
         print!("  {:<16}", target.name);
 
         if !target.is_installed() {
-            println!("{}", "[SKIP] Not installed".dimmed());
+            println!("{}", "[SKIP] Not installed".muted());
             skip_count += 1;
             continue;
         }
 
+        // Entries the user wrote themselves are left in place
+        let (ours, theirs): (Vec<_>, Vec<_>) = servers_to_disable
+            .iter()
+            .partition(|server| target.owns_server(server).unwrap_or(false));
         let mut target_ok = true;
-        for server in &servers_to_disable {
+        for server in ours {
             match target.disable_server(server) {
                 Ok(_) => {}
                 Err(e) => {
                     if target_ok {
-                        println!("{} {}", "[FAIL]".red(), e);
+                        println!("{} {}", "[FAIL]".error(), e);
                         target_ok = false;
                     }
                 }
             }
         }
-        if target_ok {
-            println!("{}", "[OK]".green());
+        let kept: Vec<&str> = theirs.iter().map(|s| s.id.as_str()).collect();
+        if !target_ok {
+            fail_count += 1;
+        } else if kept.is_empty() {
+            println!("{}", "[OK]".success());
             success_count += 1;
+        } else {
+            println!(
+                "{}",
+                format!("[SKIP] Kept user-added {}", kept.join(", ")).muted()
+            );
+            skip_count += 1;
         }
     }
 
@@ -244,41 +431,155 @@ pub fn handle_disable(server_name: &str) -> Result<()> {
             "Done! Disabled {} in {} tool(s), skipped {}.",
             label, success_count, skip_count
         )
-        .green()
+        .success()
     );
     println!();
     println!(
         "{}",
-        "Note: You may need to restart your CLI tools for changes to take effect.".dimmed()
+        "Note: You may need to restart your CLI tools for changes to take effect.".muted()
     );
 
-    Ok(())
+    if success_count == 0 && fail_count == 0 {
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(fail_count))
 }
 
-pub fn handle_doctor() -> Result<()> {
+/// Servers enabled in some configured tools but not in others
+///
+/// `enabled` holds each tool's enabled server ids; `servers` gives the order.
+fn drift(servers: &[&str], enabled: &[(&str, Vec<&str>)]) -> Vec<String> {
+    servers
+        .iter()
+        .filter_map(|id| {
+            let (with, without): (Vec<_>, Vec<_>) =
+                enabled.iter().partition(|(_, ids)| ids.contains(id));
+            if with.is_empty() || without.is_empty() {
+                return None;
+            }
+            let names = |tools: Vec<&(&str, Vec<&str>)>| {
+                tools
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            Some(format!(
+                "{} is enabled in {} but not in {}",
+                id,
+                names(with),
+                names(without)
+            ))
+        })
+        .collect()
+}
+
+/// An installed tool's MCP config as `mcp doctor` and `doctor` see it
+pub enum ConfigHealth {
+    /// Not created yet
+    Missing,
+    /// Can't be read or parsed
+    Broken(anyhow::Error),
+    /// Readable; the ids of the catalog servers it enables
+    Enabled(Vec<String>),
+}
+
+/// Check `target`'s MCP config against the catalog `servers`
+pub fn inspect(target: &McpTarget, servers: &[McpServer]) -> ConfigHealth {
+    if !target.config_path().exists() {
+        return ConfigHealth::Missing;
+    }
+    let mut enabled = Vec::new();
+    for server in servers {
+        match target.is_server_enabled(server) {
+            Ok(true) => enabled.push(server.id.clone()),
+            Ok(false) => {}
+            Err(e) => return ConfigHealth::Broken(e),
+        }
+    }
+    ConfigHealth::Enabled(enabled)
+}
+
+/// Servers enabled in some of the `checked` tools' configs but not in others
+pub fn config_drift(servers: &[McpServer], checked: &[(&str, ConfigHealth)]) -> Vec<String> {
+    let ids: Vec<&str> = servers.iter().map(|server| server.id.as_str()).collect();
+    drift(&ids, &readable(checked))
+}
+
+fn readable<'a>(checked: &'a [(&'a str, ConfigHealth)]) -> Vec<(&'a str, Vec<&'a str>)> {
+    checked
+        .iter()
+        .filter_map(|(name, health)| match health {
+            ConfigHealth::Enabled(ids) => Some((*name, ids.iter().map(String::as_str).collect())),
+            _ => None,
+        })
+        .collect()
+}
+
+pub async fn handle_doctor() -> Result<()> {
     let targets = targets::catalog();
+    let servers = servers::catalog();
+    let mut checked = Vec::new();
 
     for target in &targets {
         let installed = target.is_installed();
         let status = if installed {
-            "installed".green()
+            "installed".success()
         } else {
-            "not installed".yellow()
+            "not installed".warning()
         };
 
         println!("{:<16} [{}]", target.name.bold(), status);
-        println!("  {}", target.config_path().display().to_string().dimmed());
+        println!("  {}", target.config_path().display().to_string().muted());
 
         if installed {
-            let exists = target.config_path().exists();
-            if exists {
-                println!("  {}", "config exists".dimmed());
-            } else {
-                println!("  {}", "config not created yet".dimmed());
+            let health = inspect(target, &servers);
+            match &health {
+                ConfigHealth::Missing => println!("  {}", "config not created yet".muted()),
+                ConfigHealth::Broken(e) => println!("  {} {:#}", "✗".error(), e),
+                ConfigHealth::Enabled(_) => println!("  {}", "config exists".muted()),
             }
+            checked.push((target.name, health));
         }
         println!();
     }
 
+    let drifted = config_drift(&servers, &checked);
+    if readable(&checked).len() > 1 {
+        println!("{}", "Drift:".bold());
+        if drifted.is_empty() {
+            println!("  {} Enabled servers match across tools", "✓".success());
+        }
+        for line in &drifted {
+            println!("  {} {}", "!".warning(), line);
+        }
+        if !drifted.is_empty() {
+            println!(
+                "    {} {}",
+                "→".info(),
+                "Run `ai-cli mcp enable <server>` to enable it in every tool".muted()
+            );
+        }
+    }
+    notify::send(Event::ConfigDrift, "mcp doctor", &drifted).await;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_servers_missing_from_some_tools() {
+        let enabled = vec![
+            ("Claude Code", vec!["linear", "github"]),
+            ("Codex CLI", vec!["github"]),
+            ("Gemini CLI", vec!["github"]),
+        ];
+        assert_eq!(
+            drift(&["linear", "github", "sentry"], &enabled),
+            vec!["linear is enabled in Claude Code but not in Codex CLI, Gemini CLI"]
+        );
+    }
+}
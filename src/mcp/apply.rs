@@ -0,0 +1,458 @@
+//! `mcp apply`: the MCP servers of every tool declared in one manifest.
+//!
+//! `mcp.yaml` lists servers with their configuration and, per tool, which of
+//! them it gets. Applying it plans the adds, changes and removals for each
+//! tool's config file, shows the plan, and makes them; applying the same
+//! manifest again changes nothing. Tools the manifest doesn't name are left
+//! alone, and only entries ai-cli wrote are ever changed or removed.
+//!
+//! ```yaml
+//! servers:
+//!   github:
+//!     config:
+//!       GITHUB_PERSONAL_ACCESS_TOKEN: ${GITHUB_TOKEN}
+//!   time:
+//!     command: uvx
+//!     args: [mcp-server-time]
+//!   sentry:
+//!     url: https://mcp.sentry.dev/mcp
+//! tools:
+//!   claude: [github, time, playwright]
+//!   codex: [github]
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+
+use super::ownership;
+use super::servers::{self, McpServer, Transport};
+use super::targets::{self, McpTarget};
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// Manifest used when no path is given
+pub fn default_path() -> PathBuf {
+    paths::config_home().join("ai-cli").join("mcp.yaml")
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    #[serde(default)]
+    servers: BTreeMap<String, ServerSpec>,
+    /// Server ids per tool binary name
+    #[serde(default)]
+    tools: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServerSpec {
+    /// Defines a server that isn't in the catalog
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    /// Defines a remote server that isn't in the catalog
+    url: Option<String>,
+    /// `http` (default) or `sse`, for `url`
+    transport: Option<Transport>,
+    /// Values for the server's inputs, by key
+    #[serde(default)]
+    config: BTreeMap<String, String>,
+    /// Extra environment of the server process
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+}
+
+/// `value` with `${VAR}` replaced from the environment
+fn expand(value: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..]
+            .find('}')
+            .with_context(|| format!("Unclosed ${{ in '{}'", value))?;
+        let name = &rest[start + 2..start + end];
+        let var = env::var(name)
+            .map_err(|_| exit::usage(format!("${{{}}} is not set in the environment", name)))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// The server `id` as the manifest configures it
+fn resolve(id: &str, spec: Option<&ServerSpec>, catalog: &[McpServer]) -> Result<McpServer> {
+    let default = ServerSpec::default();
+    let spec = spec.unwrap_or(&default);
+    let base = match (&spec.command, &spec.url) {
+        (Some(command), _) => McpServer {
+            command: command.clone(),
+            args: spec.args.clone(),
+            ..McpServer::new(id, id, &[], "")
+        },
+        (None, Some(url)) => {
+            McpServer::remote(id, id, spec.transport.unwrap_or(Transport::Http), url, "")
+        }
+        (None, None) => catalog
+            .iter()
+            .find(|s| s.id == id)
+            .cloned()
+            .ok_or_else(|| {
+                exit::usage(format!(
+                    "Unknown server '{}'; give it a command or url under servers.{}",
+                    id, id
+                ))
+            })?,
+    };
+
+    let mut values = BTreeMap::new();
+    for (key, value) in &spec.config {
+        let input = base
+            .inputs
+            .iter()
+            .find(|i| &i.key == key)
+            .ok_or_else(|| exit::usage(format!("{} has no setting '{}'", id, key)))?;
+        let value = expand(value)?;
+        input
+            .validate(&value)
+            .map_err(|e| exit::usage(format!("{}.{}: {}", id, key, e)))?;
+        if input.secret {
+            logging::redact(&value);
+        }
+        values.insert(key.clone(), value);
+    }
+    if let Some(missing) = base
+        .inputs
+        .iter()
+        .find(|i| i.required && !values.contains_key(&i.key))
+    {
+        return Err(exit::usage(format!(
+            "{} needs servers.{}.config.{}",
+            id, id, missing.key
+        )));
+    }
+
+    let mut server = base.with_values(&values);
+    for (key, value) in &spec.env {
+        server.env.insert(key.clone(), expand(value)?);
+    }
+    Ok(server)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Add,
+    Change,
+    Remove,
+    /// An entry with the server's name that ai-cli didn't write
+    Conflict,
+}
+
+impl Action {
+    fn symbol(self) -> String {
+        match self {
+            Action::Add => "+".success().to_string(),
+            Action::Change => "~".warning().to_string(),
+            Action::Remove => "-".error().to_string(),
+            Action::Conflict => "!".warning().to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Step {
+    action: Action,
+    /// Entry name in the tool's config
+    name: String,
+    server: Option<McpServer>,
+}
+
+/// What it takes to bring `target` to exactly the `desired` servers
+fn plan(target: &McpTarget, desired: &[McpServer]) -> Result<Vec<Step>> {
+    let mut steps = Vec::new();
+    for server in desired {
+        let name = target.entry_name(server).to_string();
+        let action = if !target.is_server_enabled(server)? {
+            Action::Add
+        } else if target.is_up_to_date(server)? {
+            continue;
+        } else if target.owns_server(server)? {
+            Action::Change
+        } else {
+            Action::Conflict
+        };
+        steps.push(Step {
+            action,
+            name,
+            server: Some(server.clone()),
+        });
+    }
+
+    let wanted: Vec<&str> = desired.iter().map(|s| target.entry_name(s)).collect();
+    for (name, _) in target.configured_servers()? {
+        if !wanted.contains(&name.as_str()) && ownership::is_recorded(target.config_path(), &name) {
+            steps.push(Step {
+                action: Action::Remove,
+                name,
+                server: None,
+            });
+        }
+    }
+    Ok(steps)
+}
+
+fn execute(target: &McpTarget, step: &Step) -> Result<String> {
+    match (step.action, &step.server) {
+        (Action::Add, Some(server)) => target.enable_server(server),
+        (Action::Change, Some(server)) => {
+            // Rewritten from scratch so settings dropped from the manifest go too
+            target.remove_recorded(&step.name)?;
+            target.enable_server(server)
+        }
+        (Action::Remove, _) => target.remove_recorded(&step.name),
+        _ => Ok(String::new()),
+    }
+}
+
+fn load(path: &Path) -> Result<Manifest> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        exit::usage(format!(
+            "Can't read {}: {} (see `ai-cli mcp apply --help`)",
+            path.display(),
+            e
+        ))
+    })?;
+    serde_norway::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Steps per tool the manifest names, `None` for tools that aren't installed
+type Plans<'a> = Vec<(&'a McpTarget, Option<Vec<Step>>)>;
+
+fn plan_manifest<'a>(manifest: &Manifest, targets: &'a [McpTarget]) -> Result<Plans<'a>> {
+    let catalog = servers::catalog();
+    let mut resolved = BTreeMap::new();
+    for ids in manifest.tools.values() {
+        for id in ids {
+            if !resolved.contains_key(id) {
+                let server = resolve(id, manifest.servers.get(id), &catalog)?;
+                resolved.insert(id.clone(), server);
+            }
+        }
+    }
+
+    let mut plans = Vec::new();
+    for (tool, ids) in &manifest.tools {
+        // Amp can have several config locations in use
+        let matching: Vec<&McpTarget> = targets.iter().filter(|t| t.binary_name == tool).collect();
+        if matching.is_empty() {
+            let mut known: Vec<&str> = targets.iter().map(|t| t.binary_name).collect();
+            known.dedup();
+            return Err(exit::usage(format!(
+                "Unknown tool '{}'. Supported tools: {}",
+                tool,
+                known.join(", ")
+            )));
+        }
+        for target in matching {
+            if !target.is_installed() {
+                plans.push((target, None));
+                continue;
+            }
+            let desired: Vec<McpServer> = ids.iter().map(|id| resolved[id].clone()).collect();
+            plans.push((target, Some(plan(target, &desired)?)));
+        }
+    }
+    Ok(plans)
+}
+
+/// Adds, changes and removals applying the default manifest would make, or
+/// `None` without a manifest
+pub fn pending_changes() -> Result<Option<usize>> {
+    let path = default_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let targets = targets::catalog();
+    let plans = plan_manifest(&load(&path)?, &targets)?;
+    Ok(Some(
+        plans
+            .iter()
+            .flat_map(|(_, steps)| steps.iter().flatten())
+            .filter(|s| s.action != Action::Conflict)
+            .count(),
+    ))
+}
+
+/// Handle `mcp apply [FILE] [--dry-run]` command
+pub fn handle_apply(file: Option<&Path>, dry_run: bool) -> Result<Outcome> {
+    let path = file.map(Path::to_path_buf).unwrap_or_else(default_path);
+    let manifest = load(&path)?;
+    let targets = targets::catalog();
+
+    println!("{}", format!("Planning {}...", path.display()).bold());
+    println!();
+    let mut plans = Vec::new();
+    for (target, steps) in plan_manifest(&manifest, &targets)? {
+        println!(
+            "  {} {}",
+            target.name.bold(),
+            target.config_path().display().to_string().muted()
+        );
+        let Some(steps) = steps else {
+            println!("    {}", "[SKIP] Not installed".muted());
+            continue;
+        };
+        if steps.is_empty() {
+            println!("    {}", "no changes".muted());
+        }
+        for step in &steps {
+            let note = if step.action == Action::Conflict {
+                " (entry wasn't added by ai-cli; left as is)"
+                    .muted()
+                    .to_string()
+            } else {
+                String::new()
+            };
+            println!("    {} {}{}", step.action.symbol(), step.name, note);
+        }
+        plans.push((target, steps));
+    }
+    println!();
+
+    let count = |action| {
+        plans
+            .iter()
+            .flat_map(|(_, steps)| steps)
+            .filter(|s| s.action == action)
+            .count()
+    };
+    let (add, change, remove) = (
+        count(Action::Add),
+        count(Action::Change),
+        count(Action::Remove),
+    );
+    let conflicts = count(Action::Conflict);
+    if add + change + remove == 0 {
+        println!("{} Everything matches {}", "✓".success(), path.display());
+        return Ok(if conflicts > 0 {
+            Outcome::PartialFailure
+        } else {
+            Outcome::NothingToDo
+        });
+    }
+    println!(
+        "Plan: {} to add, {} to change, {} to remove.",
+        add, change, remove
+    );
+    if dry_run {
+        println!("{}", "Dry run: nothing changed".muted());
+        return Ok(Outcome::Success);
+    }
+    if !prompt::confirm("Apply these changes?")? {
+        println!("Cancelled");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = conflicts;
+    for (target, steps) in &plans {
+        for step in steps {
+            if let Err(e) = execute(target, step) {
+                println!(
+                    "  {} {} {}: {:#}",
+                    "[FAIL]".error(),
+                    target.name,
+                    step.name,
+                    e
+                );
+                failed += 1;
+            }
+        }
+    }
+    let applied = add + change + remove + conflicts - failed;
+    println!("{} Applied {} change(s)", "✓".success(), applied);
+    println!(
+        "{}",
+        "Note: You may need to restart your CLI tools for changes to take effect.".muted()
+    );
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::targets::ConfigMethod;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolves_manifest_servers() {
+        let manifest: Manifest = serde_norway::from_str(
+            "servers:\n  time:\n    command: uvx\n    args: [mcp-server-time]\n    env:\n      TZ: UTC\n\
+             \x20 wiki:\n    url: https://wiki.example/sse\n    transport: sse\n\
+             tools:\n  claude: [time, github]\n",
+        )
+        .unwrap();
+        let catalog = servers::catalog();
+
+        let time = resolve("time", manifest.servers.get("time"), &catalog).unwrap();
+        assert_eq!((time.command.as_str(), time.args.len()), ("uvx", 1));
+        assert_eq!(time.env["TZ"], "UTC");
+        let wiki = resolve("wiki", manifest.servers.get("wiki"), &catalog).unwrap();
+        assert_eq!(wiki.transport, Transport::Sse);
+        let missing = resolve("github", None, &catalog).unwrap_err();
+        assert!(missing.to_string().contains("GITHUB_PERSONAL_ACCESS_TOKEN"));
+        assert!(resolve("nope", None, &catalog).is_err());
+        assert!(expand("${AI_CLI_TEST_SURELY_UNSET}").is_err());
+    }
+
+    #[test]
+    fn plans_only_the_difference() {
+        let dir = TempDir::new().unwrap();
+        let target = McpTarget {
+            name: "Test",
+            binary_name: "test",
+            config_method: ConfigMethod::JsonConfig {
+                path: dir.path().join("settings.json"),
+                servers_key: "mcpServers",
+                server_name_override: None,
+                type_value: None,
+                include_tools_field: false,
+            },
+        };
+        let catalog = servers::catalog();
+        let linear = resolve("linear", None, &catalog).unwrap();
+        let playwright = resolve("playwright", None, &catalog).unwrap();
+
+        let steps = plan(&target, std::slice::from_ref(&linear)).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].action, Action::Add);
+        execute(&target, &steps[0]).unwrap();
+        assert!(
+            plan(&target, std::slice::from_ref(&linear))
+                .unwrap()
+                .is_empty()
+        );
+
+        let steps = plan(&target, std::slice::from_ref(&playwright)).unwrap();
+        let actions: Vec<_> = steps.iter().map(|s| (s.action, s.name.as_str())).collect();
+        assert_eq!(
+            actions,
+            [(Action::Add, "playwright"), (Action::Remove, "linear")]
+        );
+        for step in &steps {
+            execute(&target, step).unwrap();
+        }
+        let _ = target.remove_recorded("playwright");
+    }
+}
@@ -0,0 +1,559 @@
+//! One MCP server that proxies to the catalog servers enabled at the gateway.
+//!
+//! Agents get a single `ai-cli` entry; backends are toggled in the `[gateway]`
+//! table of config.toml and picked up the next time an agent starts the gateway.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::thread;
+
+use anyhow::{Context, Result, anyhow, bail};
+use colored::*;
+use serde_json::{Value, json};
+
+use super::servers::{self, McpServer};
+use super::targets;
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// Protocol version the gateway speaks to its backends
+const PROTOCOL_VERSION: &str = "2025-06-18";
+/// Separates the backend id from the tool name in gateway tool names
+const SEPARATOR: &str = "__";
+
+/// The entry agents are pointed at instead of the individual servers
+pub fn server() -> McpServer {
+    McpServer {
+        command: "ai-cli".to_string(),
+        ..McpServer::new(
+            "ai-cli",
+            "ai-cli gateway",
+            &["mcp", "gateway", "serve"],
+            "Proxies to the servers enabled at the gateway",
+        )
+    }
+}
+
+/// Catalog servers enabled at the gateway; remote ones run through mcp-remote
+fn backend_servers() -> Result<Vec<McpServer>> {
+    let ids = config::load()?.gateway.servers;
+    ids.iter()
+        .map(|id| {
+            servers::find(id)
+                .map(|server| server.bridged())
+                .ok_or_else(|| anyhow!("Unknown gateway server '{}' in config.toml", id))
+        })
+        .collect()
+}
+
+/// A running backend server speaking JSON-RPC over stdio
+struct Backend {
+    id: String,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+    tools: Vec<Value>,
+}
+
+impl Backend {
+    fn start(server: &McpServer) -> Result<Self> {
+        let mut child = Command::new(&server.command)
+            .args(&server.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to start {}", server.command))?;
+        let stdin = child.stdin.take().context("Backend has no stdin")?;
+        let stdout = BufReader::new(child.stdout.take().context("Backend has no stdout")?);
+        let mut backend = Backend {
+            id: server.id.clone(),
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+            tools: Vec::new(),
+        };
+
+        backend.request(
+            "initialize",
+            json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": { "name": "ai-cli-gateway", "version": env!("CARGO_PKG_VERSION") },
+            }),
+        )?;
+        backend.send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))?;
+
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let page = backend.request("tools/list", params)?;
+            if let Some(tools) = page["tools"].as_array() {
+                backend.tools.extend(tools.iter().cloned());
+            }
+            match page["nextCursor"].as_str() {
+                Some(next) => cursor = Some(next.to_string()),
+                None => break,
+            }
+        }
+        Ok(backend)
+    }
+
+    fn send(&mut self, message: &Value) -> Result<()> {
+        writeln!(self.stdin, "{}", message)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Send a request and wait for its response, skipping notifications
+    fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.stdout.read_line(&mut line)? == 0 {
+                bail!("{} exited", self.id);
+            }
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if message.get("method").is_some() {
+                // Requests from the backend (sampling, roots, ...) aren't supported
+                if let Some(request_id) = message.get("id") {
+                    self.send(&error_response(
+                        request_id.clone(),
+                        -32601,
+                        "Not supported by the ai-cli gateway",
+                    ))?;
+                }
+                continue;
+            }
+            if message["id"] != json!(id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                bail!(
+                    "{}",
+                    error["message"]
+                        .as_str()
+                        .unwrap_or("backend returned an error")
+                );
+            }
+            return Ok(message["result"].clone());
+        }
+    }
+}
+
+impl Drop for Backend {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Prefix each tool name with the backend id
+fn prefixed_tools(id: &str, tools: &[Value]) -> Vec<Value> {
+    tools
+        .iter()
+        .filter_map(|tool| {
+            let name = tool["name"].as_str()?;
+            let mut tool = tool.clone();
+            tool["name"] = json!(format!("{}{}{}", id, SEPARATOR, name));
+            Some(tool)
+        })
+        .collect()
+}
+
+/// Split a gateway tool name into backend id and tool name
+fn split_tool_name(name: &str) -> Option<(&str, &str)> {
+    name.split_once(SEPARATOR)
+}
+
+/// The gateway's state while serving
+struct Gateway {
+    backends: Vec<Backend>,
+}
+
+impl Gateway {
+    /// Start every backend in parallel; ones that fail are reported and skipped
+    fn start(servers: &[McpServer]) -> Self {
+        let started: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = servers
+                .iter()
+                .map(|server| scope.spawn(move || (server.id.as_str(), Backend::start(server))))
+                .collect();
+            handles.into_iter().filter_map(|h| h.join().ok()).collect()
+        });
+
+        let mut backends = Vec::new();
+        for (id, result) in started {
+            match result {
+                Ok(backend) => backends.push(backend),
+                Err(e) => {
+                    eprintln!("ai-cli gateway: {} unavailable: {:#}", id, e);
+                    logging::line(&format!("gateway backend {} failed: {:#}", id, e));
+                }
+            }
+        }
+        Gateway { backends }
+    }
+
+    /// Answer one client message; notifications get no response
+    fn handle(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id")?.clone();
+        let method = message["method"].as_str().unwrap_or_default();
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": message["params"]["protocolVersion"]
+                    .as_str()
+                    .unwrap_or(PROTOCOL_VERSION),
+                "capabilities": { "tools": { "listChanged": false } },
+                "serverInfo": { "name": "ai-cli-gateway", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => {
+                let tools: Vec<Value> = self
+                    .backends
+                    .iter()
+                    .flat_map(|b| prefixed_tools(&b.id, &b.tools))
+                    .collect();
+                Ok(json!({ "tools": tools }))
+            }
+            "tools/call" => self.call_tool(&message["params"]),
+            _ => return Some(error_response(id, -32601, "Method not found")),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, -32603, &format!("{:#}", e)),
+        })
+    }
+
+    fn call_tool(&mut self, params: &Value) -> Result<Value> {
+        let name = params["name"].as_str().unwrap_or_default();
+        let (backend_id, tool) =
+            split_tool_name(name).ok_or_else(|| anyhow!("Unknown tool '{}'", name))?;
+        let backend = self
+            .backends
+            .iter_mut()
+            .find(|b| b.id == backend_id)
+            .ok_or_else(|| anyhow!("Server '{}' is not running", backend_id))?;
+        let mut forwarded = params.clone();
+        forwarded["name"] = json!(tool);
+        backend.request("tools/call", forwarded)
+    }
+}
+
+/// Handle `mcp gateway serve`: speak MCP on stdin/stdout until the client disconnects
+pub fn handle_serve() -> Result<()> {
+    let mut gateway = Gateway::start(&backend_servers()?);
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line.context("Failed to read from stdin")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => gateway.handle(&message),
+            Err(_) => Some(error_response(Value::Null, -32700, "Parse error")),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle `mcp gateway status`
+pub fn handle_status() -> Result<()> {
+    let enabled = config::load()?.gateway.servers;
+    println!("{}", "Gateway servers:".bold());
+    for server in servers::catalog() {
+        let on = enabled.contains(&server.id);
+        println!(
+            "  {} {:<12} {}",
+            if on { "✓".success() } else { "-".muted() },
+            server.id,
+            if on { "enabled" } else { "disabled" }.muted()
+        );
+    }
+    println!();
+
+    let gateway = server();
+    println!("{}", "Tools using the gateway:".bold());
+    for target in targets::catalog() {
+        if !target.is_installed() {
+            continue;
+        }
+        let uses = target.is_server_enabled(&gateway).unwrap_or(false);
+        println!(
+            "  {} {}",
+            if uses { "✓".success() } else { "-".muted() },
+            target.name
+        );
+    }
+    println!();
+    println!(
+        "{} `ai-cli mcp gateway install` points installed tools at the gateway",
+        "→".info()
+    );
+    Ok(())
+}
+
+fn catalog_server(id: &str) -> Result<McpServer> {
+    servers::find(id).ok_or_else(|| exit::usage(format!("Unknown server: {}", id)))
+}
+
+/// Handle `mcp gateway enable|disable <server>`
+pub fn handle_toggle(id: &str, enable: bool) -> Result<Outcome> {
+    let server = catalog_server(id)?;
+    if enable && !server.inputs.is_empty() {
+        return Err(exit::usage(format!(
+            "{} needs its own configuration and can't be proxied; use `ai-cli mcp enable {}`",
+            server.id, server.id
+        )));
+    }
+    let mut enabled = config::load()?.gateway.servers;
+    let present = enabled.contains(&server.id);
+    if present == enable {
+        println!(
+            "{} is already {} at the gateway",
+            server.id,
+            if enable { "enabled" } else { "disabled" }
+        );
+        return Ok(Outcome::NothingToDo);
+    }
+    if enable {
+        enabled.push(server.id.to_string());
+    } else {
+        enabled.retain(|s| *s != server.id);
+    }
+    config::set_list("gateway", "servers", &enabled)?;
+    println!(
+        "{} {} {} at the gateway",
+        "✓".success(),
+        if enable { "Enabled" } else { "Disabled" },
+        server.id
+    );
+    println!(
+        "  {}",
+        "Agents pick this up the next time they start the gateway".muted()
+    );
+    Ok(Outcome::Success)
+}
+
+/// Handle `mcp gateway install`: replace per-server entries with the gateway
+pub fn handle_install() -> Result<Outcome> {
+    let gateway = server();
+    let installed: Vec<_> = targets::catalog()
+        .into_iter()
+        .filter(|t| t.is_installed())
+        .collect();
+    if installed.is_empty() {
+        println!("No tools with MCP support are installed");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    // Servers configured directly in any tool move behind the gateway, except
+    // those holding their own configuration values
+    let mut enabled = config::load()?.gateway.servers;
+    let mut moved = Vec::new();
+    for server in servers::catalog()
+        .into_iter()
+        .filter(|s| s.inputs.is_empty())
+    {
+        let direct = installed
+            .iter()
+            .any(|t| t.is_server_enabled(&server).unwrap_or(false));
+        if direct && !enabled.contains(&server.id) {
+            enabled.push(server.id.to_string());
+            moved.push(server.id);
+        }
+    }
+
+    println!(
+        "Point {} at the gateway, proxying {}",
+        installed
+            .iter()
+            .map(|t| t.name)
+            .collect::<Vec<_>>()
+            .join(", "),
+        if enabled.is_empty() {
+            "no servers yet".to_string()
+        } else {
+            enabled.join(", ")
+        }
+    );
+    if !prompt::confirm("Continue?")? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+    if !moved.is_empty() {
+        config::set_list("gateway", "servers", &enabled)?;
+    }
+
+    let backends: Vec<_> = enabled.iter().filter_map(|id| servers::find(id)).collect();
+    let mut failed = 0;
+    for target in &installed {
+        let result = target.enable_server(&gateway).and_then(|_| {
+            for backend in &backends {
+                if target.is_server_enabled(backend)? {
+                    target.disable_server(backend)?;
+                }
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => println!("{} {}", "✓".success(), target.name),
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), target.name, e);
+                failed += 1;
+            }
+        }
+    }
+    println!();
+    println!(
+        "{} Toggle backends with `ai-cli mcp gateway enable|disable <server>`",
+        "→".info()
+    );
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `mcp gateway uninstall`: configure the gateway's servers directly again
+pub fn handle_uninstall() -> Result<Outcome> {
+    let gateway = server();
+    let backends = backend_servers()?;
+    let mut changed = 0;
+    let mut failed = 0;
+    for target in targets::catalog().into_iter().filter(|t| t.is_installed()) {
+        if !target.is_server_enabled(&gateway).unwrap_or(false) {
+            continue;
+        }
+        let result = target.disable_server(&gateway).and_then(|_| {
+            for backend in &backends {
+                target.enable_server(backend)?;
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => {
+                println!("{} {}", "✓".success(), target.name);
+                changed += 1;
+            }
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), target.name, e);
+                failed += 1;
+            }
+        }
+    }
+    if changed == 0 && failed == 0 {
+        println!("No tools use the gateway");
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes_and_splits_tool_names() {
+        let tools = vec![
+            json!({ "name": "create_issue", "inputSchema": { "type": "object" } }),
+            json!({ "description": "nameless" }),
+        ];
+        let prefixed = prefixed_tools("linear", &tools);
+        assert_eq!(prefixed.len(), 1);
+        assert_eq!(prefixed[0]["name"], "linear__create_issue");
+        assert_eq!(prefixed[0]["inputSchema"]["type"], "object");
+        assert_eq!(
+            split_tool_name("linear__create__issue"),
+            Some(("linear", "create__issue"))
+        );
+    }
+
+    #[test]
+    fn answers_protocol_requests_without_backends() {
+        let mut gateway = Gateway {
+            backends: Vec::new(),
+        };
+        let init = gateway
+            .handle(&json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2025-03-26" }
+            }))
+            .unwrap();
+        assert_eq!(init["result"]["protocolVersion"], "2025-03-26");
+
+        assert!(
+            gateway
+                .handle(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+                .is_none()
+        );
+        let list = gateway
+            .handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }))
+            .unwrap();
+        assert_eq!(list["result"]["tools"], json!([]));
+
+        let call = gateway
+            .handle(&json!({
+                "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                "params": { "name": "linear__create_issue" }
+            }))
+            .unwrap();
+        assert_eq!(call["error"]["message"], "Server 'linear' is not running");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn proxies_tool_calls_to_a_backend() {
+        // Answers initialize (id 1), tools/list (id 2) and one tools/call (id 3)
+        const SCRIPT: &str = r#"
+read line
+echo '{"jsonrpc":"2.0","id":1,"result":{"capabilities":{}}}'
+read line
+read line
+echo '{"jsonrpc":"2.0","method":"notifications/message","params":{}}'
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo"}]}}'
+read line
+case "$line" in
+  *'"name":"echo"'*) echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"hi"}]}}' ;;
+esac
+"#;
+        let fake = McpServer {
+            command: "sh".to_string(),
+            ..McpServer::new("fake", "Fake", &["-c", SCRIPT], "")
+        };
+        let mut gateway = Gateway::start(&[fake]);
+        let list = gateway
+            .handle(&json!({ "jsonrpc": "2.0", "id": "a", "method": "tools/list" }))
+            .unwrap();
+        assert_eq!(list["result"]["tools"][0]["name"], "fake__echo");
+
+        let call = gateway
+            .handle(&json!({
+                "jsonrpc": "2.0", "id": "b", "method": "tools/call",
+                "params": { "name": "fake__echo", "arguments": {} }
+            }))
+            .unwrap();
+        assert_eq!(call["id"], "b");
+        assert_eq!(call["result"]["content"][0]["text"], "hi");
+    }
+}
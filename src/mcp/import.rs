@@ -0,0 +1,259 @@
+//! `mcp add --from`: custom server definitions imported from a registry.
+//!
+//! Smithery manifests are converted into a `[mcp_servers.<id>]` table in
+//! config.toml, after which the server is enabled like a built-in one.
+//! Servers that only publish a hosted or bundled runtime are started through
+//! the Smithery CLI. Smithery's API takes a key from `SMITHERY_API_KEY`.
+
+use std::collections::BTreeMap;
+use std::env;
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+
+use super::servers::{self, Placement, Transport};
+use crate::config::{self, CustomServer, ServerSetting};
+use crate::exit::{self, Outcome};
+use crate::http;
+use crate::theme::Themed;
+
+const SMITHERY_API_URL: &str = "https://registry.smithery.ai/servers";
+
+/// Id a qualified name is registered under, e.g. `@acme/exa-mcp` → `exa-mcp`
+fn default_id(qualified_name: &str) -> String {
+    let last = qualified_name.rsplit('/').next().unwrap_or(qualified_name);
+    last.trim_start_matches('@')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Settings declared by a connection's JSON schema, required ones first
+fn settings(schema: &Value, placement: Placement) -> Vec<ServerSetting> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let mut settings: Vec<ServerSetting> = schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(key, property)| ServerSetting {
+            key: key.clone(),
+            description: property["description"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            required: required.contains(&key.as_str()),
+            placement,
+        })
+        .collect();
+    settings.sort_by_key(|s| !s.required);
+    settings
+}
+
+/// Convert a Smithery server manifest into a local definition
+fn definition(qualified_name: &str, manifest: &Value) -> CustomServer {
+    let connections = manifest["connections"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let stdio = connections.iter().find(|c| c["type"] == "stdio");
+    let connection = stdio.or(connections.first());
+
+    let (command, args, placement) = match stdio.and_then(|c| c["command"].as_str()) {
+        Some(command) => (
+            command.to_string(),
+            stdio
+                .map(|c| &c["args"])
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            Placement::Env,
+        ),
+        None => (
+            "npx".to_string(),
+            ["-y", "@smithery/cli@latest", "run", qualified_name]
+                .map(str::to_string)
+                .to_vec(),
+            Placement::ConfigJson,
+        ),
+    };
+
+    CustomServer {
+        name: manifest["displayName"]
+            .as_str()
+            .unwrap_or(qualified_name)
+            .to_string(),
+        description: manifest["description"]
+            .as_str()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        transport: Transport::Stdio,
+        url: None,
+        command,
+        args,
+        env: BTreeMap::new(),
+        source: Some(format!("smithery/{}", qualified_name)),
+        settings: connection
+            .map(|c| settings(&c["configSchema"], placement))
+            .unwrap_or_default(),
+    }
+}
+
+async fn fetch_manifest(base_url: &str, qualified_name: &str) -> Result<Value> {
+    let url = format!("{}/{}", base_url, qualified_name);
+    let mut request = http::client().get(&url);
+    if let Some(key) = env::var("SMITHERY_API_KEY").ok().filter(|k| !k.is_empty()) {
+        request = request.bearer_auth(key);
+    }
+    let response = http::send(request).await?;
+    match response.status() {
+        reqwest::StatusCode::NOT_FOUND => {
+            return Err(exit::usage(format!(
+                "No server '{}' on Smithery",
+                qualified_name
+            )));
+        }
+        reqwest::StatusCode::UNAUTHORIZED => {
+            return Err(anyhow!(
+                "Smithery rejected the request; set SMITHERY_API_KEY to an API key from smithery.ai"
+            ));
+        }
+        _ => {}
+    }
+    response
+        .error_for_status()
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse the manifest from {}", url))
+}
+
+/// Handle `mcp add --from <source> [--id <id>]` command
+pub async fn handle_add(from: &str, id: Option<&str>) -> Result<Outcome> {
+    let qualified_name = from
+        .strip_prefix("smithery/")
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| {
+            exit::usage(format!(
+                "Unsupported source '{}'; use smithery/<name>, e.g. smithery/exa",
+                from
+            ))
+        })?;
+    let id = id
+        .map(str::to_string)
+        .unwrap_or_else(|| default_id(qualified_name));
+    if servers::is_builtin(&id) {
+        return Err(exit::usage(format!(
+            "'{}' is a built-in server; pick another id with --id",
+            id
+        )));
+    }
+
+    let manifest = fetch_manifest(SMITHERY_API_URL, qualified_name).await?;
+    let server = definition(qualified_name, &manifest);
+    let existing = config::load()?.mcp_servers.remove(&id);
+    if existing.as_ref() == Some(&server) {
+        println!("{} {} is already up to date", "✓".success(), id);
+        return Ok(Outcome::NothingToDo);
+    }
+    config::set_table("mcp_servers", &id, &server)?;
+
+    let verb = if existing.is_some() {
+        "Updated"
+    } else {
+        "Added"
+    };
+    println!(
+        "{} {} {} ({}) from {}",
+        "✓".success(),
+        verb,
+        id,
+        server.name,
+        from
+    );
+    println!(
+        "  {}",
+        match &server.url {
+            Some(url) => url.muted(),
+            None => format!("{} {}", server.command, server.args.join(" ")).muted(),
+        }
+    );
+    for setting in &server.settings {
+        let label = if setting.required {
+            "needs"
+        } else {
+            "optional"
+        };
+        println!(
+            "  {} {} {}",
+            format!("{}:", label).muted(),
+            setting.key,
+            setting.description.muted()
+        );
+    }
+    println!("{} Enable it with `ai-cli mcp enable {}`", "→".info(), id);
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_smithery_manifests() {
+        let manifest = json!({
+            "qualifiedName": "@acme/exa-search",
+            "displayName": "Exa Search",
+            "description": "Web search ",
+            "connections": [{
+                "type": "http",
+                "configSchema": {
+                    "type": "object",
+                    "required": ["exaApiKey"],
+                    "properties": {
+                        "debug": { "type": "boolean" },
+                        "exaApiKey": { "type": "string", "description": "Exa API key" }
+                    }
+                }
+            }]
+        });
+        let server = definition("@acme/exa-search", &manifest);
+        assert_eq!(default_id("@acme/exa-search"), "exa-search");
+        assert_eq!(server.name, "Exa Search");
+        assert_eq!(server.description, "Web search");
+        assert_eq!(server.command, "npx");
+        assert_eq!(
+            server.args,
+            ["-y", "@smithery/cli@latest", "run", "@acme/exa-search"]
+        );
+        assert_eq!(server.settings[0].key, "exaApiKey");
+        assert!(server.settings[0].required);
+        assert_eq!(server.settings[0].placement, Placement::ConfigJson);
+        assert!(!server.settings[1].required);
+
+        let stdio = json!({
+            "connections": [{ "type": "stdio", "command": "uvx", "args": ["mcp-server-time"] }]
+        });
+        let server = definition("time", &stdio);
+        assert_eq!(
+            (server.command.as_str(), server.args.as_slice()),
+            ("uvx", &["mcp-server-time".to_string()][..])
+        );
+        assert_eq!(server.source.as_deref(), Some("smithery/time"));
+    }
+}
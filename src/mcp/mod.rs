@@ -1,5 +1,13 @@
 pub mod actions;
+pub mod apply;
+pub mod gateway;
+pub mod import;
+pub mod ownership;
+pub mod secrets;
 pub mod servers;
 pub mod targets;
 
-pub use actions::{handle_disable, handle_doctor, handle_enable, handle_list};
+pub use actions::{
+    ConfigHealth, config_drift, configure, configure_all, handle_disable, handle_doctor,
+    handle_enable, handle_list, inspect,
+};
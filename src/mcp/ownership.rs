@@ -0,0 +1,145 @@
+//! Which MCP server entries ai-cli wrote.
+//!
+//! Tools validate their server entries strictly (Codex and VS Code reject
+//! unknown keys), so instead of a marker field inside each entry, the names
+//! ai-cli created are recorded per config file in
+//! `~/.local/state/ai-cli/mcp-entries.json`. Disabling only removes entries
+//! found there or identical to what ai-cli writes.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::journal;
+use crate::logging;
+use crate::paths;
+use crate::theme::Themed;
+
+/// Entry names per config file path
+type Entries = BTreeMap<String, BTreeSet<String>>;
+
+/// Under the config root when one is set, like every other managed path
+fn entries_path() -> PathBuf {
+    paths::state_home().join("ai-cli").join("mcp-entries.json")
+}
+
+fn load() -> Entries {
+    fs::read_to_string(entries_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(path: &Path, entries: &Entries) -> Result<()> {
+    journal::record_file(path);
+    fs::write(path, serde_json::to_string_pretty(entries)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Apply `change` to the recorded entries, holding a lock so that two runs
+/// don't lose each other's changes; entries of config files that are gone are
+/// dropped on the way
+fn update(change: impl FnOnce(&mut Entries) -> bool) -> Result<()> {
+    let path = entries_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let lock_path = path.with_extension("lock");
+    let lock = File::create(&lock_path)
+        .with_context(|| format!("Failed to create {}", lock_path.display()))?;
+    lock.lock()
+        .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+
+    let mut entries = load();
+    let before = entries.len();
+    entries.retain(|config, _| Path::new(config).exists());
+    let pruned = entries.len() != before;
+    if change(&mut entries) || pruned {
+        save(&path, &entries)?;
+    }
+    Ok(())
+}
+
+/// The config change itself already happened, so a failure to record it is
+/// only reported
+fn warn_unrecorded(config: &Path, name: &str, result: Result<()>) {
+    if let Err(e) = result {
+        logging::line(&format!(
+            "mcp: can't record entry {} of {}: {:#}",
+            name,
+            config.display(),
+            e
+        ));
+        eprintln!(
+            "{} Couldn't record that ai-cli manages {} in {}: {:#}",
+            "!".warning(),
+            name,
+            config.display(),
+            e
+        );
+    }
+}
+
+fn key(config: &Path) -> String {
+    config.display().to_string()
+}
+
+/// Whether ai-cli wrote the entry `name` in `config`
+pub fn is_recorded(config: &Path, name: &str) -> bool {
+    load()
+        .get(&key(config))
+        .is_some_and(|names| names.contains(name))
+}
+
+/// Remember that ai-cli wrote the entry `name` in `config`
+pub fn record(config: &Path, name: &str) {
+    let result = update(|entries| {
+        entries
+            .entry(key(config))
+            .or_default()
+            .insert(name.to_string())
+    });
+    warn_unrecorded(config, name, result);
+}
+
+/// Forget the entry `name` in `config` after it was removed
+pub fn forget(config: &Path, name: &str) {
+    let result = update(|entries| {
+        let Some(names) = entries.get_mut(&key(config)) else {
+            return false;
+        };
+        let removed = names.remove(name);
+        if names.is_empty() {
+            entries.remove(&key(config));
+        }
+        removed
+    });
+    warn_unrecorded(config, name, result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_entries_of_existing_configs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = dir.path().join("mcp.json");
+        let gone = dir.path().join("gone.json");
+        fs::write(&config, "{}").unwrap();
+        fs::write(&gone, "{}").unwrap();
+
+        record(&config, "linear");
+        record(&gone, "github");
+        assert!(entries_path().starts_with(paths::home()));
+        assert!(is_recorded(&config, "linear"));
+        assert!(is_recorded(&gone, "github"));
+
+        fs::remove_file(&gone).unwrap();
+        forget(&config, "linear");
+        assert!(load().is_empty());
+    }
+}
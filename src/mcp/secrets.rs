@@ -0,0 +1,244 @@
+//! Server secrets kept in the OS keychain instead of the tools' configs.
+//!
+//! `mcp enable --keychain` stores a server's secret values under
+//! `mcp/<server>/<key>` and writes an entry that starts the server through
+//! `ai-cli mcp exec`, which reads them back and runs the real command:
+//! environment values are set in its environment, and values placed in its
+//! arguments (or their `--config` JSON) replace a `${key}` placeholder. The
+//! config files then hold no secret at all.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+
+use super::servers::{McpServer, Placement};
+use crate::exit::{self, Outcome};
+use crate::keys::keychain;
+use crate::paths;
+
+/// Keychain account of `server`'s variable `key`
+fn account(server: &str, key: &str) -> String {
+    format!("mcp/{}/{}", server, key)
+}
+
+/// Placeholder `mcp exec` replaces with the secret `key`
+fn placeholder(key: &str) -> String {
+    format!("${{{}}}", key)
+}
+
+/// Secrets of a server kept in the keychain, by where they go
+#[derive(Debug, Default, PartialEq)]
+pub struct Stored {
+    /// Environment variables
+    pub env: Vec<String>,
+    /// Keys whose placeholder is in the arguments
+    pub args: Vec<String>,
+}
+
+impl Stored {
+    pub fn is_empty(&self) -> bool {
+        self.env.is_empty() && self.args.is_empty()
+    }
+
+    pub fn keys(&self) -> Vec<&str> {
+        self.env
+            .iter()
+            .chain(&self.args)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Move `server`'s secret values from `values` to the keychain, leaving a
+/// placeholder for those that go into the arguments
+pub fn store(server: &McpServer, values: &mut BTreeMap<String, String>) -> Result<Stored> {
+    let mut stored = Stored::default();
+    if server.is_remote() {
+        return Ok(stored);
+    }
+    for input in server.inputs.iter().filter(|i| i.secret) {
+        let Some(value) = values.remove(&input.key) else {
+            continue;
+        };
+        keychain::set(&account(&server.id, &input.key), &value)?;
+        if input.placement == Placement::Env {
+            stored.env.push(input.key.clone());
+        } else {
+            values.insert(input.key.clone(), placeholder(&input.key));
+            stored.args.push(input.key.clone());
+        }
+    }
+    Ok(stored)
+}
+
+/// `server` started through `ai-cli mcp exec`, which fills in the `stored`
+/// secrets from the keychain
+pub fn wrap(server: &McpServer, stored: &Stored) -> McpServer {
+    let mut args = Vec::new();
+    if let Some(root) = paths::config_root() {
+        args.extend(["--config-root".to_string(), root.display().to_string()]);
+    }
+    args.extend(["mcp".to_string(), "exec".to_string(), server.id.clone()]);
+    for key in &stored.env {
+        args.extend(["--secret".to_string(), key.clone()]);
+    }
+    for key in &stored.args {
+        args.extend(["--secret-arg".to_string(), key.clone()]);
+    }
+    args.push("--".to_string());
+    args.push(server.command.clone());
+    args.extend(server.args.iter().cloned());
+    let command = env::current_exe()
+        .map(|exe| exe.display().to_string())
+        .unwrap_or_else(|_| "ai-cli".to_string());
+    McpServer {
+        command,
+        args,
+        env: server
+            .env
+            .iter()
+            .filter(|(key, _)| !stored.env.contains(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        ..server.clone()
+    }
+}
+
+/// The command and arguments a [`wrap`]ped entry runs, in entry form
+pub fn unwrap(entry: &Value) -> Option<Value> {
+    let args: Vec<&str> = entry["args"]
+        .as_array()?
+        .iter()
+        .map(Value::as_str)
+        .collect::<Option<_>>()?;
+    let rest = match args.first() {
+        Some(&"--config-root") => args.get(2..)?,
+        _ => &args[..],
+    };
+    if rest.get(..2) != Some(&["mcp", "exec"][..]) {
+        return None;
+    }
+    let (command, rest) = rest.split(|a| *a == "--").nth(1)?.split_first()?;
+    Some(json!({ "command": command, "args": rest }))
+}
+
+/// Handle `mcp exec <server> [--secret VAR]... [--secret-arg KEY]... --
+/// <command>...`, run by the tools to start a server whose secrets are in the
+/// keychain
+pub fn handle_exec(
+    server: &str,
+    env_keys: &[String],
+    arg_keys: &[String],
+    command: &[String],
+) -> Result<Outcome> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| exit::usage("No command to run"))?;
+    let secret = |key: &str| {
+        keychain::get(&account(server, key)).ok_or_else(|| {
+            anyhow!(
+                "No {} for {} in the keychain; run `ai-cli mcp enable {} --keychain`",
+                key,
+                server,
+                server
+            )
+        })
+    };
+    let mut env = BTreeMap::new();
+    for key in env_keys {
+        env.insert(key.clone(), secret(key)?);
+    }
+    let mut args = args.to_vec();
+    for key in arg_keys {
+        let value = secret(key)?;
+        for arg in &mut args {
+            *arg = arg.replace(&placeholder(key), &value);
+        }
+    }
+
+    let mut command = Command::new(program);
+    command.args(&args).envs(&env);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = command.exec();
+        Err(err).with_context(|| format!("Failed to run {}", program))
+    }
+    #[cfg(not(unix))]
+    {
+        let status = command
+            .status()
+            .with_context(|| format!("Failed to run {}", program))?;
+        Ok(if status.success() {
+            Outcome::Success
+        } else {
+            Outcome::Failure
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_entries_run_the_server_command() {
+        let server = McpServer::new(
+            "github",
+            "GitHub",
+            &["-y", "@modelcontextprotocol/server-github"],
+            "",
+        );
+        let stored = Stored {
+            env: vec!["GITHUB_PERSONAL_ACCESS_TOKEN".to_string()],
+            args: Vec::new(),
+        };
+        let wrapped = wrap(&server, &stored);
+        assert_eq!(
+            wrapped.command,
+            env::current_exe().unwrap().display().to_string()
+        );
+        assert_eq!(
+            wrapped.args[..5],
+            [
+                "mcp",
+                "exec",
+                "github",
+                "--secret",
+                "GITHUB_PERSONAL_ACCESS_TOKEN"
+            ]
+        );
+
+        let entry = json!({ "command": wrapped.command, "args": wrapped.args });
+        assert_eq!(
+            unwrap(&entry),
+            Some(
+                json!({ "command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"] })
+            )
+        );
+        assert!(server.matches_entry(&entry));
+        assert_eq!(unwrap(&json!({ "command": "npx", "args": [] })), None);
+
+        let postgres = McpServer::new("postgres", "PostgreSQL", &["server-postgres"], "");
+        let stored = Stored {
+            env: Vec::new(),
+            args: vec!["database-url".to_string()],
+        };
+        let mut wrapped = wrap(&postgres, &stored);
+        wrapped.args.push(placeholder("database-url"));
+        assert!(!wrapped.args.iter().any(|a| a.contains("postgresql://")));
+        assert!(wrapped.args.contains(&"--secret-arg".to_string()));
+        let entry = json!({
+            "command": "/usr/local/bin/ai-cli",
+            "args": ["--config-root", "/tmp/root", "mcp", "exec", "postgres", "--", "npx", "x"]
+        });
+        assert_eq!(
+            unwrap(&entry),
+            Some(json!({ "command": "npx", "args": ["x"] }))
+        );
+    }
+}
@@ -1,10 +1,14 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use serde_json::{Value, json};
 
-use super::servers::McpServer;
+use super::ownership;
+use super::servers::{McpServer, Transport};
+use crate::logging;
+use crate::paths;
 
 /// How a CLI tool configures MCP servers
 #[derive(Debug, Clone)]
@@ -21,7 +25,7 @@ pub enum ConfigMethod {
         /// Include "tools": ["*"] field (Copilot format)
         include_tools_field: bool,
     },
-    /// TOML config file with [mcp_servers.<name>] sections
+    /// TOML config file with `[mcp_servers.<name>]` sections
     TomlConfig { path: PathBuf },
 }
 
@@ -35,7 +39,7 @@ pub struct McpTarget {
 
 impl McpTarget {
     /// Get the config file path for this target
-    pub fn config_path(&self) -> &std::path::Path {
+    pub fn config_path(&self) -> &Path {
         match &self.config_method {
             ConfigMethod::JsonConfig { path, .. } => path,
             ConfigMethod::TomlConfig { path } => path,
@@ -50,8 +54,8 @@ impl McpTarget {
                 // check if their config directory exists
                 if self.binary_name == "cursor" {
                     path.parent().is_some_and(|p| p.exists())
-                } else if self.binary_name == "copilot" {
-                    // Copilot: check binary OR config dir exists
+                } else if self.binary_name == "copilot" || self.binary_name == "amp" {
+                    // Copilot and Amp (also a VS Code extension): check binary OR config dir exists
                     Command::new("which")
                         .arg(self.binary_name)
                         .output()
@@ -75,8 +79,85 @@ impl McpTarget {
         }
     }
 
+    /// Name of `server`'s entry in this target's config
+    pub fn entry_name<'a>(&self, server: &'a McpServer) -> &'a str {
+        match &self.config_method {
+            ConfigMethod::JsonConfig {
+                server_name_override: Some(name),
+                ..
+            } => name,
+            _ => &server.id,
+        }
+    }
+
+    /// Whether ai-cli may change or remove `server`'s entry: it wrote the entry,
+    /// the entry is identical to what it writes, or there is no entry
+    pub fn owns_server(&self, server: &McpServer) -> Result<bool> {
+        let name = self.entry_name(server);
+        if ownership::is_recorded(self.config_path(), name) {
+            return Ok(true);
+        }
+        Ok(self
+            .configured_servers()?
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .is_none_or(|(_, entry)| server.matches_entry(entry)))
+    }
+
     /// Enable an MCP server for this target
     pub fn enable_server(&self, server: &McpServer) -> Result<String> {
+        let message = self.write_server(server)?;
+        ownership::record(self.config_path(), self.entry_name(server));
+        Ok(message)
+    }
+
+    /// Whether this target connects to servers over `transport` itself;
+    /// Codex CLI has no SSE client
+    pub fn supports(&self, transport: Transport) -> bool {
+        match self.config_method {
+            ConfigMethod::JsonConfig { .. } => true,
+            ConfigMethod::TomlConfig { .. } => transport != Transport::Sse,
+        }
+    }
+
+    /// `server` as this target runs it: remote servers it can't connect to
+    /// go through mcp-remote
+    fn as_written(&self, server: &McpServer) -> McpServer {
+        if self.supports(server.transport) {
+            server.clone()
+        } else {
+            server.bridged()
+        }
+    }
+
+    /// Key of a streamable HTTP server's URL; Gemini CLI keeps `url` for SSE
+    fn http_url_key(&self) -> &'static str {
+        if self.binary_name == "gemini" {
+            "httpUrl"
+        } else {
+            "url"
+        }
+    }
+
+    /// The entry written for remote `server`
+    fn remote_entry(&self, server: &McpServer) -> Value {
+        match &self.config_method {
+            ConfigMethod::JsonConfig {
+                type_value,
+                include_tools_field,
+                ..
+            } => remote_entry(
+                server,
+                type_value.is_some(),
+                self.http_url_key(),
+                *include_tools_field,
+            ),
+            ConfigMethod::TomlConfig { .. } => json!({ "url": server.url }),
+        }
+    }
+
+    fn write_server(&self, server: &McpServer) -> Result<String> {
+        let server = &self.as_written(server);
         match &self.config_method {
             ConfigMethod::JsonConfig {
                 path,
@@ -85,13 +166,14 @@ impl McpTarget {
                 type_value,
                 include_tools_field,
             } => {
-                let server_name = server_name_override.unwrap_or(server.id);
+                let server_name = server_name_override.unwrap_or(&server.id);
                 enable_in_json(
                     path,
                     servers_key,
                     server_name,
                     server,
                     *type_value,
+                    self.http_url_key(),
                     *include_tools_field,
                 )?;
                 Ok(format!("Updated {}", path.display()))
@@ -103,24 +185,135 @@ impl McpTarget {
         }
     }
 
-    /// Disable an MCP server for this target
+    /// Disable an MCP server for this target, refusing to remove an entry
+    /// the user wrote
     pub fn disable_server(&self, server: &McpServer) -> Result<String> {
-        match &self.config_method {
+        let name = self.entry_name(server);
+        if !self.owns_server(server)? {
+            bail!(
+                "'{}' in {} wasn't added by ai-cli; remove it by hand",
+                name,
+                self.config_path().display()
+            );
+        }
+        self.remove_recorded(name)
+    }
+
+    /// Remove the entry `name` and forget that ai-cli wrote it
+    pub fn remove_recorded(&self, name: &str) -> Result<String> {
+        let message = match &self.config_method {
             ConfigMethod::JsonConfig {
-                path,
-                servers_key,
-                server_name_override,
-                ..
+                path, servers_key, ..
             } => {
-                let server_name = server_name_override.unwrap_or(server.id);
-                disable_in_json(path, servers_key, server_name)?;
-                Ok(format!("Updated {}", path.display()))
+                disable_in_json(path, servers_key, name)?;
+                format!("Updated {}", path.display())
             }
             ConfigMethod::TomlConfig { path } => {
-                disable_in_toml(path, server)?;
-                Ok(format!("Updated {}", path.display()))
+                disable_in_toml(path, name)?;
+                format!("Updated {}", path.display())
             }
+        };
+        ownership::forget(self.config_path(), name);
+        Ok(message)
+    }
+
+    /// Whether `server`'s entry holds exactly the command, arguments and
+    /// environment (or the URL) ai-cli would write now
+    pub fn is_up_to_date(&self, server: &McpServer) -> Result<bool> {
+        let name = self.entry_name(server);
+        let servers = self.configured_servers()?;
+        let Some((_, entry)) = servers.iter().find(|(entry_name, _)| entry_name == name) else {
+            return Ok(false);
+        };
+        let server = &self.as_written(server);
+        if server.is_remote() {
+            return Ok(*entry == self.remote_entry(server));
         }
+        let env: BTreeMap<String, String> =
+            serde_json::from_value(entry["env"].clone()).unwrap_or_default();
+        Ok(entry["command"].as_str() == Some(server.command.as_str())
+            && entry["args"] == json!(server.args)
+            && env == server.env)
+    }
+
+    /// Every server entry in the config, including ones ai-cli doesn't manage
+    ///
+    /// TOML entries are converted to JSON so both formats can be inspected alike.
+    pub fn configured_servers(&self) -> Result<Vec<(String, Value)>> {
+        let path = self.config_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let servers = match &self.config_method {
+            ConfigMethod::JsonConfig { servers_key, .. } => {
+                let config: Value = serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse JSON in {}", path.display()))?;
+                config.get(*servers_key).cloned()
+            }
+            ConfigMethod::TomlConfig { .. } => {
+                let config: toml::Value = toml::from_str(&content)
+                    .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
+                config
+                    .get("mcp_servers")
+                    .map(serde_json::to_value)
+                    .transpose()?
+            }
+        };
+        Ok(servers
+            .and_then(|s| s.as_object().cloned())
+            .map(|s| s.into_iter().collect())
+            .unwrap_or_default())
+    }
+
+    /// Add a server entry as-is (already in this target's format), replacing
+    /// any entry with the same name
+    pub fn add_server_entry(&self, name: &str, entry: &Value) -> Result<String> {
+        let path = self.config_path();
+        let original = if path.exists() {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+            }
+            String::new()
+        };
+        let updated = match &self.config_method {
+            ConfigMethod::JsonConfig { servers_key, .. } => {
+                let mut config: Value = if original.trim().is_empty() {
+                    json!({})
+                } else {
+                    serde_json::from_str(&original)
+                        .with_context(|| format!("Failed to parse JSON in {}", path.display()))?
+                };
+                navigate_or_create(&mut config, servers_key)[name] = entry.clone();
+                serde_json::to_string_pretty(&config)?
+            }
+            ConfigMethod::TomlConfig { .. } => {
+                let mut doc: toml_edit::DocumentMut = original
+                    .parse()
+                    .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
+                let mut table = toml_edit::Table::new();
+                for (key, field) in entry.as_object().into_iter().flatten() {
+                    if let Some(field) = json_to_toml(field) {
+                        table[key.as_str()] = toml_edit::value(field);
+                    }
+                }
+                if !doc.contains_key("mcp_servers") {
+                    let mut servers = toml_edit::Table::new();
+                    servers.set_implicit(true);
+                    doc["mcp_servers"] = toml_edit::Item::Table(servers);
+                }
+                doc["mcp_servers"][name] = toml_edit::Item::Table(table);
+                doc.to_string()
+            }
+        };
+        logging::write_config(path, &original, &updated)?;
+        ownership::record(path, name);
+        Ok(format!("Updated {}", path.display()))
     }
 
     /// Check if an MCP server is currently enabled
@@ -132,7 +325,7 @@ impl McpTarget {
                 server_name_override,
                 ..
             } => {
-                let server_name = server_name_override.unwrap_or(server.id);
+                let server_name = server_name_override.unwrap_or(&server.id);
                 is_enabled_in_json(path, servers_key, server_name)
             }
             ConfigMethod::TomlConfig { path } => is_enabled_in_toml(path, server),
@@ -147,9 +340,7 @@ fn claude_code() -> McpTarget {
         name: "Claude Code",
         binary_name: "claude",
         config_method: ConfigMethod::JsonConfig {
-            path: dirs::home_dir()
-                .expect("Could not find home directory")
-                .join(".claude.json"),
+            path: paths::home().join(".claude.json"),
             servers_key: "mcpServers",
             server_name_override: None,
             type_value: Some("stdio"),
@@ -163,9 +354,7 @@ fn gemini_cli() -> McpTarget {
         name: "Gemini CLI",
         binary_name: "gemini",
         config_method: ConfigMethod::JsonConfig {
-            path: dirs::home_dir()
-                .expect("Could not find home directory")
-                .join(".gemini/settings.json"),
+            path: paths::home().join(".gemini/settings.json"),
             servers_key: "mcpServers",
             server_name_override: None,
             type_value: None,
@@ -179,21 +368,62 @@ fn codex_cli() -> McpTarget {
         name: "Codex CLI",
         binary_name: "codex",
         config_method: ConfigMethod::TomlConfig {
-            path: dirs::home_dir()
-                .expect("Could not find home directory")
-                .join(".codex/config.toml"),
+            path: paths::home().join(".codex/config.toml"),
         },
     }
 }
 
-fn amp() -> McpTarget {
+/// VS Code's user settings, where the Amp extension reads `amp.*` keys
+fn vscode_user_settings() -> PathBuf {
+    let dir = if cfg!(target_os = "macos") {
+        paths::home().join("Library/Application Support")
+    } else if cfg!(windows) {
+        std::env::var_os("APPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| paths::home().join("AppData/Roaming"))
+    } else {
+        paths::config_home()
+    };
+    dir.join("Code/User/settings.json")
+}
+
+fn mentions(path: &Path, key: &str) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|content| content.contains(&format!("\"{}", key)))
+}
+
+/// Amp reads `amp.mcpServers` from its own settings, from VS Code's user
+/// settings and from the workspace's `.vscode/settings.json`
+///
+/// Every location in use gets a target; Amp's own file is the default when
+/// neither VS Code file configures Amp.
+fn amp_targets() -> Vec<McpTarget> {
+    let own = paths::home().join(".config/amp/settings.json");
+    let vscode = vscode_user_settings();
+    let workspace = std::env::current_dir()
+        .map(|dir| dir.join(".vscode/settings.json"))
+        .ok()
+        .filter(|path| mentions(path, "amp.mcpServers"));
+
+    let mut targets = Vec::new();
+    let uses_vscode = mentions(&vscode, "amp.");
+    if own.exists() || (!uses_vscode && workspace.is_none()) {
+        targets.push(amp("Amp", own));
+    }
+    if uses_vscode {
+        targets.push(amp("Amp (VS Code)", vscode));
+    }
+    if let Some(workspace) = workspace {
+        targets.push(amp("Amp (workspace)", workspace));
+    }
+    targets
+}
+
+fn amp(name: &'static str, path: PathBuf) -> McpTarget {
     McpTarget {
-        name: "Amp",
+        name,
         binary_name: "amp",
         config_method: ConfigMethod::JsonConfig {
-            path: dirs::home_dir()
-                .expect("Could not find home directory")
-                .join(".config/amp/settings.json"),
+            path,
             servers_key: "amp.mcpServers",
             server_name_override: None,
             type_value: None,
@@ -207,9 +437,7 @@ fn cursor() -> McpTarget {
         name: "Cursor",
         binary_name: "cursor",
         config_method: ConfigMethod::JsonConfig {
-            path: dirs::home_dir()
-                .expect("Could not find home directory")
-                .join(".cursor/mcp.json"),
+            path: paths::home().join(".cursor/mcp.json"),
             servers_key: "mcpServers",
             server_name_override: None,
             type_value: None,
@@ -223,9 +451,7 @@ fn copilot_cli() -> McpTarget {
         name: "Copilot CLI",
         binary_name: "copilot",
         config_method: ConfigMethod::JsonConfig {
-            path: dirs::home_dir()
-                .expect("Could not find home directory")
-                .join(".copilot/mcp-config.json"),
+            path: paths::home().join(".copilot/mcp-config.json"),
             servers_key: "mcpServers",
             server_name_override: None,
             type_value: Some("local"),
@@ -236,14 +462,36 @@ fn copilot_cli() -> McpTarget {
 
 /// Returns all supported CLI tools that can have MCP servers configured
 pub fn catalog() -> Vec<McpTarget> {
-    vec![
-        claude_code(),
-        gemini_cli(),
-        codex_cli(),
-        amp(),
-        cursor(),
-        copilot_cli(),
-    ]
+    let mut targets = vec![claude_code(), gemini_cli(), codex_cli()];
+    targets.extend(amp_targets());
+    targets.extend([cursor(), copilot_cli()]);
+    targets
+}
+
+/// The targets of the tools named by binary (`claude`, `cursor`, ...), all of
+/// them when `tools` is empty
+pub fn select(tools: &[String]) -> Result<Vec<McpTarget>> {
+    let targets = catalog();
+    if tools.is_empty() {
+        return Ok(targets);
+    }
+    if let Some(unknown) = tools
+        .iter()
+        .find(|tool| !targets.iter().any(|t| t.binary_name == tool.as_str()))
+    {
+        let mut known: Vec<&str> = targets.iter().map(|t| t.binary_name).collect();
+        known.dedup();
+        return Err(crate::exit::usage(format!(
+            "Unknown tool '{}'. Supported tools: {}",
+            unknown,
+            known.join(", ")
+        )));
+    }
+    // Amp can have several config locations in use
+    Ok(targets
+        .into_iter()
+        .filter(|t| tools.iter().any(|tool| tool == t.binary_name))
+        .collect())
 }
 
 // JSON config helpers
@@ -259,31 +507,69 @@ fn navigate_or_create<'a>(config: &'a mut Value, key: &str) -> &'a mut Value {
     &mut config[key]
 }
 
+/// A remote server's entry: its URL, plus `"type"` for tools that name the
+/// transport of every entry
+fn remote_entry(
+    server: &McpServer,
+    typed: bool,
+    http_url_key: &str,
+    include_tools_field: bool,
+) -> Value {
+    let mut entry = json!({});
+    if typed {
+        entry["type"] = json!(server.transport.as_str());
+    }
+    let url_key = match server.transport {
+        Transport::Http => http_url_key,
+        _ => "url",
+    };
+    entry[url_key] = json!(server.url);
+    if include_tools_field {
+        entry["tools"] = json!(["*"]);
+    }
+    entry
+}
+
 fn enable_in_json(
     path: &PathBuf,
     servers_key: &str,
     server_name: &str,
     server: &McpServer,
     type_value: Option<&str>,
+    http_url_key: &str,
     include_tools_field: bool,
 ) -> Result<()> {
-    let mut config: Value = if path.exists() {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read {}", path.display()))?;
-        serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse JSON in {}", path.display()))?
+    let original = if path.exists() {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
     } else {
         // Create parent directories if needed
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create directory {}", parent.display()))?;
         }
+        String::new()
+    };
+    let mut config: Value = if path.exists() {
+        serde_json::from_str(&original)
+            .with_context(|| format!("Failed to parse JSON in {}", path.display()))?
+    } else {
         json!({})
     };
 
     let servers_obj = navigate_or_create(&mut config, servers_key);
+    if server.is_remote() {
+        servers_obj[server_name] = remote_entry(
+            server,
+            type_value.is_some(),
+            http_url_key,
+            include_tools_field,
+        );
+        let content = serde_json::to_string_pretty(&config)?;
+        return logging::write_config(path, &original, &content);
+    }
     let mut server_config = json!({
-        "command": "npx",
+        "command": server.command,
         "args": server.args
     });
 
@@ -294,6 +580,10 @@ fn enable_in_json(
         }
     }
 
+    if !server.env.is_empty() {
+        server_config["env"] = json!(server.env);
+    }
+
     if include_tools_field {
         server_config["tools"] = json!(["*"]);
     }
@@ -301,7 +591,7 @@ fn enable_in_json(
     servers_obj[server_name] = server_config;
 
     let content = serde_json::to_string_pretty(&config)?;
-    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    logging::write_config(path, &original, &content)?;
 
     Ok(())
 }
@@ -321,8 +611,8 @@ fn disable_in_json(path: &PathBuf, servers_key: &str, server_name: &str) -> Resu
         servers.remove(server_name);
     }
 
-    let content = serde_json::to_string_pretty(&config)?;
-    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    let updated = serde_json::to_string_pretty(&config)?;
+    logging::write_config(path, &content, &updated)?;
 
     Ok(())
 }
@@ -343,22 +633,50 @@ fn is_enabled_in_json(path: &PathBuf, servers_key: &str, server_name: &str) -> R
 
 // TOML config helpers
 
+/// Convert a JSON value to TOML, with objects as inline tables; `null` has no
+/// TOML equivalent
+fn json_to_toml(value: &Value) -> Option<toml_edit::Value> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64()?.into(),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(json_to_toml)
+            .collect::<toml_edit::Array>()
+            .into(),
+        Value::Object(fields) => {
+            let mut table = toml_edit::InlineTable::new();
+            for (key, field) in fields {
+                if let Some(field) = json_to_toml(field) {
+                    table.insert(key, field);
+                }
+            }
+            table.into()
+        }
+    })
+}
+
 fn enable_in_toml(path: &PathBuf, server: &McpServer) -> Result<()> {
     use toml_edit::{Array, DocumentMut, value};
 
-    let mut doc: DocumentMut = if path.exists() {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read {}", path.display()))?;
-        content
-            .parse()
-            .with_context(|| format!("Failed to parse TOML in {}", path.display()))?
+    let original = if path.exists() {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
     } else {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create directory {}", parent.display()))?;
         }
-        DocumentMut::new()
+        String::new()
     };
+    let mut doc: DocumentMut = original
+        .parse()
+        .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
 
     // Ensure [mcp_servers] table exists
     if !doc.contains_key("mcp_servers") {
@@ -367,26 +685,41 @@ fn enable_in_toml(path: &PathBuf, server: &McpServer) -> Result<()> {
 
     // Add [mcp_servers.<server_id>]
     let mcp_servers = doc["mcp_servers"].as_table_mut().unwrap();
-    if !mcp_servers.contains_key(server.id) {
-        mcp_servers[server.id] = toml_edit::table();
+    if !mcp_servers.contains_key(&server.id) {
+        mcp_servers[&server.id] = toml_edit::table();
     }
 
-    let server_table = mcp_servers[server.id].as_table_mut().unwrap();
-    server_table["command"] = value("npx");
+    let server_table = mcp_servers[&server.id].as_table_mut().unwrap();
+    if let Some(url) = server.url.as_deref().filter(|_| server.is_remote()) {
+        for key in ["command", "args", "env"] {
+            server_table.remove(key);
+        }
+        server_table["url"] = value(url);
+        return logging::write_config(path, &original, &doc.to_string());
+    }
+    server_table.remove("url");
+    server_table["command"] = value(&server.command);
 
     let mut args = Array::new();
-    for arg in server.args {
-        args.push(*arg);
+    for arg in &server.args {
+        args.push(arg);
     }
     server_table["args"] = value(args);
 
-    std::fs::write(path, doc.to_string())
-        .with_context(|| format!("Failed to write {}", path.display()))?;
+    if !server.env.is_empty() {
+        let mut env = toml_edit::InlineTable::new();
+        for (key, val) in &server.env {
+            env.insert(key, val.as_str().into());
+        }
+        server_table["env"] = value(env);
+    }
+
+    logging::write_config(path, &original, &doc.to_string())?;
 
     Ok(())
 }
 
-fn disable_in_toml(path: &PathBuf, server: &McpServer) -> Result<()> {
+fn disable_in_toml(path: &PathBuf, server_name: &str) -> Result<()> {
     use toml_edit::DocumentMut;
 
     if !path.exists() {
@@ -400,11 +733,10 @@ fn disable_in_toml(path: &PathBuf, server: &McpServer) -> Result<()> {
         .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
 
     if let Some(mcp_servers) = doc.get_mut("mcp_servers").and_then(|t| t.as_table_mut()) {
-        mcp_servers.remove(server.id);
+        mcp_servers.remove(server_name);
     }
 
-    std::fs::write(path, doc.to_string())
-        .with_context(|| format!("Failed to write {}", path.display()))?;
+    logging::write_config(path, &content, &doc.to_string())?;
 
     Ok(())
 }
@@ -425,7 +757,7 @@ fn is_enabled_in_toml(path: &PathBuf, server: &McpServer) -> Result<bool> {
     Ok(doc
         .get("mcp_servers")
         .and_then(|t| t.as_table())
-        .is_some_and(|t| t.contains_key(server.id)))
+        .is_some_and(|t| t.contains_key(&server.id)))
 }
 
 #[cfg(test)]
@@ -591,6 +923,29 @@ mod tests {
         assert!(json["mcpServers"]["playwright"].is_null());
     }
 
+    #[test]
+    fn json_disable_keeps_user_added_entries() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{"mcpServers":{"playwright":{"command":"node","args":["my-fork.js"]}}}"#,
+        )
+        .unwrap();
+        let target = json_target(path.clone(), "mcpServers", None);
+        let server = test_server();
+
+        assert!(!target.owns_server(&server).unwrap());
+        assert!(target.disable_server(&server).is_err());
+        assert!(target.is_server_enabled(&server).unwrap());
+
+        // Once ai-cli has written the entry, it may remove it again
+        target.enable_server(&server).unwrap();
+        assert!(target.owns_server(&server).unwrap());
+        target.disable_server(&server).unwrap();
+        assert!(!target.is_server_enabled(&server).unwrap());
+    }
+
     #[test]
     fn json_disable_nonexistent_file_is_noop() {
         let dir = TempDir::new().unwrap();
@@ -638,8 +993,81 @@ mod tests {
         assert!(target.is_server_enabled(&server).unwrap());
     }
 
+    #[test]
+    fn json_enable_remote_server_per_target() {
+        let dir = TempDir::new().unwrap();
+        let linear = McpServer::remote(
+            "linear",
+            "Linear",
+            Transport::Http,
+            "https://mcp.linear.app/mcp",
+            "",
+        );
+        let entry = |target: &McpTarget| {
+            target.enable_server(&linear).unwrap();
+            assert!(target.is_up_to_date(&linear).unwrap());
+            let json: Value =
+                serde_json::from_str(&std::fs::read_to_string(target.config_path()).unwrap())
+                    .unwrap();
+            json["mcpServers"]["linear"].clone()
+        };
+
+        let claude = json_target(dir.path().join("claude.json"), "mcpServers", Some("stdio"));
+        assert_eq!(
+            entry(&claude),
+            json!({ "type": "http", "url": "https://mcp.linear.app/mcp" })
+        );
+        let gemini = McpTarget {
+            binary_name: "gemini",
+            ..json_target(dir.path().join("gemini.json"), "mcpServers", None)
+        };
+        assert_eq!(
+            entry(&gemini),
+            json!({ "httpUrl": "https://mcp.linear.app/mcp" })
+        );
+        let copilot = json_target_copilot(dir.path().join("copilot.json"));
+        assert_eq!(
+            entry(&copilot),
+            json!({ "type": "http", "url": "https://mcp.linear.app/mcp", "tools": ["*"] })
+        );
+
+        // Entries from before remote servers were supported still count as ai-cli's
+        assert!(linear.matches_entry(&json!({
+            "command": "npx",
+            "args": ["mcp-remote", "https://mcp.linear.app/mcp"]
+        })));
+    }
+
     // TOML tests
 
+    #[test]
+    fn toml_remote_servers_use_url_or_mcp_remote() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let target = toml_target(path.clone());
+
+        let mut server = test_server();
+        target.enable_server(&server).unwrap();
+        server.transport = Transport::Http;
+        server.url = Some("https://example.com/mcp".to_string());
+        target.enable_server(&server).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("url = \"https://example.com/mcp\""));
+        assert!(!content.contains("command"));
+        assert!(target.is_up_to_date(&server).unwrap());
+
+        // No SSE client in Codex CLI
+        server.transport = Transport::Sse;
+        assert!(!target.is_up_to_date(&server).unwrap());
+        target.enable_server(&server).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains(
+            "args = [\"mcp-remote\", \"https://example.com/mcp\", \"--transport\", \"sse-only\"]"
+        ));
+        assert!(!content.contains("url ="));
+        assert!(target.is_up_to_date(&server).unwrap());
+    }
+
     #[test]
     fn toml_enable_creates_section() {
         let dir = TempDir::new().unwrap();
@@ -701,6 +1129,24 @@ mod tests {
         assert!(!path.exists());
     }
 
+    #[test]
+    fn toml_add_server_entry_writes_inline_tables() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "model = \"o3\"\n").unwrap();
+        let target = toml_target(path.clone());
+
+        let entry = json!({"command": "npx", "args": ["acme"], "env": {"K": "v"}});
+        target.add_server_entry("acme", &entry).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "model = \"o3\"\n\n[mcp_servers.acme]\nargs = [\"acme\"]\ncommand = \"npx\"\nenv = { K = \"v\" }\n"
+        );
+        assert_eq!(target.configured_servers().unwrap()[0].1, entry);
+    }
+
     // Full workflow tests
 
     #[test]
@@ -740,4 +1186,34 @@ mod tests {
         target.disable_server(&server).unwrap();
         assert!(!target.is_server_enabled(&server).unwrap());
     }
+
+    #[test]
+    fn selects_targets_by_tool() {
+        assert_eq!(select(&[]).unwrap().len(), catalog().len());
+        let picked = select(&["cursor".to_string(), "codex".to_string()]).unwrap();
+        let names: Vec<&str> = picked.iter().map(|t| t.name).collect();
+        assert_eq!(names, ["Codex CLI", "Cursor"]);
+        assert!(select(&["vim".to_string()]).is_err());
+    }
+
+    #[test]
+    fn amp_edits_vscode_settings_alongside_other_keys() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(
+            &path,
+            r#"{"editor.fontSize": 14, "amp.url": "https://ampcode.com"}"#,
+        )
+        .unwrap();
+        assert!(mentions(&path, "amp."));
+        assert!(!mentions(&path, "amp.mcpServers"));
+
+        let target = amp("Amp (VS Code)", path.clone());
+        target.enable_server(&test_server()).unwrap();
+
+        let config: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["editor.fontSize"], 14);
+        assert!(config["amp.mcpServers"]["playwright"].is_object());
+        assert!(mentions(&path, "amp.mcpServers"));
+    }
 }
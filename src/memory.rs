@@ -0,0 +1,406 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use colored::*;
+use inquire::MultiSelect;
+
+use crate::exit::{self, Outcome};
+use crate::journal;
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::prune::format_size;
+use crate::theme::Themed;
+
+/// Heading under which Gemini CLI's `save_memory` tool appends facts
+const GEMINI_MEMORY_HEADING: &str = "## Gemini Added Memories";
+
+/// How an agent stores what it remembers
+#[derive(Debug, Clone)]
+pub enum MemoryKind {
+    /// Bullet lines under a heading of a Markdown file
+    Section {
+        path: PathBuf,
+        heading: &'static str,
+    },
+    /// One Markdown file per memory in `<root>/<project>/memory/`
+    ProjectFiles { root: PathBuf },
+}
+
+/// An agent's memory store
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    pub id: &'static str,
+    pub agent: &'static str,
+    pub kind: MemoryKind,
+}
+
+/// A single remembered item
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub text: String,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    location: EntryLocation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum EntryLocation {
+    Line(usize),
+    File(PathBuf),
+}
+
+/// Returns the memory stores of all supported agents
+pub fn catalog() -> Vec<MemoryStore> {
+    let home = paths::home();
+    vec![
+        MemoryStore {
+            id: "claude",
+            agent: "Claude Code",
+            kind: MemoryKind::ProjectFiles {
+                root: home.join(".claude/projects"),
+            },
+        },
+        MemoryStore {
+            id: "gemini",
+            agent: "Gemini CLI",
+            kind: MemoryKind::Section {
+                path: home.join(".gemini/GEMINI.md"),
+                heading: GEMINI_MEMORY_HEADING,
+            },
+        },
+    ]
+}
+
+pub fn find(id: &str) -> Option<MemoryStore> {
+    catalog()
+        .into_iter()
+        .find(|s| s.id.eq_ignore_ascii_case(id))
+}
+
+impl MemoryStore {
+    pub fn location(&self) -> &Path {
+        match &self.kind {
+            MemoryKind::Section { path, .. } => path,
+            MemoryKind::ProjectFiles { root } => root,
+        }
+    }
+
+    pub fn entries(&self) -> Result<Vec<Entry>> {
+        match &self.kind {
+            MemoryKind::Section { path, heading } => {
+                let Ok(content) = fs::read_to_string(path) else {
+                    return Ok(Vec::new());
+                };
+                let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                Ok(section_entries(&content, heading)
+                    .into_iter()
+                    .map(|(line, text)| Entry {
+                        size: text.len() as u64,
+                        text,
+                        modified,
+                        location: EntryLocation::Line(line),
+                    })
+                    .collect())
+            }
+            MemoryKind::ProjectFiles { root } => {
+                let mut entries = Vec::new();
+                let Ok(projects) = fs::read_dir(root) else {
+                    return Ok(entries);
+                };
+                for project in projects.flatten() {
+                    let Ok(files) = fs::read_dir(project.path().join("memory")) else {
+                        continue;
+                    };
+                    for file in files.flatten() {
+                        let path = file.path();
+                        if path.extension().is_none_or(|e| e != "md") {
+                            continue;
+                        }
+                        let metadata = file.metadata().ok();
+                        let first_line = fs::read_to_string(&path)
+                            .ok()
+                            .and_then(|c| {
+                                c.lines()
+                                    .map(|l| l.trim_start_matches('#').trim().to_string())
+                                    .find(|l| !l.is_empty())
+                            })
+                            .unwrap_or_default();
+                        entries.push(Entry {
+                            text: format!(
+                                "{}: {}",
+                                project.file_name().to_string_lossy(),
+                                first_line
+                            ),
+                            size: metadata.as_ref().map_or(0, |m| m.len()),
+                            modified: metadata.and_then(|m| m.modified().ok()),
+                            location: EntryLocation::File(path),
+                        });
+                    }
+                }
+                entries.sort_by(|a, b| a.text.cmp(&b.text));
+                Ok(entries)
+            }
+        }
+    }
+
+    /// Delete the given entries
+    pub fn remove(&self, entries: &[&Entry]) -> Result<()> {
+        match &self.kind {
+            MemoryKind::Section { path, .. } => {
+                let before = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let lines: Vec<usize> = entries
+                    .iter()
+                    .filter_map(|e| match e.location {
+                        EntryLocation::Line(line) => Some(line),
+                        EntryLocation::File(_) => None,
+                    })
+                    .collect();
+                let after = remove_lines(&before, &lines);
+                logging::write_config(path, &before, &after)
+            }
+            MemoryKind::ProjectFiles { .. } => {
+                for entry in entries {
+                    if let EntryLocation::File(path) = &entry.location {
+                        journal::record_file(path);
+                        fs::remove_file(path)
+                            .with_context(|| format!("Failed to remove {}", path.display()))?;
+                        logging::line(&format!("removed memory {}", path.display()));
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Bullet entries under `heading` with their line numbers
+fn section_entries(content: &str, heading: &str) -> Vec<(usize, String)> {
+    let mut entries = Vec::new();
+    let mut in_section = false;
+    for (index, line) in content.lines().enumerate() {
+        if line.trim() == heading {
+            in_section = true;
+        } else if line.starts_with('#') {
+            in_section = false;
+        } else if in_section && let Some(text) = line.trim().strip_prefix("- ") {
+            entries.push((index, text.to_string()));
+        }
+    }
+    entries
+}
+
+/// Drop the given line numbers, keeping the trailing newline
+fn remove_lines(content: &str, lines: &[usize]) -> String {
+    let mut kept: Vec<&str> = content
+        .lines()
+        .enumerate()
+        .filter(|(index, _)| !lines.contains(index))
+        .map(|(_, line)| line)
+        .collect();
+    if content.ends_with('\n') {
+        kept.push("");
+    }
+    kept.join("\n")
+}
+
+fn count(n: usize) -> String {
+    if n == 1 {
+        "1 memory".to_string()
+    } else {
+        format!("{} memories", n)
+    }
+}
+
+fn format_modified(time: Option<SystemTime>) -> String {
+    time.map(|t| DateTime::<Local>::from(t).format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn store(id: &str) -> Result<MemoryStore> {
+    find(id).ok_or_else(|| {
+        let ids: Vec<_> = catalog().iter().map(|s| s.id).collect();
+        exit::usage(format!(
+            "Unknown agent '{}'. Agents with memory: {}",
+            id,
+            ids.join(", ")
+        ))
+    })
+}
+
+/// Handle `memory list` command
+pub fn handle_list() -> Result<()> {
+    for store in catalog() {
+        let entries = store.entries()?;
+        let size: u64 = entries.iter().map(|e| e.size).sum();
+        let last = entries.iter().filter_map(|e| e.modified).max();
+        println!(
+            "{} {}",
+            store.agent.bold(),
+            store.location().display().to_string().muted()
+        );
+        if entries.is_empty() {
+            println!("  {}", "(nothing remembered)".muted());
+        } else {
+            println!(
+                "  {}, {}, last changed {}",
+                count(entries.len()),
+                format_size(size),
+                format_modified(last)
+            );
+        }
+        println!();
+    }
+    println!(
+        "{} Review with `ai-cli memory show <agent>`; stale memories steer agents wrong",
+        "→".info()
+    );
+    Ok(())
+}
+
+/// Handle `memory show <agent>` command
+pub fn handle_show(id: &str) -> Result<()> {
+    let store = store(id)?;
+    let entries = store.entries()?;
+    println!(
+        "{} {}",
+        store.agent.bold(),
+        store.location().display().to_string().muted()
+    );
+    if entries.is_empty() {
+        println!("  {}", "(nothing remembered)".muted());
+        return Ok(());
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        println!(
+            "  {:>3}. {} {}",
+            index + 1,
+            entry.text,
+            format!("({})", format_size(entry.size)).muted()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `memory prune <agent>` command: remove entries matching `pattern`,
+/// or pick them from a list
+pub fn handle_prune(id: &str, pattern: Option<&str>) -> Result<Outcome> {
+    let store = store(id)?;
+    let entries = store.entries()?;
+    if entries.is_empty() {
+        println!("{} has nothing remembered", store.agent);
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let selected: Vec<&Entry> = match pattern {
+        Some(pattern) => {
+            let pattern = pattern.to_lowercase();
+            entries
+                .iter()
+                .filter(|e| e.text.to_lowercase().contains(&pattern))
+                .collect()
+        }
+        None => {
+            prompt::require_interactive("pass --match <text> to choose entries")?;
+            let options: Vec<String> = entries.iter().map(|e| e.text.clone()).collect();
+            let chosen = MultiSelect::new("Memories to remove:", options)
+                .with_help_message("↑↓ to move, space to select, enter to confirm")
+                .prompt()
+                .context("No entries selected")?;
+            entries
+                .iter()
+                .filter(|e| chosen.contains(&e.text))
+                .collect()
+        }
+    };
+    if selected.is_empty() {
+        println!("No matching memories");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!("{}", "Removing:".bold());
+    for entry in &selected {
+        println!("  {} {}", "-".error(), entry.text);
+    }
+    if pattern.is_some() && !prompt::confirm(&format!("Remove {}?", count(selected.len())))? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    store.remove(&selected)?;
+    println!(
+        "{} Removed {} from {}",
+        "✓".success(),
+        count(selected.len()),
+        store.agent
+    );
+    Ok(Outcome::Success)
+}
+
+/// Handle `memory wipe <agent>` command
+pub fn handle_wipe(id: &str) -> Result<Outcome> {
+    let store = store(id)?;
+    let entries = store.entries()?;
+    if entries.is_empty() {
+        println!("{} has nothing remembered", store.agent);
+        return Ok(Outcome::NothingToDo);
+    }
+    if !prompt::confirm(&format!(
+        "Forget all {} memories of {}?",
+        entries.len(),
+        store.agent
+    ))? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let all: Vec<&Entry> = entries.iter().collect();
+    store.remove(&all)?;
+    println!("{} Wiped {} memory", "✓".success(), store.agent);
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GEMINI_MD: &str = "# Project\n\nUse pnpm.\n\n## Gemini Added Memories\n- User prefers tabs\n- Deploys go through staging\n\n## Notes\n- not a memory\n";
+
+    #[test]
+    fn finds_entries_under_the_memory_heading() {
+        let entries = section_entries(GEMINI_MD, GEMINI_MEMORY_HEADING);
+        assert_eq!(
+            entries,
+            vec![
+                (5, "User prefers tabs".to_string()),
+                (6, "Deploys go through staging".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn prunes_section_entries_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("GEMINI.md");
+        fs::write(&path, GEMINI_MD).unwrap();
+        let store = MemoryStore {
+            id: "gemini",
+            agent: "Gemini CLI",
+            kind: MemoryKind::Section {
+                path: path.clone(),
+                heading: GEMINI_MEMORY_HEADING,
+            },
+        };
+
+        let entries = store.entries().unwrap();
+        store.remove(&[&entries[0]]).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Project\n\nUse pnpm.\n\n## Gemini Added Memories\n- Deploys go through staging\n\n## Notes\n- not a memory\n"
+        );
+    }
+}
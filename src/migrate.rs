@@ -0,0 +1,528 @@
+//! `migrate --from <tool> --to <tool>`: carry MCP servers, instructions,
+//! rules and ignored files over when switching primary agents.
+//!
+//! MCP servers come from the tools' user configs; instructions, rules and
+//! ignore files from the current project. Anything without an equivalent in
+//! the destination tool is reported instead of dropped silently.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde_json::{Map, Value, json};
+
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::mcp::targets::{self, ConfigMethod, McpTarget};
+use crate::rules::{self, Rule};
+use crate::theme::Themed;
+
+/// Where a tool reads the paths it must not look at
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Ignore {
+    /// A gitignore-style file in the project root
+    File(&'static str),
+    /// `Read(...)` deny rules in `.claude/settings.json`
+    ClaudeDeny,
+    None,
+}
+
+/// The project files of a tool that can be migrated
+#[derive(Debug, Clone)]
+struct Agent {
+    /// Binary name, matching the MCP target catalog
+    id: &'static str,
+    /// Instructions file, relative to the project root
+    instructions: &'static str,
+    /// Rules directory and the rule file extension
+    rules: Option<(&'static str, &'static str)>,
+    /// Single-file rules from older versions
+    legacy_rules: Option<&'static str>,
+    ignore: Ignore,
+}
+
+fn agents() -> Vec<Agent> {
+    vec![
+        Agent {
+            id: "claude",
+            instructions: "CLAUDE.md",
+            rules: None,
+            legacy_rules: None,
+            ignore: Ignore::ClaudeDeny,
+        },
+        Agent {
+            id: "codex",
+            instructions: "AGENTS.md",
+            rules: None,
+            legacy_rules: None,
+            ignore: Ignore::None,
+        },
+        Agent {
+            id: "gemini",
+            instructions: "GEMINI.md",
+            rules: None,
+            legacy_rules: None,
+            ignore: Ignore::File(".geminiignore"),
+        },
+        Agent {
+            id: "amp",
+            instructions: "AGENTS.md",
+            rules: None,
+            legacy_rules: None,
+            ignore: Ignore::None,
+        },
+        Agent {
+            id: "cursor",
+            instructions: "AGENTS.md",
+            rules: Some((".cursor/rules", "mdc")),
+            legacy_rules: Some(".cursorrules"),
+            ignore: Ignore::File(".cursorignore"),
+        },
+        Agent {
+            id: "copilot",
+            instructions: ".github/copilot-instructions.md",
+            rules: None,
+            legacy_rules: None,
+            ignore: Ignore::None,
+        },
+    ]
+}
+
+fn find(id: &str) -> Result<(Agent, McpTarget)> {
+    let agent = agents().into_iter().find(|a| a.id.eq_ignore_ascii_case(id));
+    let target = targets::catalog()
+        .into_iter()
+        .find(|t| t.binary_name.eq_ignore_ascii_case(id));
+    match (agent, target) {
+        (Some(agent), Some(target)) => Ok((agent, target)),
+        _ => {
+            let ids: Vec<&str> = agents().iter().map(|a| a.id).collect();
+            Err(exit::usage(format!(
+                "Unknown tool '{}' (expected one of: {})",
+                id,
+                ids.join(", ")
+            )))
+        }
+    }
+}
+
+/// Fields every MCP client understands; anything else is tool specific
+const PORTABLE_FIELDS: &[&str] = &["command", "args", "env", "url", "headers"];
+/// Fields rewritten for the destination rather than copied
+const FORMAT_FIELDS: &[&str] = &["type", "tools", "httpUrl", "http_headers"];
+
+/// Translate an MCP server entry into `to`'s format
+///
+/// Returns `None` for entries with neither a command nor a URL, along with
+/// the fields that have no portable equivalent.
+fn translate_server(entry: &Value, to: &McpTarget) -> (Option<Value>, Vec<String>) {
+    let Some(fields) = entry.as_object() else {
+        return (None, Vec::new());
+    };
+    let mut portable: Map<String, Value> = fields
+        .iter()
+        .filter(|(key, _)| PORTABLE_FIELDS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    // Gemini's streamable HTTP URL and Codex's header table
+    if let Some(url) = fields.get("httpUrl") {
+        portable.insert("url".to_string(), url.clone());
+    }
+    if let Some(headers) = fields.get("http_headers") {
+        portable.insert("headers".to_string(), headers.clone());
+    }
+    let dropped: Vec<String> = fields
+        .keys()
+        .filter(|key| {
+            !PORTABLE_FIELDS.contains(&key.as_str()) && !FORMAT_FIELDS.contains(&key.as_str())
+        })
+        .cloned()
+        .collect();
+
+    let remote = portable.contains_key("url");
+    if !remote && !portable.contains_key("command") {
+        return (None, dropped);
+    }
+    match &to.config_method {
+        ConfigMethod::JsonConfig {
+            type_value,
+            include_tools_field,
+            ..
+        } => {
+            if remote && to.binary_name == "gemini" {
+                let url = portable.remove("url").unwrap_or_default();
+                portable.insert("httpUrl".to_string(), url);
+            }
+            if let Some(type_value) = type_value {
+                let kind = if remote { "http" } else { type_value };
+                portable.insert("type".to_string(), json!(kind));
+            }
+            if *include_tools_field {
+                portable.insert("tools".to_string(), json!(["*"]));
+            }
+        }
+        ConfigMethod::TomlConfig { .. } => {
+            if let Some(headers) = portable.remove("headers") {
+                portable.insert("http_headers".to_string(), headers);
+            }
+        }
+    }
+    (Some(Value::Object(portable)), dropped)
+}
+
+/// Everything a migration changes, worked out before touching any file
+#[derive(Debug, Default)]
+struct Plan {
+    /// MCP server entries to add, in the destination's format
+    servers: Vec<(String, Value)>,
+    /// Final content of each project file to write
+    files: BTreeMap<PathBuf, String>,
+    /// What was carried over, for the summary
+    done: Vec<String>,
+    /// What couldn't be mapped
+    skipped: Vec<String>,
+}
+
+impl Plan {
+    /// Current content of `path`, including earlier planned changes
+    fn content(&self, path: &Path) -> Option<String> {
+        self.files
+            .get(path)
+            .cloned()
+            .or_else(|| fs::read_to_string(path).ok())
+    }
+}
+
+fn plan_servers(plan: &mut Plan, from: &McpTarget, to: &McpTarget) -> Result<()> {
+    let existing: Vec<String> = to
+        .configured_servers()?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    for (name, entry) in from.configured_servers()? {
+        if existing.contains(&name) {
+            continue;
+        }
+        match translate_server(&entry, to) {
+            (Some(translated), dropped) => {
+                if !dropped.is_empty() {
+                    plan.skipped.push(format!(
+                        "MCP server {}: {} not supported by {}",
+                        name,
+                        dropped.join(", "),
+                        to.name
+                    ));
+                }
+                plan.done.push(format!("MCP server {}", name));
+                plan.servers.push((name, translated));
+            }
+            (None, _) => plan.skipped.push(format!(
+                "MCP server {}: no command or URL to carry over",
+                name
+            )),
+        }
+    }
+    Ok(())
+}
+
+/// Rules of `from` in the project, including the legacy single-file format
+fn read_rules(root: &Path, from: &Agent) -> Result<Vec<Rule>> {
+    let mut rules = match from.rules {
+        Some((dir, extension)) => rules::read_rule_dir(&root.join(dir), extension)?,
+        None => Vec::new(),
+    };
+    if let Some(legacy) = from.legacy_rules
+        && let Ok(content) = fs::read_to_string(root.join(legacy))
+    {
+        let mut rule = Rule::parse(legacy.trim_start_matches('.'), &content);
+        rule.always = true;
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+fn plan_instructions(plan: &mut Plan, root: &Path, from: &Agent, to: &Agent) -> Result<()> {
+    let source = root.join(from.instructions);
+    let target = root.join(to.instructions);
+    if source != target
+        && let Ok(content) = fs::read_to_string(&source)
+    {
+        match plan.content(&target) {
+            None => {
+                plan.files.insert(target.clone(), content);
+                plan.done.push(format!(
+                    "{} copied to {}",
+                    from.instructions, to.instructions
+                ));
+            }
+            Some(existing) if existing.trim() == content.trim() => {}
+            Some(_) => plan.skipped.push(format!(
+                "{}: {} already exists with different content; merge them by hand",
+                from.instructions, to.instructions
+            )),
+        }
+    }
+
+    let rules = read_rules(root, from)?;
+    if rules.is_empty() {
+        return Ok(());
+    }
+    if let Some((dir, "mdc")) = to.rules {
+        for rule in &rules {
+            let path = root.join(dir).join(format!("{}.mdc", rule.name));
+            if plan.content(&path).is_none() {
+                plan.files.insert(path, rules::render_cursor(rule));
+                plan.done.push(format!("rule {}", rule.name));
+            }
+        }
+        return Ok(());
+    }
+
+    // Tools without scoped rules get them appended to their instructions,
+    // with the files each rule applies to spelled out
+    let section = rules::render_windsurf_global(&rules);
+    let existing = plan.content(&target).unwrap_or_default();
+    if existing.contains(section.trim()) {
+        return Ok(());
+    }
+    let content = if existing.trim().is_empty() {
+        section
+    } else {
+        format!("{}\n\n{}", existing.trim_end(), section)
+    };
+    plan.files.insert(target, content);
+    plan.done.push(format!(
+        "{} rule(s) appended to {}",
+        rules.len(),
+        to.instructions
+    ));
+    Ok(())
+}
+
+fn read_ignores(root: &Path, agent: &Agent) -> Vec<String> {
+    match agent.ignore {
+        Ignore::File(file) => fs::read_to_string(root.join(file))
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(str::to_string)
+            .collect(),
+        Ignore::ClaudeDeny => {
+            let settings: Value = fs::read_to_string(root.join(".claude/settings.json"))
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .unwrap_or_default();
+            settings["permissions"]["deny"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|rule| rule.as_str()?.strip_prefix("Read(")?.strip_suffix(')'))
+                .map(|pattern| pattern.trim_start_matches("./").to_string())
+                .collect()
+        }
+        Ignore::None => Vec::new(),
+    }
+}
+
+fn plan_ignores(plan: &mut Plan, root: &Path, from: &Agent, to: &Agent) -> Result<()> {
+    let patterns = read_ignores(root, from);
+    if patterns.is_empty() {
+        return Ok(());
+    }
+    let existing = read_ignores(root, to);
+    let missing: Vec<&String> = patterns.iter().filter(|p| !existing.contains(p)).collect();
+    match to.ignore {
+        _ if missing.is_empty() => {}
+        Ignore::File(file) => {
+            let path = root.join(file);
+            let mut content = plan.content(&path).unwrap_or_default();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            for pattern in &missing {
+                content.push_str(&format!("{}\n", pattern));
+            }
+            plan.files.insert(path, content);
+            plan.done.push(format!(
+                "{} ignored path(s) added to {}",
+                missing.len(),
+                file
+            ));
+        }
+        Ignore::ClaudeDeny => {
+            let path = root.join(".claude/settings.json");
+            let mut settings: Value = match plan.content(&path) {
+                Some(content) => serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse JSON in {}", path.display()))?,
+                None => json!({}),
+            };
+            if !settings["permissions"]["deny"].is_array() {
+                settings["permissions"]["deny"] = json!([]);
+            }
+            if let Some(deny) = settings["permissions"]["deny"].as_array_mut() {
+                deny.extend(
+                    missing
+                        .iter()
+                        .map(|p| json!(format!("Read(./{})", p.trim_start_matches('/')))),
+                );
+            }
+            plan.files
+                .insert(path, serde_json::to_string_pretty(&settings)? + "\n");
+            plan.done.push(format!(
+                "{} ignored path(s) added as Read deny rules in .claude/settings.json",
+                missing.len()
+            ));
+        }
+        Ignore::None => plan.skipped.push(format!(
+            "{} ignored path(s): {} has no ignore file",
+            missing.len(),
+            to.id
+        )),
+    }
+    Ok(())
+}
+
+fn plan_migration(
+    root: &Path,
+    (from, from_target): (&Agent, &McpTarget),
+    (to, to_target): (&Agent, &McpTarget),
+) -> Result<Plan> {
+    let mut plan = Plan::default();
+    plan_servers(&mut plan, from_target, to_target)?;
+    plan_instructions(&mut plan, root, from, to)?;
+    plan_ignores(&mut plan, root, from, to)?;
+    Ok(plan)
+}
+
+/// Handle `migrate --from <tool> --to <tool>` command
+pub fn handle_migrate(from: &str, to: &str, dry_run: bool) -> Result<Outcome> {
+    let from = find(from)?;
+    let to = find(to)?;
+    if from.0.id == to.0.id {
+        return Err(exit::usage("--from and --to are the same tool"));
+    }
+    let root = env::current_dir().context("Failed to get current directory")?;
+    let plan = plan_migration(&root, (&from.0, &from.1), (&to.0, &to.1))?;
+
+    println!(
+        "{} {} → {} (MCP from user configs, project files from {})",
+        "Migrating".bold(),
+        from.1.name,
+        to.1.name,
+        root.display()
+    );
+    if plan.done.is_empty() && plan.skipped.is_empty() {
+        println!("  {}", "(nothing to migrate)".muted());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+    if !dry_run {
+        for (name, entry) in &plan.servers {
+            if let Err(e) = to.1.add_server_entry(name, entry) {
+                println!("  {} MCP server {}: {:#}", "✗".error(), name, e);
+                failed += 1;
+            }
+        }
+        for (path, content) in &plan.files {
+            logging::write_file(path, content)?;
+        }
+    }
+    let marker = if dry_run { "~".info() } else { "✓".success() };
+    for item in &plan.done {
+        println!("  {} {}", marker, item);
+    }
+    if !plan.skipped.is_empty() {
+        println!();
+        println!("{}", "Not migrated:".bold());
+        for item in &plan.skipped {
+            println!("  {} {}", "!".warning(), item);
+        }
+    }
+    if dry_run {
+        println!();
+        println!("{} Dry run; nothing was written", "→".info());
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(binary_name: &'static str) -> McpTarget {
+        targets::catalog()
+            .into_iter()
+            .find(|t| t.binary_name == binary_name)
+            .unwrap()
+    }
+
+    #[test]
+    fn translates_server_entries_between_formats() {
+        let codex_entry = json!({
+            "command": "npx",
+            "args": ["-y", "@acme/mcp"],
+            "env": { "ACME_TOKEN": "x" },
+            "startup_timeout_sec": 30
+        });
+        let (entry, dropped) = translate_server(&codex_entry, &target("claude"));
+        assert_eq!(
+            entry.unwrap(),
+            json!({
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "@acme/mcp"],
+                "env": { "ACME_TOKEN": "x" }
+            })
+        );
+        assert_eq!(dropped, vec!["startup_timeout_sec".to_string()]);
+
+        let gemini_entry = json!({ "httpUrl": "https://mcp.example.com", "trust": true });
+        let (entry, _) = translate_server(&gemini_entry, &target("copilot"));
+        assert_eq!(
+            entry.unwrap(),
+            json!({ "type": "http", "url": "https://mcp.example.com", "tools": ["*"] })
+        );
+        assert_eq!(
+            translate_server(&json!({"disabled": true}), &target("codex")).0,
+            None
+        );
+    }
+
+    #[test]
+    fn moves_cursor_rules_and_ignores_to_claude() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        fs::create_dir_all(root.join(".cursor/rules")).unwrap();
+        fs::write(
+            root.join(".cursor/rules/tests.mdc"),
+            "---\nglobs: **/*_test.go\nalwaysApply: false\n---\n\nUse table tests.",
+        )
+        .unwrap();
+        fs::write(root.join("AGENTS.md"), "# Project\n").unwrap();
+        fs::write(root.join(".cursorignore"), "# secrets\n.env\ndist/\n").unwrap();
+
+        let agents = agents();
+        let cursor = agents.iter().find(|a| a.id == "cursor").unwrap();
+        let claude = agents.iter().find(|a| a.id == "claude").unwrap();
+        let mut plan = Plan::default();
+        plan_instructions(&mut plan, root, cursor, claude).unwrap();
+        plan_ignores(&mut plan, root, cursor, claude).unwrap();
+
+        assert_eq!(
+            plan.files[&root.join("CLAUDE.md")],
+            "# Project\n\n# tests\n\nApplies to **/*_test.go\n\nUse table tests.\n"
+        );
+        let settings: Value =
+            serde_json::from_str(&plan.files[&root.join(".claude/settings.json")]).unwrap();
+        assert_eq!(
+            settings["permissions"]["deny"],
+            json!(["Read(./.env)", "Read(./dist/)"])
+        );
+        assert!(plan.skipped.is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde_json::{Value, json};
+
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::paths;
+use crate::theme::Themed;
+
+/// Where a tool keeps its default model
+#[derive(Debug, Clone)]
+pub enum ModelFile {
+    /// JSON settings file; `pointer` is a JSON pointer like "/model/name"
+    Json {
+        path: PathBuf,
+        pointer: &'static str,
+    },
+    /// Top-level key in a TOML config file
+    Toml { path: PathBuf, key: &'static str },
+}
+
+/// A tool's default-model setting
+#[derive(Debug, Clone)]
+pub struct ModelSetting {
+    pub tool: &'static str,
+    pub binary_name: &'static str,
+    pub file: ModelFile,
+    /// Providers whose models this tool can run
+    pub providers: &'static [&'static str],
+    /// Model ids are written as "provider/model" (OpenCode)
+    pub qualified: bool,
+}
+
+impl ModelSetting {
+    pub fn path(&self) -> &Path {
+        match &self.file {
+            ModelFile::Json { path, .. } | ModelFile::Toml { path, .. } => path,
+        }
+    }
+
+    /// Tools are considered present when their config directory exists
+    pub fn is_installed(&self) -> bool {
+        self.path().parent().is_some_and(|p| p.exists())
+    }
+
+    fn accepts(&self, provider: &str) -> bool {
+        self.providers.contains(&provider)
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.tool.eq_ignore_ascii_case(name) || self.binary_name.eq_ignore_ascii_case(name)
+    }
+
+    /// The model as this tool expects it written
+    fn format_model(&self, model: &str) -> String {
+        if self.qualified
+            && !model.contains('/')
+            && let Some(provider) = provider_for(model)
+        {
+            format!("{}/{}", provider, model)
+        } else {
+            model.to_string()
+        }
+    }
+
+    /// Currently configured model, `None` when the tool uses its built-in default
+    pub fn current(&self) -> Result<Option<String>> {
+        let path = self.path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        match &self.file {
+            ModelFile::Json { pointer, .. } => {
+                let json: Value = serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse JSON in {}", path.display()))?;
+                Ok(json
+                    .pointer(pointer)
+                    .and_then(Value::as_str)
+                    .map(str::to_string))
+            }
+            ModelFile::Toml { key, .. } => {
+                let doc: toml_edit::DocumentMut = content
+                    .parse()
+                    .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
+                Ok(doc
+                    .get(key)
+                    .and_then(|item| item.as_str())
+                    .map(str::to_string))
+            }
+        }
+    }
+
+    /// Write `model` as the default model, keeping the rest of the file intact
+    pub fn set(&self, model: &str) -> Result<()> {
+        let path = self.path();
+        let original = if path.exists() {
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+        } else {
+            String::new()
+        };
+
+        let updated = match &self.file {
+            ModelFile::Json { pointer, .. } => {
+                let mut json: Value = if original.trim().is_empty() {
+                    json!({})
+                } else {
+                    serde_json::from_str(&original)
+                        .with_context(|| format!("Failed to parse JSON in {}", path.display()))?
+                };
+                set_pointer(&mut json, pointer, model);
+                format!("{}\n", serde_json::to_string_pretty(&json)?)
+            }
+            ModelFile::Toml { key, .. } => {
+                let mut doc: toml_edit::DocumentMut = original
+                    .parse()
+                    .with_context(|| format!("Failed to parse TOML in {}", path.display()))?;
+                doc[key] = toml_edit::value(model);
+                doc.to_string()
+            }
+        };
+
+        logging::write_config(path, &original, &updated)
+    }
+}
+
+/// Set a string at a JSON pointer, creating intermediate objects
+fn set_pointer(json: &mut Value, pointer: &str, model: &str) {
+    let mut current = json;
+    for part in pointer.trim_start_matches('/').split('/') {
+        if !current.is_object() {
+            *current = json!({});
+        }
+        current = current
+            .as_object_mut()
+            .unwrap()
+            .entry(part)
+            .or_insert(Value::Null);
+    }
+    *current = Value::String(model.to_string());
+}
+
+/// Guess the provider of a model id from its name
+pub fn provider_for(model: &str) -> Option<&'static str> {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    let families: &[(&str, &[&str])] = &[
+        ("anthropic", &["claude", "opus", "sonnet", "haiku"]),
+        ("openai", &["gpt", "o1", "o3", "o4", "codex"]),
+        ("google", &["gemini"]),
+        (
+            "mistral",
+            &["mistral", "devstral", "codestral", "magistral"],
+        ),
+    ];
+    if let Some((provider, _)) = model.split_once('/') {
+        return families
+            .iter()
+            .map(|(p, _)| *p)
+            .find(|p| p.eq_ignore_ascii_case(provider));
+    }
+    families
+        .iter()
+        .find(|(_, prefixes)| prefixes.iter().any(|prefix| name.starts_with(prefix)))
+        .map(|(provider, _)| *provider)
+}
+
+/// Returns the default-model setting of every supported tool
+pub fn catalog() -> Vec<ModelSetting> {
+    let home = paths::home();
+    vec![
+        ModelSetting {
+            tool: "Claude Code",
+            binary_name: "claude",
+            file: ModelFile::Json {
+                path: home.join(".claude/settings.json"),
+                pointer: "/model",
+            },
+            providers: &["anthropic"],
+            qualified: false,
+        },
+        ModelSetting {
+            tool: "Codex CLI",
+            binary_name: "codex",
+            file: ModelFile::Toml {
+                path: home.join(".codex/config.toml"),
+                key: "model",
+            },
+            providers: &["openai"],
+            qualified: false,
+        },
+        ModelSetting {
+            tool: "Gemini CLI",
+            binary_name: "gemini",
+            file: ModelFile::Json {
+                path: home.join(".gemini/settings.json"),
+                pointer: "/model/name",
+            },
+            providers: &["google"],
+            qualified: false,
+        },
+        ModelSetting {
+            tool: "OpenCode",
+            binary_name: "opencode",
+            file: ModelFile::Json {
+                path: paths::config_home().join("opencode/opencode.json"),
+                pointer: "/model",
+            },
+            providers: &["anthropic", "openai", "google", "mistral"],
+            qualified: true,
+        },
+        ModelSetting {
+            tool: "Mistral Vibe",
+            binary_name: "vibe",
+            file: ModelFile::Toml {
+                path: home.join(".vibe/config.toml"),
+                key: "active_model",
+            },
+            providers: &["mistral"],
+            qualified: false,
+        },
+    ]
+}
+
+/// Handle `models list` command
+pub fn handle_list() -> Result<()> {
+    let settings = catalog();
+    let width = settings.iter().map(|s| s.tool.len()).max().unwrap_or(0);
+
+    for setting in &settings {
+        let name = format!("{:<width$}", setting.tool, width = width);
+        if !setting.is_installed() {
+            println!("  {}  {}", name.muted(), "not installed".muted());
+            continue;
+        }
+        let model = match setting.current() {
+            Ok(Some(model)) => model.bold().to_string(),
+            Ok(None) => "(default)".muted().to_string(),
+            Err(e) => format!("{} {:#}", "✗".error(), e),
+        };
+        println!(
+            "  {}  {}  {}",
+            name,
+            model,
+            setting.path().display().to_string().muted()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `models set <model>` command
+///
+/// Without `--tool` the model is written to every installed tool that can run
+/// models from its provider.
+pub fn handle_set(model: &str, tool: Option<&str>) -> Result<Outcome> {
+    let settings = catalog();
+    let selected: Vec<&ModelSetting> = match tool {
+        Some(name) => {
+            let setting = settings.iter().find(|s| s.matches(name)).ok_or_else(|| {
+                let names: Vec<_> = settings.iter().map(|s| s.binary_name).collect();
+                exit::usage(format!(
+                    "Unknown tool '{}'. Tools with a model setting: {}",
+                    name,
+                    names.join(", ")
+                ))
+            })?;
+            vec![setting]
+        }
+        None => {
+            let provider = provider_for(model).ok_or_else(|| {
+                exit::usage(format!(
+                    "Can't tell which provider '{}' belongs to; pass --tool to pick the tool",
+                    model
+                ))
+            })?;
+            settings
+                .iter()
+                .filter(|s| s.accepts(provider) && s.is_installed())
+                .collect()
+        }
+    };
+
+    if selected.is_empty() {
+        println!("{} No installed tool can use {}", "!".warning(), model);
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut changed = 0;
+    let mut failed = 0;
+    for setting in selected {
+        let value = setting.format_model(model);
+        if setting.current().ok().flatten().as_deref() == Some(value.as_str()) {
+            println!("{} {} already uses {}", "✓".success(), setting.tool, value);
+            continue;
+        }
+        match setting.set(&value) {
+            Ok(()) => {
+                println!(
+                    "{} {} → {} {}",
+                    "✓".success(),
+                    setting.tool,
+                    value.bold(),
+                    format!("({})", setting.path().display()).muted()
+                );
+                changed += 1;
+            }
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), setting.tool, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed == 0 && changed == 0 {
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_provider_from_model_name() {
+        assert_eq!(provider_for("claude-opus-4-5"), Some("anthropic"));
+        assert_eq!(provider_for("opus"), Some("anthropic"));
+        assert_eq!(provider_for("gpt-5-codex"), Some("openai"));
+        assert_eq!(provider_for("gemini-2.5-pro"), Some("google"));
+        assert_eq!(provider_for("openai/gpt-5"), Some("openai"));
+        assert_eq!(provider_for("llama-3"), None);
+    }
+
+    #[test]
+    fn sets_nested_json_and_toml_models() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("settings.json");
+        fs::write(
+            &json_path,
+            r#"{"model": "gemini-1.5-pro", "theme": "dark"}"#,
+        )
+        .unwrap();
+        let gemini = ModelSetting {
+            tool: "Gemini CLI",
+            binary_name: "gemini",
+            file: ModelFile::Json {
+                path: json_path.clone(),
+                pointer: "/model/name",
+            },
+            providers: &["google"],
+            qualified: false,
+        };
+        gemini.set("gemini-2.5-pro").unwrap();
+        assert_eq!(gemini.current().unwrap().as_deref(), Some("gemini-2.5-pro"));
+        let json: Value = serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json["theme"], "dark");
+
+        let toml_path = dir.path().join("config.toml");
+        fs::write(
+            &toml_path,
+            "# keep me\n[mcp_servers.linear]\ncommand = \"npx\"\n",
+        )
+        .unwrap();
+        let codex = ModelSetting {
+            tool: "Codex CLI",
+            binary_name: "codex",
+            file: ModelFile::Toml {
+                path: toml_path.clone(),
+                key: "model",
+            },
+            providers: &["openai"],
+            qualified: false,
+        };
+        assert_eq!(codex.current().unwrap(), None);
+        codex.set("gpt-5").unwrap();
+        assert_eq!(codex.current().unwrap().as_deref(), Some("gpt-5"));
+        assert!(
+            fs::read_to_string(&toml_path)
+                .unwrap()
+                .contains("# keep me")
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Node version managers and where `npm install -g` puts tools.
+//!
+//! Under nvm, fnm or mise the global npm prefix belongs to the active Node
+//! version, so npm-installed tools disappear when Node is upgraded or switched.
+//! With `[npm] stable_prefix = true`, ai-cli installs them into
+//! `~/.local/share/ai-cli/npm` instead, which outlives every Node version.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config;
+use crate::paths;
+use crate::theme::Themed;
+use crate::tools::{Tool, command_output};
+
+/// A tool that keeps one Node installation per version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Manager {
+    Nvm,
+    Fnm,
+    Mise,
+}
+
+impl Manager {
+    pub fn name(self) -> &'static str {
+        match self {
+            Manager::Nvm => "nvm",
+            Manager::Fnm => "fnm",
+            Manager::Mise => "mise",
+        }
+    }
+}
+
+/// The version manager owning `prefix`, if it is a per-version prefix
+pub fn manager_of(prefix: &Path) -> Option<Manager> {
+    let prefix = prefix.to_string_lossy().replace('\\', "/");
+    if prefix.contains("/.nvm/versions/node/") {
+        Some(Manager::Nvm)
+    } else if prefix.contains("/fnm/node-versions/") || prefix.contains("/fnm_multishells/") {
+        Some(Manager::Fnm)
+    } else if prefix.contains("/mise/installs/node/") {
+        Some(Manager::Mise)
+    } else {
+        None
+    }
+}
+
+/// npm's current global prefix
+pub fn global_prefix() -> Option<PathBuf> {
+    command_output("npm", &["prefix", "-g"]).map(|p| PathBuf::from(p.trim()))
+}
+
+/// Prefix npm-installed tools go into when `[npm] stable_prefix` is set
+pub fn stable_prefix() -> PathBuf {
+    paths::data_home().join("ai-cli").join("npm")
+}
+
+fn use_stable_prefix() -> bool {
+    config::load().is_ok_and(|c| c.npm.stable_prefix)
+}
+
+/// `npm <subcommand> -g`, into the stable prefix when configured
+pub fn npm_global(subcommand: &str) -> Command {
+    let mut command = Command::new("npm");
+    command.args([subcommand, "-g"]);
+    if use_stable_prefix() {
+        command.arg("--prefix").arg(stable_prefix());
+    } else {
+        warn_per_version_prefix();
+    }
+    command
+}
+
+/// Names of the packages installed with `npm install -g`
+pub fn global_packages() -> Vec<String> {
+    let Ok(output) = npm_global("ls").args(["--depth=0", "--json"]).output() else {
+        return Vec::new();
+    };
+    // npm exits non-zero on problems like invalid peers but still lists everything
+    serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .ok()
+        .and_then(|tree| tree["dependencies"].as_object().cloned())
+        .map(|deps| deps.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// A global npm package no installed tool uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orphan {
+    pub package: String,
+    /// Name of the tool the package belongs to
+    pub tool: String,
+    pub reason: &'static str,
+}
+
+/// Packages among `installed` left behind by `tools`, given for each tool
+/// whether it is installed the way ai-cli installs it now
+pub fn orphaned_packages(tools: &[(Tool, bool)], installed: &[String]) -> Vec<Orphan> {
+    let mut orphans = Vec::new();
+    for (tool, current) in tools {
+        for package in tool.npm_packages() {
+            if !installed.iter().any(|p| p == package) {
+                continue;
+            }
+            let legacy = tool.legacy_npm_packages.iter().any(|p| p == package);
+            let reason = match (legacy, current) {
+                (true, true) => "replaced by the native install",
+                (false, false) => "its command is gone",
+                _ => continue,
+            };
+            orphans.push(Orphan {
+                package: package.to_string(),
+                tool: tool.name.clone(),
+                reason,
+            });
+        }
+    }
+    orphans
+}
+
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Point out once per run that global installs land in a per-version prefix
+fn warn_per_version_prefix() {
+    let Some(prefix) = global_prefix() else {
+        return;
+    };
+    let Some(manager) = manager_of(&prefix) else {
+        return;
+    };
+    if WARNED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    println!(
+        "{} npm installs into {}, which belongs to the Node version {} has active; \
+         tools installed there are gone after switching Node versions",
+        "!".warning(),
+        prefix.display(),
+        manager.name()
+    );
+    println!(
+        "  {}",
+        format!(
+            "Set `[npm] stable_prefix = true` in config.toml to install into {}",
+            stable_prefix().display()
+        )
+        .muted()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_per_version_prefixes() {
+        assert_eq!(
+            manager_of(Path::new("/home/me/.nvm/versions/node/v22.11.0")),
+            Some(Manager::Nvm)
+        );
+        assert_eq!(
+            manager_of(Path::new(
+                "/home/me/.local/share/fnm/node-versions/v20.18.0/installation"
+            )),
+            Some(Manager::Fnm)
+        );
+        assert_eq!(
+            manager_of(Path::new("/home/me/.local/share/mise/installs/node/22")),
+            Some(Manager::Mise)
+        );
+        assert_eq!(manager_of(Path::new("/usr/local")), None);
+    }
+
+    #[test]
+    fn finds_packages_no_tool_uses() {
+        use crate::tools::{InstallMethod, claude_tool};
+
+        let codex = Tool::new(
+            "Codex CLI",
+            InstallMethod::Npm("@openai/codex".into()),
+            vec![],
+        );
+        let installed = [
+            "@anthropic-ai/claude-code".to_string(),
+            "@openai/codex".to_string(),
+            "typescript".to_string(),
+        ];
+
+        let orphans =
+            orphaned_packages(&[(claude_tool(), true), (codex.clone(), false)], &installed);
+        let packages: Vec<&str> = orphans.iter().map(|o| o.package.as_str()).collect();
+        assert_eq!(packages, ["@anthropic-ai/claude-code", "@openai/codex"]);
+
+        // Claude Code still running from its npm package, Codex in use
+        assert!(orphaned_packages(&[(claude_tool(), false), (codex, true)], &installed).is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Webhook notifications for fleet events.
+//!
+//! When `[notify] webhook` is set, `apps check`, `mcp doctor`, `doctor` and the
+//! background update check POST what they find. The payload carries a `text`
+//! field, so Slack incoming webhooks can take it as is, next to structured
+//! fields for other receivers. An event is only sent again once its items change.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::http;
+use crate::logging;
+use crate::paths;
+use crate::tools::command_output;
+
+/// Kinds of events a webhook can receive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Event {
+    ToolOutdated,
+    ConfigDrift,
+    PolicyViolation,
+    SkillsAdded,
+}
+
+impl Event {
+    fn as_str(self) -> &'static str {
+        match self {
+            Event::ToolOutdated => "tool-outdated",
+            Event::ConfigDrift => "config-drift",
+            Event::PolicyViolation => "policy-violation",
+            Event::SkillsAdded => "skills-added",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Event::ToolOutdated => "Outdated AI tools",
+            Event::ConfigDrift => "MCP config drift detected",
+            Event::PolicyViolation => "Organization policy violations",
+            Event::SkillsAdded => "New skills from subscribed repositories",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Payload<'a> {
+    /// Human-readable summary, shown by Slack
+    text: String,
+    event: Event,
+    /// Command that found the event, e.g. `apps check`
+    source: &'a str,
+    host: String,
+    ai_cli_version: &'static str,
+    items: &'a [String],
+}
+
+fn payload<'a>(event: Event, source: &'a str, host: String, items: &'a [String]) -> Payload<'a> {
+    let mut text = format!("*{}* on {} (`ai-cli {}`)", event.title(), host, source);
+    for item in items {
+        text.push_str(&format!("\n• {}", item));
+    }
+    Payload {
+        text,
+        event,
+        source,
+        host,
+        ai_cli_version: env!("CARGO_PKG_VERSION"),
+        items,
+    }
+}
+
+/// Items last sent per event, so unchanged state isn't reported again
+fn sent_path() -> PathBuf {
+    paths::state_home().join("ai-cli").join("notify-sent.json")
+}
+
+fn load_sent() -> BTreeMap<Event, Vec<String>> {
+    fs::read_to_string(sent_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_sent(sent: &BTreeMap<Event, Vec<String>>) -> Result<()> {
+    let path = sent_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&path, serde_json::to_string(sent)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+async fn post(url: &str, payload: &Payload<'_>) -> Result<()> {
+    let request = http::client()
+        .post(url)
+        .timeout(Duration::from_secs(5))
+        .json(payload);
+    http::send(request)
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to post to webhook {}", url))?;
+    Ok(())
+}
+
+/// Report the current `items` of `event` to the configured webhook
+///
+/// Empty `items` mean the problem is gone; the next occurrence is sent again.
+/// Failures are logged and never affect the command.
+pub async fn send(event: Event, source: &str, items: &[String]) {
+    let Some(notify) = config::load().ok().map(|c| c.notify) else {
+        return;
+    };
+    let Some(url) = notify.webhook else {
+        return;
+    };
+    if !notify.events.is_empty() && !notify.events.contains(&event) {
+        return;
+    }
+
+    let mut sent = load_sent();
+    if sent.get(&event).map(Vec::as_slice).unwrap_or_default() == items {
+        return;
+    }
+    if !items.is_empty() {
+        let host = command_output("hostname", &[]).unwrap_or_else(|| "unknown host".into());
+        let payload = payload(event, source, host.trim().to_string(), items);
+        if let Err(e) = post(&url, &payload).await {
+            logging::line(&format!("notify: {:#}", e));
+            return;
+        }
+        logging::line(&format!("notify: sent {} to {}", event.as_str(), url));
+    }
+    sent.insert(event, items.to_vec());
+    if let Err(e) = save_sent(&sent) {
+        logging::line(&format!("notify: {:#}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn posts_slack_compatible_payload() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/hook")
+                    .json_body_includes(r#"{"event":"tool-outdated","source":"apps check"}"#)
+                    .json_body_includes(
+                        r#"{"text":"*Outdated AI tools* on box (`ai-cli apps check`)\n• Codex CLI 0.49.0 → 0.50.0"}"#,
+                    );
+                then.status(200);
+            })
+            .await;
+
+        let items = vec!["Codex CLI 0.49.0 → 0.50.0".to_string()];
+        let payload = payload(Event::ToolOutdated, "apps check", "box".into(), &items);
+        post(&server.url("/hook"), &payload).await.unwrap();
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,204 @@
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+use colored::*;
+use inquire::MultiSelect;
+use toml_edit::{Array, DocumentMut, Item, Table, value};
+
+use crate::config;
+use crate::mcp::{self, servers, targets};
+use crate::output;
+use crate::prompt;
+use crate::skills::actions::install_from_repo;
+use crate::skills::agents;
+use crate::theme::Themed;
+use crate::tools;
+
+/// Skills offered during setup
+const STARTER_SKILLS: &str = "anthropics/skills";
+
+/// No config yet and a person at the keyboard
+pub fn is_first_run() -> bool {
+    !config::config_path().exists()
+        && std::io::stdin().is_terminal()
+        && !output::is_plain()
+        && !prompt::is_non_interactive()
+}
+
+/// Handle `onboard` command, also offered automatically on first run
+pub fn handle_onboard() -> Result<()> {
+    output::header("👋", "AI CLI - Setup");
+
+    let installed_tools: Vec<_> = tools::catalog()
+        .into_iter()
+        .filter(|t| t.is_installed())
+        .collect();
+    if installed_tools.is_empty() {
+        println!("{} No AI CLI tools found yet", "!".warning());
+        println!(
+            "  {} Install some later with `ai-cli apps install`",
+            "→".info()
+        );
+    } else {
+        println!("{}", "Found tools:".bold());
+        for tool in &installed_tools {
+            println!("  {} {}", "✓".success(), tool.name);
+        }
+    }
+    println!();
+
+    // MCP servers to enable in every installed tool
+    let mcp_targets: Vec<_> = targets::catalog()
+        .into_iter()
+        .filter(|t| t.is_installed())
+        .collect();
+    let mut selected_servers = Vec::new();
+    if !mcp_targets.is_empty() {
+        prompt::require_interactive("run `ai-cli onboard` from a terminal")?;
+        let options: Vec<String> = servers::catalog()
+            .iter()
+            .map(|s| format!("{} - {}", s.id, s.description))
+            .collect();
+        let selections = MultiSelect::new("MCP servers to enable everywhere:", options)
+            .with_help_message("↑↓ to move, space to select, enter to confirm")
+            .prompt()
+            .context("Setup cancelled")?;
+        for selection in selections {
+            if let Some(server) = servers::catalog()
+                .into_iter()
+                .find(|s| selection.starts_with(&format!("{} - ", s.id)))
+            {
+                selected_servers.push(server);
+            }
+        }
+
+        let configured = mcp::configure_all(&selected_servers);
+        selected_servers.retain(|s| configured.contains_key(&s.id));
+        for server in configured.values() {
+            for target in &mcp_targets {
+                match target.enable_server(server) {
+                    Ok(_) => println!("{} Enabled {} in {}", "✓".success(), server.id, target.name),
+                    Err(e) => println!(
+                        "{} Failed to enable {} in {}: {}",
+                        "✗".error(),
+                        server.id,
+                        target.name,
+                        e
+                    ),
+                }
+            }
+        }
+        println!();
+    }
+
+    // Starter skill pack
+    let skill_agents: Vec<_> = agents::catalog()
+        .into_iter()
+        .filter(|a| a.is_installed())
+        .collect();
+    let mut starter_installed = false;
+    if !skill_agents.is_empty()
+        && prompt::confirm(&format!(
+            "Install the starter skills from {}?",
+            STARTER_SKILLS
+        ))?
+    {
+        match install_from_repo(STARTER_SKILLS, &skill_agents, None) {
+            Ok(()) => starter_installed = true,
+            Err(e) => println!("{} Failed to install starter skills: {}", "✗".error(), e),
+        }
+        println!();
+    }
+
+    let server_ids: Vec<&str> = selected_servers.iter().map(|s| s.id.as_str()).collect();
+    let skills: Vec<&str> = if starter_installed {
+        vec![STARTER_SKILLS]
+    } else {
+        Vec::new()
+    };
+    write_config(&server_ids, &skills)?;
+
+    println!(
+        "{} Wrote {}",
+        "✓".success(),
+        config::config_path().display()
+    );
+    println!(
+        "  {} Your choices are saved as the 'default' profile (`ai-cli profile show default`)",
+        "→".info()
+    );
+    println!();
+    Ok(())
+}
+
+fn new_config() -> DocumentMut {
+    let mut doc = DocumentMut::new();
+    doc.decor_mut()
+        .set_prefix("# ai-cli configuration, created by `ai-cli onboard`\n\n");
+    doc["update_check"] = value(true);
+    doc
+}
+
+/// Write the setup choices as the active `default` profile
+fn write_config(server_ids: &[&str], skills: &[&str]) -> Result<()> {
+    save(&default_profile_config(server_ids, skills))
+}
+
+fn default_profile_config(server_ids: &[&str], skills: &[&str]) -> DocumentMut {
+    let mut doc = new_config();
+    doc["active_profile"] = value("default");
+
+    let mut profile = Table::new();
+    profile["mcp_servers"] = value(server_ids.iter().copied().collect::<Array>());
+    profile["skills"] = value(skills.iter().copied().collect::<Array>());
+    let mut profiles = Table::new();
+    profiles.set_implicit(true);
+    profiles["default"] = Item::Table(profile);
+    doc["profiles"] = Item::Table(profiles);
+    doc
+}
+
+fn save(doc: &DocumentMut) -> Result<()> {
+    let path = config::config_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(&path, doc.to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Offer setup on first run; declining writes an empty config so we don't ask again
+pub fn offer() -> Result<()> {
+    println!(
+        "{} No ai-cli config found at {}",
+        "→".info(),
+        config::config_path().display()
+    );
+    if prompt::confirm("Run the guided setup now?")? {
+        handle_onboard()?;
+    } else {
+        save(&new_config())?;
+        println!("  {} Run `ai-cli onboard` any time", "→".info());
+        println!();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn setup_choices_become_the_active_default_profile() {
+        let doc = default_profile_config(&["linear"], &[STARTER_SKILLS]);
+        let config: Config = toml::from_str(&doc.to_string()).unwrap();
+
+        assert_eq!(config.active_profile.as_deref(), Some("default"));
+        assert_eq!(config.update_check, Some(true));
+        let profile = &config.profiles["default"];
+        assert_eq!(profile.mcp_servers, vec!["linear"]);
+        assert_eq!(profile.skills, vec![STARTER_SKILLS]);
+    }
+}
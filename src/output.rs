@@ -0,0 +1,55 @@
+use std::env;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::theme::Themed;
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Switch to plain output when forced or when stdout isn't a terminal (CI, pipes, `| tee`)
+pub fn init(force_plain: bool) {
+    let plain = force_plain || !std::io::stdout().is_terminal();
+    PLAIN.store(plain, Ordering::Relaxed);
+    if plain && env::var_os("CLICOLOR_FORCE").is_none_or(|v| v == "0") {
+        colored::control::set_override(false);
+    }
+}
+
+/// Line-oriented output without colors, spinners or header art
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Print a command header like `🤖 AI CLI - Tools` with an underline
+pub fn header(icon: &str, title: &str) {
+    if is_plain() {
+        println!("{}", title);
+        println!();
+        return;
+    }
+    println!("\n{}", format!("{} {}", icon, title).accent().bold());
+    // The icon renders two columns wide
+    println!("{}\n", "=".repeat(title.chars().count() + 3).accent());
+}
+
+/// A spinner showing `message`; in plain mode the message is printed once instead
+pub fn spinner(message: &str) -> ProgressBar {
+    if is_plain() {
+        println!("{}", message);
+        return ProgressBar::hidden();
+    }
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap(),
+    );
+    spinner.enable_steady_tick(Duration::from_millis(80));
+    spinner.set_message(message.to_string());
+    spinner
+}
@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::journal;
+use crate::logging;
+use crate::paths;
+use crate::settings::read_json;
+use crate::skills::actions::clone_repo;
+use crate::theme::Themed;
+
+/// Where style packs keep their styles, in priority order
+const DISCOVERY_PATHS: &[&str] = &[".claude/output-styles", "output-styles", "styles"];
+
+/// A Claude Code output style (`<name>.md` with `name`/`description` frontmatter)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputStyle {
+    /// File name without `.md`
+    pub file_name: String,
+    /// Name shown in Claude Code's `/output-style` menu
+    pub name: String,
+    pub description: Option<String>,
+    pub path: PathBuf,
+}
+
+/// Claude Code's output styles directory
+pub fn styles_dir(scope: &Scope) -> PathBuf {
+    match scope {
+        Scope::Global => paths::home().join(".claude/output-styles"),
+        Scope::Project(root) => root.join(".claude/output-styles"),
+    }
+}
+
+fn frontmatter_value(content: &str, key: &str) -> Option<String> {
+    let rest = content.strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    rest[..end].lines().find_map(|line| {
+        let value = line.trim().strip_prefix(key)?.strip_prefix(':')?;
+        Some(value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn parse_style(path: &Path) -> Option<OutputStyle> {
+    let file_name = path.file_stem()?.to_string_lossy().to_string();
+    let content = fs::read_to_string(path).ok()?;
+    Some(OutputStyle {
+        name: frontmatter_value(&content, "name").unwrap_or_else(|| file_name.clone()),
+        description: frontmatter_value(&content, "description"),
+        file_name,
+        path: path.to_path_buf(),
+    })
+}
+
+/// List the styles in a directory
+pub fn list_styles(dir: &Path) -> Vec<OutputStyle> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut styles: Vec<OutputStyle> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension().is_some_and(|e| e == "md")
+                && !p
+                    .file_name()
+                    .is_some_and(|n| n.eq_ignore_ascii_case("README.md"))
+        })
+        .filter_map(|p| parse_style(&p))
+        .collect();
+    styles.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    styles
+}
+
+/// Discover styles in a cloned repository
+pub fn discover_styles(repo_path: &Path) -> Vec<OutputStyle> {
+    DISCOVERY_PATHS
+        .iter()
+        .map(|subpath| list_styles(&repo_path.join(subpath)))
+        .find(|styles| !styles.is_empty())
+        .unwrap_or_default()
+}
+
+/// The style selected in Claude Code's settings, if any
+fn active_style(scope: &Scope) -> Option<String> {
+    let files = match scope {
+        Scope::Global => vec![paths::home().join(".claude/settings.json")],
+        Scope::Project(root) => vec![
+            root.join(".claude/settings.local.json"),
+            root.join(".claude/settings.json"),
+        ],
+    };
+    files.iter().find_map(|path| {
+        read_json(path).ok()?["outputStyle"]
+            .as_str()
+            .map(str::to_string)
+    })
+}
+
+/// Handle `output-styles list` command
+pub fn handle_list(scope: &Scope) -> Result<()> {
+    let dir = styles_dir(scope);
+    let styles = list_styles(&dir);
+    println!(
+        "{} {}",
+        "Output styles".bold(),
+        dir.display().to_string().muted()
+    );
+    if styles.is_empty() {
+        println!("  {}", "(no output styles installed)".muted());
+        return Ok(());
+    }
+
+    let active = active_style(scope);
+    for style in &styles {
+        let marker = if active.as_deref() == Some(style.name.as_str()) {
+            "*".accent()
+        } else {
+            "-".info()
+        };
+        print!("  {} {}", marker, style.name);
+        if let Some(description) = &style.description {
+            print!(" - {}", description.muted());
+        }
+        println!();
+    }
+    if let Some(active) = active {
+        println!();
+        println!("{} Active: {}", "→".info(), active);
+    }
+    Ok(())
+}
+
+/// Handle `output-styles install <repo>` command
+pub fn handle_install(repo: &str, scope: &Scope) -> Result<Outcome> {
+    let temp_dir = clone_repo(repo)?;
+    let styles = discover_styles(temp_dir.path());
+    if styles.is_empty() {
+        anyhow::bail!(
+            "No output styles found in repository (expected .md files in output-styles/ or .claude/output-styles/)"
+        );
+    }
+
+    println!("{} Found {} output style(s):", "->".info(), styles.len());
+    let dir = styles_dir(scope);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    let mut failed = 0;
+    for style in &styles {
+        let target = dir.join(format!("{}.md", style.file_name));
+        match fs::copy(&style.path, &target) {
+            Ok(_) => {
+                logging::line(&format!("installed output style {}", target.display()));
+                println!("  {} {}", "✓".success(), style.name);
+            }
+            Err(e) => {
+                println!("  {} {}: {}", "✗".error(), style.name, e);
+                failed += 1;
+            }
+        }
+    }
+    println!();
+    println!("{} Pick one with /output-style in Claude Code", "→".info());
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `output-styles remove <name>` command
+pub fn handle_remove(name: &str, scope: &Scope) -> Result<Outcome> {
+    let dir = styles_dir(scope);
+    let style = list_styles(&dir)
+        .into_iter()
+        .find(|s| s.file_name == name || s.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            exit::usage(format!(
+                "Output style '{}' not found in {}",
+                name,
+                dir.display()
+            ))
+        })?;
+
+    journal::record_file(&style.path);
+    fs::remove_file(&style.path)
+        .with_context(|| format!("Failed to remove {}", style.path.display()))?;
+    logging::line(&format!("removed output style {}", style.path.display()));
+    println!("{} Removed output style {}", "✓".success(), style.name);
+    if active_style(scope).as_deref() == Some(style.name.as_str()) {
+        println!(
+            "{} It is still selected in Claude Code's settings; pick another with /output-style",
+            "!".warning()
+        );
+    }
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_styles_with_frontmatter() {
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path().join("output-styles");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("teacher.md"),
+            "---\nname: Patient Teacher\ndescription: \"Explains every step\"\n---\n\nTeach.",
+        )
+        .unwrap();
+        fs::write(dir.join("terse.md"), "Answer in one line.").unwrap();
+        fs::write(dir.join("README.md"), "Docs").unwrap();
+
+        let styles = discover_styles(repo.path());
+        assert_eq!(styles.len(), 2);
+        assert_eq!(styles[0].name, "Patient Teacher");
+        assert_eq!(
+            styles[0].description.as_deref(),
+            Some("Explains every step")
+        );
+        assert_eq!(styles[1].name, "terse");
+        assert_eq!(styles[1].description, None);
+    }
+}
@@ -0,0 +1,187 @@
+//! `ai-cli status`: the state of the machine on one screen.
+//!
+//! Everything here is read locally: outdated counts come from the last
+//! background update check rather than a fresh registry query, so the
+//! overview stays fast and works offline.
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::exit::Outcome;
+use crate::managed;
+use crate::mcp::{apply, targets};
+use crate::skills::{agents, discovery};
+use crate::theme::Themed;
+use crate::tools;
+use crate::update_notice;
+
+/// `checked_at` relative to `now` (both Unix timestamps), e.g. "3h ago"
+fn ago(checked_at: i64, now: i64) -> String {
+    let secs = (now - checked_at).max(0);
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+fn section(title: &str) {
+    println!("{}", title.bold());
+}
+
+fn row(label: &str, value: impl std::fmt::Display) {
+    println!("  {:<18} {}", label, value);
+}
+
+fn print_tools() {
+    section("Tools");
+    let versions = tools::installed_versions();
+    let installed = versions.iter().filter(|v| v.installed.is_some()).count();
+    row("Installed", format!("{} of {}", installed, versions.len()));
+
+    match update_notice::last_check() {
+        Some(check) => {
+            let outdated = if check.outdated.is_empty() {
+                "none".success().to_string()
+            } else {
+                check.outdated.len().to_string().warning().to_string()
+            };
+            row("Outdated", outdated);
+            for tool in &check.outdated {
+                println!("  {:<18} {}", "", tool.muted());
+            }
+            if let Some(latest) = &check.ai_cli {
+                row("ai-cli", format!("{} available", latest).warning());
+            }
+            row(
+                "Last update check",
+                ago(check.checked_at, chrono::Utc::now().timestamp()),
+            );
+        }
+        None => row("Last update check", "never".muted()),
+    }
+    println!();
+}
+
+fn print_mcp() {
+    section("MCP servers");
+    let mut any = false;
+    for target in targets::catalog().iter().filter(|t| t.is_installed()) {
+        any = true;
+        match target.configured_servers() {
+            Ok(servers) if servers.is_empty() => row(target.name, "none".muted()),
+            Ok(servers) => {
+                let names: Vec<String> = servers.into_iter().map(|(name, _)| name).collect();
+                row(
+                    target.name,
+                    format!("{} ({})", names.len(), names.join(", ")),
+                );
+            }
+            Err(e) => row(target.name, format!("{:#}", e).error()),
+        }
+    }
+    if !any {
+        println!("  {}", "No supported tool installed".muted());
+    }
+    println!();
+}
+
+fn print_skills() {
+    section("Skills");
+    let mut any = false;
+    for agent in agents::catalog().iter().filter(|a| a.is_installed()) {
+        any = true;
+        match discovery::list_installed_skills(&agent.skills_path) {
+            Ok(skills) => row(agent.id, skills.len()),
+            Err(e) => row(agent.id, format!("{:#}", e).error()),
+        }
+    }
+    if !any {
+        println!("  {}", "No supported agent installed".muted());
+    }
+    println!();
+}
+
+/// Print pending changes against desired state; returns whether there are any
+async fn print_drift() -> bool {
+    section("Desired state");
+    let mut drifted = false;
+
+    match apply::pending_changes() {
+        Ok(None) => row("mcp.yaml", "not used".muted()),
+        Ok(Some(0)) => row("mcp.yaml", "applied".success()),
+        Ok(Some(pending)) => {
+            drifted = true;
+            row(
+                "mcp.yaml",
+                format!("{} pending change(s) (run `ai-cli mcp apply`)", pending).warning(),
+            );
+        }
+        Err(e) => {
+            drifted = true;
+            row("mcp.yaml", format!("{:#}", e).error());
+        }
+    }
+
+    match managed::load().await {
+        Ok(None) => row("Managed policy", "not used".muted()),
+        Ok(Some(policy)) => {
+            let deviations = managed::check(&policy);
+            if deviations.is_empty() {
+                row("Managed policy", "matches".success());
+            } else {
+                drifted = true;
+                row(
+                    "Managed policy",
+                    format!(
+                        "{} deviation(s) (run `ai-cli doctor` for details)",
+                        deviations.len()
+                    )
+                    .warning(),
+                );
+            }
+        }
+        Err(e) => {
+            drifted = true;
+            row("Managed policy", format!("{:#}", e).error());
+        }
+    }
+    println!();
+    drifted
+}
+
+/// Handle `status` command
+///
+/// Returns `UpdatesAvailable` when tools are outdated or the machine drifted
+/// from its manifest or policy.
+pub async fn handle_overview() -> Result<Outcome> {
+    print_tools();
+    print_mcp();
+    print_skills();
+    let drifted = print_drift().await;
+
+    let outdated = update_notice::last_check()
+        .is_some_and(|check| !check.outdated.is_empty() || check.ai_cli.is_some());
+    Ok(if drifted || outdated {
+        Outcome::UpdatesAvailable
+    } else {
+        Outcome::Success
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_time_since_last_check() {
+        let now = 1_700_000_000;
+        assert_eq!(ago(now - 5, now), "just now");
+        assert_eq!(ago(now - 125, now), "2m ago");
+        assert_eq!(ago(now - 3 * 3600, now), "3h ago");
+        assert_eq!(ago(now - 2 * 86400 - 60, now), "2d ago");
+        // Clock moved backwards since the check
+        assert_eq!(ago(now + 30, now), "just now");
+    }
+}
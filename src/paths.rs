@@ -0,0 +1,126 @@
+//! Resolution of every path ai-cli reads or writes.
+//!
+//! All paths hang off [`home`], which is the user's home directory unless a
+//! config root was set with `--config-root` / `AI_CLI_HOME`. With a config
+//! root the `XDG_*` variables are ignored too, so nothing outside the root is
+//! touched by ai-cli itself (installers it spawns still use the real home).
+
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use anyhow::{Context, Result};
+
+static CONFIG_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Use `root` in place of the home directory for all managed paths
+pub fn set_config_root(root: Option<PathBuf>) -> Result<()> {
+    let root = match root {
+        Some(root) => Some(
+            std::path::absolute(&root)
+                .with_context(|| format!("Invalid config root {}", root.display()))?,
+        ),
+        None => {
+            dirs::home_dir().context(
+                "Could not determine home directory; pass --config-root or set AI_CLI_HOME",
+            )?;
+            None
+        }
+    };
+    *CONFIG_ROOT.write().unwrap_or_else(|e| e.into_inner()) = root;
+    Ok(())
+}
+
+/// The config root, when one overrides the home directory
+pub fn config_root() -> Option<PathBuf> {
+    CONFIG_ROOT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Home directory all managed paths are resolved against
+pub fn home() -> PathBuf {
+    config_root().or_else(default_home).unwrap_or_default()
+}
+
+#[cfg(not(test))]
+fn default_home() -> Option<PathBuf> {
+    dirs::home_dir()
+}
+
+/// Unit tests get an empty home of their own on each thread, so they never
+/// read or write the developer's files
+#[cfg(test)]
+fn default_home() -> Option<PathBuf> {
+    thread_local! {
+        static HOME: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+    }
+    Some(HOME.with(|home| home.path().to_path_buf()))
+}
+
+/// `$XDG_CONFIG_HOME` or `~/.config`
+pub fn config_home() -> PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_DATA_HOME` or `~/.local/share`
+pub fn data_home() -> PathBuf {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// `$XDG_STATE_HOME` or `~/.local/state`
+pub fn state_home() -> PathBuf {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+/// `$XDG_CACHE_HOME` or `~/.cache`
+pub fn cache_home() -> PathBuf {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+/// `~/.local/bin`, where bootstrap installers put their binaries
+pub fn local_bin() -> PathBuf {
+    home().join(".local").join("bin")
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> PathBuf {
+    // Unit tests stay inside their own home like a config root
+    let from_env = if config_root().is_some() || cfg!(test) {
+        None
+    } else {
+        env::var_os(var)
+    };
+    resolve_xdg(from_env, &home(), fallback)
+}
+
+fn resolve_xdg(from_env: Option<OsString>, home: &Path, fallback: &str) -> PathBuf {
+    match from_env {
+        // The XDG spec says relative values are invalid and should be ignored
+        Some(value) if Path::new(&value).is_absolute() => PathBuf::from(value),
+        _ => home.join(fallback),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xdg_dirs_fall_back_to_home() {
+        let home = Path::new("/sandbox");
+        assert_eq!(
+            resolve_xdg(None, home, ".config"),
+            PathBuf::from("/sandbox/.config")
+        );
+        assert_eq!(
+            resolve_xdg(Some("/xdg/config".into()), home, ".config"),
+            PathBuf::from("/xdg/config")
+        );
+        assert_eq!(
+            resolve_xdg(Some("relative".into()), home, ".local/share"),
+            PathBuf::from("/sandbox/.local/share")
+        );
+    }
+}
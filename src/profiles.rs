@@ -0,0 +1,298 @@
+use anyhow::{Result, anyhow};
+use colored::*;
+
+use crate::config::{self, Profile};
+use crate::exit::{self, Outcome};
+use crate::mcp::{self, servers, targets};
+use crate::prompt;
+use crate::skills::actions::install_from_repo;
+use crate::skills::manifest::Manifest;
+use crate::skills::{agents, discovery};
+use crate::theme::Themed;
+
+fn find_profile(name: &str) -> Result<Profile> {
+    let config = config::load()?;
+    config.profiles.get(name).cloned().ok_or_else(|| {
+        let available: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+        exit::usage(format!(
+            "Profile '{}' not found. Available profiles: {}",
+            name,
+            if available.is_empty() {
+                "(none)".to_string()
+            } else {
+                available.join(", ")
+            }
+        ))
+    })
+}
+
+/// Handle `profile list` command
+pub fn handle_list() -> Result<()> {
+    let config = config::load()?;
+
+    if config.profiles.is_empty() {
+        println!("{}", "No profiles defined.".muted());
+        println!(
+            "{} Add [profiles.<name>] tables to {}",
+            "→".info(),
+            config::config_path().display()
+        );
+        return Ok(());
+    }
+
+    for (name, profile) in &config.profiles {
+        let marker = if config.active_profile.as_deref() == Some(name) {
+            "*".success().to_string()
+        } else {
+            " ".to_string()
+        };
+        println!(
+            "  {} {:<16} {}",
+            marker,
+            name.bold(),
+            format!(
+                "{} MCP server(s), {} skill(s), {} env var(s)",
+                profile.mcp_servers.len(),
+                profile.skills.len(),
+                profile.env.len()
+            )
+            .muted()
+        );
+    }
+
+    Ok(())
+}
+
+/// Handle `profile show <name>` command
+pub fn handle_show(name: &str) -> Result<()> {
+    let profile = find_profile(name)?;
+
+    println!("{}", name.bold());
+    if let Some(model) = &profile.model {
+        println!("  {} {}", "Model:".muted(), model);
+    }
+    println!("  {}", "MCP servers:".muted());
+    for id in &profile.mcp_servers {
+        println!("    {} {}", "-".info(), id);
+    }
+    println!("  {}", "Skills:".muted());
+    for skill in &profile.skills {
+        println!("    {} {}", "-".info(), skill);
+    }
+    println!("  {}", "Environment:".muted());
+    for (key, value) in &profile.env {
+        println!("    {} {}={}", "-".info(), key, value);
+    }
+
+    Ok(())
+}
+
+/// Handle `profile env [name]` command: print `export` lines for `eval`
+pub fn handle_env(name: Option<&str>) -> Result<()> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => config::load()?
+            .active_profile
+            .ok_or_else(|| exit::usage("No active profile; pass a profile name"))?,
+    };
+    let profile = find_profile(&name)?;
+
+    for (key, value) in &profile.env {
+        println!("export {}='{}'", key, value.replace('\'', r"'\''"));
+    }
+
+    Ok(())
+}
+
+/// Handle `profile use <name>` command
+pub fn handle_use(name: &str) -> Result<Outcome> {
+    let profile = find_profile(name)?;
+
+    let all_servers = servers::catalog();
+    for id in &profile.mcp_servers {
+        if !all_servers.iter().any(|s| s.id == *id) {
+            return Err(exit::usage(format!(
+                "Profile '{}' references unknown MCP server '{}'",
+                name, id
+            )));
+        }
+    }
+
+    // MCP: enable the profile's servers and disable every other catalog server
+    let mcp_targets: Vec<_> = targets::catalog()
+        .into_iter()
+        .filter(|t| t.is_installed())
+        .collect();
+    let mut mcp_changes = Vec::new();
+    for target in &mcp_targets {
+        for server in &all_servers {
+            let wanted = profile.mcp_servers.contains(&server.id);
+            let enabled = target.is_server_enabled(server).unwrap_or(false);
+            // Entries the user added themselves are never disabled
+            if wanted != enabled && (wanted || target.owns_server(server).unwrap_or(false)) {
+                mcp_changes.push((target, server, wanted));
+            }
+        }
+    }
+
+    // Skills: install anything missing; skills outside the profile are left alone
+    let installed_agents: Vec<_> = agents::catalog()
+        .into_iter()
+        .filter(|a| a.is_installed())
+        .collect();
+    let manifest = Manifest::load()?;
+    let agent_ids: Vec<&str> = installed_agents.iter().map(|a| a.id).collect();
+    let mut repo_installs = Vec::new();
+    let mut skill_installs = Vec::new();
+    let mut unsourced = Vec::new();
+    for entry in &profile.skills {
+        if entry.contains('/') {
+            if !manifest.has_source(entry, &agent_ids) {
+                repo_installs.push(entry.clone());
+            }
+            continue;
+        }
+        let missing: Vec<_> = installed_agents
+            .iter()
+            .filter(|agent| {
+                !discovery::list_installed_skills(&agent.skills_path)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|s| &s.name == entry)
+            })
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        match manifest.source_of(entry) {
+            Some(source) => skill_installs.push((entry.clone(), source.to_string(), missing)),
+            None => unsourced.push(entry.clone()),
+        }
+    }
+
+    if mcp_changes.is_empty() && repo_installs.is_empty() && skill_installs.is_empty() {
+        println!("{} Already matches profile {}", "✓".success(), name.bold());
+        report_unsourced(&unsourced);
+        config::set_value("active_profile", name)?;
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!("{}", "Planned changes:".bold());
+    for (target, server, enable) in &mcp_changes {
+        if *enable {
+            println!(
+                "  {} enable {} in {}",
+                "+".success(),
+                server.id,
+                target.name
+            );
+        } else {
+            println!("  {} disable {} in {}", "-".error(), server.id, target.name);
+        }
+    }
+    for repo in &repo_installs {
+        println!("  {} install skills from {}", "+".success(), repo);
+    }
+    for (skill, source, _) in &skill_installs {
+        println!(
+            "  {} install skill {} from {}",
+            "+".success(),
+            skill,
+            source
+        );
+    }
+    println!();
+
+    if !prompt::confirm(&format!("Switch to profile '{}'?", name))? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+
+    let configured = mcp::configure_all(
+        mcp_changes
+            .iter()
+            .filter(|(_, _, enable)| *enable)
+            .map(|(_, server, _)| *server),
+    );
+    for (target, server, enable) in mcp_changes {
+        let (result, verb) = if enable {
+            let result = match configured.get(&server.id) {
+                Some(server) => target.enable_server(server),
+                None => Err(anyhow!("not configured")),
+            };
+            (result, "enable")
+        } else {
+            (target.disable_server(server), "disable")
+        };
+        match result {
+            Ok(_) => println!(
+                "{} {}d {} in {}",
+                "✓".success(),
+                if enable { "Enable" } else { "Disable" },
+                server.id,
+                target.name
+            ),
+            Err(e) => {
+                println!(
+                    "{} Failed to {} {} in {}: {}",
+                    "✗".error(),
+                    verb,
+                    server.id,
+                    target.name,
+                    e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    for repo in repo_installs {
+        if let Err(e) = install_from_repo(&repo, &installed_agents, None) {
+            println!(
+                "{} Failed to install skills from {}: {}",
+                "✗".error(),
+                repo,
+                e
+            );
+            failed += 1;
+        }
+    }
+
+    for (skill, source, agents) in skill_installs {
+        if let Err(e) = install_from_repo(&source, &agents, Some(std::slice::from_ref(&skill))) {
+            println!("{} Failed to install skill {}: {}", "✗".error(), skill, e);
+            failed += 1;
+        }
+    }
+
+    report_unsourced(&unsourced);
+
+    println!();
+    if failed == 0 {
+        config::set_value("active_profile", name)?;
+        println!("{} Active profile: {}", "✓".success(), name.bold());
+    } else {
+        println!(
+            "{} Profile {} not fully applied; run `ai-cli profile use {}` again",
+            "!".warning(),
+            name.bold(),
+            name
+        );
+    }
+
+    Ok(Outcome::from_failures(failed))
+}
+
+fn report_unsourced(names: &[String]) {
+    if names.is_empty() {
+        return;
+    }
+    println!(
+        "{} No known source for skill(s): {} (install them once with `ai-cli skills install`)",
+        "!".warning(),
+        names.join(", ")
+    );
+}
@@ -0,0 +1,40 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+use crate::theme::Themed;
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Answer all confirmations with yes and refuse prompts that need real input
+pub fn set_non_interactive(enabled: bool) {
+    NON_INTERACTIVE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// Ask a yes/no question, defaulting to no. Always yes in non-interactive mode.
+pub fn confirm(question: &str) -> Result<bool> {
+    print!("{} {} [y/N] ", "?".warning(), question);
+
+    if is_non_interactive() {
+        println!("y {}", "(--yes)".muted());
+        return Ok(true);
+    }
+
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Fail fast when a prompt needs a selection the user can't give non-interactively
+pub fn require_interactive(hint: &str) -> Result<()> {
+    if is_non_interactive() {
+        anyhow::bail!("Input required but running non-interactively; {}", hint);
+    }
+    Ok(())
+}
@@ -0,0 +1,398 @@
+use std::fs;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::journal;
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// A reusable prompt from the central library
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompt {
+    pub name: String,
+    pub description: Option<String>,
+    /// Prompt text; `$ARGUMENTS` marks where command arguments go
+    pub body: String,
+}
+
+impl Prompt {
+    /// Parse a library file: Markdown with optional `description:` frontmatter
+    pub fn parse(name: &str, content: &str) -> Self {
+        let mut description = None;
+        let mut body = content;
+        if let Some(rest) = content.strip_prefix("---\n")
+            && let Some(end) = rest.find("\n---")
+        {
+            for line in rest[..end].lines() {
+                if let Some(value) = line.trim().strip_prefix("description:") {
+                    description = Some(value.trim().trim_matches('"').to_string());
+                }
+            }
+            body = rest[end + 4..].trim_start_matches('\n');
+        }
+        Prompt {
+            name: name.to_string(),
+            description,
+            body: body.trim_end().to_string(),
+        }
+    }
+}
+
+/// How an agent stores custom commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `<name>.md` with optional `description` frontmatter and `$ARGUMENTS`
+    Markdown,
+    /// `<name>.toml` with `description` and `prompt` keys and `{{args}}`
+    GeminiToml,
+}
+
+/// Directory an agent reads custom commands from
+#[derive(Debug, Clone)]
+pub struct PromptTarget {
+    pub agent: &'static str,
+    pub dir: PathBuf,
+    pub format: Format,
+}
+
+impl PromptTarget {
+    pub fn file_for(&self, name: &str) -> PathBuf {
+        match self.format {
+            Format::Markdown => self.dir.join(format!("{}.md", name)),
+            Format::GeminiToml => self.dir.join(format!("{}.toml", name)),
+        }
+    }
+
+    /// The agent's config directory exists
+    pub fn is_installed(&self) -> bool {
+        self.dir.parent().is_some_and(|p| p.exists())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetStatus {
+    InSync,
+    Drifted,
+    Missing,
+}
+
+/// Returns the command directories of all agents prompts are rendered to
+pub fn targets() -> Vec<PromptTarget> {
+    let home = paths::home();
+    vec![
+        PromptTarget {
+            agent: "Claude Code",
+            dir: home.join(".claude/commands"),
+            format: Format::Markdown,
+        },
+        PromptTarget {
+            agent: "Codex CLI",
+            dir: home.join(".codex/prompts"),
+            format: Format::Markdown,
+        },
+        PromptTarget {
+            agent: "Gemini CLI",
+            dir: home.join(".gemini/commands"),
+            format: Format::GeminiToml,
+        },
+        PromptTarget {
+            agent: "OpenCode",
+            dir: paths::config_home().join("opencode/command"),
+            format: Format::Markdown,
+        },
+    ]
+}
+
+/// Render a prompt in an agent's native command format
+pub fn render(prompt: &Prompt, format: Format) -> String {
+    match format {
+        Format::Markdown => match &prompt.description {
+            Some(description) => format!(
+                "---\ndescription: {}\n---\n\n{}\n",
+                description, prompt.body
+            ),
+            None => format!("{}\n", prompt.body),
+        },
+        Format::GeminiToml => {
+            let body = prompt.body.replace("$ARGUMENTS", "{{args}}");
+            let mut out = String::new();
+            if let Some(description) = &prompt.description {
+                out.push_str(&format!(
+                    "description = {}\n",
+                    toml_edit::Value::from(description.as_str())
+                ));
+            }
+            if body.contains("'''") {
+                out.push_str(&format!(
+                    "prompt = {}\n",
+                    toml_edit::Value::from(body.as_str())
+                ));
+            } else {
+                out.push_str(&format!("prompt = '''\n{}\n'''\n", body));
+            }
+            out
+        }
+    }
+}
+
+/// Where the central library lives
+pub fn library_dir() -> PathBuf {
+    config::config_dir().join("prompts")
+}
+
+/// All prompts in the library, sorted by name
+pub fn load_library() -> Result<Vec<Prompt>> {
+    let dir = library_dir();
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut prompts = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|e| e != "md") {
+            continue;
+        }
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        prompts.push(Prompt::parse(&name, &content));
+    }
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(prompts)
+}
+
+pub fn status_of(target: &PromptTarget, prompt: &Prompt) -> TargetStatus {
+    match fs::read_to_string(target.file_for(&prompt.name)) {
+        Ok(content) if content == render(prompt, target.format) => TargetStatus::InSync,
+        Ok(_) => TargetStatus::Drifted,
+        Err(_) => TargetStatus::Missing,
+    }
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(exit::usage(format!(
+            "Invalid prompt name '{}': use letters, digits, '-' and '_'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Handle `prompts list` command
+pub fn handle_list() -> Result<()> {
+    let prompts = load_library()?;
+    if prompts.is_empty() {
+        println!("No prompts in {}", library_dir().display());
+        println!(
+            "  {} Add one with `ai-cli prompts add <name> --from <file>`",
+            "→".info()
+        );
+        return Ok(());
+    }
+
+    let targets: Vec<_> = targets().into_iter().filter(|t| t.is_installed()).collect();
+    for prompt in &prompts {
+        println!(
+            "{} {}",
+            format!("/{}", prompt.name).bold(),
+            prompt.description.as_deref().unwrap_or("").muted()
+        );
+        let statuses: Vec<String> = targets
+            .iter()
+            .map(|t| match status_of(t, prompt) {
+                TargetStatus::InSync => format!("{} {}", "✓".success(), t.agent),
+                TargetStatus::Drifted => format!("{} {}", "!".warning(), t.agent),
+                TargetStatus::Missing => format!("{} {}", "-".muted(), t.agent.muted()),
+            })
+            .collect();
+        println!("  {}", statuses.join("  "));
+    }
+    println!();
+    println!(
+        "{}",
+        format!("Library: {}", library_dir().display()).muted()
+    );
+    Ok(())
+}
+
+/// Handle `prompts add <name>` command: copy a file (or stdin) into the library
+pub fn handle_add(name: &str, from: Option<&Path>) -> Result<()> {
+    validate_name(name)?;
+    let path = library_dir().join(format!("{}.md", name));
+    if path.exists() && !prompt::confirm(&format!("Prompt '{}' exists. Replace it?", name))? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let content = match from {
+        Some(file) => fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?,
+        None if !std::io::stdin().is_terminal() => {
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .context("Failed to read prompt from stdin")?;
+            content
+        }
+        None => format!(
+            "---\ndescription: {}\n---\n\nDescribe the task here. $ARGUMENTS\n",
+            name
+        ),
+    };
+
+    logging::write_file(&path, &content)?;
+    println!("{} Added {}", "✓".success(), path.display());
+    println!(
+        "  {} Run `ai-cli prompts sync` to install it as a command in every agent",
+        "→".info()
+    );
+    Ok(())
+}
+
+/// Handle `prompts sync` command: render every prompt into each agent's command directory
+pub fn handle_sync() -> Result<Outcome> {
+    let prompts = load_library()?;
+    if prompts.is_empty() {
+        println!("No prompts in {}", library_dir().display());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut stale = Vec::new();
+    for target in targets().into_iter().filter(|t| t.is_installed()) {
+        for prompt in &prompts {
+            let status = status_of(&target, prompt);
+            if status != TargetStatus::InSync {
+                stale.push((target.clone(), prompt, status));
+            }
+        }
+    }
+    if stale.is_empty() {
+        println!("{} All prompts are in sync", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let drifted: Vec<_> = stale
+        .iter()
+        .filter(|(_, _, status)| *status == TargetStatus::Drifted)
+        .collect();
+    if !drifted.is_empty() {
+        println!("{}", "These commands differ from the library:".bold());
+        for (target, prompt, _) in &drifted {
+            println!(
+                "  {} {}",
+                "!".warning(),
+                target.file_for(&prompt.name).display()
+            );
+        }
+        if !prompt::confirm("Overwrite them?")? {
+            println!("Cancelled.");
+            return Ok(Outcome::NothingToDo);
+        }
+    }
+
+    let mut failed = 0;
+    for (target, prompt, _) in stale {
+        let path = target.file_for(&prompt.name);
+        match logging::write_file(&path, &render(prompt, target.format)) {
+            Ok(()) => println!(
+                "{} /{} → {} {}",
+                "✓".success(),
+                prompt.name,
+                target.agent,
+                format!("({})", path.display()).muted()
+            ),
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `prompts remove <name>` command: delete it from the library and every agent
+pub fn handle_remove(name: &str) -> Result<Outcome> {
+    validate_name(name)?;
+    let library_path = library_dir().join(format!("{}.md", name));
+    if !library_path.exists() {
+        return Err(exit::usage(format!("No prompt named '{}'", name)));
+    }
+    let content = fs::read_to_string(&library_path)
+        .with_context(|| format!("Failed to read {}", library_path.display()))?;
+    let prompt = Prompt::parse(name, &content);
+
+    let mut files = vec![library_path];
+    for target in targets() {
+        match status_of(&target, &prompt) {
+            TargetStatus::InSync => files.push(target.file_for(name)),
+            TargetStatus::Drifted => println!(
+                "{} Keeping {} (edited outside ai-cli)",
+                "!".warning(),
+                target.file_for(name).display()
+            ),
+            TargetStatus::Missing => {}
+        }
+    }
+
+    let mut failed = 0;
+    for path in files {
+        journal::record_file(&path);
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                logging::line(&format!("removed {}", path.display()));
+                println!("{} Removed {}", "✓".success(), path.display());
+            }
+            Err(e) => {
+                println!("{} {}: {}", "✗".error(), path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_frontmatter_and_renders_formats() {
+        let prompt = Prompt::parse(
+            "review",
+            "---\ndescription: Review the diff\n---\n\nReview $ARGUMENTS carefully.\n",
+        );
+        assert_eq!(prompt.description.as_deref(), Some("Review the diff"));
+        assert_eq!(prompt.body, "Review $ARGUMENTS carefully.");
+
+        assert_eq!(
+            render(&prompt, Format::Markdown),
+            "---\ndescription: Review the diff\n---\n\nReview $ARGUMENTS carefully.\n"
+        );
+
+        let toml = render(&prompt, Format::GeminiToml);
+        let doc: toml::Table = toml::from_str(&toml).unwrap();
+        assert_eq!(doc["description"].as_str(), Some("Review the diff"));
+        assert_eq!(doc["prompt"].as_str(), Some("Review {{args}} carefully.\n"));
+    }
+
+    #[test]
+    fn plain_markdown_has_no_description() {
+        let prompt = Prompt::parse("fix", "Fix the failing test.\n");
+        assert_eq!(prompt.description, None);
+        assert_eq!(render(&prompt, Format::Markdown), "Fix the failing test.\n");
+    }
+}
@@ -0,0 +1,278 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// A directory where a tool accumulates data it can recreate or doesn't need
+#[derive(Debug, Clone)]
+pub struct Store {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub root: PathBuf,
+    /// Depth below `root` of the entries deleted as a whole
+    /// (1 = direct children, 2 = children of children)
+    pub depth: usize,
+}
+
+/// Returns the caches and transcript stores of all supported tools
+pub fn catalog() -> Vec<Store> {
+    let home = paths::home();
+    vec![
+        Store {
+            id: "claude-transcripts",
+            name: "Claude Code transcripts",
+            root: home.join(".claude/projects"),
+            depth: 2,
+        },
+        Store {
+            id: "claude-snapshots",
+            name: "Claude Code shell snapshots",
+            root: home.join(".claude/shell-snapshots"),
+            depth: 1,
+        },
+        Store {
+            id: "claude-logs",
+            name: "Claude Code debug logs",
+            root: home.join(".claude/debug"),
+            depth: 1,
+        },
+        Store {
+            id: "codex-sessions",
+            name: "Codex CLI sessions",
+            root: home.join(".codex/sessions"),
+            depth: 4,
+        },
+        Store {
+            id: "codex-logs",
+            name: "Codex CLI logs",
+            root: home.join(".codex/log"),
+            depth: 1,
+        },
+        Store {
+            id: "gemini-chats",
+            name: "Gemini CLI chats and checkpoints",
+            root: home.join(".gemini/tmp"),
+            depth: 1,
+        },
+        Store {
+            id: "npx",
+            name: "npx cache (MCP servers)",
+            root: home.join(".npm/_npx"),
+            depth: 1,
+        },
+    ]
+}
+
+/// Something that can be deleted as a whole
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Latest modification of the item or anything inside it
+    pub modified: SystemTime,
+}
+
+/// Total size and latest modification time of `path`
+pub(crate) fn measure(path: &Path) -> (u64, SystemTime) {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return (0, SystemTime::UNIX_EPOCH);
+    };
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    if !metadata.is_dir() {
+        return (metadata.len(), modified);
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return (0, modified);
+    };
+    entries
+        .flatten()
+        .map(|entry| measure(&entry.path()))
+        .fold((0, modified), |(size, latest), (s, m)| {
+            (size + s, latest.max(m))
+        })
+}
+
+/// Entries `depth` levels below `dir`
+fn entries_at(dir: &Path, depth: usize) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let paths = entries.flatten().map(|e| e.path());
+    if depth <= 1 {
+        return paths.collect();
+    }
+    paths
+        .filter(|p| p.is_dir())
+        .flat_map(|p| entries_at(&p, depth - 1))
+        .collect()
+}
+
+impl Store {
+    pub fn items(&self) -> Vec<Item> {
+        entries_at(&self.root, self.depth)
+            .into_iter()
+            .map(|path| {
+                let (size, modified) = measure(&path);
+                Item {
+                    path,
+                    size,
+                    modified,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Items last modified before `cutoff`
+fn older_than(items: &[Item], cutoff: SystemTime) -> Vec<&Item> {
+    items.iter().filter(|i| i.modified < cutoff).collect()
+}
+
+fn remove(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+    .with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Handle `prune` command: report sizes and delete entries older than `days`
+pub fn handle_prune(stores: &[String], days: u64, dry_run: bool) -> Result<Outcome> {
+    let catalog = catalog();
+    for id in stores {
+        if !catalog.iter().any(|s| s.id == id) {
+            let ids: Vec<_> = catalog.iter().map(|s| s.id).collect();
+            return Err(exit::usage(format!(
+                "Unknown store '{}'. Available: {}",
+                id,
+                ids.join(", ")
+            )));
+        }
+    }
+    let selected: Vec<&Store> = catalog
+        .iter()
+        .filter(|s| stores.is_empty() || stores.iter().any(|id| id == s.id))
+        .collect();
+
+    let cutoff = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+    let mut stale: Vec<Item> = Vec::new();
+    for store in selected {
+        let items = store.items();
+        if items.is_empty() {
+            continue;
+        }
+        let total: u64 = items.iter().map(|i| i.size).sum();
+        let old = older_than(&items, cutoff);
+        let old_size: u64 = old.iter().map(|i| i.size).sum();
+        println!(
+            "{:<34} {:>10}   {} older than {} days ({})",
+            store.name,
+            format_size(total),
+            old.len(),
+            days,
+            format_size(old_size)
+        );
+        println!("  {}", store.root.display().to_string().muted());
+        stale.extend(old.into_iter().cloned());
+    }
+
+    if stale.is_empty() {
+        println!("{} Nothing older than {} days", "✓".success(), days);
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let size: u64 = stale.iter().map(|i| i.size).sum();
+    println!();
+    if dry_run {
+        println!(
+            "{} Would free {} by removing {} entries (dry run)",
+            "→".info(),
+            format_size(size).bold(),
+            stale.len()
+        );
+        return Ok(Outcome::Success);
+    }
+    if !prompt::confirm(&format!(
+        "Remove {} entries older than {} days ({})?",
+        stale.len(),
+        days,
+        format_size(size)
+    ))? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+    for item in &stale {
+        match remove(&item.path) {
+            Ok(()) => logging::line(&format!("prune: removed {}", item.path.display())),
+            Err(e) => {
+                println!("{} {:#}", "✗".error(), e);
+                failed += 1;
+            }
+        }
+    }
+    println!(
+        "{} Freed {} from {} entries",
+        "✓".success(),
+        format_size(size),
+        stale.len() - failed
+    );
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_entries_at_depth_by_their_newest_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("-home-me-app");
+        fs::create_dir_all(project.join("session-a")).unwrap();
+        fs::write(project.join("session-a.jsonl"), "x".repeat(100)).unwrap();
+        fs::write(project.join("session-a/tool.txt"), "y".repeat(20)).unwrap();
+
+        let store = Store {
+            id: "test",
+            name: "Test",
+            root: dir.path().to_path_buf(),
+            depth: 2,
+        };
+        let mut items = store.items();
+        items.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].path, project.join("session-a"));
+        assert_eq!(items[0].size, 20);
+        assert_eq!(items[1].size, 100);
+
+        let future = SystemTime::now() + Duration::from_secs(60);
+        assert_eq!(older_than(&items, future).len(), 2);
+        assert!(older_than(&items, SystemTime::UNIX_EPOCH).is_empty());
+        assert_eq!(format_size(1536 * 1024), "1.5 MB");
+    }
+}
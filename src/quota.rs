@@ -0,0 +1,370 @@
+use std::env;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::http;
+use crate::keys::{self, keychain};
+use crate::logging;
+use crate::output;
+use crate::theme::Themed;
+
+/// How a provider exposes its limits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuotaApi {
+    /// `anthropic-ratelimit-*` headers on API responses
+    Anthropic,
+    /// `x-ratelimit-*` headers on API responses
+    OpenAi,
+    /// GitHub's Copilot entitlement endpoint
+    Copilot,
+}
+
+/// A provider whose limits can be queried
+#[derive(Debug, Clone)]
+pub struct QuotaSource {
+    /// Provider id in `keys::catalog()`
+    pub provider: &'static str,
+    pub name: &'static str,
+    pub url: String,
+    pub api: QuotaApi,
+}
+
+/// Returns the providers that expose quota or rate limits
+pub fn sources() -> Vec<QuotaSource> {
+    vec![
+        QuotaSource {
+            provider: "anthropic",
+            name: "Anthropic",
+            url: "https://api.anthropic.com/v1/models".to_string(),
+            api: QuotaApi::Anthropic,
+        },
+        QuotaSource {
+            provider: "openai",
+            name: "OpenAI",
+            url: "https://api.openai.com/v1/models".to_string(),
+            api: QuotaApi::OpenAi,
+        },
+        QuotaSource {
+            provider: "github",
+            name: "Copilot",
+            url: "https://api.github.com/copilot_internal/user".to_string(),
+            api: QuotaApi::Copilot,
+        },
+    ]
+}
+
+/// One limit and how much of it is left
+#[derive(Debug, Clone, PartialEq)]
+pub struct Limit {
+    pub name: String,
+    pub remaining: Option<u64>,
+    pub limit: Option<u64>,
+    /// When the limit resets, as reported by the provider
+    pub reset: Option<String>,
+}
+
+/// A credential to query with, and where it came from
+#[derive(Debug, Clone)]
+pub struct Credential {
+    pub secret: String,
+    pub origin: String,
+}
+
+/// The provider's key from the environment, the keychain or (for GitHub) the gh CLI
+fn credential(provider: &str) -> Option<Credential> {
+    let provider = keys::find(provider)?;
+    for var in provider.env_vars {
+        if let Ok(secret) = env::var(var)
+            && !secret.is_empty()
+        {
+            return Some(Credential {
+                secret,
+                origin: format!("${}", var),
+            });
+        }
+    }
+    if let Some(secret) = keychain::get(provider.id) {
+        return Some(Credential {
+            secret,
+            origin: "keychain".to_string(),
+        });
+    }
+    if provider.id == "github" {
+        let output = Command::new("gh").args(["auth", "token"]).output().ok()?;
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !token.is_empty() {
+            return Some(Credential {
+                secret: token,
+                origin: "gh auth token".to_string(),
+            });
+        }
+    }
+    None
+}
+
+/// The provider refused the credential
+#[derive(Debug)]
+struct Rejected(reqwest::StatusCode);
+
+impl std::fmt::Display for Rejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "credential rejected ({})", self.0)
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+fn header_number(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn header_text(headers: &HeaderMap, name: &str) -> Option<String> {
+    Some(headers.get(name)?.to_str().ok()?.to_string())
+}
+
+/// Rate limits from response headers; kinds without headers are skipped
+fn parse_headers(headers: &HeaderMap, api: QuotaApi) -> Vec<Limit> {
+    let kinds: &[&str] = match api {
+        QuotaApi::Anthropic => &["requests", "tokens", "input-tokens", "output-tokens"],
+        QuotaApi::OpenAi => &["requests", "tokens"],
+        QuotaApi::Copilot => &[],
+    };
+    kinds
+        .iter()
+        .filter_map(|kind| {
+            let (limit, remaining, reset) = match api {
+                QuotaApi::Anthropic => (
+                    format!("anthropic-ratelimit-{}-limit", kind),
+                    format!("anthropic-ratelimit-{}-remaining", kind),
+                    format!("anthropic-ratelimit-{}-reset", kind),
+                ),
+                _ => (
+                    format!("x-ratelimit-limit-{}", kind),
+                    format!("x-ratelimit-remaining-{}", kind),
+                    format!("x-ratelimit-reset-{}", kind),
+                ),
+            };
+            let remaining = header_number(headers, &remaining)?;
+            Some(Limit {
+                name: kind.replace('-', " "),
+                remaining: Some(remaining),
+                limit: header_number(headers, &limit),
+                reset: header_text(headers, &reset),
+            })
+        })
+        .collect()
+}
+
+/// Quotas from the Copilot entitlement response
+fn parse_copilot(json: &Value) -> Vec<Limit> {
+    let reset = json["quota_reset_date"].as_str().map(str::to_string);
+    let Some(snapshots) = json["quota_snapshots"].as_object() else {
+        return Vec::new();
+    };
+    snapshots
+        .iter()
+        .filter(|(_, quota)| !quota["unlimited"].as_bool().unwrap_or(false))
+        .map(|(name, quota)| Limit {
+            name: name.replace('_', " "),
+            remaining: quota["remaining"].as_f64().map(|n| n.max(0.0) as u64),
+            limit: quota["entitlement"].as_f64().map(|n| n as u64),
+            reset: reset.clone(),
+        })
+        .collect()
+}
+
+async fn fetch(source: &QuotaSource, secret: &str) -> Result<Vec<Limit>> {
+    let request = http::client().get(&source.url);
+    let request = match source.api {
+        QuotaApi::Anthropic => request
+            .header("x-api-key", secret)
+            .header("anthropic-version", "2023-06-01"),
+        QuotaApi::OpenAi => request.bearer_auth(secret),
+        QuotaApi::Copilot => request.header("Authorization", format!("token {}", secret)),
+    };
+    let response = http::send(request).await?;
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(Rejected(status).into());
+    }
+    let response = response
+        .error_for_status()
+        .with_context(|| format!("Failed to fetch {}", source.url))?;
+    if source.api == QuotaApi::Copilot {
+        let json: Value = response
+            .json()
+            .await
+            .context("Unexpected Copilot entitlement response")?;
+        return Ok(parse_copilot(&json));
+    }
+    Ok(parse_headers(response.headers(), source.api))
+}
+
+fn format_limit(limit: &Limit) -> String {
+    let amount = match (limit.remaining, limit.limit) {
+        (Some(remaining), Some(total)) => format!("{} / {} left", remaining, total),
+        (Some(remaining), None) => format!("{} left", remaining),
+        _ => "unknown".to_string(),
+    };
+    match &limit.reset {
+        Some(reset) => format!("{}, resets {}", amount, reset),
+        None => amount,
+    }
+}
+
+/// Handle `quota` command. Returns `false` when a credential is rejected.
+pub async fn handle_quota() -> Result<bool> {
+    let sources = sources();
+    let credentials: Vec<Option<Credential>> =
+        sources.iter().map(|s| credential(s.provider)).collect();
+    let spinner = output::spinner("Checking provider limits...");
+    let results = join_all(sources.iter().zip(&credentials).map(
+        |(source, credential)| async move {
+            match credential {
+                Some(credential) => Some(fetch(source, &credential.secret).await),
+                None => None,
+            }
+        },
+    ))
+    .await;
+    spinner.finish_and_clear();
+
+    let mut rejected = 0;
+    for ((source, credential), result) in sources.iter().zip(&credentials).zip(results) {
+        let (Some(credential), Some(result)) = (credential, result) else {
+            println!(
+                "{} {:<10} {}",
+                "-".muted(),
+                source.name,
+                "no credential configured".muted()
+            );
+            continue;
+        };
+        match result {
+            Ok(limits) if limits.is_empty() => println!(
+                "{} {:<10} {}",
+                "✓".success(),
+                source.name,
+                format!(
+                    "credential works, no limits reported ({})",
+                    credential.origin
+                )
+                .muted()
+            ),
+            Ok(limits) => {
+                println!(
+                    "{} {:<10} {}",
+                    "✓".success(),
+                    source.name,
+                    credential.origin.muted()
+                );
+                for limit in &limits {
+                    let low =
+                        matches!((limit.remaining, limit.limit), (Some(r), Some(t)) if r * 10 < t);
+                    let text = format_limit(limit);
+                    println!(
+                        "    {:<22} {}",
+                        limit.name,
+                        if low {
+                            text.warning().to_string()
+                        } else {
+                            text
+                        }
+                    );
+                }
+            }
+            Err(e) if e.downcast_ref::<Rejected>().is_some() => {
+                rejected += 1;
+                println!(
+                    "{} {:<10} {} ({})",
+                    "✗".error(),
+                    source.name,
+                    e,
+                    credential.origin
+                );
+            }
+            Err(e) => {
+                logging::line(&format!("quota {}: {:#}", source.name, e));
+                println!(
+                    "{} {:<10} {}",
+                    "?".muted(),
+                    source.name,
+                    "limits unavailable (see the log)".muted()
+                );
+            }
+        }
+    }
+    Ok(rejected == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn reads_rate_limit_headers() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/v1/models")
+                    .header("x-api-key", "sk-ant-test");
+                then.status(200)
+                    .header("anthropic-ratelimit-requests-limit", "50")
+                    .header("anthropic-ratelimit-requests-remaining", "49")
+                    .header("anthropic-ratelimit-requests-reset", "2026-10-16T10:00:00Z")
+                    .header("anthropic-ratelimit-tokens-remaining", "40000")
+                    .body("{}");
+            })
+            .await;
+
+        let source = QuotaSource {
+            provider: "anthropic",
+            name: "Anthropic",
+            url: format!("{}/v1/models", server.base_url()),
+            api: QuotaApi::Anthropic,
+        };
+        let limits = fetch(&source, "sk-ant-test").await.unwrap();
+        assert_eq!(
+            limits,
+            vec![
+                Limit {
+                    name: "requests".to_string(),
+                    remaining: Some(49),
+                    limit: Some(50),
+                    reset: Some("2026-10-16T10:00:00Z".to_string()),
+                },
+                Limit {
+                    name: "tokens".to_string(),
+                    remaining: Some(40000),
+                    limit: None,
+                    reset: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_copilot_quota_snapshots() {
+        let json: Value = serde_json::from_str(
+            r#"{"quota_reset_date":"2026-11-01","quota_snapshots":{
+                "chat":{"entitlement":0,"remaining":0,"unlimited":true},
+                "premium_interactions":{"entitlement":300,"remaining":112.5,"unlimited":false}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parse_copilot(&json),
+            vec![Limit {
+                name: "premium interactions".to_string(),
+                remaining: Some(112),
+                limit: Some(300),
+                reset: Some("2026-11-01".to_string()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// Windsurf ignores global rules past this many characters
+const WINDSURF_GLOBAL_LIMIT: usize = 6000;
+
+/// A rule from the canonical ruleset
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub name: String,
+    pub description: Option<String>,
+    /// Comma-separated file globs the rule applies to
+    pub globs: Option<String>,
+    /// Apply to every request regardless of files
+    pub always: bool,
+    pub body: String,
+}
+
+impl Rule {
+    /// Parse a rule file: Markdown (or Cursor `.mdc`) with optional frontmatter
+    pub fn parse(name: &str, content: &str) -> Self {
+        let mut rule = Rule {
+            name: name.to_string(),
+            description: None,
+            globs: None,
+            always: false,
+            body: content.trim().to_string(),
+        };
+        if let Some(rest) = content.strip_prefix("---\n")
+            && let Some(end) = rest.find("\n---")
+        {
+            for line in rest[..end].lines() {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"');
+                let value = (!value.is_empty()).then(|| value.to_string());
+                match key.trim() {
+                    "description" => rule.description = value,
+                    "globs" => rule.globs = value,
+                    "alwaysApply" | "always" => rule.always = value.as_deref() == Some("true"),
+                    "trigger" => rule.always = value.as_deref() == Some("always_on"),
+                    _ => {}
+                }
+            }
+            rule.body = rest[end + 4..].trim().to_string();
+        }
+        rule
+    }
+}
+
+/// Cursor project rule (`.cursor/rules/<name>.mdc`)
+pub fn render_cursor(rule: &Rule) -> String {
+    format!(
+        "---\ndescription: {}\nglobs: {}\nalwaysApply: {}\n---\n\n{}\n",
+        rule.description.as_deref().unwrap_or(""),
+        rule.globs.as_deref().unwrap_or(""),
+        rule.always,
+        rule.body
+    )
+}
+
+/// Windsurf project rule (`.windsurf/rules/<name>.md`)
+pub fn render_windsurf(rule: &Rule) -> String {
+    let trigger = if rule.always {
+        "always_on"
+    } else if rule.globs.is_some() {
+        "glob"
+    } else if rule.description.is_some() {
+        "model_decision"
+    } else {
+        "manual"
+    };
+    let mut out = format!("---\ntrigger: {}\n", trigger);
+    if let Some(description) = &rule.description {
+        out.push_str(&format!("description: {}\n", description));
+    }
+    if let Some(globs) = &rule.globs {
+        out.push_str(&format!("globs: {}\n", globs));
+    }
+    out.push_str(&format!("---\n\n{}\n", rule.body));
+    out
+}
+
+/// Windsurf's single global rules file holds every rule
+pub fn render_windsurf_global(rules: &[Rule]) -> String {
+    let sections: Vec<String> = rules
+        .iter()
+        .map(|rule| match &rule.globs {
+            Some(globs) if !rule.always => {
+                format!("# {}\n\nApplies to {}\n\n{}\n", rule.name, globs, rule.body)
+            }
+            _ => format!("# {}\n\n{}\n", rule.name, rule.body),
+        })
+        .collect();
+    sections.join("\n")
+}
+
+/// A file generated from the canonical ruleset
+#[derive(Debug, Clone)]
+pub struct RuleFile {
+    pub product: &'static str,
+    pub path: PathBuf,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    InSync,
+    Drifted,
+    Missing,
+}
+
+impl RuleFile {
+    pub fn status(&self) -> FileStatus {
+        match fs::read_to_string(&self.path) {
+            Ok(content) if content == self.content => FileStatus::InSync,
+            Ok(_) => FileStatus::Drifted,
+            Err(_) => FileStatus::Missing,
+        }
+    }
+}
+
+/// Directory holding the canonical ruleset
+pub fn canonical_dir(scope: &Scope) -> PathBuf {
+    match scope {
+        Scope::Global => config::config_dir().join("rules"),
+        Scope::Project(root) => root.join(".ai-cli/rules"),
+    }
+}
+
+/// All rules in the canonical ruleset, sorted by name
+pub fn load_rules(scope: &Scope) -> Result<Vec<Rule>> {
+    read_rule_dir(&canonical_dir(scope), "md")
+}
+
+pub(crate) fn read_rule_dir(dir: &Path, extension: &str) -> Result<Vec<Rule>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut rules = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|e| e != extension) {
+            continue;
+        }
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        rules.push(Rule::parse(&name, &content));
+    }
+    rules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(rules)
+}
+
+fn cursor_installed() -> bool {
+    paths::home().join(".cursor").exists()
+}
+
+fn windsurf_installed() -> bool {
+    paths::home().join(".codeium/windsurf").exists()
+}
+
+/// The files the ruleset renders to for installed products
+///
+/// Cursor keeps global rules in its settings database, so only project rules
+/// are written for it.
+pub fn rule_files(scope: &Scope, rules: &[Rule]) -> Vec<RuleFile> {
+    let mut files = Vec::new();
+    match scope {
+        Scope::Global => {
+            if windsurf_installed() && !rules.is_empty() {
+                files.push(RuleFile {
+                    product: "Windsurf",
+                    path: paths::home().join(".codeium/windsurf/memories/global_rules.md"),
+                    content: render_windsurf_global(rules),
+                });
+            }
+        }
+        Scope::Project(root) => {
+            if cursor_installed() || root.join(".cursor").exists() {
+                files.extend(rules.iter().map(|rule| {
+                    RuleFile {
+                        product: "Cursor",
+                        path: root
+                            .join(".cursor/rules")
+                            .join(format!("{}.mdc", rule.name)),
+                        content: render_cursor(rule),
+                    }
+                }));
+            }
+            if windsurf_installed() || root.join(".windsurf").exists() {
+                files.extend(rules.iter().map(|rule| {
+                    RuleFile {
+                        product: "Windsurf",
+                        path: root
+                            .join(".windsurf/rules")
+                            .join(format!("{}.md", rule.name)),
+                        content: render_windsurf(rule),
+                    }
+                }));
+            }
+        }
+    }
+    files
+}
+
+fn require_rules(scope: &Scope) -> Result<Vec<Rule>> {
+    let rules = load_rules(scope)?;
+    if rules.is_empty() {
+        let hint = match scope {
+            Scope::Global => "add <name>.md files",
+            Scope::Project(_) => "add <name>.md files or run `ai-cli rules import --project`",
+        };
+        return Err(exit::usage(format!(
+            "No rules in {} ({})",
+            canonical_dir(scope).display(),
+            hint
+        )));
+    }
+    Ok(rules)
+}
+
+/// Handle `rules list` command
+pub fn handle_list(scope: &Scope) -> Result<()> {
+    let rules = require_rules(scope)?;
+    println!("{} {}", "Ruleset:".bold(), canonical_dir(scope).display());
+    println!();
+    for rule in &rules {
+        let applies = if rule.always {
+            "always".to_string()
+        } else if let Some(globs) = &rule.globs {
+            globs.clone()
+        } else {
+            "on request".to_string()
+        };
+        println!(
+            "  {} {} {}",
+            "-".info(),
+            rule.name,
+            format!("({})", applies).muted()
+        );
+    }
+
+    let files = rule_files(scope, &rules);
+    if files.is_empty() {
+        println!();
+        println!(
+            "{}",
+            match scope {
+                Scope::Global =>
+                    "No Windsurf install found; Cursor global rules live in its settings",
+                Scope::Project(_) => "Neither Cursor nor Windsurf is installed",
+            }
+            .muted()
+        );
+        return Ok(());
+    }
+
+    println!();
+    for file in &files {
+        let (marker, state) = match file.status() {
+            FileStatus::InSync => ("✓".success(), "in sync".success()),
+            FileStatus::Drifted => ("!".warning(), "drifted".warning()),
+            FileStatus::Missing => ("-".muted(), "missing".muted()),
+        };
+        println!(
+            "  {} {:<9} {} {}",
+            marker,
+            file.product,
+            state,
+            file.path.display().to_string().muted()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `rules sync` command: write the ruleset in each product's format
+pub fn handle_sync(scope: &Scope) -> Result<Outcome> {
+    let rules = require_rules(scope)?;
+    let files = rule_files(scope, &rules);
+    if files.is_empty() {
+        println!("{} Neither Cursor nor Windsurf is installed", "!".warning());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let stale: Vec<_> = files
+        .iter()
+        .map(|f| (f, f.status()))
+        .filter(|(_, status)| *status != FileStatus::InSync)
+        .collect();
+    if stale.is_empty() {
+        println!("{} All rule files are in sync", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let drifted: Vec<_> = stale
+        .iter()
+        .filter(|(_, status)| *status == FileStatus::Drifted)
+        .collect();
+    if !drifted.is_empty() {
+        println!("{}", "These files differ from the ruleset:".bold());
+        for (file, _) in &drifted {
+            println!("  {} {}", "!".warning(), file.path.display());
+        }
+        if !prompt::confirm("Overwrite them?")? {
+            println!("Cancelled.");
+            return Ok(Outcome::NothingToDo);
+        }
+    }
+
+    let mut failed = 0;
+    for (file, _) in stale {
+        match logging::write_file(&file.path, &file.content) {
+            Ok(()) => {
+                println!(
+                    "{} {} ({})",
+                    "✓".success(),
+                    file.path.display(),
+                    file.product
+                );
+                if matches!(scope, Scope::Global) && file.content.len() > WINDSURF_GLOBAL_LIMIT {
+                    println!(
+                        "  {} {} characters; Windsurf only reads the first {}",
+                        "!".warning(),
+                        file.content.len(),
+                        WINDSURF_GLOBAL_LIMIT
+                    );
+                }
+            }
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), file.path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `rules import` command: seed the ruleset from existing Cursor or Windsurf project rules
+pub fn handle_import(scope: &Scope) -> Result<Outcome> {
+    let root = match scope {
+        Scope::Project(root) => root,
+        Scope::Global => {
+            return Err(exit::usage(
+                "Only project rules can be imported; run it with --project in a project that has .cursor/rules or .windsurf/rules",
+            ));
+        }
+    };
+
+    let mut rules = read_rule_dir(&root.join(".cursor/rules"), "mdc")?;
+    let mut source = root.join(".cursor/rules");
+    if rules.is_empty() {
+        rules = read_rule_dir(&root.join(".windsurf/rules"), "md")?;
+        source = root.join(".windsurf/rules");
+    }
+    if rules.is_empty() {
+        println!("No Cursor or Windsurf rules found in {}", root.display());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let dir = canonical_dir(scope);
+    let mut imported = 0;
+    for rule in &rules {
+        let path = dir.join(format!("{}.md", rule.name));
+        if path.exists() {
+            println!(
+                "{} {} already exists, skipping",
+                "-".muted(),
+                path.display()
+            );
+            continue;
+        }
+        logging::write_file(&path, &render_canonical(rule))?;
+        println!("{} {}", "✓".success(), path.display());
+        imported += 1;
+    }
+
+    println!();
+    println!(
+        "{} Imported {} rule(s) from {}",
+        "→".info(),
+        imported,
+        source.display()
+    );
+    Ok(if imported == 0 {
+        Outcome::NothingToDo
+    } else {
+        Outcome::Success
+    })
+}
+
+fn render_canonical(rule: &Rule) -> String {
+    let mut out = "---\n".to_string();
+    if let Some(description) = &rule.description {
+        out.push_str(&format!("description: {}\n", description));
+    }
+    if let Some(globs) = &rule.globs {
+        out.push_str(&format!("globs: {}\n", globs));
+    }
+    out.push_str(&format!("always: {}\n---\n\n{}\n", rule.always, rule.body));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_formats() {
+        let rule = Rule::parse(
+            "typescript",
+            "---\ndescription: TS style\nglobs: *.ts,*.tsx\nalways: false\n---\n\nUse strict mode.\n",
+        );
+        assert_eq!(rule.globs.as_deref(), Some("*.ts,*.tsx"));
+        assert!(!rule.always);
+
+        let cursor = render_cursor(&rule);
+        assert_eq!(
+            cursor,
+            "---\ndescription: TS style\nglobs: *.ts,*.tsx\nalwaysApply: false\n---\n\nUse strict mode.\n"
+        );
+        assert_eq!(Rule::parse("typescript", &cursor), rule);
+
+        let windsurf = render_windsurf(&rule);
+        assert!(windsurf.starts_with("---\ntrigger: glob\n"));
+        assert_eq!(Rule::parse("typescript", &windsurf), rule);
+        assert_eq!(Rule::parse("typescript", &render_canonical(&rule)), rule);
+    }
+
+    #[test]
+    fn plain_markdown_is_a_manual_rule() {
+        let rule = Rule::parse("tone", "Be concise.\n");
+        assert_eq!(rule.body, "Be concise.");
+        assert!(render_windsurf(&rule).contains("trigger: manual"));
+        assert_eq!(render_windsurf_global(&[rule]), "# tone\n\nBe concise.\n");
+    }
+}
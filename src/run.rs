@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+
+use crate::actions::find_tool;
+use crate::config::{self, Profile};
+use crate::exit::{self, Outcome};
+use crate::keys::{self, keychain};
+use crate::logging;
+use crate::paths;
+use crate::theme::Themed;
+use crate::tools::{self, Tool, installed_versions};
+use crate::versions::{fetch_latest_versions, is_newer_version};
+
+/// How long `--update-check` waits for the registries
+const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+/// `--update-check` looks each tool up at most once a day
+const UPDATE_CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// Binaries that accept `--model <name>`
+const MODEL_FLAG_BINARIES: &[&str] = &[
+    "claude",
+    "codex",
+    "gemini",
+    "opencode",
+    "copilot",
+    "cursor-agent",
+];
+
+/// Environment and arguments prepared for a tool
+#[derive(Debug, Default, PartialEq)]
+pub struct Launch {
+    pub env: BTreeMap<String, String>,
+    pub args: Vec<String>,
+    /// Variables filled from the keychain, reported without their values
+    pub keys: Vec<String>,
+}
+
+/// Resolve the profile's env vars, missing API keys and the model flag for `tool`
+///
+/// `lookup_env` and `lookup_key` stand in for the process environment and the
+/// keychain so the resolution can be tested.
+pub fn prepare(
+    tool: &Tool,
+    profile: Option<&Profile>,
+    model: Option<&str>,
+    args: &[String],
+    lookup_env: impl Fn(&str) -> Option<String>,
+    lookup_key: impl Fn(&str) -> Option<String>,
+) -> Result<Launch> {
+    let mut launch = Launch::default();
+    if let Some(profile) = profile {
+        launch.env.extend(profile.env.clone());
+    }
+
+    for provider in keys::catalog()
+        .into_iter()
+        .filter(|p| p.tools.contains(&tool.name.as_str()))
+    {
+        let already_set = provider
+            .env_vars
+            .iter()
+            .any(|var| launch.env.contains_key(*var) || lookup_env(var).is_some());
+        if already_set {
+            continue;
+        }
+        if let Some(secret) = lookup_key(provider.id) {
+            let var = provider.env_vars[0].to_string();
+            launch.env.insert(var.clone(), secret);
+            launch.keys.push(var);
+        }
+    }
+
+    let binary = tool.binary_name.as_deref().unwrap_or_default();
+    if let Some(model) = model.or(profile.and_then(|p| p.model.as_deref())) {
+        if !MODEL_FLAG_BINARIES.contains(&binary) {
+            bail!(
+                "{} has no --model flag; set its model with `ai-cli models set`",
+                tool.name
+            );
+        }
+        launch.args.push("--model".to_string());
+        launch.args.push(model.to_string());
+    }
+    launch.args.extend(args.iter().cloned());
+    Ok(launch)
+}
+
+/// When `--update-check` last looked up each tool, by tool name
+fn update_checks_path() -> PathBuf {
+    paths::state_home()
+        .join("ai-cli")
+        .join("run-update-checks.json")
+}
+
+fn load_update_checks() -> BTreeMap<String, i64> {
+    fs::read_to_string(update_checks_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_update_checks(checks: &BTreeMap<String, i64>) -> Result<()> {
+    let path = update_checks_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&path, serde_json::to_string(checks)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Print a hint when a newer version of `tool` is published
+///
+/// Skipped when `tool` was looked up less than a day ago, so wrappers from
+/// `ai-cli shell-init` don't hit the registries on every launch.
+async fn check_for_update(tool: &Tool) {
+    let now = chrono::Utc::now().timestamp();
+    let mut checks = load_update_checks();
+    if checks
+        .get(&tool.name)
+        .is_some_and(|checked_at| now - checked_at < UPDATE_CHECK_INTERVAL_SECS)
+    {
+        return;
+    }
+
+    let name = tool.name.clone();
+    let installed = tokio::task::spawn_blocking(move || {
+        installed_versions()
+            .into_iter()
+            .find(|v| v.name == name)
+            .and_then(|v| v.installed)
+    });
+    let check = async {
+        let (installed, latest) = tokio::join!(installed, fetch_latest_versions());
+        let installed = installed.ok().flatten()?;
+        let latest = latest.get(tool.name.as_str())?.clone()?;
+        Some((installed, latest))
+    };
+    // Offline or slow registries: try again next launch
+    let Ok(Some((installed, latest))) = tokio::time::timeout(UPDATE_CHECK_TIMEOUT, check).await
+    else {
+        return;
+    };
+    checks.insert(tool.name.clone(), now);
+    if let Err(e) = save_update_checks(&checks) {
+        logging::line(&format!("run: {:#}", e));
+    }
+    if is_newer_version(&latest, &installed) {
+        eprintln!(
+            "{} {} {} is available (you have {}); run `ai-cli apps update`",
+            "→".info(),
+            tool.name,
+            latest,
+            installed
+        );
+    }
+}
+
+/// Handle `run <tool>` command: launch the tool with the profile's environment
+pub async fn handle_run(
+    name: &str,
+    profile: Option<&str>,
+    model: Option<&str>,
+    update_check: bool,
+    args: &[String],
+) -> Result<Outcome> {
+    let all_tools = tools::catalog();
+    let tool = find_tool(&all_tools, name)
+        .ok_or_else(|| exit::usage(format!("Unknown tool '{}'", name)))?;
+    let binary = tool
+        .binary_name
+        .clone()
+        .ok_or_else(|| exit::usage(format!("{} has no command to run", tool.name)))?;
+    if !tool.is_installed() {
+        bail!(
+            "{} is not installed; install it with `ai-cli apps install {}`",
+            tool.name,
+            binary
+        );
+    }
+
+    let config = config::load()?;
+    let profile_name = profile
+        .map(str::to_string)
+        .or(config.active_profile.clone());
+    let profile = match &profile_name {
+        Some(name) => Some(
+            config
+                .profiles
+                .get(name)
+                .ok_or_else(|| exit::usage(format!("Profile '{}' not found", name)))?,
+        ),
+        None => None,
+    };
+
+    if update_check {
+        check_for_update(tool).await;
+    }
+
+    let launch = prepare(
+        tool,
+        profile,
+        model,
+        args,
+        |var| env::var(var).ok().filter(|v| !v.is_empty()),
+        keychain::get,
+    )?;
+
+    let mut details = Vec::new();
+    if let Some(name) = &profile_name {
+        details.push(format!("profile {}", name));
+    }
+    if !launch.keys.is_empty() {
+        details.push(format!("keys from keychain: {}", launch.keys.join(", ")));
+    }
+    if !details.is_empty() {
+        eprintln!("{} {}", "→".info(), details.join("; ").muted());
+    }
+    logging::line(&format!(
+        "run {} {} (env: {})",
+        binary,
+        launch.args.join(" "),
+        launch.env.keys().cloned().collect::<Vec<_>>().join(", ")
+    ));
+
+    let mut command = Command::new(&binary);
+    command.args(&launch.args).envs(&launch.env);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = command.exec();
+        Err(err).with_context(|| format!("Failed to run {}", binary))
+    }
+    #[cfg(not(unix))]
+    {
+        let status = command
+            .status()
+            .with_context(|| format!("Failed to run {}", binary))?;
+        Ok(if status.success() {
+            Outcome::Success
+        } else {
+            Outcome::Failure
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_missing_keys_and_adds_the_model_flag() {
+        let profile = Profile {
+            env: BTreeMap::from([(
+                "ANTHROPIC_BASE_URL".to_string(),
+                "https://proxy.example.com".to_string(),
+            )]),
+            model: Some("opus".to_string()),
+            ..Profile::default()
+        };
+        let launch = prepare(
+            &tools::claude_tool(),
+            Some(&profile),
+            None,
+            &["-c".to_string()],
+            |_| None,
+            |provider| (provider == "anthropic").then(|| "sk-ant-test".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(launch.env["ANTHROPIC_API_KEY"], "sk-ant-test");
+        assert_eq!(
+            launch.env["ANTHROPIC_BASE_URL"],
+            "https://proxy.example.com"
+        );
+        assert_eq!(launch.keys, vec!["ANTHROPIC_API_KEY"]);
+        assert_eq!(launch.args, vec!["--model", "opus", "-c"]);
+    }
+
+    #[test]
+    fn keeps_keys_already_in_the_environment() {
+        let launch = prepare(
+            &tools::gemini_tool(),
+            None,
+            Some("gemini-2.5-pro"),
+            &[],
+            |var| (var == "GOOGLE_API_KEY").then(|| "from-env".to_string()),
+            |_| Some("from-keychain".to_string()),
+        )
+        .unwrap();
+
+        assert!(launch.env.is_empty());
+        assert_eq!(launch.args, vec!["--model", "gemini-2.5-pro"]);
+    }
+}
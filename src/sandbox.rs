@@ -0,0 +1,351 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use colored::*;
+use serde_json::json;
+
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::logging;
+use crate::paths;
+use crate::settings::{read_json, read_toml};
+use crate::theme::Themed;
+
+/// A named combination of sandbox and approval settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preset {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Codex CLI `approval_policy`
+    pub approval: &'static str,
+    /// Codex CLI `sandbox_mode`
+    pub sandbox: &'static str,
+    /// Claude Code `permissions.defaultMode`
+    pub claude_mode: &'static str,
+}
+
+/// Returns all presets, from most to least restrictive
+pub fn presets() -> Vec<Preset> {
+    vec![
+        Preset {
+            name: "read-only",
+            description: "plan and read, never write",
+            approval: "on-request",
+            sandbox: "read-only",
+            claude_mode: "plan",
+        },
+        Preset {
+            name: "safe",
+            description: "ask before edits and commands",
+            approval: "untrusted",
+            sandbox: "workspace-write",
+            claude_mode: "default",
+        },
+        Preset {
+            name: "auto",
+            description: "edit the workspace freely, ask for anything else",
+            approval: "on-request",
+            sandbox: "workspace-write",
+            claude_mode: "acceptEdits",
+        },
+        Preset {
+            name: "yolo",
+            description: "no sandbox, no approvals",
+            approval: "never",
+            sandbox: "danger-full-access",
+            claude_mode: "bypassPermissions",
+        },
+    ]
+}
+
+pub fn find_preset(name: &str) -> Option<Preset> {
+    presets()
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Claude,
+    Codex,
+}
+
+/// A settings file holding an agent's sandbox settings
+#[derive(Debug, Clone)]
+pub struct SandboxTarget {
+    pub agent: &'static str,
+    pub path: PathBuf,
+    pub tool: Tool,
+}
+
+/// Returns the settings files for `scope`
+///
+/// Codex CLI only reads these settings from `~/.codex/config.toml`, so
+/// projects only get a Claude Code file.
+pub fn targets(scope: &Scope) -> Vec<SandboxTarget> {
+    match scope {
+        Scope::Global => {
+            let home = paths::home();
+            vec![
+                SandboxTarget {
+                    agent: "Claude Code",
+                    path: home.join(".claude/settings.json"),
+                    tool: Tool::Claude,
+                },
+                SandboxTarget {
+                    agent: "Codex CLI",
+                    path: home.join(".codex/config.toml"),
+                    tool: Tool::Codex,
+                },
+            ]
+        }
+        Scope::Project(root) => vec![SandboxTarget {
+            agent: "Claude Code",
+            path: root.join(".claude/settings.local.json"),
+            tool: Tool::Claude,
+        }],
+    }
+}
+
+impl SandboxTarget {
+    /// The agent's config directory exists (always true for project files)
+    pub fn is_installed(&self, scope: &Scope) -> bool {
+        matches!(scope, Scope::Project(_)) || self.path.parent().is_some_and(|p| p.exists())
+    }
+
+    /// Current settings as `(name, value)` pairs
+    pub fn current(&self) -> Result<Vec<(&'static str, Option<String>)>> {
+        Ok(match self.tool {
+            Tool::Claude => {
+                let json = read_json(&self.path)?;
+                vec![(
+                    "defaultMode",
+                    json["permissions"]["defaultMode"]
+                        .as_str()
+                        .map(str::to_string),
+                )]
+            }
+            Tool::Codex => {
+                let doc = read_toml(&self.path)?;
+                let get = |key: &str| doc.get(key).and_then(|v| v.as_str()).map(str::to_string);
+                vec![
+                    ("approval_policy", get("approval_policy")),
+                    ("sandbox_mode", get("sandbox_mode")),
+                ]
+            }
+        })
+    }
+
+    /// The preset's values for this agent, in the order of `current`
+    fn expected(&self, preset: &Preset) -> Vec<&'static str> {
+        match self.tool {
+            Tool::Claude => vec![preset.claude_mode],
+            Tool::Codex => vec![preset.approval, preset.sandbox],
+        }
+    }
+
+    /// The preset the current settings match, if any
+    pub fn matching_preset(&self) -> Result<Option<Preset>> {
+        let current = self.current()?;
+        Ok(presets().into_iter().find(|preset| {
+            self.expected(preset)
+                .iter()
+                .zip(&current)
+                .all(|(expected, (_, actual))| actual.as_deref() == Some(*expected))
+        }))
+    }
+
+    /// Write the preset's settings, keeping everything else
+    pub fn apply(&self, preset: &Preset) -> Result<()> {
+        let before = fs::read_to_string(&self.path).unwrap_or_default();
+        let after = match self.tool {
+            Tool::Claude => {
+                let mut json = read_json(&self.path)?;
+                if !json.is_object() {
+                    json = json!({});
+                }
+                if !json["permissions"].is_object() {
+                    json["permissions"] = json!({});
+                }
+                json["permissions"]["defaultMode"] = json!(preset.claude_mode);
+                format!("{}\n", serde_json::to_string_pretty(&json)?)
+            }
+            Tool::Codex => {
+                let mut doc = read_toml(&self.path)?;
+                doc["approval_policy"] = toml_edit::value(preset.approval);
+                doc["sandbox_mode"] = toml_edit::value(preset.sandbox);
+                doc.to_string()
+            }
+        };
+        logging::write_config(&self.path, &before, &after)
+    }
+}
+
+/// Handle `sandbox show` command
+pub fn handle_show(scope: &Scope) -> Result<()> {
+    for target in targets(scope) {
+        if !target.is_installed(scope) {
+            println!("{} {}", target.agent.bold(), "not installed".muted());
+            continue;
+        }
+        let preset = match target.matching_preset()? {
+            Some(preset) => preset.name.accent().to_string(),
+            None => "custom".muted().to_string(),
+        };
+        println!(
+            "{} {} {}",
+            target.agent.bold(),
+            preset,
+            target.path.display().to_string().muted()
+        );
+        for (name, value) in target.current()? {
+            let value = value.unwrap_or_else(|| "(unset)".muted().to_string());
+            println!("  {:<16} {}", name, value);
+        }
+    }
+    if matches!(scope, Scope::Project(_)) {
+        println!(
+            "{} Codex CLI has no project settings; see `ai-cli sandbox` for its global mode",
+            "→".info()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `sandbox list` command
+pub fn handle_list() -> Result<()> {
+    println!("{}", "Presets:".bold());
+    for preset in presets() {
+        println!(
+            "  {} {:<10} {}",
+            "-".info(),
+            preset.name,
+            preset.description.muted()
+        );
+        println!(
+            "    {:<12} {}",
+            "Claude Code".muted(),
+            format!("defaultMode = {}", preset.claude_mode).muted()
+        );
+        println!(
+            "    {:<12} {}",
+            "Codex CLI".muted(),
+            format!(
+                "approval_policy = {}, sandbox_mode = {}",
+                preset.approval, preset.sandbox
+            )
+            .muted()
+        );
+    }
+    println!();
+    println!(
+        "{} Switch with `ai-cli sandbox use <preset>` (add --project for this project only)",
+        "→".info()
+    );
+    Ok(())
+}
+
+/// Handle `sandbox use <preset>` command
+pub fn handle_use(name: &str, scope: &Scope) -> Result<Outcome> {
+    let preset = find_preset(name).ok_or_else(|| {
+        let names: Vec<_> = presets().iter().map(|p| p.name).collect();
+        exit::usage(format!(
+            "Unknown preset '{}'. Available: {}",
+            name,
+            names.join(", ")
+        ))
+    })?;
+
+    let mut changed = 0;
+    let mut failed = 0;
+    for target in targets(scope) {
+        if !target.is_installed(scope) {
+            continue;
+        }
+        if target.matching_preset()?.as_ref() == Some(&preset) {
+            println!(
+                "{} {} {}",
+                "✓".success(),
+                target.agent,
+                format!("already {}", preset.name).muted()
+            );
+            continue;
+        }
+        match target.apply(&preset) {
+            Ok(()) => {
+                println!(
+                    "{} {} → {} {}",
+                    "✓".success(),
+                    target.agent,
+                    preset.name,
+                    format!("({})", target.path.display()).muted()
+                );
+                changed += 1;
+            }
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), target.agent, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if preset.name == "yolo" && changed > 0 {
+        println!(
+            "  {} Agents now run commands without asking and outside any sandbox",
+            "!".warning()
+        );
+    }
+    if failed == 0 && changed == 0 {
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_presets_and_recognizes_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let codex = SandboxTarget {
+            agent: "Codex CLI",
+            path: dir.path().join("config.toml"),
+            tool: Tool::Codex,
+        };
+        fs::write(
+            &codex.path,
+            "model = \"o3\"\nsandbox_mode = \"read-only\"\n",
+        )
+        .unwrap();
+        assert_eq!(codex.matching_preset().unwrap(), None);
+
+        let yolo = find_preset("yolo").unwrap();
+        codex.apply(&yolo).unwrap();
+        assert_eq!(codex.matching_preset().unwrap(), Some(yolo));
+        let content = fs::read_to_string(&codex.path).unwrap();
+        assert!(content.starts_with("model = \"o3\"\n"));
+
+        let claude = SandboxTarget {
+            agent: "Claude Code",
+            path: dir.path().join(".claude/settings.local.json"),
+            tool: Tool::Claude,
+        };
+        let read_only = find_preset("read-only").unwrap();
+        claude.apply(&read_only).unwrap();
+        assert_eq!(claude.matching_preset().unwrap(), Some(read_only));
+    }
+
+    #[test]
+    fn presets_use_valid_codex_values() {
+        let policy_of = |p: &Preset| crate::settings::Policy {
+            approval: Some(p.approval.to_string()),
+            sandbox: Some(p.sandbox.to_string()),
+            ..Default::default()
+        };
+        for preset in presets() {
+            assert!(policy_of(&preset).validate().is_ok(), "{}", preset.name);
+        }
+    }
+}
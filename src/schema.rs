@@ -0,0 +1,152 @@
+//! Checking configs against the JSON schemas their tools publish.
+//!
+//! Before ai-cli writes one of these files, the new content is validated
+//! against the tool's schema, fetched through the HTTP cache. Problems the
+//! file already had are left to the user; an edit that adds one is refused,
+//! since the tool would otherwise reject or ignore the file at startup. When
+//! the schema can't be fetched the file is written unchecked. Set
+//! `schema_check = false` in config.toml to turn this off.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use serde_json::Value;
+
+use crate::config;
+use crate::http;
+use crate::logging;
+
+/// Schemas change with tool releases; refetched once a day
+const SCHEMA_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Config files with a published schema, by the end of their path
+const SCHEMAS: &[(&str, &str)] = &[
+    (
+        ".claude/settings.json",
+        "https://json.schemastore.org/claude-code-settings.json",
+    ),
+    (
+        ".claude/settings.local.json",
+        "https://json.schemastore.org/claude-code-settings.json",
+    ),
+    (
+        ".gemini/settings.json",
+        "https://raw.githubusercontent.com/google-gemini/gemini-cli/main/schemas/settings.schema.json",
+    ),
+];
+
+/// Schema URL for the config at `path`, if its tool publishes one
+pub fn schema_url(path: &Path) -> Option<&'static str> {
+    SCHEMAS
+        .iter()
+        .find(|(suffix, _)| path.ends_with(suffix))
+        .map(|(_, url)| *url)
+}
+
+/// Fetch a schema from synchronous code running inside the multi-threaded
+/// runtime; without one (tests) there is no schema
+fn fetch(url: &str) -> Option<Value> {
+    let handle = tokio::runtime::Handle::try_current().ok()?;
+    if handle.runtime_flavor() != tokio::runtime::RuntimeFlavor::MultiThread {
+        return None;
+    }
+    let result =
+        tokio::task::block_in_place(|| handle.block_on(http::get_json::<Value>(url, SCHEMA_TTL)));
+    match result {
+        Ok(schema) => Some(schema),
+        Err(e) => {
+            logging::line(&format!("schema: not checking against {}: {:#}", url, e));
+            None
+        }
+    }
+}
+
+/// Problems in `after` that `before` doesn't have, as `location: message`
+fn new_problems(schema: &Value, before: Option<&Value>, after: &Value) -> Result<Vec<String>> {
+    let validator =
+        jsonschema::validator_for(schema).map_err(|e| anyhow::anyhow!("Unusable schema: {}", e))?;
+    let describe = |instance| -> Vec<String> {
+        validator
+            .iter_errors(instance)
+            .map(|e| {
+                let location = e.instance_path().as_str();
+                let location = if location.is_empty() { "/" } else { location };
+                format!("{}: {}", location, e)
+            })
+            .collect()
+    };
+    let existing = before.map(describe).unwrap_or_default();
+    Ok(describe(after)
+        .into_iter()
+        .filter(|problem| !existing.contains(problem))
+        .collect())
+}
+
+/// Refuse `after` as the new content of `path` if it breaks the tool's schema
+/// in a way `before` didn't
+pub fn check(path: &Path, before: &str, after: &str) -> Result<()> {
+    let Some(url) = schema_url(path) else {
+        return Ok(());
+    };
+    if config::load().is_ok_and(|c| c.schema_check == Some(false)) {
+        return Ok(());
+    }
+    let Ok(instance) = serde_json::from_str::<Value>(after) else {
+        bail!("Refusing to write {}: not valid JSON", path.display());
+    };
+    let Some(schema) = fetch(url) else {
+        return Ok(());
+    };
+    let before = serde_json::from_str::<Value>(before).ok();
+    let problems = match new_problems(&schema, before.as_ref(), &instance) {
+        Ok(problems) => problems,
+        Err(e) => {
+            logging::line(&format!("schema: {}: {:#}", url, e));
+            return Ok(());
+        }
+    };
+    if !problems.is_empty() {
+        bail!(
+            "Refusing to write {}: the change doesn't match the tool's schema ({})\n  {}",
+            path.display(),
+            url,
+            problems.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_only_problems_an_edit_adds() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "theme": { "enum": ["dark", "light"] },
+                "model": { "type": "string" }
+            }
+        });
+        let before = json!({ "theme": "neon" });
+        let after = json!({ "theme": "neon", "model": 4 });
+
+        let problems = new_problems(&schema, Some(&before), &after).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("/model: "));
+        assert!(
+            new_problems(&schema, Some(&before), &before)
+                .unwrap()
+                .is_empty()
+        );
+
+        assert_eq!(
+            schema_url(Path::new("/home/me/.gemini/settings.json")),
+            Some(SCHEMAS[2].1)
+        );
+        assert_eq!(schema_url(Path::new("/home/me/.codex/config.toml")), None);
+    }
+}
@@ -0,0 +1,319 @@
+//! `search <term>`: one query across tools, MCP servers and skills.
+//!
+//! The built-in catalogs and installed skills are searched locally; the MCP
+//! registry and GitHub's skill repositories are queried at the same time and
+//! skipped with a note when they can't be reached. Set `GITHUB_TOKEN` for
+//! GitHub's higher rate limit.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::exit::Outcome;
+use crate::http;
+use crate::logging;
+use crate::mcp::servers;
+use crate::output;
+use crate::skills::{agents, discovery};
+use crate::theme::Themed;
+use crate::tools;
+
+/// Official MCP server registry
+const MCP_REGISTRY_URL: &str = "https://registry.modelcontextprotocol.io/v0/servers";
+/// Repositories tagged as skill collections are found through GitHub search
+pub const GITHUB_SEARCH_URL: &str = "https://api.github.com/search/repositories";
+/// GitHub topics skill collections are tagged with
+const SKILL_TOPICS: &[&str] = &["claude-skills", "agent-skills"];
+/// Results taken from each remote source
+const REMOTE_LIMIT: usize = 10;
+/// Remote results are reused for repeated searches within this time
+const SEARCH_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// What a result is, in the order results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    Tool,
+    McpServer,
+    McpRegistry,
+    Skill,
+    SkillRepo,
+}
+
+impl Kind {
+    fn title(self) -> &'static str {
+        match self {
+            Kind::Tool => "Tools",
+            Kind::McpServer => "MCP servers",
+            Kind::McpRegistry => "MCP registry",
+            Kind::Skill => "Installed skills",
+            Kind::SkillRepo => "Skill repositories",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Hit {
+    kind: Kind,
+    name: String,
+    description: String,
+    /// Command or link to act on the result
+    hint: Option<String>,
+}
+
+fn contains(haystack: &str, term: &str) -> bool {
+    haystack.to_lowercase().contains(&term.to_lowercase())
+}
+
+/// Matches in the tool and MCP catalogs and the installed skills
+fn local_hits(term: &str) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for tool in tools::catalog() {
+        let binary = tool.binary_name.clone().unwrap_or_default();
+        if contains(&tool.name, term) || contains(&binary, term) {
+            hits.push(Hit {
+                kind: Kind::Tool,
+                name: binary.clone(),
+                description: tool.name.clone(),
+                hint: Some(format!("ai-cli apps install {}", binary)),
+            });
+        }
+    }
+
+    for server in servers::catalog() {
+        if contains(&server.id, term)
+            || contains(&server.name, term)
+            || contains(&server.description, term)
+        {
+            hits.push(Hit {
+                kind: Kind::McpServer,
+                name: server.id.clone(),
+                description: server.description,
+                hint: Some(format!("ai-cli mcp enable {}", server.id)),
+            });
+        }
+    }
+
+    let mut skills: Vec<(discovery::Skill, Vec<&str>)> = Vec::new();
+    let catalog = agents::catalog();
+    for agent in &catalog {
+        for skill in discovery::list_installed_skills(&agent.skills_path).unwrap_or_default() {
+            match skills.iter_mut().find(|(s, _)| s.name == skill.name) {
+                Some((_, ids)) => ids.push(agent.id),
+                None => skills.push((skill, vec![agent.id])),
+            }
+        }
+    }
+    for (skill, ids) in skills {
+        let description = skill.description.unwrap_or_default();
+        if contains(&skill.name, term) || contains(&description, term) {
+            hits.push(Hit {
+                kind: Kind::Skill,
+                name: skill.name,
+                description: format!("{} ({})", description, ids.join(", "))
+                    .trim()
+                    .to_string(),
+                hint: None,
+            });
+        }
+    }
+    hits
+}
+
+/// Servers in the MCP registry matching `term`
+async fn registry_hits(url: &str, term: &str) -> Result<Vec<Hit>> {
+    let url = reqwest::Url::parse_with_params(
+        url,
+        &[("search", term), ("limit", &REMOTE_LIMIT.to_string())],
+    )?;
+    let body: Value = http::get_json(url.as_str(), SEARCH_TTL).await?;
+    let entries = body["servers"].as_array().cloned().unwrap_or_default();
+    Ok(entries
+        .iter()
+        // Newer registry versions wrap each entry as `{ "server": {...}, "_meta": {...} }`
+        .map(|entry| entry.get("server").unwrap_or(entry))
+        .filter_map(|server| {
+            Some(Hit {
+                kind: Kind::McpRegistry,
+                name: server["name"].as_str()?.to_string(),
+                description: server["description"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                hint: server["repository"]["url"].as_str().map(str::to_string),
+            })
+        })
+        .take(REMOTE_LIMIT)
+        .collect())
+}
+
+/// A repository of skills, from GitHub search or a skills registry index
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SkillRepo {
+    /// `owner/name`
+    pub repo: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub stars: u64,
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+/// GitHub repositories tagged with one of the skill topics matching `term`,
+/// most starred first
+pub async fn skill_repos(url: &str, term: &str) -> Result<Vec<SkillRepo>> {
+    let mut repos: Vec<SkillRepo> = Vec::new();
+    // Search qualifiers can't be combined with OR, so each topic is one query
+    for topic in SKILL_TOPICS {
+        let url = reqwest::Url::parse_with_params(
+            url,
+            &[
+                ("q", format!("{} topic:{}", term, topic)),
+                ("sort", "stars".to_string()),
+                ("per_page", REMOTE_LIMIT.to_string()),
+            ],
+        )?;
+        let body: Value = http::get_json(url.as_str(), SEARCH_TTL).await?;
+        for item in body["items"].as_array().into_iter().flatten() {
+            let Some(name) = item["full_name"].as_str() else {
+                continue;
+            };
+            if repos.iter().any(|r| r.repo == name) {
+                continue;
+            }
+            repos.push(SkillRepo {
+                repo: name.to_string(),
+                description: item["description"].as_str().unwrap_or_default().to_string(),
+                stars: item["stargazers_count"].as_u64().unwrap_or(0),
+                topics: item["topics"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|t| t.as_str().map(str::to_string))
+                    .collect(),
+            });
+        }
+    }
+    repos.sort_by_key(|r| std::cmp::Reverse(r.stars));
+    repos.truncate(REMOTE_LIMIT);
+    Ok(repos)
+}
+
+/// Skill repositories on GitHub matching `term`
+async fn skill_repo_hits(url: &str, term: &str) -> Result<Vec<Hit>> {
+    Ok(skill_repos(url, term)
+        .await?
+        .into_iter()
+        .map(|repo| Hit {
+            kind: Kind::SkillRepo,
+            description: format!("★ {} {}", repo.stars, repo.description)
+                .trim()
+                .to_string(),
+            hint: Some(format!("ai-cli skills install {}", repo.repo)),
+            name: repo.repo,
+        })
+        .collect())
+}
+
+/// Handle `search <term>` command
+pub async fn handle_search(term: &str) -> Result<Outcome> {
+    let spinner = output::spinner(&format!("Searching for '{}'...", term));
+    let (registry, repos) = tokio::join!(
+        registry_hits(MCP_REGISTRY_URL, term),
+        skill_repo_hits(GITHUB_SEARCH_URL, term)
+    );
+    let mut hits = local_hits(term);
+    spinner.finish_and_clear();
+
+    let mut unavailable = Vec::new();
+    for (source, result) in [("MCP registry", registry), ("GitHub", repos)] {
+        match result {
+            Ok(found) => hits.extend(found),
+            Err(e) => {
+                logging::line(&format!("search {}: {:#}", source, e));
+                unavailable.push(source);
+            }
+        }
+    }
+    hits.sort_by_key(|hit| hit.kind);
+
+    let width = hits.iter().map(|h| h.name.len()).max().unwrap_or(0);
+    let mut kind = None;
+    for hit in &hits {
+        if kind != Some(hit.kind) {
+            if kind.is_some() {
+                println!();
+            }
+            println!("{}", hit.kind.title().bold());
+            kind = Some(hit.kind);
+        }
+        println!("  {:<width$}  {}", hit.name.info(), hit.description.muted());
+        if let Some(hint) = &hit.hint {
+            println!("  {:<width$}  {} {}", "", "→".info(), hint);
+        }
+    }
+
+    if !unavailable.is_empty() {
+        if !hits.is_empty() {
+            println!();
+        }
+        println!(
+            "{} Not searched: {} (unreachable)",
+            "?".muted(),
+            unavailable.join(", ")
+        );
+    }
+    if hits.is_empty() {
+        println!("No matches for '{}'", term);
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[test]
+    fn finds_catalog_entries_case_insensitively() {
+        let hits = local_hits("LINEAR");
+        assert!(hits.iter().any(|h| h.kind == Kind::McpServer
+            && h.name == "linear"
+            && h.hint.as_deref() == Some("ai-cli mcp enable linear")));
+        assert!(local_hits("codex").iter().any(|h| h.kind == Kind::Tool));
+    }
+
+    #[tokio::test]
+    async fn reads_wrapped_and_plain_registry_entries() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/v0/servers")
+                    .query_param("search", "sentry");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        r#"{"servers":[
+                            {"server":{"name":"io.github.getsentry/sentry-mcp","description":"Sentry issues",
+                              "repository":{"url":"https://github.com/getsentry/sentry-mcp"}},"_meta":{}},
+                            {"name":"com.example/sentry-lite"}]}"#,
+                    );
+            })
+            .await;
+
+        let url = server.url("/v0/servers");
+        let hits = registry_hits(&url, "sentry").await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].name, "io.github.getsentry/sentry-mcp");
+        assert_eq!(
+            hits[0].hint.as_deref(),
+            Some("https://github.com/getsentry/sentry-mcp")
+        );
+        assert_eq!(hits[1].description, "");
+    }
+}
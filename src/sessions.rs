@@ -0,0 +1,405 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use colored::*;
+use serde_json::Value;
+
+use crate::exit;
+use crate::paths;
+use crate::theme::Themed;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub role: Role,
+    pub text: String,
+}
+
+/// A conversation read from a tool's on-disk transcript
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub tool: &'static str,
+    pub id: String,
+    pub path: PathBuf,
+    pub cwd: Option<String>,
+    pub started: Option<DateTime<Local>>,
+    pub messages: Vec<Message>,
+}
+
+impl Session {
+    /// First line of the first user message
+    pub fn title(&self) -> String {
+        self.messages
+            .iter()
+            .find(|m| m.role == Role::User)
+            .and_then(|m| m.text.lines().find(|l| !l.trim().is_empty()))
+            .map(|l| truncate(l.trim(), 70))
+            .unwrap_or_else(|| "(no messages)".to_string())
+    }
+
+    fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(8)]
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max - 1).collect::<String>())
+    }
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Local>> {
+    value
+        .as_str()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Local))
+}
+
+/// Text blocks of a message `content`, which is either a string or an array of blocks
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| {
+                matches!(
+                    b["type"].as_str(),
+                    Some("text" | "input_text" | "output_text")
+                )
+            })
+            .filter_map(|b| b["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn push_message(messages: &mut Vec<Message>, role: Role, text: String) {
+    // Tool output and injected context (e.g. <environment_context>) aren't conversation
+    let trimmed = text.trim();
+    if !trimmed.is_empty() && !trimmed.starts_with('<') {
+        messages.push(Message {
+            role,
+            text: trimmed.to_string(),
+        });
+    }
+}
+
+/// Parse a Claude Code transcript (`~/.claude/projects/<project>/<id>.jsonl`)
+pub fn parse_claude(path: &Path, content: &str) -> Option<Session> {
+    let mut session = Session {
+        tool: "Claude Code",
+        id: path.file_stem()?.to_string_lossy().to_string(),
+        path: path.to_path_buf(),
+        cwd: None,
+        started: None,
+        messages: Vec::new(),
+    };
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if session.cwd.is_none() {
+            session.cwd = entry["cwd"].as_str().map(str::to_string);
+        }
+        if session.started.is_none() {
+            session.started = timestamp(&entry["timestamp"]);
+        }
+        let role = match entry["type"].as_str() {
+            Some("user") => Role::User,
+            Some("assistant") => Role::Assistant,
+            _ => continue,
+        };
+        push_message(
+            &mut session.messages,
+            role,
+            content_text(&entry["message"]["content"]),
+        );
+    }
+    (!session.messages.is_empty()).then_some(session)
+}
+
+/// Parse a Codex CLI session log (`~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl`)
+pub fn parse_codex(path: &Path, content: &str) -> Option<Session> {
+    let mut session = Session {
+        tool: "Codex CLI",
+        id: path.file_stem()?.to_string_lossy().to_string(),
+        path: path.to_path_buf(),
+        cwd: None,
+        started: None,
+        messages: Vec::new(),
+    };
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let payload = &entry["payload"];
+        if entry["type"] == "session_meta" {
+            if let Some(id) = payload["id"].as_str() {
+                session.id = id.to_string();
+            }
+            session.cwd = payload["cwd"].as_str().map(str::to_string);
+            session.started = timestamp(&payload["timestamp"]).or(timestamp(&entry["timestamp"]));
+            continue;
+        }
+        if entry["type"] != "response_item" || payload["type"] != "message" {
+            continue;
+        }
+        let role = match payload["role"].as_str() {
+            Some("user") => Role::User,
+            Some("assistant") => Role::Assistant,
+            _ => continue,
+        };
+        push_message(
+            &mut session.messages,
+            role,
+            content_text(&payload["content"]),
+        );
+    }
+    (!session.messages.is_empty()).then_some(session)
+}
+
+/// Parse a Gemini CLI chat (`~/.gemini/tmp/<project>/chats/session-*.json`)
+pub fn parse_gemini(path: &Path, content: &str) -> Option<Session> {
+    let chat: Value = serde_json::from_str(content).ok()?;
+    let mut session = Session {
+        tool: "Gemini CLI",
+        id: chat["sessionId"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| Some(path.file_stem()?.to_string_lossy().to_string()))?,
+        path: path.to_path_buf(),
+        cwd: None,
+        started: timestamp(&chat["startTime"]),
+        messages: Vec::new(),
+    };
+    for message in chat["messages"].as_array()? {
+        let role = match message["type"].as_str() {
+            Some("user") => Role::User,
+            Some("gemini") => Role::Assistant,
+            _ => continue,
+        };
+        push_message(
+            &mut session.messages,
+            role,
+            content_text(&message["content"]),
+        );
+    }
+    (!session.messages.is_empty()).then_some(session)
+}
+
+/// Files with the given extension anywhere under `dir`
+fn find_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(find_files(&path, extension));
+        } else if path.extension().is_some_and(|e| e == extension) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+type Parser = fn(&Path, &str) -> Option<Session>;
+
+/// All sessions of all tools, newest first
+pub fn discover() -> Vec<Session> {
+    let home = paths::home();
+    let sources: [(PathBuf, &str, Parser); 3] = [
+        (home.join(".claude/projects"), "jsonl", parse_claude),
+        (home.join(".codex/sessions"), "jsonl", parse_codex),
+        (home.join(".gemini/tmp"), "json", parse_gemini),
+    ];
+
+    let mut sessions: Vec<Session> = sources
+        .iter()
+        .flat_map(|(dir, extension, parse)| {
+            find_files(dir, extension).into_iter().filter_map(|path| {
+                let content = fs::read_to_string(&path).ok()?;
+                parse(&path, &content)
+            })
+        })
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.started));
+    sessions
+}
+
+fn matches_tool(session: &Session, tool: Option<&str>) -> bool {
+    tool.is_none_or(|t| session.tool.to_lowercase().contains(&t.to_lowercase()))
+}
+
+fn print_session_line(session: &Session) {
+    let started = session
+        .started
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".repeat(16));
+    println!(
+        "{}  {}  {:<11}  {}",
+        session.short_id().accent(),
+        started.muted(),
+        session.tool,
+        session.title()
+    );
+    if let Some(cwd) = &session.cwd {
+        println!("    {}", cwd.muted());
+    }
+}
+
+/// Handle `sessions list` command
+pub fn handle_list(tool: Option<&str>, limit: usize) -> Result<()> {
+    let sessions: Vec<Session> = discover()
+        .into_iter()
+        .filter(|s| matches_tool(s, tool))
+        .collect();
+    if sessions.is_empty() {
+        println!("No sessions found.");
+        return Ok(());
+    }
+    for session in sessions.iter().take(limit) {
+        print_session_line(session);
+    }
+    if sessions.len() > limit {
+        println!();
+        println!(
+            "{}",
+            format!("{} more; use --limit to show more", sessions.len() - limit).muted()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `sessions show <id>` command
+pub fn handle_show(id: &str) -> Result<()> {
+    let sessions = discover();
+    let matching: Vec<&Session> = sessions.iter().filter(|s| s.id.starts_with(id)).collect();
+    let session = match matching.as_slice() {
+        [session] => *session,
+        [] => return Err(exit::usage(format!("No session with id '{}'", id))),
+        _ => {
+            return Err(exit::usage(format!(
+                "Session id '{}' is ambiguous ({} matches); give more characters",
+                id,
+                matching.len()
+            )));
+        }
+    };
+
+    println!("{} {}", session.tool.bold(), session.id.muted());
+    if let Some(cwd) = &session.cwd {
+        println!("{}", cwd.muted());
+    }
+    println!("{}", session.path.display().to_string().muted());
+    println!();
+    for message in &session.messages {
+        let label = match message.role {
+            Role::User => "User".info().bold(),
+            Role::Assistant => "Assistant".accent().bold(),
+        };
+        println!("{}", label);
+        println!("{}", message.text);
+        println!();
+    }
+    Ok(())
+}
+
+/// Text around the first case-insensitive match of `query` in `text`
+fn snippet(text: &str, query: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    let query = query.to_lowercase();
+    let line = lower
+        .lines()
+        .zip(text.lines())
+        .find(|(l, _)| l.contains(&query))
+        .map(|(_, original)| original.trim())?;
+    Some(truncate(line, 100))
+}
+
+/// Handle `sessions search <query>` command
+pub fn handle_search(query: &str, tool: Option<&str>) -> Result<bool> {
+    let mut found = 0;
+    for session in discover().iter().filter(|s| matches_tool(s, tool)) {
+        let hits: Vec<String> = session
+            .messages
+            .iter()
+            .filter_map(|m| snippet(&m.text, query))
+            .collect();
+        if hits.is_empty() {
+            continue;
+        }
+        found += 1;
+        print_session_line(session);
+        for hit in hits.iter().take(3) {
+            println!("    {} {}", "→".info(), hit);
+        }
+        if hits.len() > 3 {
+            println!("    {}", format!("{} more matches", hits.len() - 3).muted());
+        }
+        println!();
+    }
+
+    if found == 0 {
+        println!("No sessions mention '{}'.", query);
+        return Ok(false);
+    }
+    println!("{}", "Open one with `ai-cli sessions show <id>`".muted());
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_claude_and_codex_transcripts() {
+        let claude = [
+            r#"{"type":"user","cwd":"/work/api","timestamp":"2025-11-20T12:00:00Z","message":{"role":"user","content":"Fix the auth bug in login"}}"#,
+            r#"{"type":"assistant","timestamp":"2025-11-20T12:00:05Z","message":{"content":[{"type":"thinking","thinking":"..."},{"type":"text","text":"The token check was inverted."}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","content":"ok"}]}}"#,
+        ]
+        .join("\n");
+        let session = parse_claude(Path::new("/p/abc123.jsonl"), &claude).unwrap();
+        assert_eq!(session.id, "abc123");
+        assert_eq!(session.cwd.as_deref(), Some("/work/api"));
+        assert_eq!(session.messages.len(), 2);
+        assert_eq!(session.title(), "Fix the auth bug in login");
+
+        let codex = [
+            r#"{"timestamp":"2025-11-20T12:00:00Z","type":"session_meta","payload":{"id":"0199-uuid","cwd":"/work/web","timestamp":"2025-11-20T12:00:00Z"}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>...</environment_context>"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Add dark mode"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"function_call","name":"shell"}}"#,
+        ]
+        .join("\n");
+        let session = parse_codex(Path::new("/s/rollout.jsonl"), &codex).unwrap();
+        assert_eq!(session.id, "0199-uuid");
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.title(), "Add dark mode");
+    }
+
+    #[test]
+    fn parses_gemini_chats_and_finds_snippets() {
+        let chat = r#"{"sessionId":"g-1","startTime":"2025-11-20T12:00:00Z","messages":[{"type":"user","content":"why does the AUTH middleware fail?"},{"type":"gemini","content":"Because the header is missing."},{"type":"info","content":"x"}]}"#;
+        let session = parse_gemini(Path::new("/g/session.json"), chat).unwrap();
+        assert_eq!(session.id, "g-1");
+        assert_eq!(session.messages.len(), 2);
+        assert_eq!(
+            snippet(&session.messages[0].text, "auth").as_deref(),
+            Some("why does the AUTH middleware fail?")
+        );
+        assert_eq!(snippet(&session.messages[1].text, "auth"), None);
+    }
+}
@@ -0,0 +1,523 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::logging;
+use crate::paths;
+use crate::prompt;
+use crate::theme::Themed;
+
+const APPROVAL_POLICIES: &[&str] = &["untrusted", "on-failure", "on-request", "never"];
+const SANDBOX_MODES: &[&str] = &["read-only", "workspace-write", "danger-full-access"];
+
+/// Permission policy applied to every agent, from `[policy]` in config.toml
+///
+/// Unset fields leave the agents' own settings alone.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Policy {
+    /// Claude Code `permissions.allow` rules
+    pub allow: Option<Vec<String>>,
+    /// Claude Code `permissions.deny` rules
+    pub deny: Option<Vec<String>>,
+    /// Codex CLI `approval_policy`
+    pub approval: Option<String>,
+    /// Codex CLI `sandbox_mode`
+    pub sandbox: Option<String>,
+    /// Copilot CLI `trusted_folders`
+    pub trusted_folders: Option<Vec<String>>,
+}
+
+impl Policy {
+    /// Used when the config has no `[policy]` table
+    pub fn hardened() -> Self {
+        Policy {
+            allow: None,
+            deny: Some(
+                [
+                    "Read(./.env)",
+                    "Read(./.env.*)",
+                    "Read(./secrets/**)",
+                    "Read(~/.ssh/**)",
+                    "Bash(sudo:*)",
+                ]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ),
+            approval: Some("on-request".to_string()),
+            sandbox: Some("workspace-write".to_string()),
+            trusted_folders: Some(Vec::new()),
+        }
+    }
+
+    /// The configured policy, or the hardened default
+    pub fn load() -> Result<Self> {
+        let policy = config::load()?.policy.unwrap_or_else(Policy::hardened);
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        check_choice("approval", self.approval.as_deref(), APPROVAL_POLICIES)?;
+        check_choice("sandbox", self.sandbox.as_deref(), SANDBOX_MODES)
+    }
+}
+
+fn check_choice(key: &str, value: Option<&str>, choices: &[&str]) -> Result<()> {
+    match value {
+        Some(value) if !choices.contains(&value) => Err(exit::usage(format!(
+            "Invalid policy {} '{}'. Expected one of: {}",
+            key,
+            value,
+            choices.join(", ")
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// A setting that differs from the policy
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deviation {
+    pub setting: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentKind {
+    Claude,
+    Codex,
+    Copilot,
+}
+
+/// An agent's settings file the policy is applied to
+#[derive(Debug, Clone)]
+pub struct PolicyTarget {
+    pub agent: &'static str,
+    pub path: PathBuf,
+    pub kind: AgentKind,
+}
+
+/// Returns the settings files of all agents with permission settings
+pub fn targets() -> Vec<PolicyTarget> {
+    let home = paths::home();
+    vec![
+        PolicyTarget {
+            agent: "Claude Code",
+            path: home.join(".claude/settings.json"),
+            kind: AgentKind::Claude,
+        },
+        PolicyTarget {
+            agent: "Codex CLI",
+            path: home.join(".codex/config.toml"),
+            kind: AgentKind::Codex,
+        },
+        PolicyTarget {
+            agent: "Copilot CLI",
+            path: home.join(".copilot/config.json"),
+            kind: AgentKind::Copilot,
+        },
+    ]
+}
+
+pub(crate) fn read_json(path: &Path) -> Result<Value> {
+    match fs::read_to_string(path) {
+        Ok(content) if !content.trim().is_empty() => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON in {}", path.display())),
+        _ => Ok(json!({})),
+    }
+}
+
+pub(crate) fn read_toml(path: &Path) -> Result<toml_edit::DocumentMut> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .parse()
+        .with_context(|| format!("Failed to parse TOML in {}", path.display()))
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn list_deviation(setting: &str, expected: &[String], actual: &[String]) -> Option<Deviation> {
+    let mut sorted_expected = expected.to_vec();
+    let mut sorted_actual = actual.to_vec();
+    sorted_expected.sort();
+    sorted_actual.sort();
+    if sorted_expected == sorted_actual {
+        return None;
+    }
+    let missing: Vec<_> = expected.iter().filter(|e| !actual.contains(e)).collect();
+    let extra: Vec<_> = actual.iter().filter(|a| !expected.contains(a)).collect();
+    let mut actual_desc = Vec::new();
+    if !missing.is_empty() {
+        actual_desc.push(format!(
+            "missing {}",
+            missing
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    if !extra.is_empty() {
+        actual_desc.push(format!(
+            "extra {}",
+            extra
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    Some(Deviation {
+        setting: setting.to_string(),
+        expected: format!("{} rule(s)", expected.len()),
+        actual: format!("{} ({})", actual.len(), actual_desc.join("; ")),
+    })
+}
+
+fn value_deviation(setting: &str, expected: &str, actual: Option<&str>) -> Option<Deviation> {
+    (actual != Some(expected)).then(|| Deviation {
+        setting: setting.to_string(),
+        expected: expected.to_string(),
+        actual: actual.unwrap_or("(unset)").to_string(),
+    })
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => paths::home().join(rest).display().to_string(),
+        None => path.to_string(),
+    }
+}
+
+impl PolicyTarget {
+    /// The agent's config directory exists
+    pub fn is_installed(&self) -> bool {
+        self.path.parent().is_some_and(|p| p.exists())
+    }
+
+    /// Settings that differ from the policy
+    pub fn audit(&self, policy: &Policy) -> Result<Vec<Deviation>> {
+        let mut deviations = Vec::new();
+        match self.kind {
+            AgentKind::Claude => {
+                let json = read_json(&self.path)?;
+                let permissions = &json["permissions"];
+                if let Some(allow) = &policy.allow {
+                    deviations.extend(list_deviation(
+                        "permissions.allow",
+                        allow,
+                        &string_list(permissions.get("allow")),
+                    ));
+                }
+                if let Some(deny) = &policy.deny {
+                    deviations.extend(list_deviation(
+                        "permissions.deny",
+                        deny,
+                        &string_list(permissions.get("deny")),
+                    ));
+                }
+            }
+            AgentKind::Codex => {
+                let doc = read_toml(&self.path)?;
+                if let Some(approval) = &policy.approval {
+                    deviations.extend(value_deviation(
+                        "approval_policy",
+                        approval,
+                        doc.get("approval_policy").and_then(|v| v.as_str()),
+                    ));
+                }
+                if let Some(sandbox) = &policy.sandbox {
+                    deviations.extend(value_deviation(
+                        "sandbox_mode",
+                        sandbox,
+                        doc.get("sandbox_mode").and_then(|v| v.as_str()),
+                    ));
+                }
+            }
+            AgentKind::Copilot => {
+                if let Some(folders) = &policy.trusted_folders {
+                    let json = read_json(&self.path)?;
+                    let expected: Vec<String> = folders.iter().map(|f| expand_home(f)).collect();
+                    deviations.extend(list_deviation(
+                        "trusted_folders",
+                        &expected,
+                        &string_list(json.get("trusted_folders")),
+                    ));
+                }
+            }
+        }
+        Ok(deviations)
+    }
+
+    /// Rewrite the settings the policy covers, keeping everything else
+    pub fn apply(&self, policy: &Policy) -> Result<()> {
+        let before = fs::read_to_string(&self.path).unwrap_or_default();
+        let after = match self.kind {
+            AgentKind::Claude => {
+                let mut json = read_json(&self.path)?;
+                if !json["permissions"].is_object() {
+                    json["permissions"] = json!({});
+                }
+                if let Some(allow) = &policy.allow {
+                    json["permissions"]["allow"] = json!(allow);
+                }
+                if let Some(deny) = &policy.deny {
+                    json["permissions"]["deny"] = json!(deny);
+                }
+                format!("{}\n", serde_json::to_string_pretty(&json)?)
+            }
+            AgentKind::Codex => {
+                let mut doc = read_toml(&self.path)?;
+                if let Some(approval) = &policy.approval {
+                    doc["approval_policy"] = toml_edit::value(approval.as_str());
+                }
+                if let Some(sandbox) = &policy.sandbox {
+                    doc["sandbox_mode"] = toml_edit::value(sandbox.as_str());
+                }
+                doc.to_string()
+            }
+            AgentKind::Copilot => {
+                let mut json = read_json(&self.path)?;
+                if let Some(folders) = &policy.trusted_folders {
+                    let expanded: Vec<String> = folders.iter().map(|f| expand_home(f)).collect();
+                    json["trusted_folders"] = json!(expanded);
+                }
+                format!("{}\n", serde_json::to_string_pretty(&json)?)
+            }
+        };
+
+        logging::write_config(&self.path, &before, &after)
+    }
+}
+
+/// Handle `settings show` command
+pub fn handle_show() -> Result<()> {
+    let configured = config::load()?.policy.is_some();
+    let policy = Policy::load()?;
+    println!(
+        "{} {}",
+        "Policy:".bold(),
+        if configured {
+            format!("[policy] in {}", config::config_path().display())
+        } else {
+            "built-in hardened default (add [policy] to config.toml to change it)".to_string()
+        }
+        .muted()
+    );
+    println!();
+
+    let print_list = |label: &str, items: &Option<Vec<String>>| match items {
+        Some(items) if items.is_empty() => println!("  {:<16} {}", label, "(none)".muted()),
+        Some(items) => {
+            println!("  {:<16} {}", label, items[0]);
+            for item in &items[1..] {
+                println!("  {:<16} {}", "", item);
+            }
+        }
+        None => println!("  {:<16} {}", label, "(not managed)".muted()),
+    };
+    let print_value = |label: &str, value: &Option<String>| match value {
+        Some(value) => println!("  {:<16} {}", label, value),
+        None => println!("  {:<16} {}", label, "(not managed)".muted()),
+    };
+
+    print_list("allow", &policy.allow);
+    print_list("deny", &policy.deny);
+    print_value("approval", &policy.approval);
+    print_value("sandbox", &policy.sandbox);
+    print_list("trusted_folders", &policy.trusted_folders);
+    Ok(())
+}
+
+fn print_deviations(target: &PolicyTarget, deviations: &[Deviation]) {
+    if deviations.is_empty() {
+        println!(
+            "{} {} {}",
+            "✓".success(),
+            target.agent,
+            "matches the policy".muted()
+        );
+        return;
+    }
+    println!(
+        "{} {} {}",
+        "!".warning(),
+        target.agent,
+        target.path.display().to_string().muted()
+    );
+    for deviation in deviations {
+        println!(
+            "    {}: expected {}, found {}",
+            deviation.setting, deviation.expected, deviation.actual
+        );
+    }
+}
+
+/// Handle `settings audit` command. Returns `false` when any agent deviates.
+pub fn handle_audit() -> Result<bool> {
+    let policy = Policy::load()?;
+    let mut deviating = 0;
+    for target in targets() {
+        if !target.is_installed() {
+            println!(
+                "{} {} {}",
+                "-".muted(),
+                target.agent,
+                "not installed".muted()
+            );
+            continue;
+        }
+        let deviations = target.audit(&policy)?;
+        if !deviations.is_empty() {
+            deviating += 1;
+        }
+        print_deviations(&target, &deviations);
+    }
+
+    if deviating > 0 {
+        println!();
+        println!(
+            "{} {} agent(s) deviate from the policy; `ai-cli settings apply` fixes them",
+            "→".info(),
+            deviating
+        );
+    }
+    Ok(deviating == 0)
+}
+
+/// Apply `policy` to every installed agent that deviates from it
+pub fn apply_policy(policy: &Policy) -> Result<Outcome> {
+    let mut pending = Vec::new();
+    for target in targets().into_iter().filter(|t| t.is_installed()) {
+        let deviations = target.audit(policy)?;
+        if !deviations.is_empty() {
+            print_deviations(&target, &deviations);
+            pending.push(target);
+        }
+    }
+    if pending.is_empty() {
+        println!("{} Every installed agent matches the policy", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!();
+    if !prompt::confirm("Apply the policy to these agents?")? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+    for target in pending {
+        match target.apply(policy) {
+            Ok(()) => println!(
+                "{} {} {}",
+                "✓".success(),
+                target.agent,
+                format!("({})", target.path.display()).muted()
+            ),
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".error(), target.agent, e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Handle `settings apply` command
+pub fn handle_apply() -> Result<Outcome> {
+    apply_policy(&Policy::load()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audits_and_applies_claude_and_codex_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = Policy {
+            allow: None,
+            deny: Some(vec!["Read(./.env)".to_string()]),
+            approval: Some("on-request".to_string()),
+            sandbox: Some("read-only".to_string()),
+            trusted_folders: None,
+        };
+
+        let claude = PolicyTarget {
+            agent: "Claude Code",
+            path: dir.path().join("settings.json"),
+            kind: AgentKind::Claude,
+        };
+        fs::write(
+            &claude.path,
+            r#"{"model":"opus","permissions":{"allow":["Bash(ls)"],"deny":["Bash(rm:*)"]}}"#,
+        )
+        .unwrap();
+        let deviations = claude.audit(&policy).unwrap();
+        assert_eq!(deviations.len(), 1);
+        assert_eq!(
+            deviations[0].actual,
+            "1 (missing Read(./.env); extra Bash(rm:*))"
+        );
+        claude.apply(&policy).unwrap();
+        assert!(claude.audit(&policy).unwrap().is_empty());
+        let json = read_json(&claude.path).unwrap();
+        assert_eq!(json["model"], "opus");
+        assert_eq!(json["permissions"]["allow"], json!(["Bash(ls)"]));
+
+        let codex = PolicyTarget {
+            agent: "Codex CLI",
+            path: dir.path().join("config.toml"),
+            kind: AgentKind::Codex,
+        };
+        fs::write(&codex.path, "approval_policy = \"never\"\n").unwrap();
+        let deviations = codex.audit(&policy).unwrap();
+        assert_eq!(
+            deviations,
+            vec![
+                Deviation {
+                    setting: "approval_policy".to_string(),
+                    expected: "on-request".to_string(),
+                    actual: "never".to_string(),
+                },
+                Deviation {
+                    setting: "sandbox_mode".to_string(),
+                    expected: "read-only".to_string(),
+                    actual: "(unset)".to_string(),
+                },
+            ]
+        );
+        codex.apply(&policy).unwrap();
+        assert!(codex.audit(&policy).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_sandbox_modes() {
+        let policy = Policy {
+            sandbox: Some("yolo".to_string()),
+            ..Policy::default()
+        };
+        assert!(policy.validate().is_err());
+        assert!(Policy::hardened().validate().is_ok());
+    }
+}
@@ -0,0 +1,368 @@
+//! `setup <manifest>`: bring a fresh machine to a declared state in one go.
+//!
+//! The manifest is turned into a [`Snapshot`] and applied the same way as
+//! `ai-cli import`, so running it again only does what is still missing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::Deserialize;
+
+use crate::actions::find_tool;
+use crate::exit::{self, Outcome};
+use crate::instructions::{self, Scope, TargetStatus};
+use crate::logging;
+use crate::mcp::{servers, targets};
+use crate::skills::{agents, discovery};
+use crate::snapshot::{self, SkillEntry, Snapshot, ToolEntry};
+use crate::theme::Themed;
+use crate::tools;
+
+/// A machine setup manifest (`ai-cli-setup.toml`)
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Tool names, optionally pinned as `name@version`
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub mcp: McpSection,
+    #[serde(default)]
+    pub skills: Vec<SkillSource>,
+    /// Markdown file (relative to the manifest) used as the global instructions
+    #[serde(default)]
+    pub instructions: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpSection {
+    /// Catalog server ids to enable
+    #[serde(default)]
+    pub servers: Vec<String>,
+    /// Tools to enable them in (default: every manifest tool that supports MCP)
+    #[serde(default)]
+    pub targets: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillSource {
+    /// Repository to install from (e.g., 'anthropics/skills')
+    pub source: String,
+    pub names: Vec<String>,
+    /// Skill agent ids (default: claude)
+    #[serde(default)]
+    pub agents: Vec<String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse TOML in {}", path.display()))
+    }
+}
+
+/// Split `name@version`; scoped npm-style names keep their leading `@`
+fn parse_tool(spec: &str) -> (&str, Option<&str>) {
+    match spec.rfind('@') {
+        Some(at) if at > 0 => (&spec[..at], Some(&spec[at + 1..])),
+        _ => (spec, None),
+    }
+}
+
+/// The snapshot `manifest` describes, validated against the catalogs
+fn to_snapshot(manifest: &Manifest) -> Result<Snapshot> {
+    let catalog = tools::catalog();
+    let mut snapshot = Snapshot::default();
+    let mut binaries = Vec::new();
+    for spec in &manifest.tools {
+        let (name, version) = parse_tool(spec);
+        let tool = find_tool(&catalog, name)
+            .ok_or_else(|| exit::usage(format!("Unknown tool '{}' in manifest", name)))?;
+        binaries.extend(tool.binary_name.clone());
+        snapshot.tools.push(ToolEntry {
+            name: tool.name.clone(),
+            version: version.map(str::to_string),
+        });
+    }
+
+    for id in &manifest.mcp.servers {
+        if servers::find(id).is_none() {
+            return Err(exit::usage(format!(
+                "Unknown MCP server '{}' in manifest",
+                id
+            )));
+        }
+    }
+    let mcp_targets = targets::catalog();
+    let selected: Vec<&targets::McpTarget> = if manifest.mcp.targets.is_empty() {
+        mcp_targets
+            .iter()
+            .filter(|t| binaries.iter().any(|b| b == t.binary_name))
+            .collect()
+    } else {
+        manifest
+            .mcp
+            .targets
+            .iter()
+            .map(|name| {
+                mcp_targets
+                    .iter()
+                    .find(|t| {
+                        t.binary_name.eq_ignore_ascii_case(name)
+                            || t.name.eq_ignore_ascii_case(name)
+                    })
+                    .ok_or_else(|| {
+                        exit::usage(format!("Unknown MCP target '{}' in manifest", name))
+                    })
+            })
+            .collect::<Result<_>>()?
+    };
+    if !manifest.mcp.servers.is_empty() {
+        for target in selected {
+            snapshot
+                .mcp
+                .insert(target.name.to_string(), manifest.mcp.servers.clone());
+        }
+    }
+
+    for skills in &manifest.skills {
+        let agent_ids = if skills.agents.is_empty() {
+            vec!["claude".to_string()]
+        } else {
+            skills.agents.clone()
+        };
+        for id in &agent_ids {
+            if agents::find(id).is_none() {
+                return Err(exit::usage(format!(
+                    "Unknown skills agent '{}' in manifest",
+                    id
+                )));
+            }
+        }
+        snapshot
+            .skills
+            .extend(skills.names.iter().map(|name| SkillEntry {
+                name: name.clone(),
+                source: Some(skills.source.clone()),
+                agents: agent_ids.clone(),
+            }));
+    }
+    Ok(snapshot)
+}
+
+/// Make `content` the canonical global instructions. Returns whether it changed.
+fn set_canonical_instructions(content: &str) -> Result<bool> {
+    let path = Scope::Global.canonical_path();
+    let before = fs::read_to_string(&path).unwrap_or_default();
+    if before == content {
+        return Ok(false);
+    }
+    logging::write_config(&path, &before, content)?;
+    Ok(true)
+}
+
+/// One item of the manifest and whether this machine has it
+#[derive(Debug, Clone, PartialEq)]
+struct Check {
+    item: String,
+    converged: bool,
+    detail: Option<String>,
+}
+
+fn check(item: String, converged: bool, detail: Option<String>) -> Check {
+    Check {
+        item,
+        converged,
+        detail,
+    }
+}
+
+/// Compare the machine with `snapshot` and the instructions
+fn convergence(snapshot: &Snapshot, instructions: Option<&str>) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let installed = tools::installed_versions();
+    for entry in &snapshot.tools {
+        let version = installed
+            .iter()
+            .find(|v| v.name == entry.name)
+            .and_then(|v| v.installed.as_deref())
+            // "2.0.14 (Claude Code)" -> "2.0.14"
+            .and_then(|v| v.split_whitespace().next());
+        let item = format!("tool {}", entry.name);
+        checks.push(match (version, entry.version.as_deref()) {
+            (None, _) => check(item, false, Some("not installed".to_string())),
+            (Some(version), Some(pin)) if version != pin => check(
+                item,
+                false,
+                Some(format!("{} installed, manifest pins {}", version, pin)),
+            ),
+            (Some(version), _) => check(item, true, Some(version.to_string())),
+        });
+    }
+
+    let mcp_targets = targets::catalog();
+    for (target_name, ids) in &snapshot.mcp {
+        let Some(target) = mcp_targets.iter().find(|t| t.name == target_name) else {
+            continue;
+        };
+        for server in ids.iter().filter_map(|id| servers::find(id)) {
+            checks.push(check(
+                format!("MCP {} in {}", server.id, target.name),
+                target.is_server_enabled(&server).unwrap_or(false),
+                None,
+            ));
+        }
+    }
+
+    for skill in &snapshot.skills {
+        for agent in skill.agents.iter().filter_map(|id| agents::find(id)) {
+            let present = discovery::list_installed_skills(&agent.skills_path)
+                .unwrap_or_default()
+                .iter()
+                .any(|s| s.name == skill.name);
+            checks.push(check(
+                format!("skill {} for {}", skill.name, agent.name),
+                present,
+                None,
+            ));
+        }
+    }
+
+    if let Some(content) = instructions {
+        for target in Scope::Global.targets() {
+            // Agents that were never set up are skipped by `instructions sync`
+            if target.path.parent().is_some_and(|p| !p.exists()) {
+                continue;
+            }
+            let status = instructions::status_of(&target, content);
+            checks.push(check(
+                format!("instructions {}", target.path.display()),
+                status == TargetStatus::InSync,
+                (status != TargetStatus::InSync).then(|| format!("{:?}", status).to_lowercase()),
+            ));
+        }
+    }
+    checks
+}
+
+/// Handle `setup <manifest>` command
+pub async fn handle_setup(path: &Path) -> Result<Outcome> {
+    if !path.exists() {
+        return Err(exit::usage(format!(
+            "Manifest {} not found",
+            path.display()
+        )));
+    }
+    let manifest = Manifest::load(path)?;
+    let snapshot = to_snapshot(&manifest)?;
+    let instructions = match &manifest.instructions {
+        Some(file) => {
+            let file = path.parent().unwrap_or(Path::new(".")).join(file);
+            Some(
+                fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?,
+            )
+        }
+        None => None,
+    };
+
+    let mut changed = snapshot::apply(&snapshot).await? != Outcome::NothingToDo;
+    if let Some(content) = &instructions {
+        println!();
+        if set_canonical_instructions(content)? {
+            println!(
+                "{} Updated {}",
+                "✓".success(),
+                Scope::Global.canonical_path().display()
+            );
+            changed = true;
+        }
+        changed |= instructions::handle_sync(&Scope::Global)? != Outcome::NothingToDo;
+    }
+
+    let spinner = crate::output::spinner("Checking the result...");
+    let checks = convergence(&snapshot, instructions.as_deref());
+    spinner.finish_and_clear();
+
+    println!();
+    println!("{}", "Convergence:".bold());
+    for check in &checks {
+        let marker = if check.converged {
+            "✓".success()
+        } else {
+            "✗".error()
+        };
+        print!("  {} {}", marker, check.item);
+        if let Some(detail) = &check.detail {
+            print!(" {}", format!("({})", detail).muted());
+        }
+        println!();
+    }
+    let pending = checks.iter().filter(|c| !c.converged).count();
+    println!();
+    if pending == 0 {
+        println!(
+            "{} {} of {} item(s) match {}",
+            "✓".success(),
+            checks.len(),
+            checks.len(),
+            path.display()
+        );
+        return Ok(if changed {
+            Outcome::Success
+        } else {
+            Outcome::NothingToDo
+        });
+    }
+    println!(
+        "{} {} of {} item(s) don't match {} yet",
+        "!".warning(),
+        pending,
+        checks.len(),
+        path.display()
+    );
+    Ok(Outcome::PartialFailure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_snapshot_from_manifest() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            tools = ["claude@2.0.14", "codex"]
+            instructions = "AGENTS.md"
+
+            [mcp]
+            servers = ["linear"]
+
+            [[skills]]
+            source = "anthropics/skills"
+            names = ["pdf", "docx"]
+            agents = ["claude", "codex"]
+            "#,
+        )
+        .unwrap();
+        let snapshot = to_snapshot(&manifest).unwrap();
+
+        assert_eq!(snapshot.tools[0].name, "Claude Code");
+        assert_eq!(snapshot.tools[0].version.as_deref(), Some("2.0.14"));
+        assert_eq!(snapshot.tools[1].version, None);
+        let targets: Vec<&String> = snapshot.mcp.keys().collect();
+        assert_eq!(targets, vec!["Claude Code", "Codex CLI"]);
+        assert_eq!(snapshot.skills.len(), 2);
+        assert_eq!(snapshot.skills[1].agents, vec!["claude", "codex"]);
+
+        let unknown: Manifest = toml::from_str("tools = [\"vim\"]").unwrap();
+        assert!(to_snapshot(&unknown).is_err());
+        assert_eq!(parse_tool("@scope/pkg"), ("@scope/pkg", None));
+    }
+}
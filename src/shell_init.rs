@@ -0,0 +1,88 @@
+//! `shell-init`: shell functions that launch managed tools through `ai-cli run`.
+//!
+//! Each wrapper injects the active profile's environment and keychain keys and
+//! checks for updates at most once a day, then execs the real binary.
+
+use anyhow::Result;
+use clap_complete::Shell;
+
+use crate::exit;
+use crate::tools;
+
+/// Binaries of the catalog's tools, in catalog order
+fn binaries() -> Vec<String> {
+    let mut binaries: Vec<String> = Vec::new();
+    for tool in tools::catalog() {
+        if let Some(binary) = tool.binary_name
+            && !binaries.contains(&binary)
+        {
+            binaries.push(binary);
+        }
+    }
+    binaries
+}
+
+/// Wrapper functions for `binaries`, defined only for those on PATH
+fn script(shell: Shell, binaries: &[String]) -> Result<String> {
+    let mut out = String::from("# Generated by `ai-cli shell-init`\n");
+    for binary in binaries {
+        let wrapper = match shell {
+            Shell::Bash | Shell::Zsh => format!(
+                "if command -v {b} >/dev/null 2>&1; then\n    \
+                 {b}() {{ command ai-cli run --update-check {b} -- \"$@\"; }}\n\
+                 fi\n",
+                b = binary
+            ),
+            Shell::Fish => format!(
+                "if command -q {b}\n    \
+                 function {b} --wraps {b}\n        \
+                 command ai-cli run --update-check {b} -- $argv\n    \
+                 end\n\
+                 end\n",
+                b = binary
+            ),
+            other => {
+                return Err(exit::usage(format!(
+                    "shell-init supports bash, zsh and fish, not {}",
+                    other
+                )));
+            }
+        };
+        out.push_str(&wrapper);
+    }
+    Ok(out)
+}
+
+/// Handle `shell-init [shell]` command
+pub fn handle_shell_init(shell: Option<Shell>) -> Result<()> {
+    let shell = shell.or_else(Shell::from_env).ok_or_else(|| {
+        exit::usage(
+            "Could not detect your shell from $SHELL; pass it, e.g. `ai-cli shell-init zsh`",
+        )
+    })?;
+    print!("{}", script(shell, &binaries())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_installed_binaries_per_shell() {
+        let binaries = vec!["claude".to_string(), "cursor-agent".to_string()];
+        let zsh = script(Shell::Zsh, &binaries).unwrap();
+        assert!(zsh.contains(
+            "if command -v claude >/dev/null 2>&1; then\n    \
+             claude() { command ai-cli run --update-check claude -- \"$@\"; }\nfi\n"
+        ));
+        assert!(zsh.contains("cursor-agent() {"));
+
+        let fish = script(Shell::Fish, &binaries).unwrap();
+        assert!(fish.contains(
+            "if command -q claude\n    function claude --wraps claude\n        \
+             command ai-cli run --update-check claude -- $argv\n    end\nend\n"
+        ));
+        assert!(script(Shell::PowerShell, &binaries).is_err());
+    }
+}
@@ -1,46 +1,81 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
+use inquire::MultiSelect;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::TempDir;
 
 use super::agents::{self, SkillAgent};
-use super::discovery;
+use super::auth;
+use super::conflict;
+use super::discovery::{self, Skill};
+use super::lock::Lockfile;
+use super::manifest::{Manifest, Subscription};
+use super::skillignore::{self, skill_files};
+use super::toggle::{disabled_path, is_disabled};
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::journal;
+use crate::logging;
+use crate::notify::{self, Event};
+use crate::paths;
+use crate::prompt;
+use crate::prune::format_size;
+use crate::theme::Themed;
 
-/// Handle `skills list` command
-pub fn handle_list(agent_filter: Option<&str>) -> Result<()> {
+/// Handle `skills list [--project]` command
+pub fn handle_list(agent_filter: Option<&str>, scope: &Scope) -> Result<()> {
     let agents = if let Some(agent_id) = agent_filter {
-        vec![agents::find(agent_id).with_context(|| format!("Unknown agent: {}", agent_id))?]
+        vec![
+            agents::find_in(scope, agent_id)
+                .ok_or_else(|| exit::usage(format!("Unknown agent: {}", agent_id)))?,
+        ]
     } else {
-        agents::catalog()
+        agents::catalog_for(scope)
     };
+    if let Scope::Project(root) = scope {
+        println!(
+            "{}",
+            format!("Project skills in {}", root.display()).muted()
+        );
+        println!();
+    }
 
     for agent in &agents {
         println!("{}", agent.name.bold());
 
         if !agent.is_installed() {
-            println!("  {}", "(not installed)".dimmed());
+            println!("  {}", "(not installed)".muted());
             println!();
             continue;
         }
 
         let skills = discovery::list_installed_skills(&agent.skills_path)?;
+        let disabled = discovery::list_installed_skills(&agent.skills_path.join(".disabled"))?;
 
-        if skills.is_empty() {
-            println!("  {}", "(no skills installed)".dimmed());
+        if skills.is_empty() && disabled.is_empty() {
+            println!("  {}", "(no skills installed)".muted());
         } else {
             for skill in skills {
-                print!("  {} {}", "-".cyan(), skill.name);
+                print!("  {} {}", "-".info(), skill.name);
+                if let Some(version) = &skill.version {
+                    print!(" {}", format!("v{}", version).muted());
+                }
                 if let Some(desc) = &skill.description {
-                    // Truncate description if too long
-                    let truncated = if desc.len() > 60 {
-                        format!("{}...", &desc[..57])
-                    } else {
-                        desc.clone()
-                    };
-                    print!(" - {}", truncated.dimmed());
+                    print!(" - {}", one_line(desc, 60).muted());
                 }
                 println!();
             }
+            for skill in disabled {
+                println!(
+                    "  {} {}",
+                    "-".muted(),
+                    format!("{} (disabled)", skill.name).muted()
+                );
+            }
         }
         println!();
     }
@@ -48,65 +83,247 @@ pub fn handle_list(agent_filter: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-/// Handle `skills install <repo>` command
-pub fn handle_install(repo: &str, agent_filter: Option<&str>) -> Result<()> {
-    // Parse repo input (owner/repo or full URL)
-    let repo_url = parse_repo_url(repo)?;
+/// The agent named by `--agent`, or every installed agent
+fn target_agents(agent_filter: Option<&str>, scope: &Scope) -> Result<Vec<SkillAgent>> {
+    let agents: Vec<SkillAgent> = if let Some(agent_id) = agent_filter {
+        vec![
+            agents::find_in(scope, agent_id)
+                .ok_or_else(|| exit::usage(format!("Unknown agent: {}", agent_id)))?,
+        ]
+    } else {
+        agents::catalog_for(scope)
+            .into_iter()
+            .filter(|a| a.is_installed())
+            .collect()
+    };
 
-    // Clone to temp directory
-    println!("{} Cloning {}...", "->".cyan(), repo);
-    let temp_dir = TempDir::new().context("Failed to create temp directory")?;
+    if agents.is_empty() {
+        anyhow::bail!("No AI agents installed to install skills to");
+    }
+    Ok(agents)
+}
 
-    let status = Command::new("git")
-        .args([
-            "clone",
-            "--depth",
-            "1",
-            &repo_url,
-            temp_dir.path().to_str().unwrap(),
-        ])
-        .status()
-        .context("Failed to run git clone")?;
+/// Which of a repository's skills `skills install` installs
+#[derive(Debug, Default)]
+pub struct SkillSelection {
+    /// `--only`; empty for no filter
+    pub only: Vec<String>,
+    /// `--exclude`
+    pub exclude: Vec<String>,
+    /// `--all`: install every skill without asking
+    pub all: bool,
+}
 
-    if !status.success() {
-        anyhow::bail!("git clone failed for {}", repo);
+/// Skills picked by `--only` and `--exclude` (empty: no filter), refusing
+/// names the repository doesn't have
+fn select_skills(skills: Vec<Skill>, only: &[String], exclude: &[String]) -> Result<Vec<Skill>> {
+    if let Some(unknown) = only
+        .iter()
+        .chain(exclude)
+        .find(|name| !skills.iter().any(|s| &s.name == *name))
+    {
+        let available: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+        return Err(exit::usage(format!(
+            "No skill named '{}' in the repository. Available: {}",
+            unknown,
+            available.join(", ")
+        )));
+    }
+    let selected: Vec<Skill> = skills
+        .into_iter()
+        .filter(|s| only.is_empty() || only.contains(&s.name))
+        .filter(|s| !exclude.contains(&s.name))
+        .collect();
+    if selected.is_empty() {
+        return Err(exit::usage(
+            "--only and --exclude leave no skills to install",
+        ));
     }
+    Ok(selected)
+}
 
-    // Discover skills in repo
-    let skills = discovery::discover_skills(temp_dir.path())?;
+/// One line of at most `max` characters
+fn one_line(text: &str, max: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > max {
+        format!("{}...", text.chars().take(max - 3).collect::<String>())
+    } else {
+        text
+    }
+}
 
-    if skills.is_empty() {
-        anyhow::bail!("No skills found in repository (no SKILL.md files)");
+/// Let the user tick which of several `skills` to install; every one of
+/// them when there's no terminal to ask on
+fn pick_skills(skills: Vec<Skill>) -> Result<Vec<Skill>> {
+    if skills.len() < 2 || prompt::is_non_interactive() || !std::io::stdin().is_terminal() {
+        return Ok(skills);
     }
+    let options: Vec<String> = skills
+        .iter()
+        .map(|skill| match &skill.description {
+            Some(description) => format!("{} - {}", skill.name, one_line(description, 60)),
+            None => skill.name.clone(),
+        })
+        .collect();
+    let picked = MultiSelect::new(
+        &format!("{} skills found; which to install?", skills.len()),
+        options,
+    )
+    .with_help_message("↑↓ to move, space to select, → all, enter to confirm")
+    .raw_prompt()
+    .context("Install cancelled")?;
+    let indexes: Vec<usize> = picked.iter().map(|option| option.index).collect();
+    Ok(skills
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| indexes.contains(i))
+        .map(|(_, skill)| skill)
+        .collect())
+}
 
-    println!("{} Found {} skill(s):", "->".cyan(), skills.len());
-    for skill in &skills {
-        println!("  {} {}", "-".cyan(), skill.name);
+/// Handle `skills install <repo> [--ref REF] [--only ...] [--exclude ...] [--all] [--project]` command
+///
+/// With several skills to choose from and no `--only` or `--all`, asks which
+/// to install. The ref becomes part of the recorded source
+/// (`owner/repo#ref`), so `skills update` stays on it. A different skill
+/// already installed under the same name is only replaced with `force` or
+/// when the user says so.
+pub fn handle_install(
+    repo: &str,
+    git_ref: Option<&str>,
+    agent_filter: Option<&str>,
+    selection: &SkillSelection,
+    copy: bool,
+    force: bool,
+    scope: &Scope,
+) -> Result<Outcome> {
+    let source = match git_ref {
+        Some(_) if split_ref(repo).1.is_some() => {
+            return Err(exit::usage(
+                "Give the ref either as owner/repo#ref or with --ref, not both",
+            ));
+        }
+        Some(git_ref) => format!("{}#{}", repo, git_ref),
+        None => repo.to_string(),
+    };
+    let repo = source.as_str();
+    let agents = target_agents(agent_filter, scope)?;
+    let temp_dir = clone_skills(repo)?;
+    let mut skills = select_skills(
+        discovery::discover_skills(temp_dir.path())?,
+        &selection.only,
+        &selection.exclude,
+    )?;
+    if selection.only.is_empty() && !selection.all {
+        skills = pick_skills(skills)?;
+        if skills.is_empty() {
+            println!("{}", "No skills selected.".warning());
+            return Ok(Outcome::NothingToDo);
+        }
+    }
+    // Renamed copies have to outlive the install
+    let scratch = TempDir::new().context("Failed to create temporary directory")?;
+    let mut renamed = Vec::new();
+    if !force {
+        let resolved = conflict::resolve(repo, skills, &agents, scope, scratch.path())?;
+        (skills, renamed) = (resolved.skills, resolved.renamed);
+        if skills.is_empty() {
+            return Ok(Outcome::NothingToDo);
+        }
     }
+    install_skills(
+        repo,
+        &skills,
+        &agents,
+        head_commit(temp_dir.path()).as_deref(),
+        copy || copy_by_default(),
+        scope,
+    )?;
+    if !renamed.is_empty() && matches!(scope, Scope::Global) {
+        let mut lock = Lockfile::load()?;
+        for name in &renamed {
+            lock.remove(name);
+        }
+        lock.save()?;
+    }
+
     println!();
+    println!("{}", "Skills installed successfully!".success());
 
-    // Get target agents
-    let agents: Vec<SkillAgent> = if let Some(agent_id) = agent_filter {
-        vec![agents::find(agent_id).with_context(|| format!("Unknown agent: {}", agent_id))?]
-    } else {
-        agents::catalog()
-            .into_iter()
-            .filter(|a| a.is_installed())
-            .collect()
-    };
+    Ok(Outcome::Success)
+}
 
-    if agents.is_empty() {
-        anyhow::bail!("No AI agents installed to install skills to");
+/// Whether `[skills] copy` in config.toml turns linking off
+pub(super) fn copy_by_default() -> bool {
+    config::load().is_ok_and(|c| c.skills.copy)
+}
+
+/// Clone `repo` and install its skills into each agent, optionally only the named ones
+pub fn install_from_repo(repo: &str, agents: &[SkillAgent], only: Option<&[String]>) -> Result<()> {
+    let temp_dir = clone_skills(repo)?;
+
+    // Discover skills in repo
+    let mut skills = discovery::discover_skills(temp_dir.path())?;
+    if let Some(names) = only {
+        skills.retain(|s| names.contains(&s.name));
+    }
+    install_skills(
+        repo,
+        &skills,
+        agents,
+        head_commit(temp_dir.path()).as_deref(),
+        copy_by_default(),
+        &Scope::Global,
+    )
+}
+
+/// Install `skills` cloned from `repo` into each agent and record them
+///
+/// Project skills are copies meant to be committed with the project, so they
+/// are never linked to the store nor recorded in the manifest or lockfile.
+pub(super) fn install_skills(
+    repo: &str,
+    skills: &[Skill],
+    agents: &[SkillAgent],
+    commit: Option<&str>,
+    copy: bool,
+    scope: &Scope,
+) -> Result<()> {
+    let global = matches!(scope, Scope::Global);
+    let copy = copy || !global;
+    if skills.is_empty() {
+        anyhow::bail!("No skills found in repository (no SKILL.md files)");
     }
 
+    println!("{} Found {} skill(s):", "->".info(), skills.len());
+    let max_size_mb = config::load()
+        .ok()
+        .and_then(|c| c.skills.max_size_mb)
+        .unwrap_or(skillignore::DEFAULT_MAX_SIZE_MB);
+    for skill in skills {
+        println!("  {} {}", "-".info(), skill.name);
+        let size = skillignore::copied_size(&skill.path);
+        if size > max_size_mb * 1024 * 1024 {
+            println!(
+                "    {} {} is over the {} MB limit; a .skillignore can leave out files agents don't need",
+                "!".warning(),
+                format_size(size),
+                max_size_mb
+            );
+        }
+    }
+    println!();
+
     // Install skills to each agent
     println!("{}", "Installing skills:".bold());
+    let mut manifest = Manifest::load()?;
+    let mut lock = Lockfile::load()?;
 
-    for agent in &agents {
+    for agent in agents {
         print!("  {:<16}", agent.name);
 
         if !agent.is_installed() {
-            println!("{}", "[SKIP] Not installed".dimmed());
+            println!("{}", "[SKIP] Not installed".muted());
             continue;
         }
 
@@ -115,111 +332,1171 @@ pub fn handle_install(repo: &str, agent_filter: Option<&str>) -> Result<()> {
             .ensure_skills_dir()
             .with_context(|| format!("Failed to create skills directory for {}", agent.name))?;
 
-        // Copy each skill
+        let mut linked = true;
+        for skill in skills {
+            linked &= place_skill(skill, agent, copy)?;
+            if global {
+                manifest.record_install(&skill.name, repo, agent.id);
+                manifest.record_commit(&skill.name, commit);
+                if let Some(commit) = commit {
+                    lock.record(skill, repo, commit);
+                }
+            }
+        }
+
+        if linked || copy {
+            println!("{}", "[OK]".success());
+        } else {
+            println!(
+                "{} {}",
+                "[OK]".success(),
+                "(copied; links aren't supported here)".muted()
+            );
+        }
+    }
+
+    manifest.save()?;
+    if global {
+        lock.save()?;
+    }
+    Ok(())
+}
+
+/// Where linked skills are kept, once for every agent
+pub fn store_dir() -> PathBuf {
+    paths::home().join(".ai-cli").join("skills")
+}
+
+/// Whether the agent's `name` skill is a link into the store
+pub(super) fn is_linked(agent: &SkillAgent, name: &str) -> bool {
+    std::fs::read_link(agent.skills_path.join(name))
+        .is_ok_and(|target| target == store_dir().join(name))
+}
+
+/// Put `skill` into the store, replacing an older version; returns whether it changed
+fn store_skill(skill: &Skill) -> Result<bool> {
+    store_skill_in(&store_dir(), skill)
+}
+
+fn store_skill_in(store: &Path, skill: &Skill) -> Result<bool> {
+    let dest = store.join(&skill.name);
+    if same_contents(&skill.path, &dest) {
+        return Ok(false);
+    }
+    journal::record_dir(&dest);
+    if dest.exists() {
+        std::fs::remove_dir_all(&dest)
+            .with_context(|| format!("Failed to remove stored skill {}", skill.name))?;
+    }
+    copy_dir_recursive(&skill.path, &dest)
+        .with_context(|| format!("Failed to store skill {}", skill.name))?;
+    Ok(true)
+}
+
+#[cfg(unix)]
+pub(super) fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+pub(super) fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+/// Install one skill into an agent's skills directory, replacing what is
+/// there: a link to the store, or a copy with `copy` or where links can't be
+/// made. Returns whether it was linked.
+pub(super) fn place_skill(skill: &Skill, agent: &SkillAgent, copy: bool) -> Result<bool> {
+    place_skill_in(&store_dir(), skill, agent, copy)
+}
+
+fn place_skill_in(store: &Path, skill: &Skill, agent: &SkillAgent, copy: bool) -> Result<bool> {
+    let dest = agent.skills_path.join(&skill.name);
+
+    journal::record_dir(&dest);
+    // A link is removed without touching the store it points to
+    if dest.symlink_metadata().is_ok_and(|m| m.is_symlink()) {
+        std::fs::remove_file(&dest)
+    } else if dest.exists() {
+        std::fs::remove_dir_all(&dest)
+    } else {
+        Ok(())
+    }
+    .with_context(|| format!("Failed to remove existing skill {}", skill.name))?;
+
+    if !copy {
+        store_skill_in(store, skill)?;
+        match symlink_dir(&store.join(&skill.name), &dest) {
+            Ok(()) => {
+                logging::line(&format!(
+                    "linked skill {} into {}",
+                    skill.name,
+                    dest.display()
+                ));
+                return Ok(true);
+            }
+            Err(e) => logging::line(&format!(
+                "can't link {}: {}; copying instead",
+                dest.display(),
+                e
+            )),
+        }
+    }
+
+    copy_dir_recursive(&skill.path, &dest)
+        .with_context(|| format!("Failed to copy skill {}", skill.name))?;
+    logging::line(&format!(
+        "installed skill {} to {}",
+        skill.name,
+        dest.display()
+    ));
+    Ok(false)
+}
+
+/// Handle `skills subscribe <repo>` command: install the repo's skills now and
+/// the ones added to it later on `skills update`
+pub fn handle_subscribe(repo: &str, agent_filter: Option<&str>, copy: bool) -> Result<Outcome> {
+    if Manifest::load()?.subscriptions.contains_key(repo) {
+        println!("{} Already subscribed to {}", "✓".success(), repo);
+        return Ok(Outcome::NothingToDo);
+    }
+    let agents = target_agents(agent_filter, &Scope::Global)?;
+    let copy = copy || copy_by_default();
+
+    let temp_dir = clone_skills(repo)?;
+    let skills = discovery::discover_skills(temp_dir.path())?;
+    install_skills(
+        repo,
+        &skills,
+        &agents,
+        head_commit(temp_dir.path()).as_deref(),
+        copy,
+        &Scope::Global,
+    )?;
+
+    let mut manifest = Manifest::load()?;
+    manifest.subscriptions.insert(
+        repo.to_string(),
+        Subscription {
+            agents: agent_filter
+                .map(|id| vec![id.to_string()])
+                .unwrap_or_default(),
+            seen: skills.iter().map(|s| s.name.clone()).collect(),
+            copy,
+        },
+    );
+    manifest.save()?;
+
+    println!();
+    println!(
+        "{} Subscribed to {}; `ai-cli skills update` installs skills added there later",
+        "✓".success(),
+        repo
+    );
+    Ok(Outcome::Success)
+}
+
+/// Handle `skills unsubscribe <repo>` command; installed skills stay
+pub fn handle_unsubscribe(repo: &str) -> Result<Outcome> {
+    let mut manifest = Manifest::load()?;
+    if manifest.subscriptions.remove(repo).is_none() {
+        return Err(exit::usage(format!("Not subscribed to {}", repo)));
+    }
+    manifest.save()?;
+    println!(
+        "{} Unsubscribed from {}; its installed skills are kept",
+        "✓".success(),
+        repo
+    );
+    Ok(Outcome::Success)
+}
+
+/// Handle `skills update [skill]` command: refresh skills whose source changed
+/// and install skills that appeared in subscribed repos
+pub async fn handle_update(skill_filter: Option<&str>) -> Result<Outcome> {
+    let mut manifest = Manifest::load()?;
+    let mut lock = Lockfile::load()?;
+    let repos: BTreeSet<String> = match skill_filter {
+        Some(name) => {
+            let source = manifest.source_of(name).ok_or_else(|| {
+                exit::usage(format!(
+                    "'{}' wasn't installed by ai-cli from a repository",
+                    name
+                ))
+            })?;
+            BTreeSet::from([source.to_string()])
+        }
+        None => manifest
+            .skills
+            .values()
+            .map(|r| r.source.clone())
+            .chain(manifest.subscriptions.keys().cloned())
+            .collect(),
+    };
+    if repos.is_empty() {
+        println!("No skills installed by ai-cli and no subscriptions");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut updated = 0;
+    let mut unchanged = 0;
+    let mut failed = 0;
+    let mut appeared = Vec::new();
+    for repo in &repos {
+        // The clone has to outlive the copying below
+        let (skills, clone) = match clone_skills(repo)
+            .and_then(|dir| Ok((discovery::discover_skills(dir.path())?, dir)))
+        {
+            Ok(found) => found,
+            Err(e) => {
+                println!("  {} {}: {:#}", "[FAIL]".error(), repo, e);
+                failed += 1;
+                continue;
+            }
+        };
+        let commit = head_commit(clone.path());
+
+        // Skills installed from here before
         for skill in &skills {
-            let dest = agent.skills_path.join(&skill.name);
+            if skill_filter.is_some_and(|name| name != skill.name) {
+                continue;
+            }
+            let Some(record) = manifest
+                .skills
+                .get(&skill.name)
+                .filter(|r| &r.source == repo)
+                .cloned()
+            else {
+                continue;
+            };
+            let mut current = true;
+            let installed: Vec<SkillAgent> = record
+                .agents
+                .iter()
+                .filter_map(|id| agents::find(id))
+                .filter(|a| a.is_installed())
+                .collect();
+            let installed: Vec<SkillAgent> = installed
+                .into_iter()
+                .filter(|a| !is_disabled(a, &skill.name) || is_linked(a, &skill.name))
+                .collect();
 
-            // Remove existing skill if present
-            if dest.exists() {
-                std::fs::remove_dir_all(&dest)
-                    .with_context(|| format!("Failed to remove existing skill {}", skill.name))?;
+            // One store update reaches every agent linked to it
+            let linked: Vec<&str> = installed
+                .iter()
+                .filter(|a| is_linked(a, &skill.name))
+                .map(|a| a.id)
+                .collect();
+            if !linked.is_empty() {
+                match store_skill(skill) {
+                    Ok(false) => unchanged += 1,
+                    Ok(true) => {
+                        updated += 1;
+                        println!(
+                            "  {} {} ({}) {}",
+                            "✓".success(),
+                            skill.name,
+                            linked.join(", "),
+                            commit_range(record.commit.as_deref(), commit.as_deref()).muted()
+                        );
+                    }
+                    Err(e) => {
+                        current = false;
+                        println!("  {} {}: {:#}", "[FAIL]".error(), skill.name, e);
+                        failed += 1;
+                    }
+                }
             }
 
-            // Copy skill directory
-            copy_dir_recursive(&skill.path, &dest)
-                .with_context(|| format!("Failed to copy skill {}", skill.name))?;
+            for agent in installed.iter().filter(|a| !linked.contains(&a.id)) {
+                if same_contents(&skill.path, &agent.skills_path.join(&skill.name)) {
+                    unchanged += 1;
+                    continue;
+                }
+                match place_skill(skill, agent, true) {
+                    Ok(_) => {
+                        updated += 1;
+                        println!(
+                            "  {} {} ({}) {}",
+                            "✓".success(),
+                            skill.name,
+                            agent.id,
+                            commit_range(record.commit.as_deref(), commit.as_deref()).muted()
+                        );
+                    }
+                    Err(e) => {
+                        current = false;
+                        println!(
+                            "  {} {} ({}): {:#}",
+                            "[FAIL]".error(),
+                            skill.name,
+                            agent.id,
+                            e
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+            if current {
+                manifest.record_commit(&skill.name, commit.as_deref());
+                if let Some(commit) = &commit {
+                    lock.record(skill, repo, commit);
+                }
+            }
         }
 
-        println!("{}", "[OK]".green());
+        // Skills added to a subscribed repo since the last check
+        if skill_filter.is_some() {
+            continue;
+        }
+        let Some(subscription) = manifest.subscriptions.get_mut(repo) else {
+            continue;
+        };
+        let names: Vec<String> = skills.iter().map(|s| s.name.clone()).collect();
+        let new = subscription.take_new(&names);
+        if new.is_empty() {
+            continue;
+        }
+        let agent_ids = subscription.agents.clone();
+        let copy = subscription.copy;
+        let targets: Vec<SkillAgent> = agents::catalog()
+            .into_iter()
+            .filter(|a| a.is_installed())
+            .filter(|a| agent_ids.is_empty() || agent_ids.iter().any(|id| id == a.id))
+            .collect();
+        for skill in skills.iter().filter(|s| new.contains(&s.name)) {
+            for agent in &targets {
+                let installed = agent
+                    .ensure_skills_dir()
+                    .with_context(|| {
+                        format!("Failed to create skills directory for {}", agent.name)
+                    })
+                    .and_then(|()| place_skill(skill, agent, copy));
+                match installed {
+                    Ok(_) => {
+                        manifest.record_install(&skill.name, repo, agent.id);
+                        manifest.record_commit(&skill.name, commit.as_deref());
+                        if let Some(commit) = &commit {
+                            lock.record(skill, repo, commit);
+                        }
+                    }
+                    Err(e) => {
+                        println!(
+                            "  {} {} ({}): {:#}",
+                            "[FAIL]".error(),
+                            skill.name,
+                            agent.id,
+                            e
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+        }
+        println!("{} New in {}: {}", "+".success(), repo, new.join(", "));
+        appeared.extend(new.iter().map(|name| format!("{} ({})", name, repo)));
     }
+    manifest.save()?;
+    lock.save()?;
 
     println!();
-    println!("{}", "Skills installed successfully!".green());
+    println!(
+        "{}",
+        format!(
+            "Updated {} skill install(s), {} unchanged, added {} new skill(s)",
+            updated,
+            unchanged,
+            appeared.len()
+        )
+        .success()
+    );
+    notify::send(Event::SkillsAdded, "skills update", &appeared).await;
+    if failed == 0 && updated == 0 && appeared.is_empty() {
+        return Ok(Outcome::NothingToDo);
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// `old → new` for two commits, shortened
+pub(super) fn commit_range(old: Option<&str>, new: Option<&str>) -> String {
+    let short = |commit: Option<&str>| {
+        commit
+            .map(|c| c.chars().take(7).collect())
+            .unwrap_or_else(|| "?".to_string())
+    };
+    format!("{} → {}", short(old), short(new))
+}
 
+/// The agent named by `--agent`, or every agent of the scope
+fn removal_agents(agent_filter: Option<&str>, scope: &Scope) -> Result<Vec<SkillAgent>> {
+    match agent_filter {
+        Some(agent_id) => {
+            Ok(vec![agents::find_in(scope, agent_id).ok_or_else(|| {
+                exit::usage(format!("Unknown agent: {}", agent_id))
+            })?])
+        }
+        None => Ok(agents::catalog_for(scope)),
+    }
+}
+
+/// Delete an agent's copy of or link to `skill_name`, disabled ones as well;
+/// returns whether it had one
+fn delete_skill(agent: &SkillAgent, skill_name: &str) -> Result<bool> {
+    let skill_paths: Vec<PathBuf> = [
+        agent.skills_path.join(skill_name),
+        disabled_path(agent, skill_name),
+    ]
+    .into_iter()
+    // Links whose target is gone count too
+    .filter(|path| path.symlink_metadata().is_ok())
+    .collect();
+
+    for skill_path in &skill_paths {
+        journal::record_dir(skill_path);
+        std::fs::remove_dir_all(skill_path)
+            .with_context(|| format!("Failed to remove skill from {}", agent.name))?;
+        logging::line(&format!("removed skill {}", skill_path.display()));
+    }
+    Ok(!skill_paths.is_empty())
+}
+
+/// Drop the lock entry and stored copy of a skill no agent has any more
+fn forget_skill(skill_name: &str, manifest: &Manifest) -> Result<()> {
+    if manifest.source_of(skill_name).is_none() {
+        let mut lock = Lockfile::load()?;
+        if lock.skills.contains_key(skill_name) {
+            lock.remove(skill_name);
+            lock.save()?;
+        }
+    }
+
+    // The stored copy goes with the last link to it
+    let stored = store_dir().join(skill_name);
+    if stored.exists()
+        && !agents::catalog().iter().any(|agent| {
+            is_linked(agent, skill_name)
+                || std::fs::read_link(disabled_path(agent, skill_name))
+                    .is_ok_and(|target| target == stored)
+        })
+    {
+        journal::record_dir(&stored);
+        std::fs::remove_dir_all(&stored)
+            .with_context(|| format!("Failed to remove {}", stored.display()))?;
+    }
     Ok(())
 }
 
-/// Handle `skills remove <skill>` command
-pub fn handle_remove(skill_name: &str, agent_filter: Option<&str>) -> Result<()> {
-    let agents = if let Some(agent_id) = agent_filter {
-        vec![agents::find(agent_id).with_context(|| format!("Unknown agent: {}", agent_id))?]
-    } else {
-        agents::catalog()
-    };
+/// Handle `skills remove <skill> [--project]` command
+pub fn handle_remove(
+    skill_name: &str,
+    agent_filter: Option<&str>,
+    scope: &Scope,
+) -> Result<Outcome> {
+    let agents = removal_agents(agent_filter, scope)?;
+    let global = matches!(scope, Scope::Global);
 
     println!("{}", format!("Removing skill '{}':", skill_name).bold());
 
     let mut removed_count = 0;
+    let mut manifest = Manifest::load()?;
 
     for agent in &agents {
         print!("  {:<16}", agent.name);
 
         if !agent.is_installed() {
-            println!("{}", "[SKIP] Not installed".dimmed());
+            println!("{}", "[SKIP] Not installed".muted());
             continue;
         }
 
-        let skill_path = agent.skills_path.join(skill_name);
-
-        if !skill_path.exists() {
-            println!("{}", "[SKIP] Not found".dimmed());
+        if !delete_skill(agent, skill_name)? {
+            println!("{}", "[SKIP] Not found".muted());
             continue;
         }
+        if global {
+            manifest.record_removal(skill_name, agent.id);
+        }
 
-        std::fs::remove_dir_all(&skill_path)
-            .with_context(|| format!("Failed to remove skill from {}", agent.name))?;
-
-        println!("{}", "[OK]".green());
+        println!("{}", "[OK]".success());
         removed_count += 1;
     }
 
+    manifest.save()?;
+    if global {
+        forget_skill(skill_name, &manifest)?;
+    }
+
     println!();
     if removed_count == 0 {
         println!(
             "{}",
-            format!("Skill '{}' not found in any agent", skill_name).yellow()
+            format!("Skill '{}' not found in any agent", skill_name).warning()
         );
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!(
+        "{}",
+        format!("Removed skill from {} agent(s)", removed_count).success()
+    );
+    Ok(Outcome::Success)
+}
+
+/// Handle `skills remove --repo <repo>` (every skill installed from it) and
+/// `skills remove --all` (every skill of the agents) commands, after asking
+pub fn handle_remove_many(
+    repo: Option<&str>,
+    all: bool,
+    agent_filter: Option<&str>,
+    scope: &Scope,
+) -> Result<Outcome> {
+    if repo.is_some() == all {
+        return Err(exit::usage("Pass either --repo or --all"));
+    }
+    let agents: Vec<SkillAgent> = removal_agents(agent_filter, scope)?
+        .into_iter()
+        .filter(|a| a.is_installed())
+        .collect();
+    let global = matches!(scope, Scope::Global);
+    let mut manifest = Manifest::load()?;
+
+    let targets = removal_targets(&agents, &manifest, repo)?;
+    if targets.is_empty() {
+        match repo {
+            Some(repo) => println!("No installed skills came from {}", repo),
+            None => println!("No skills installed"),
+        }
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!("{}", "Planned changes:".bold());
+    for (name, holders) in &targets {
+        let ids: Vec<&str> = holders.iter().map(|a| a.id).collect();
+        println!("  {} remove {} from {}", "-".error(), name, ids.join(", "));
+    }
+    println!();
+    if !prompt::confirm(&format!("Remove these {} skill(s)?", targets.len()))? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut removed = 0;
+    let mut failed = 0;
+    for (name, holders) in &targets {
+        let mut skill_failed = false;
+        for agent in holders {
+            match delete_skill(agent, name) {
+                Ok(_) if global => manifest.record_removal(name, agent.id),
+                Ok(_) => {}
+                Err(e) => {
+                    println!("  {} {} ({}): {:#}", "✗".error(), name, agent.id, e);
+                    failed += 1;
+                    skill_failed = true;
+                }
+            }
+        }
+        if !skill_failed {
+            removed += 1;
+        }
+    }
+    manifest.save()?;
+    if global {
+        for name in targets.keys() {
+            forget_skill(name, &manifest)?;
+        }
+    }
+
+    println!("{}", format!("Removed {} skill(s)", removed).success());
+    if failed > 0 {
+        println!("{}", format!("{} removal(s) failed", failed).warning());
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Skills to remove from `agents`, enabled or disabled, with the agents that
+/// have them: those installed from `repo` (whatever ref), or all of them
+fn removal_targets<'a>(
+    agents: &'a [SkillAgent],
+    manifest: &Manifest,
+    repo: Option<&str>,
+) -> Result<BTreeMap<String, Vec<&'a SkillAgent>>> {
+    let mut targets: BTreeMap<String, Vec<&SkillAgent>> = BTreeMap::new();
+    for agent in agents {
+        let installed = discovery::list_installed_skills(&agent.skills_path)?
+            .into_iter()
+            .chain(discovery::list_installed_skills(
+                &agent.skills_path.join(".disabled"),
+            )?);
+        for skill in installed {
+            let from_repo = repo.is_none_or(|repo| {
+                manifest
+                    .source_of(&skill.name)
+                    .is_some_and(|source| split_ref(source).0 == split_ref(repo).0)
+            });
+            if from_repo {
+                targets.entry(skill.name).or_default().push(agent);
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// Handle `skills prune` command: remove installed skills the manifest doesn't know
+pub fn handle_prune(agent_filter: Option<&str>, dry_run: bool) -> Result<Outcome> {
+    let agents = if let Some(agent_id) = agent_filter {
+        vec![
+            agents::find(agent_id)
+                .ok_or_else(|| exit::usage(format!("Unknown agent: {}", agent_id)))?,
+        ]
     } else {
+        agents::catalog()
+    };
+    let mut manifest = Manifest::load()?;
+
+    let mut leftovers = Vec::new();
+    for agent in agents.iter().filter(|a| a.is_installed()) {
+        let skills: Vec<_> = discovery::list_installed_skills(&agent.skills_path)?
+            .into_iter()
+            .filter(|skill| !manifest.is_recorded(&skill.name, agent.id))
+            .collect();
+        if !skills.is_empty() {
+            leftovers.push((agent, skills));
+        }
+    }
+    if leftovers.is_empty() {
         println!(
-            "{}",
-            format!("Removed skill from {} agent(s)", removed_count).green()
+            "{} Every installed skill is recorded in the manifest",
+            "✓".success()
+        );
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!("{}", "Skills not installed by ai-cli:".bold());
+    for (agent, skills) in &leftovers {
+        println!("  {}", agent.name);
+        for skill in skills {
+            println!("    {} {}", "-".error(), skill.path.display());
+        }
+    }
+    println!();
+    if dry_run {
+        println!("{}", "Dry run: nothing removed".muted());
+        return Ok(Outcome::Success);
+    }
+
+    let mut removed = 0;
+    let mut failed = 0;
+    for (agent, skills) in &leftovers {
+        if !prompt::confirm(&format!(
+            "Remove {} skill(s) from {}?",
+            skills.len(),
+            agent.name
+        ))? {
+            continue;
+        }
+        for skill in skills {
+            journal::record_dir(&skill.path);
+            match std::fs::remove_dir_all(&skill.path) {
+                Ok(()) => {
+                    logging::line(&format!("pruned skill {}", skill.path.display()));
+                    manifest.record_removal(&skill.name, agent.id);
+                    removed += 1;
+                }
+                Err(e) => {
+                    println!(
+                        "{} Failed to remove {}: {}",
+                        "✗".error(),
+                        skill.path.display(),
+                        e
+                    );
+                    failed += 1;
+                }
+            }
+        }
+    }
+    manifest.save()?;
+
+    println!();
+    if removed == 0 && failed == 0 {
+        println!("Nothing removed.");
+        return Ok(Outcome::NothingToDo);
+    }
+    println!("{}", format!("Removed {} skill(s)", removed).success());
+    Ok(Outcome::from_failures(failed))
+}
+
+/// Shallow-clone `repo` (owner/repo or full URL) into a temporary directory
+pub fn clone_repo(repo: &str) -> Result<TempDir> {
+    clone(repo, false)
+}
+
+/// Clone `repo` for its skills: blobless and sparse, so only the files of
+/// directories holding a SKILL.md are downloaded, not a whole monorepo
+pub(super) fn clone_skills(repo: &str) -> Result<TempDir> {
+    clone(repo, true)
+}
+
+fn clone(repo: &str, skills_only: bool) -> Result<TempDir> {
+    // Parse repo input (owner/repo or full URL, optionally with #ref)
+    let (repo_part, git_ref) = split_ref(repo);
+    let repo_url = parse_repo_url(repo_part)?;
+
+    println!("{} Cloning {}...", "->".info(), repo);
+    let temp_dir = TempDir::new().context("Failed to create temp directory")?;
+    let dest = temp_dir.path().to_str().unwrap();
+
+    let mut clone = Command::new("git");
+    clone.args(["-c", "advice.detachedHead=false", "clone"]);
+    if skills_only {
+        // Trees now, file contents only once checked out below
+        clone.args(["--filter=blob:none", "--sparse"]);
+    }
+    match git_ref {
+        // A commit can't be cloned directly and may be anywhere in the history
+        Some(commit) if is_commit(commit) => clone.args([&repo_url, dest]),
+        Some(branch_or_tag) => {
+            clone.args(["--depth", "1", "--branch", branch_or_tag, &repo_url, dest])
+        }
+        None => clone.args(["--depth", "1", &repo_url, dest]),
+    };
+    auth::configure(&mut clone, &repo_url);
+    let (status, stderr) =
+        logging::run_capturing_stderr(&mut clone).context("Failed to run git clone")?;
+    if !status.success() {
+        match auth::explain(&stderr, &repo_url) {
+            Some(hint) => anyhow::bail!("git clone failed for {}: {}", repo, hint),
+            None => anyhow::bail!("git clone failed for {}", repo),
+        }
+    }
+
+    if let Some(commit) = git_ref.filter(|r| is_commit(r)) {
+        let status = logging::run(
+            Command::new("git")
+                .arg("-C")
+                .arg(temp_dir.path())
+                .args(["checkout", "--quiet", commit]),
+        )
+        .context("Failed to run git checkout")?;
+        if !status.success() {
+            anyhow::bail!("Commit {} not found in {}", commit, repo_part);
+        }
+    }
+    if skills_only {
+        check_out_skills(temp_dir.path(), &repo_url)?;
+    }
+    Ok(temp_dir)
+}
+
+/// Sparse-checkout patterns for the directories holding a SKILL.md, given
+/// every path in the tree; `None` when the whole tree is needed, for a skill
+/// at the root or when there is no SKILL.md to go by
+fn skill_patterns(paths: &str) -> Option<Vec<String>> {
+    let mut dirs = BTreeSet::new();
+    for path in paths.lines() {
+        if path == "SKILL.md" {
+            return None;
+        }
+        if let Some(dir) = path.strip_suffix("/SKILL.md") {
+            dirs.insert(format!("/{}/", dir));
+        }
+    }
+    (!dirs.is_empty()).then(|| dirs.into_iter().collect())
+}
+
+/// Check out just the skill directories of the sparse clone at `dir`
+fn check_out_skills(dir: &Path, repo_url: &str) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["ls-tree", "-r", "--name-only", "HEAD"])
+        .output()
+        .context("Failed to run git ls-tree")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git ls-tree failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
+    let mut sparse = Command::new("git");
+    sparse.arg("-C").arg(dir).arg("sparse-checkout");
+    match skill_patterns(&String::from_utf8_lossy(&output.stdout)) {
+        Some(patterns) => sparse.args(["set", "--no-cone"]).args(patterns),
+        None => sparse.arg("disable"),
+    };
+    // Checking out fetches the file contents, so it needs the credentials too
+    auth::configure(&mut sparse, repo_url);
+    let (status, stderr) =
+        logging::run_capturing_stderr(&mut sparse).context("Failed to run git sparse-checkout")?;
+    if !status.success() {
+        match auth::explain(&stderr, repo_url) {
+            Some(hint) => anyhow::bail!("Failed to check out skills: {}", hint),
+            None => anyhow::bail!("Failed to check out skills: {}", stderr.trim()),
+        }
+    }
     Ok(())
 }
 
+/// `owner/repo#ref` as the repository and the branch, tag or commit
+pub fn split_ref(repo: &str) -> (&str, Option<&str>) {
+    match repo.rsplit_once('#') {
+        Some((repo, git_ref)) if !git_ref.is_empty() => (repo, Some(git_ref)),
+        _ => (repo.trim_end_matches('#'), None),
+    }
+}
+
+/// Whether `git_ref` looks like a (possibly abbreviated) commit hash
+pub(super) fn is_commit(git_ref: &str) -> bool {
+    (7..=40).contains(&git_ref.len()) && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Commit checked out in the clone at `dir`
+fn head_commit(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Hosts of `<prefix>:owner/repo` shorthands; plain `owner/repo` is GitHub
+const HOST_SHORTHANDS: &[(&str, &str)] = &[
+    ("github", "github.com"),
+    ("gitlab", "gitlab.com"),
+    ("bitbucket", "bitbucket.org"),
+    ("codeberg", "codeberg.org"),
+];
+
+/// URL schemes passed to git as they are
+const URL_PREFIXES: &[&str] = &["https://", "http://", "ssh://", "git@", "file://"];
+
 /// Parse repository input to full URL
-fn parse_repo_url(repo: &str) -> Result<String> {
-    if repo.starts_with("https://") || repo.starts_with("git@") {
-        Ok(repo.to_string())
-    } else if repo.contains('/') {
-        // GitHub shorthand: owner/repo
-        Ok(format!("https://github.com/{}.git", repo))
-    } else {
-        anyhow::bail!("Invalid repository format. Use 'owner/repo' or full URL");
+pub(super) fn parse_repo_url(repo: &str) -> Result<String> {
+    if URL_PREFIXES.iter().any(|prefix| repo.starts_with(prefix)) {
+        return Ok(repo.to_string());
     }
+    let (host, path) = match repo.split_once(':') {
+        Some((prefix, path)) => {
+            let host = HOST_SHORTHANDS
+                .iter()
+                .find(|(shorthand, _)| *shorthand == prefix)
+                .map(|(_, host)| *host)
+                .ok_or_else(|| {
+                    exit::usage(format!(
+                        "Unknown host '{}:'. Use github:, gitlab:, bitbucket:, codeberg: or a full URL",
+                        prefix
+                    ))
+                })?;
+            (host, path)
+        }
+        None => ("github.com", repo),
+    };
+    // GitLab allows nested groups: gitlab:group/subgroup/repo
+    let path = path.trim_matches('/').trim_end_matches(".git");
+    if path.split('/').filter(|part| !part.is_empty()).count() < 2 {
+        return Err(exit::usage(
+            "Invalid repository format. Use 'owner/repo', 'gitlab:owner/repo' or a full URL",
+        ));
+    }
+    Ok(format!("https://{}/{}.git", host, path))
 }
 
-/// Recursively copy directory contents
-fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<()> {
+/// Copy the skill at `src` to `dst`, leaving out what `.skillignore` and the
+/// defaults exclude
+pub(super) fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     std::fs::create_dir_all(dst)?;
+    for relative in skill_files(src) {
+        let target = dst.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(src.join(&relative), &target)?;
+    }
+    Ok(())
+}
 
-    for entry in std::fs::read_dir(src)? {
-        let entry = entry?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
+/// Files of the skill at `dir` that a copy keeps, by path relative to it,
+/// with their content
+pub(super) fn files_in(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+    skill_files(dir)
+        .into_iter()
+        .filter_map(|relative| {
+            let content = std::fs::read(dir.join(&relative)).ok()?;
+            Some((relative, content))
+        })
+        .collect()
+}
 
-        if src_path.is_dir() {
-            // Skip .git directory
-            if src_path.file_name().is_some_and(|n| n == ".git") {
-                continue;
-            }
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            std::fs::copy(&src_path, &dst_path)?;
+/// Whether the installed copy `installed` has exactly the files of `source`
+pub(super) fn same_contents(source: &Path, installed: &Path) -> bool {
+    installed.is_dir() && files_in(source) == files_in(installed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn skill(name: &str) -> Skill {
+        Skill {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            ..Default::default()
         }
     }
 
-    Ok(())
+    #[test]
+    fn selects_skills_with_only_and_exclude() {
+        let names =
+            |skills: Vec<Skill>| -> Vec<String> { skills.into_iter().map(|s| s.name).collect() };
+        let repo = || vec![skill("pdf"), skill("docx"), skill("xlsx")];
+        let list =
+            |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+
+        assert_eq!(names(select_skills(repo(), &[], &[]).unwrap()).len(), 3);
+        assert_eq!(
+            names(select_skills(repo(), &list(&["pdf", "xlsx"]), &[]).unwrap()),
+            ["pdf", "xlsx"]
+        );
+        assert_eq!(
+            names(select_skills(repo(), &[], &list(&["docx"])).unwrap()),
+            ["pdf", "xlsx"]
+        );
+        assert!(select_skills(repo(), &list(&["pfd"]), &[]).is_err());
+        assert!(select_skills(repo(), &list(&["pdf"]), &list(&["pdf"])).is_err());
+    }
+
+    #[test]
+    fn expands_repository_shorthands() {
+        let url = |repo| parse_repo_url(repo).unwrap();
+        assert_eq!(url("acme/skills"), "https://github.com/acme/skills.git");
+        assert_eq!(
+            url("gitlab:acme/tools/skills"),
+            "https://gitlab.com/acme/tools/skills.git"
+        );
+        assert_eq!(
+            url("bitbucket:acme/skills.git"),
+            "https://bitbucket.org/acme/skills.git"
+        );
+        assert_eq!(
+            url("codeberg:acme/skills"),
+            "https://codeberg.org/acme/skills.git"
+        );
+        assert_eq!(
+            url("ssh://git@git.example.com/acme/skills.git"),
+            "ssh://git@git.example.com/acme/skills.git"
+        );
+        assert!(parse_repo_url("sourcehut:acme/skills").is_err());
+        assert!(parse_repo_url("gitlab:skills").is_err());
+        assert!(parse_repo_url("skills").is_err());
+    }
+
+    #[test]
+    fn splits_refs_off_repositories() {
+        assert_eq!(split_ref("anthropics/skills"), ("anthropics/skills", None));
+        assert_eq!(
+            split_ref("anthropics/skills#v1.2.0"),
+            ("anthropics/skills", Some("v1.2.0"))
+        );
+        assert_eq!(
+            split_ref("https://github.com/acme/skills.git#feature/pdf"),
+            ("https://github.com/acme/skills.git", Some("feature/pdf"))
+        );
+        assert_eq!(split_ref("acme/skills#"), ("acme/skills", None));
+        assert_eq!(
+            split_ref("gitlab:acme/skills#v2"),
+            ("gitlab:acme/skills", Some("v2"))
+        );
+        assert!(is_commit("3f761ec"));
+        assert!(!is_commit("main"));
+        assert!(!is_commit("v1.2.0"));
+    }
+
+    #[test]
+    fn compares_skill_directories_by_content() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        let installed = dir.path().join("installed");
+        for root in [&source, &installed] {
+            std::fs::create_dir_all(root.join("scripts")).unwrap();
+            std::fs::write(root.join("SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+            std::fs::write(root.join("scripts/fill.py"), "print()").unwrap();
+        }
+        std::fs::create_dir_all(source.join(".git")).unwrap();
+        std::fs::write(source.join(".git/HEAD"), "ref: main").unwrap();
+        assert!(same_contents(&source, &installed));
+
+        std::fs::write(source.join("scripts/fill.py"), "print('v2')").unwrap();
+        assert!(!same_contents(&source, &installed));
+        assert!(!same_contents(&source, &dir.path().join("missing")));
+    }
+
+    fn agent(id: &'static str, skills_path: PathBuf) -> SkillAgent {
+        SkillAgent {
+            name: id,
+            id,
+            binary_name: id,
+            skills_path,
+        }
+    }
+
+    fn write_skill(dir: &Path, body: &str) -> Skill {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), body).unwrap();
+        Skill {
+            name: "pdf".to_string(),
+            path: dir.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn links_skills_into_the_store() {
+        let dir = TempDir::new().unwrap();
+        let store = dir.path().join("store");
+        let skill = write_skill(&dir.path().join("repo/pdf"), "v1");
+        let claude = agent("claude", dir.path().join("claude/skills"));
+        std::fs::create_dir_all(&claude.skills_path).unwrap();
+
+        assert!(place_skill_in(&store, &skill, &claude, false).unwrap());
+        let installed = claude.skills_path.join("pdf");
+        assert_eq!(std::fs::read_link(&installed).unwrap(), store.join("pdf"));
+        assert_eq!(
+            std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+            "v1"
+        );
+
+        // Replacing the link leaves the store alone
+        assert!(place_skill_in(&store, &skill, &claude, false).unwrap());
+        assert!(store.join("pdf/SKILL.md").is_file());
+        assert!(!store_skill_in(&store, &skill).unwrap());
+
+        // A copy replaces the link, still without touching the store
+        assert!(!place_skill_in(&store, &skill, &claude, true).unwrap());
+        assert!(!installed.symlink_metadata().unwrap().is_symlink());
+        assert!(store.join("pdf/SKILL.md").is_file());
+    }
+
+    #[test]
+    fn copies_skills_where_links_fail() {
+        let dir = TempDir::new().unwrap();
+        let store = dir.path().join("store");
+        let skill = write_skill(&dir.path().join("repo/pdf"), "v1");
+        // No skills directory yet, so there is nowhere to put the link
+        let codex = agent("codex", dir.path().join("codex/skills"));
+
+        assert!(!place_skill_in(&store, &skill, &codex, false).unwrap());
+        let installed = codex.skills_path.join("pdf");
+        assert!(!installed.symlink_metadata().unwrap().is_symlink());
+        assert_eq!(
+            std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+            "v1"
+        );
+    }
+
+    #[test]
+    fn store_updates_reach_every_linked_agent() {
+        let dir = TempDir::new().unwrap();
+        let store = dir.path().join("store");
+        let source = dir.path().join("repo/pdf");
+        let skill = write_skill(&source, "v1");
+        let agents = [
+            agent("claude", dir.path().join("claude/skills")),
+            agent("codex", dir.path().join("codex/skills")),
+        ];
+        for agent in &agents {
+            std::fs::create_dir_all(&agent.skills_path).unwrap();
+            assert!(place_skill_in(&store, &skill, agent, false).unwrap());
+        }
+
+        write_skill(&source, "v2");
+        assert!(store_skill_in(&store, &skill).unwrap());
+        for agent in &agents {
+            assert_eq!(
+                std::fs::read_to_string(agent.skills_path.join("pdf/SKILL.md")).unwrap(),
+                "v2"
+            );
+        }
+    }
+
+    #[test]
+    fn removes_skills_by_repository_and_agent() {
+        let dir = TempDir::new().unwrap();
+        let scope = Scope::Project(dir.path().to_path_buf());
+        let agents = removal_agents(None, &scope).unwrap();
+        let claude = removal_agents(Some("claude"), &scope).unwrap();
+        assert_eq!(claude.len(), 1);
+        assert!(claude[0].skills_path.starts_with(dir.path()));
+        assert!(removal_agents(Some("clippy"), &scope).is_err());
+
+        let install = |skills_path: &Path, name: &str| {
+            let skill = skills_path.join(name);
+            std::fs::create_dir_all(&skill).unwrap();
+            std::fs::write(
+                skill.join("SKILL.md"),
+                format!("---\nname: {}\n---\n", name),
+            )
+            .unwrap();
+        };
+        install(&claude[0].skills_path, "pdf");
+        install(&claude[0].skills_path.join(".disabled"), "docx");
+        install(&claude[0].skills_path, "lint");
+        let codex = agents.iter().find(|a| a.id == "codex").unwrap();
+        install(&codex.skills_path, "pdf");
+
+        let mut manifest = Manifest::default();
+        manifest.record_install("pdf", "anthropics/skills#v2", "claude");
+        manifest.record_install("docx", "anthropics/skills", "claude");
+        manifest.record_install("lint", "acme/skills", "claude");
+
+        let names = |targets: BTreeMap<String, Vec<&SkillAgent>>| -> Vec<(String, Vec<&str>)> {
+            targets
+                .into_iter()
+                .map(|(name, holders)| (name, holders.iter().map(|a| a.id).collect()))
+                .collect()
+        };
+        let repo = Some("anthropics/skills");
+        assert_eq!(
+            names(removal_targets(&agents, &manifest, repo).unwrap()),
+            [
+                ("docx".to_string(), vec!["claude"]),
+                ("pdf".to_string(), vec!["claude", "codex"])
+            ]
+        );
+        assert_eq!(
+            names(removal_targets(&claude, &manifest, repo).unwrap()),
+            [
+                ("docx".to_string(), vec!["claude"]),
+                ("pdf".to_string(), vec!["claude"])
+            ]
+        );
+        assert_eq!(
+            names(removal_targets(&claude, &manifest, None).unwrap())
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["docx", "lint", "pdf"]
+        );
+        assert!(
+            removal_targets(&claude, &manifest, Some("acme/other"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn checks_out_only_skill_directories() {
+        let tree = "README.md\nassets/video.mp4\nskills/pdf/SKILL.md\nskills/pdf/fill.py\n\
+                    skills/.curated/docx/SKILL.md\ntools/lint/SKILL.md\n";
+        assert_eq!(
+            skill_patterns(tree).unwrap(),
+            ["/skills/.curated/docx/", "/skills/pdf/", "/tools/lint/"]
+        );
+        assert_eq!(skill_patterns("SKILL.md\nscripts/run.sh\n"), None);
+        assert_eq!(skill_patterns("README.md\n"), None);
+    }
 }
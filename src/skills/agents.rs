@@ -1,6 +1,62 @@
-use std::path::PathBuf;
+use std::env;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use anyhow::{Context, Result};
+
+use crate::instructions::Scope;
+use crate::paths;
+
+/// Skills directories inside a project, relative to its root, by agent id
+const PROJECT_DIRS: &[(&str, &str)] = &[
+    ("claude", ".claude/skills"),
+    ("gemini", ".gemini/skills"),
+    ("codex", ".codex/skills"),
+    ("amp", ".agents/skills"),
+    ("cursor", ".cursor/skills"),
+    ("copilot", ".github/skills"),
+    ("opencode", ".opencode/skill"),
+    ("windsurf", ".windsurf/skills"),
+    ("cline", ".cline/skills"),
+    ("roo", ".roo/skills"),
+    ("goose", ".goose/skills"),
+    ("aider", ".aider/skills"),
+];
+
+/// Agents without a CLI binary, by id, with the directory name prefixes of
+/// their VS Code extensions; they count as installed when their config
+/// directory (the skills directory's parent) or such an extension exists
+const GUI_AGENTS: &[(&str, &[&str])] = &[
+    ("cursor", &[]),
+    ("windsurf", &[]),
+    ("cline", &["saoudrizwan.claude-dev-"]),
+    ("roo", &["rooveterinaryinc.roo-cline-"]),
+];
+
+/// Where VS Code and its forks keep extensions, relative to the home directory
+const EXTENSION_DIRS: &[&str] = &[
+    ".vscode/extensions",
+    ".vscode-insiders/extensions",
+    ".vscode-oss/extensions",
+    ".vscode-server/extensions",
+    ".cursor/extensions",
+    ".windsurf/extensions",
+];
+
+/// Whether an extension whose directory starts with one of `prefixes` is
+/// installed in any editor under `home`
+fn has_extension(home: &Path, prefixes: &[&str]) -> bool {
+    EXTENSION_DIRS
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(home.join(dir)).ok())
+        .flat_map(|entries| entries.flatten())
+        .any(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            prefixes.iter().any(|prefix| name.starts_with(prefix))
+        })
+}
+
 /// Represents an AI agent that can have skills installed
 #[derive(Debug, Clone)]
 pub struct SkillAgent {
@@ -10,16 +66,16 @@ pub struct SkillAgent {
     pub id: &'static str,
     /// Binary name to check if installed
     pub binary_name: &'static str,
-    /// Global skills directory path
+    /// Skills directory path: global, or inside a project for `--project`
     pub skills_path: PathBuf,
 }
 
 impl SkillAgent {
     /// Check if this agent is installed
     pub fn is_installed(&self) -> bool {
-        // Special handling for agents without CLI binaries (like Cursor)
-        if self.binary_name == "cursor" {
-            return self.skills_path.parent().is_some_and(|p| p.exists());
+        if let Some((_, extensions)) = GUI_AGENTS.iter().find(|(id, _)| *id == self.id) {
+            return self.skills_path.parent().is_some_and(|p| p.exists())
+                || has_extension(&home_dir(), extensions);
         }
 
         Command::new("which")
@@ -35,7 +91,7 @@ impl SkillAgent {
 }
 
 fn home_dir() -> PathBuf {
-    dirs::home_dir().expect("Could not find home directory")
+    paths::home()
 }
 
 fn claude_code() -> SkillAgent {
@@ -101,6 +157,53 @@ fn opencode() -> SkillAgent {
     }
 }
 
+fn windsurf() -> SkillAgent {
+    SkillAgent {
+        name: "Windsurf",
+        id: "windsurf",
+        binary_name: "windsurf",
+        skills_path: home_dir().join(".codeium/windsurf/skills"),
+    }
+}
+
+fn cline() -> SkillAgent {
+    SkillAgent {
+        name: "Cline",
+        id: "cline",
+        binary_name: "cline",
+        skills_path: home_dir().join(".cline/skills"),
+    }
+}
+
+fn roo_code() -> SkillAgent {
+    SkillAgent {
+        name: "Roo Code",
+        id: "roo",
+        binary_name: "roo",
+        skills_path: home_dir().join(".roo/skills"),
+    }
+}
+
+fn goose() -> SkillAgent {
+    SkillAgent {
+        name: "Goose",
+        id: "goose",
+        binary_name: "goose",
+        skills_path: home_dir().join(".config/goose/skills"),
+    }
+}
+
+/// Aider has no skills convention of its own; the directory is for
+/// `--read`-ing skills into a session
+fn aider() -> SkillAgent {
+    SkillAgent {
+        name: "Aider",
+        id: "aider",
+        binary_name: "aider",
+        skills_path: home_dir().join(".config/aider/skills"),
+    }
+}
+
 /// Returns all supported AI agents for skills
 pub fn catalog() -> Vec<SkillAgent> {
     vec![
@@ -111,6 +214,11 @@ pub fn catalog() -> Vec<SkillAgent> {
         cursor(),
         copilot_cli(),
         opencode(),
+        windsurf(),
+        cline(),
+        roo_code(),
+        goose(),
+        aider(),
     ]
 }
 
@@ -120,3 +228,80 @@ pub fn find(id: &str) -> Option<SkillAgent> {
         .into_iter()
         .find(|a| a.id.eq_ignore_ascii_case(id))
 }
+
+/// The agents with their skills directories in `scope`
+pub fn catalog_for(scope: &Scope) -> Vec<SkillAgent> {
+    match scope {
+        Scope::Global => catalog(),
+        Scope::Project(root) => catalog()
+            .into_iter()
+            .filter_map(|mut agent| {
+                let (_, dir) = PROJECT_DIRS.iter().find(|(id, _)| *id == agent.id)?;
+                agent.skills_path = root.join(dir);
+                Some(agent)
+            })
+            .collect(),
+    }
+}
+
+/// Find an agent by ID with its skills directory in `scope`
+pub fn find_in(scope: &Scope, id: &str) -> Option<SkillAgent> {
+    catalog_for(scope)
+        .into_iter()
+        .find(|a| a.id.eq_ignore_ascii_case(id))
+}
+
+/// Nearest directory from `start` upwards that is a git repository's root,
+/// or `start` itself outside of one
+pub(super) fn project_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// Scope for `--project`: the project containing the current directory
+pub fn scope(project: bool) -> Result<Scope> {
+    if !project {
+        return Ok(Scope::Global);
+    }
+    let cwd = env::current_dir().context("Failed to get current directory")?;
+    Ok(Scope::Project(project_root(&cwd)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn project_skills_live_under_the_repository_root() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("src/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(project_root(&nested), nested);
+
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        assert_eq!(project_root(&nested), dir.path());
+
+        let scope = Scope::Project(dir.path().to_path_buf());
+        assert_eq!(
+            find_in(&scope, "claude").unwrap().skills_path,
+            dir.path().join(".claude/skills")
+        );
+        assert_eq!(catalog_for(&scope).len(), catalog().len());
+    }
+
+    #[test]
+    fn finds_editor_extensions() {
+        let home = TempDir::new().unwrap();
+        let extensions = home.path().join(".cursor/extensions");
+        std::fs::create_dir_all(extensions.join("ms-python.python-2024.1.0")).unwrap();
+        assert!(!has_extension(home.path(), &["saoudrizwan.claude-dev-"]));
+
+        std::fs::create_dir_all(extensions.join("saoudrizwan.claude-dev-3.17.0")).unwrap();
+        assert!(has_extension(home.path(), &["saoudrizwan.claude-dev-"]));
+        assert!(!has_extension(home.path(), &[]));
+    }
+}
@@ -0,0 +1,175 @@
+//! Credentials for cloning private skill repositories.
+//!
+//! Clones run the `git` binary rather than libgit2 or gix: neither does the
+//! blobless, sparse clones skill installs rely on, and git brings the user's
+//! own SSH and credential helper setup along. SSH URLs use the SSH agent and
+//! HTTPS URLs whatever credential helper git is configured with. On top of that a token
+//! from `--token` (any HTTPS host), `GITHUB_TOKEN`/`GH_TOKEN` (github.com) or
+//! `GITLAB_TOKEN` (gitlab.com) is handed to git through a credential helper in
+//! its environment, never on the command line, with the user name each host
+//! expects for tokens. Git is told not to prompt, so a clone without access
+//! fails with an explanation instead of waiting for a password in CI.
+
+use std::env;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Token given with `--token`
+static TOKEN: OnceLock<String> = OnceLock::new();
+
+/// Environment variable the credential helper reads the token from
+const TOKEN_VAR: &str = "AI_CLI_GIT_TOKEN";
+
+/// Environment variables holding a token for a host
+const HOST_TOKENS: &[(&str, &[&str])] = &[
+    ("github.com", &["GITHUB_TOKEN", "GH_TOKEN"]),
+    ("gitlab.com", &["GITLAB_TOKEN"]),
+];
+
+/// User name to send with a token; GitHub and Codeberg accept any
+fn token_user(host: &str) -> &'static str {
+    match host {
+        "gitlab.com" => "oauth2",
+        "bitbucket.org" => "x-token-auth",
+        _ => "x-access-token",
+    }
+}
+
+/// Use `token` for every HTTPS clone from now on
+pub fn set_token(token: String) {
+    let _ = TOKEN.set(token);
+}
+
+fn host(url: &str) -> Option<&str> {
+    let rest = url.strip_prefix("https://")?;
+    let authority = rest.split('/').next()?;
+    Some(authority.rsplit('@').next().unwrap_or(authority))
+}
+
+/// The token to clone `url` with: `--token` for any HTTPS host, a host's
+/// environment variables only for that host
+fn token_for(url: &str, explicit: Option<&str>) -> Option<String> {
+    let host = host(url)?;
+    if let Some(token) = explicit {
+        return Some(token.to_string());
+    }
+    let (_, vars) = HOST_TOKENS.iter().find(|(h, _)| *h == host)?;
+    vars.iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|token| !token.is_empty())
+}
+
+/// Prepare a `git clone` of `url`: no prompts, and the token if there is one
+pub(super) fn configure(cmd: &mut Command, url: &str) {
+    cmd.env("GIT_TERMINAL_PROMPT", "0");
+    let Some(token) = token_for(url, TOKEN.get().map(String::as_str)) else {
+        return;
+    };
+    // An empty helper drops the configured ones, so a stale stored password
+    // doesn't win over the token
+    let helper = format!(
+        "!f() {{ test \"$1\" = get && echo username={} && echo \"password=${}\"; }}; f",
+        token_user(host(url).unwrap_or_default()),
+        TOKEN_VAR
+    );
+    cmd.env(TOKEN_VAR, token)
+        .env("GIT_CONFIG_COUNT", "2")
+        .env("GIT_CONFIG_KEY_0", "credential.helper")
+        .env("GIT_CONFIG_VALUE_0", "")
+        .env("GIT_CONFIG_KEY_1", "credential.helper")
+        .env("GIT_CONFIG_VALUE_1", helper);
+}
+
+/// What to do about a clone of `url` that failed with `stderr`, when it
+/// looks like missing or rejected credentials
+pub(super) fn explain(stderr: &str, url: &str) -> Option<String> {
+    let stderr = stderr.to_lowercase();
+    if stderr.contains("host key verification failed") {
+        return Some(format!(
+            "The SSH host key of {} isn't trusted yet; connect once with `ssh` to accept it",
+            url
+        ));
+    }
+    if stderr.contains("permission denied (publickey)") {
+        return Some(
+            "SSH authentication failed; add a key with access to the repository to your \
+             SSH agent (`ssh-add`), or use the HTTPS URL with --token"
+                .to_string(),
+        );
+    }
+    let denied = [
+        "could not read username",
+        "terminal prompts disabled",
+        "authentication failed",
+        "repository not found",
+        "the requested url returned error: 401",
+        "the requested url returned error: 403",
+    ];
+    if !denied.iter().any(|text| stderr.contains(text)) {
+        return None;
+    }
+    let host = host(url).unwrap_or(url);
+    Some(
+        if token_for(url, TOKEN.get().map(String::as_str)).is_some() {
+            format!(
+                "{} rejected the token or it can't read this repository; check its scopes \
+                 (GitHub needs `repo` or Contents: read)",
+                host
+            )
+        } else {
+            format!(
+                "{} needs credentials for this repository (or it doesn't exist); pass \
+                 --token, set GITHUB_TOKEN or GITLAB_TOKEN, configure a git credential helper, \
+                 or use an SSH URL (git@{}:owner/repo.git)",
+                host, host
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_go_to_https_hosts_only() {
+        assert_eq!(
+            host("https://github.com/acme/skills.git"),
+            Some("github.com")
+        );
+        assert_eq!(
+            host("https://ci@gitlab.example.com/acme/skills.git"),
+            Some("gitlab.example.com")
+        );
+        assert_eq!(host("git@github.com:acme/skills.git"), None);
+        assert_eq!(token_user("gitlab.com"), "oauth2");
+        assert_eq!(token_user("bitbucket.org"), "x-token-auth");
+
+        assert_eq!(
+            token_for("https://gitlab.example.com/acme/skills.git", Some("t0k")).as_deref(),
+            Some("t0k")
+        );
+        assert_eq!(
+            token_for("git@github.com:acme/skills.git", Some("t0k")),
+            None
+        );
+    }
+
+    #[test]
+    fn explains_auth_failures() {
+        let prompt = "fatal: could not read Username for 'https://git.example.com': \
+                      terminal prompts disabled";
+        let hint = explain(prompt, "https://git.example.com/acme/private.git").unwrap();
+        assert!(hint.starts_with("git.example.com needs credentials"));
+        assert!(hint.contains("--token"));
+
+        let ssh = "git@github.com: Permission denied (publickey).\nfatal: Could not read";
+        assert!(
+            explain(ssh, "git@github.com:acme/private.git")
+                .unwrap()
+                .contains("ssh-add")
+        );
+
+        assert!(explain("fatal: Remote branch v9 not found", "https://x.org/a/b.git").is_none());
+    }
+}
@@ -0,0 +1,234 @@
+//! `skills export` / `skills import`: share a skill set as one file.
+//!
+//! A bundle lists skill names with the repository (and ref) each came from.
+//! Agents are left out on purpose: whoever imports a bundle gets its skills
+//! in every agent they have installed, or the one given with `--agent`.
+//!
+//! ```json
+//! { "version": 1,
+//!   "skills": [{ "name": "pdf", "source": "anthropics/skills#v1.2.0" }] }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use super::actions::install_from_repo;
+use super::agents::{self, SkillAgent};
+use super::discovery;
+use super::manifest::Manifest;
+use crate::exit::{self, Outcome};
+use crate::prompt;
+use crate::theme::Themed;
+
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: Vec<BundleSkill>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleSkill {
+    pub name: String,
+    /// Repository, with `#ref` when installed from a branch, tag or commit
+    pub source: String,
+}
+
+impl Bundle {
+    /// The skills recorded in `manifest`; those installed some other way have
+    /// no source to share
+    fn from_manifest(manifest: &Manifest) -> Self {
+        Bundle {
+            version: BUNDLE_VERSION,
+            skills: manifest
+                .skills
+                .iter()
+                .map(|(name, record)| BundleSkill {
+                    name: name.clone(),
+                    source: record.source.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let bundle: Bundle = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON in {}", path.display()))?;
+        if bundle.version > BUNDLE_VERSION {
+            anyhow::bail!(
+                "{} was written by a newer ai-cli (format version {})",
+                path.display(),
+                bundle.version
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Skill names per source that some of `agents` don't have yet
+    fn missing(&self, agents: &[SkillAgent]) -> BTreeMap<String, Vec<String>> {
+        let installed: Vec<(String, &str)> = agents
+            .iter()
+            .flat_map(|agent| {
+                discovery::list_installed_skills(&agent.skills_path)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|s| (s.name, agent.id))
+            })
+            .collect();
+        let mut missing: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for skill in &self.skills {
+            let everywhere = agents
+                .iter()
+                .all(|a| installed.contains(&(skill.name.clone(), a.id)));
+            if !everywhere {
+                missing
+                    .entry(skill.source.clone())
+                    .or_default()
+                    .push(skill.name.clone());
+            }
+        }
+        missing
+    }
+}
+
+/// Handle `skills export <file>` command
+pub fn handle_export(path: &Path) -> Result<Outcome> {
+    let manifest = Manifest::load()?;
+    let bundle = Bundle::from_manifest(&manifest);
+    if bundle.skills.is_empty() {
+        println!("No skills installed by ai-cli to export");
+        return Ok(Outcome::NothingToDo);
+    }
+    let content = serde_json::to_string_pretty(&bundle)?;
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mut unsourced: Vec<String> = agents::catalog()
+        .iter()
+        .flat_map(|agent| discovery::list_installed_skills(&agent.skills_path).unwrap_or_default())
+        .map(|skill| skill.name)
+        .filter(|name| manifest.source_of(name).is_none())
+        .collect();
+    unsourced.sort();
+    unsourced.dedup();
+
+    println!(
+        "{} Exported {} skill(s) to {}",
+        "✓".success(),
+        bundle.skills.len(),
+        path.display()
+    );
+    if !unsourced.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Left out skills not installed from a repository: {}",
+                unsourced.join(", ")
+            )
+            .muted()
+        );
+    }
+    Ok(Outcome::Success)
+}
+
+/// Handle `skills import <file> [--agent]` command
+pub fn handle_import(path: &Path, agent_filter: Option<&str>) -> Result<Outcome> {
+    let bundle = Bundle::load(path)?;
+    let agents: Vec<SkillAgent> = match agent_filter {
+        Some(id) => {
+            vec![agents::find(id).ok_or_else(|| exit::usage(format!("Unknown agent: {}", id)))?]
+        }
+        None => agents::catalog()
+            .into_iter()
+            .filter(|a| a.is_installed())
+            .collect(),
+    };
+    if agents.is_empty() {
+        anyhow::bail!("No AI agents installed to install skills to");
+    }
+
+    let missing = bundle.missing(&agents);
+    if missing.is_empty() {
+        println!(
+            "{} Every skill in {} is installed",
+            "✓".success(),
+            path.display()
+        );
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let ids: Vec<&str> = agents.iter().map(|a| a.id).collect();
+    println!("{}", "Planned changes:".bold());
+    for (source, names) in &missing {
+        println!(
+            "  {} install skill(s) {} from {} for {}",
+            "+".success(),
+            names.join(", "),
+            source,
+            ids.join(", ")
+        );
+    }
+    println!();
+    if !prompt::confirm("Install these skills?")? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+    for (source, names) in &missing {
+        if let Err(e) = install_from_repo(source, &agents, Some(names)) {
+            println!(
+                "{} Failed to install skills from {}: {:#}",
+                "✗".error(),
+                source,
+                e
+            );
+            failed += 1;
+        }
+        println!();
+    }
+    if failed == 0 {
+        println!("{}", "Skills imported successfully!".success());
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_recorded_sources_with_their_refs() {
+        let mut manifest = Manifest::default();
+        manifest.record_install("pdf", "anthropics/skills#v1.2.0", "claude");
+        manifest.record_install("pdf", "anthropics/skills#v1.2.0", "codex");
+        manifest.record_install("release-notes", "acme/skills", "claude");
+
+        let bundle = Bundle::from_manifest(&manifest);
+        assert_eq!(
+            bundle.skills,
+            [
+                BundleSkill {
+                    name: "pdf".to_string(),
+                    source: "anthropics/skills#v1.2.0".to_string(),
+                },
+                BundleSkill {
+                    name: "release-notes".to_string(),
+                    source: "acme/skills".to_string(),
+                },
+            ]
+        );
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed: Bundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.skills, bundle.skills);
+        assert_eq!(parsed.version, BUNDLE_VERSION);
+    }
+}
@@ -0,0 +1,270 @@
+//! Installing a skill whose name is already taken by a different skill.
+//!
+//! A skill recorded from the same repository is simply replaced, as an
+//! update. One recorded from another repository, or an unrecorded one whose
+//! files differ, is a conflict: the files that would change are listed and
+//! the user picks overwrite, skip or rename. A renamed skill is installed as
+//! a copy with the new name in its frontmatter; it is recorded as coming from
+//! the repository but not locked, since the repository has no skill by that
+//! name. `--force` overwrites without asking.
+
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use inquire::validator::Validation;
+use inquire::{Select, Text};
+
+use super::actions::{copy_dir_recursive, files_in, same_contents, split_ref};
+use super::agents::SkillAgent;
+use super::diff::compare;
+use super::discovery::{self, Skill};
+use super::manifest::Manifest;
+use crate::instructions::Scope;
+use crate::prompt;
+use crate::theme::Themed;
+
+/// What to do with an incoming skill whose name is taken
+#[derive(Debug, Clone, PartialEq)]
+enum Resolution {
+    Overwrite,
+    Skip,
+    Rename(String),
+}
+
+/// Skills to install after conflicts were resolved
+pub(super) struct Resolved {
+    pub skills: Vec<Skill>,
+    /// Names of renamed copies, which can't be locked
+    pub renamed: Vec<String>,
+}
+
+/// The installed copy that `skill` from `repo` would replace, when it is a
+/// different skill: recorded from another repository (`recorded`), or
+/// unrecorded with other files
+fn conflict(
+    repo: &str,
+    skill: &Skill,
+    agents: &[SkillAgent],
+    recorded: Option<&str>,
+) -> Option<PathBuf> {
+    if recorded.is_some_and(|source| split_ref(source).0 == split_ref(repo).0) {
+        return None;
+    }
+    agents
+        .iter()
+        .filter(|a| a.is_installed())
+        .map(|a| a.skills_path.join(&skill.name))
+        .find(|dest| dest.is_dir() && (recorded.is_some() || !same_contents(&skill.path, dest)))
+}
+
+/// `content` of a SKILL.md with the frontmatter `name:` set to `name`
+fn rename_frontmatter(content: &str, name: &str) -> String {
+    let mut renamed = String::with_capacity(content.len());
+    let mut delimiters = 0;
+    let mut done = false;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end();
+        if text == "---" {
+            delimiters += 1;
+        } else if delimiters == 1 && !done && text.starts_with("name:") {
+            renamed.push_str(&format!("name: {}", name));
+            renamed.push_str(&line[text.len()..]);
+            done = true;
+            continue;
+        }
+        renamed.push_str(line);
+    }
+    renamed
+}
+
+/// A copy of `skill` under `scratch`, named `name`
+fn renamed_copy(skill: &Skill, name: &str, scratch: &Path) -> Result<Skill> {
+    let dest = scratch.join(name);
+    copy_dir_recursive(&skill.path, &dest)
+        .with_context(|| format!("Failed to copy skill {}", skill.name))?;
+    let skill_file = dest.join("SKILL.md");
+    let content = fs::read_to_string(&skill_file)
+        .with_context(|| format!("Failed to read {}", skill_file.display()))?;
+    fs::write(&skill_file, rename_frontmatter(&content, name))
+        .with_context(|| format!("Failed to write {}", skill_file.display()))?;
+    discovery::check_skill_dir(&dest)
+}
+
+/// Ask what to do about the conflict over `name`
+fn choose(name: &str, suggested: &str, agents: &[SkillAgent]) -> Result<Resolution> {
+    let options = vec!["Overwrite", "Skip", "Rename"];
+    let picked = Select::new(&format!("What to do with '{}'?", name), options)
+        .with_starting_cursor(1)
+        .prompt()
+        .context("Install cancelled")?;
+    Ok(match picked {
+        "Overwrite" => Resolution::Overwrite,
+        "Skip" => Resolution::Skip,
+        _ => {
+            let taken: Vec<PathBuf> = agents.iter().map(|a| a.skills_path.clone()).collect();
+            let new_name = Text::new("New name:")
+                .with_default(suggested)
+                .with_validator(move |input: &str| {
+                    let input = input.trim();
+                    Ok(
+                        if input.is_empty() || input.contains(['/', '\\']) || input.starts_with('.')
+                        {
+                            Validation::Invalid("Not a valid skill name".into())
+                        } else if taken.iter().any(|dir| dir.join(input).exists()) {
+                            Validation::Invalid(format!("'{}' is taken too", input).into())
+                        } else {
+                            Validation::Valid
+                        },
+                    )
+                })
+                .prompt()
+                .context("Install cancelled")?;
+            Resolution::Rename(new_name.trim().to_string())
+        }
+    })
+}
+
+/// Decide, skill by skill, what happens to installed skills with the same
+/// name as one from `repo`; renamed copies are made under `scratch`
+pub(super) fn resolve(
+    repo: &str,
+    skills: Vec<Skill>,
+    agents: &[SkillAgent],
+    scope: &Scope,
+    scratch: &Path,
+) -> Result<Resolved> {
+    let manifest = match scope {
+        Scope::Global => Manifest::load()?,
+        Scope::Project(_) => Manifest::default(),
+    };
+    let interactive = !prompt::is_non_interactive() && std::io::stdin().is_terminal();
+    let repo_name = split_ref(repo)
+        .0
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let mut resolved = Resolved {
+        skills: Vec::new(),
+        renamed: Vec::new(),
+    };
+    let mut conflicts = 0;
+    let mut skipped = Vec::new();
+    for skill in skills {
+        let recorded = manifest.source_of(&skill.name);
+        let Some(existing) = conflict(repo, &skill, agents, recorded) else {
+            resolved.skills.push(skill);
+            continue;
+        };
+        conflicts += 1;
+
+        match recorded {
+            Some(source) => println!(
+                "{} '{}' is already installed from {}",
+                "!".warning(),
+                skill.name.bold(),
+                source
+            ),
+            None => println!(
+                "{} '{}' is already installed and isn't from {}",
+                "!".warning(),
+                skill.name.bold(),
+                repo
+            ),
+        }
+        let changes = compare(&files_in(&existing), &files_in(&skill.path));
+        if changes.is_empty() {
+            println!("    {}", "(same files)".muted());
+        }
+        for (path, change) in &changes {
+            println!("    {} {}", change.symbol(), path.display());
+        }
+
+        let resolution = if interactive {
+            choose(
+                &skill.name,
+                &format!("{}-{}", skill.name, repo_name),
+                agents,
+            )?
+        } else {
+            Resolution::Skip
+        };
+        match resolution {
+            Resolution::Overwrite => resolved.skills.push(skill),
+            Resolution::Skip => skipped.push(skill.name),
+            Resolution::Rename(name) => {
+                resolved.skills.push(renamed_copy(&skill, &name, scratch)?);
+                resolved.renamed.push(name);
+            }
+        }
+    }
+
+    if !skipped.is_empty() {
+        println!(
+            "{} Skipped {}; use --force to overwrite",
+            "→".info(),
+            skipped.join(", ")
+        );
+    }
+    if conflicts > 0 {
+        println!();
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn only_other_skills_conflict() {
+        let dir = TempDir::new().unwrap();
+        let incoming = dir.path().join("clone/pdf");
+        fs::create_dir_all(&incoming).unwrap();
+        fs::write(incoming.join("SKILL.md"), "---\nname: pdf\n---\nnew").unwrap();
+        let skills_path = dir.path().join("skills");
+        fs::create_dir_all(skills_path.join("pdf")).unwrap();
+        fs::write(skills_path.join("pdf/SKILL.md"), "---\nname: pdf\n---\nold").unwrap();
+
+        // Cursor counts as installed when its directory exists
+        let agent = SkillAgent {
+            name: "Cursor",
+            id: "cursor",
+            binary_name: "cursor",
+            skills_path: skills_path.clone(),
+        };
+        let skill = discovery::check_skill_dir(&incoming).unwrap();
+        let agents = [agent];
+
+        // Same repository: an update, whatever the ref
+        assert_eq!(
+            conflict("acme/skills", &skill, &agents, Some("acme/skills#v1")),
+            None
+        );
+        assert_eq!(
+            conflict("acme/skills", &skill, &agents, Some("other/skills")),
+            Some(skills_path.join("pdf"))
+        );
+        assert!(conflict("acme/skills", &skill, &agents, None).is_some());
+
+        // An identical unrecorded copy is the same skill
+        fs::write(skills_path.join("pdf/SKILL.md"), "---\nname: pdf\n---\nnew").unwrap();
+        assert_eq!(conflict("acme/skills", &skill, &agents, None), None);
+    }
+
+    #[test]
+    fn renames_only_the_frontmatter_name() {
+        let content = "---\nname: pdf\r\ndescription: name: pdf\n---\nname: pdf\n";
+        assert_eq!(
+            rename_frontmatter(content, "pdf-acme"),
+            "---\nname: pdf-acme\r\ndescription: name: pdf\n---\nname: pdf\n"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! `skills convert`: an installed skill in another agent's own format.
+//!
+//! For tools that don't read SKILL.md, the skill's instructions are written
+//! as a Cursor rule (`.cursor/rules/<name>.mdc`, applied when its description
+//! matches), a Claude Code slash command (`.claude/commands/<name>.md`) or a
+//! Copilot instructions file (`.github/instructions/<name>.instructions.md`).
+//! Cursor and Copilot files go into the current project; commands go into
+//! `~/.claude/commands` unless `--project`. Scripts and references the skill
+//! ships stay where they are and the converted file points to them.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::actions::files_in;
+use super::agents;
+use super::discovery::{self, Skill};
+use crate::commands;
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::logging;
+use crate::prompt;
+use crate::rules::{self, Rule};
+use crate::theme::Themed;
+
+/// Formats a skill can be converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    CursorRule,
+    ClaudeCommand,
+    CopilotInstructions,
+}
+
+impl Target {
+    fn parse(id: &str) -> Option<Self> {
+        match id {
+            "cursor-rule" => Some(Target::CursorRule),
+            "claude-command" => Some(Target::ClaudeCommand),
+            "copilot-instructions" => Some(Target::CopilotInstructions),
+            _ => None,
+        }
+    }
+
+    /// Where the converted `name` is written; `scope` only matters for commands
+    fn path(self, name: &str, scope: &Scope, project: &Path) -> PathBuf {
+        match self {
+            Target::CursorRule => project.join(".cursor/rules").join(format!("{}.mdc", name)),
+            Target::ClaudeCommand => commands::source_dir(scope).join(format!("{}.md", name)),
+            Target::CopilotInstructions => project
+                .join(".github/instructions")
+                .join(format!("{}.instructions.md", name)),
+        }
+    }
+}
+
+/// The SKILL.md text after its frontmatter
+fn body(content: &str) -> &str {
+    let Some(rest) = content.trim_start().strip_prefix("---") else {
+        return content.trim();
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let after = &rest[end + 4..];
+            after.split_once('\n').map_or("", |(_, body)| body).trim()
+        }
+        None => content.trim(),
+    }
+}
+
+/// `skill` (with SKILL.md `content`) rendered for `target`
+fn render(target: Target, skill: &Skill, content: &str) -> String {
+    let mut body = body(content).to_string();
+    if files_in(&skill.path)
+        .keys()
+        .any(|file| file != Path::new("SKILL.md"))
+    {
+        body.push_str(&format!(
+            "\n\nThe scripts and reference files of this skill are in `{}`.",
+            skill.path.display()
+        ));
+    }
+    // Frontmatter values have to stay on one line
+    let description = skill
+        .description
+        .as_deref()
+        .unwrap_or("")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    match target {
+        Target::CursorRule => rules::render_cursor(&Rule {
+            name: skill.name.clone(),
+            description: (!description.is_empty()).then(|| description.clone()),
+            globs: None,
+            always: false,
+            body,
+        }),
+        Target::ClaudeCommand => {
+            let mut out = format!("---\ndescription: {}\n", description);
+            if !skill.allowed_tools.is_empty() {
+                out.push_str(&format!(
+                    "allowed-tools: {}\n",
+                    skill.allowed_tools.join(", ")
+                ));
+            }
+            out.push_str(&format!("---\n\n{}\n", body));
+            out
+        }
+        Target::CopilotInstructions => format!(
+            "---\ndescription: {}\napplyTo: \"**\"\n---\n\n{}\n",
+            description, body
+        ),
+    }
+}
+
+/// Handle `skills convert <skill> --to <format> [--project]` command
+pub fn handle_convert(skill_name: &str, to: &str, project: bool) -> Result<Outcome> {
+    let target = Target::parse(to).ok_or_else(|| exit::usage(format!("Unknown format: {}", to)))?;
+    let root =
+        agents::project_root(&env::current_dir().context("Failed to get current directory")?);
+    let scope = agents::scope(project)?;
+
+    // The project's copy wins over the global one
+    let skill = agents::catalog_for(&Scope::Project(root.clone()))
+        .into_iter()
+        .chain(agents::catalog())
+        .find_map(|agent| discovery::check_skill_dir(&agent.skills_path.join(skill_name)).ok())
+        .ok_or_else(|| {
+            exit::usage(format!(
+                "Skill '{}' is not installed (see `ai-cli skills list`)",
+                skill_name
+            ))
+        })?;
+    let skill_file = skill.path.join("SKILL.md");
+    let content = fs::read_to_string(&skill_file)
+        .with_context(|| format!("Failed to read {}", skill_file.display()))?;
+    let rendered = render(target, &skill, &content);
+
+    let path = target.path(&skill.name, &scope, &root);
+    let before = fs::read_to_string(&path).ok();
+    if before.as_deref() == Some(rendered.as_str()) {
+        println!("{} {} is up to date", "✓".success(), path.display());
+        return Ok(Outcome::NothingToDo);
+    }
+    if before.is_some() && !prompt::confirm(&format!("Overwrite {}?", path.display()))? {
+        println!("Nothing written.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    logging::write_config(&path, before.as_deref().unwrap_or_default(), &rendered)?;
+    println!(
+        "{} Converted '{}' to {}",
+        "✓".success(),
+        skill.name,
+        path.display()
+    );
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_each_format() {
+        let content = "---\nname: pdf\ndescription: Fill PDF forms\n---\n\n# PDF\n\nUse fill.py.\n";
+        let skill = Skill {
+            name: "pdf".to_string(),
+            description: Some("Fill PDF forms".to_string()),
+            allowed_tools: vec!["Bash".to_string(), "Read".to_string()],
+            path: PathBuf::from("/nonexistent/pdf"),
+            ..Default::default()
+        };
+        assert_eq!(body(content), "# PDF\n\nUse fill.py.");
+
+        let rule = render(Target::CursorRule, &skill, content);
+        assert!(
+            rule.starts_with("---\ndescription: Fill PDF forms\nglobs: \nalwaysApply: false\n")
+        );
+        assert!(rule.ends_with("# PDF\n\nUse fill.py.\n"));
+
+        assert_eq!(
+            render(Target::ClaudeCommand, &skill, content),
+            "---\ndescription: Fill PDF forms\nallowed-tools: Bash, Read\n---\n\n# PDF\n\nUse fill.py.\n"
+        );
+        assert!(
+            render(Target::CopilotInstructions, &skill, content)
+                .contains("applyTo: \"**\"\n---\n\n# PDF")
+        );
+        assert_eq!(Target::parse("cursor"), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! `skills dev`: link a skill being written into agents.
+//!
+//! Each selected agent's skills directory gets a symlink to the working
+//! directory, so edits show up in the agents straight away, without
+//! reinstalling. The links aren't recorded in the manifest; `skills remove`
+//! deletes them and leaves the working directory alone. With `--watch` the
+//! skill is validated again whenever one of its files changes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use super::actions::symlink_dir;
+use super::agents::{self, SkillAgent};
+use super::discovery;
+use super::skillignore::skill_files;
+use super::validate;
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::logging;
+use crate::theme::Themed;
+
+/// How often `--watch` looks for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Size and modification time of every file of the skill at `dir`
+fn fingerprint(dir: &Path) -> Vec<(PathBuf, u64, Option<SystemTime>)> {
+    skill_files(dir)
+        .into_iter()
+        .map(|file| {
+            let meta = fs::metadata(dir.join(&file)).ok();
+            let len = meta.as_ref().map_or(0, |m| m.len());
+            (file, len, meta.and_then(|m| m.modified().ok()))
+        })
+        .collect()
+}
+
+/// Print whether the skill at `dir` passes `skills validate`; returns whether it does
+fn report(dir: &Path) -> bool {
+    let skill_file = dir.join("SKILL.md");
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let problems = match fs::read_to_string(&skill_file) {
+        Ok(content) => validate::check(&dir_name, &content),
+        Err(e) => vec![format!("Can't read SKILL.md: {}", e)],
+    };
+    if problems.is_empty() {
+        println!("{} {} is valid", "✓".success(), skill_file.display());
+        return true;
+    }
+    println!("{} {}", "✗".error(), skill_file.display());
+    for problem in problems {
+        println!("    {}", problem);
+    }
+    false
+}
+
+/// Link `dir` into the agent as skill `name`; `false` when it already was
+fn link(dir: &Path, name: &str, agent: &SkillAgent) -> Result<bool> {
+    let dest = agent.skills_path.join(name);
+    if fs::symlink_metadata(&dest).is_ok() {
+        if fs::read_link(&dest).is_ok_and(|target| target == dir) {
+            return Ok(false);
+        }
+        anyhow::bail!(
+            "{} already exists; remove it first with `ai-cli skills remove {}`",
+            dest.display(),
+            name
+        );
+    }
+    agent
+        .ensure_skills_dir()
+        .with_context(|| format!("Failed to create skills directory for {}", agent.name))?;
+    symlink_dir(dir, &dest).with_context(|| format!("Failed to link {}", dest.display()))?;
+    logging::line(&format!("linked {} to {}", dest.display(), dir.display()));
+    Ok(true)
+}
+
+/// Handle `skills dev <path> [--agent ...] [--watch] [--project]` command
+pub fn handle_dev(path: &str, agent_ids: &[String], watch: bool, scope: &Scope) -> Result<Outcome> {
+    let dir = fs::canonicalize(path).map_err(|_| exit::usage(format!("{} doesn't exist", path)))?;
+    let skill = discovery::check_skill_dir(&dir)
+        .map_err(|e| exit::usage(format!("{} is not a skill: {:#}", dir.display(), e)))?;
+    let agents: Vec<SkillAgent> = if agent_ids.is_empty() {
+        agents::catalog_for(scope)
+            .into_iter()
+            .filter(|a| a.is_installed())
+            .collect()
+    } else {
+        agent_ids
+            .iter()
+            .map(|id| {
+                agents::find_in(scope, id)
+                    .ok_or_else(|| exit::usage(format!("Unknown agent: {}", id)))
+            })
+            .collect::<Result<_>>()?
+    };
+    if agents.is_empty() {
+        anyhow::bail!("No AI agents installed to link the skill to");
+    }
+
+    println!(
+        "{}",
+        format!("Linking skill '{}' from {}:", skill.name, dir.display()).bold()
+    );
+    let mut failed = 0;
+    for agent in &agents {
+        print!("  {:<16}", agent.name);
+        match link(&dir, &skill.name, agent) {
+            Ok(true) => println!("{}", "[OK]".success()),
+            Ok(false) => println!("{}", "[OK] Already linked".muted()),
+            Err(e) => {
+                println!("{} {:#}", "[FAIL]".error(), e);
+                failed += 1;
+            }
+        }
+    }
+    println!();
+    let valid = report(&dir);
+    println!(
+        "{} Edits show up in the agents directly; `ai-cli skills remove {}` unlinks it",
+        "→".info(),
+        skill.name
+    );
+    if !watch {
+        if !valid {
+            return Ok(Outcome::Failure);
+        }
+        return Ok(Outcome::from_failures(failed));
+    }
+
+    println!("{} Watching for changes (Ctrl-C to stop)", "→".info());
+    let mut last = fingerprint(&dir);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = fingerprint(&dir);
+        if current == last {
+            continue;
+        }
+        last = current;
+        println!();
+        println!(
+            "{}",
+            chrono::Local::now().format("%H:%M:%S").to_string().muted()
+        );
+        report(&dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn links_once_and_refuses_other_skills() {
+        let dir = TempDir::new().unwrap();
+        let work = dir.path().join("work/pdf");
+        fs::create_dir_all(&work).unwrap();
+        fs::write(work.join("SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+        let agent = SkillAgent {
+            name: "Cursor",
+            id: "cursor",
+            binary_name: "cursor",
+            skills_path: dir.path().join("skills"),
+        };
+
+        assert!(link(&work, "pdf", &agent).unwrap());
+        assert!(!link(&work, "pdf", &agent).unwrap());
+        assert_eq!(fs::read_link(dir.path().join("skills/pdf")).unwrap(), work);
+
+        let before = fingerprint(&work);
+        fs::write(
+            work.join("SKILL.md"),
+            "---\nname: pdf\ndescription: x\n---\n",
+        )
+        .unwrap();
+        assert_ne!(fingerprint(&work), before);
+
+        fs::create_dir_all(dir.path().join("skills/docx")).unwrap();
+        assert!(link(&work, "docx", &agent).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! `skills diff`: local edits to installed skills.
+//!
+//! Each recorded skill is compared with its source repository at the commit
+//! it was installed from, so the files listed are the ones edited locally;
+//! those are what `skills update` would overwrite. Agents linked to the same
+//! stored copy are compared once.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use super::actions::{clone_skills, files_in, split_ref};
+use super::agents;
+use super::discovery;
+use super::manifest::{Manifest, SkillRecord};
+use crate::exit::{self, Outcome};
+use crate::theme::Themed;
+
+/// How a file of the installed copy differs from the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl Change {
+    pub(super) fn symbol(self) -> String {
+        match self {
+            Change::Added => "+".success().to_string(),
+            Change::Removed => "-".error().to_string(),
+            Change::Modified => "~".warning().to_string(),
+        }
+    }
+}
+
+/// Files of `installed` that differ from `source`, by relative path
+pub(super) fn compare(
+    source: &BTreeMap<PathBuf, Vec<u8>>,
+    installed: &BTreeMap<PathBuf, Vec<u8>>,
+) -> Vec<(PathBuf, Change)> {
+    let mut changes: Vec<(PathBuf, Change)> = Vec::new();
+    for (path, content) in installed {
+        match source.get(path) {
+            None => changes.push((path.clone(), Change::Added)),
+            Some(original) if original != content => changes.push((path.clone(), Change::Modified)),
+            Some(_) => {}
+        }
+    }
+    for path in source.keys().filter(|p| !installed.contains_key(*p)) {
+        changes.push((path.clone(), Change::Removed));
+    }
+    changes.sort();
+    changes
+}
+
+/// The recorded source of a skill pinned to the commit it was installed from
+fn pinned_source(record: &SkillRecord) -> String {
+    match &record.commit {
+        Some(commit) => format!("{}#{}", split_ref(&record.source).0, commit),
+        None => record.source.clone(),
+    }
+}
+
+/// Handle `skills diff [skill]` command; `Failure` when anything was edited
+pub fn handle_diff(skill_filter: Option<&str>) -> Result<Outcome> {
+    let manifest = Manifest::load()?;
+    let records: Vec<(&String, &SkillRecord)> = match skill_filter {
+        Some(name) => {
+            let record = manifest.skills.get_key_value(name).ok_or_else(|| {
+                exit::usage(format!(
+                    "'{}' wasn't installed by ai-cli from a repository",
+                    name
+                ))
+            })?;
+            vec![record]
+        }
+        None => manifest.skills.iter().collect(),
+    };
+    if records.is_empty() {
+        println!("No skills installed by ai-cli");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    // One clone per source and commit
+    let mut by_source: BTreeMap<String, Vec<(&String, &SkillRecord)>> = BTreeMap::new();
+    for (name, record) in records {
+        by_source
+            .entry(pinned_source(record))
+            .or_default()
+            .push((name, record));
+    }
+
+    let mut edited = 0;
+    let mut failed = 0;
+    for (source, skills) in &by_source {
+        let clone = match clone_skills(source) {
+            Ok(clone) => clone,
+            Err(e) => {
+                println!("  {} {}: {:#}", "[FAIL]".error(), source, e);
+                failed += 1;
+                continue;
+            }
+        };
+        let upstream = discovery::discover_skills(clone.path())?;
+        println!();
+
+        for (name, record) in skills {
+            let Some(original) = upstream.iter().find(|s| &s.name == *name) else {
+                println!(
+                    "{} {}",
+                    name.bold(),
+                    format!("(no longer in {})", source).muted()
+                );
+                continue;
+            };
+            let original = files_in(&original.path);
+
+            // Agents linked to the store share one copy
+            let mut copies: BTreeMap<PathBuf, Vec<&str>> = BTreeMap::new();
+            for agent in record.agents.iter().filter_map(|id| agents::find(id)) {
+                let path = agent.skills_path.join(name.as_str());
+                if path.is_dir() {
+                    let real = std::fs::canonicalize(&path).unwrap_or(path);
+                    copies.entry(real).or_default().push(agent.id);
+                }
+            }
+
+            for (path, ids) in &copies {
+                let changes = compare(&original, &files_in(path));
+                let label = format!("{} ({})", name, ids.join(", "));
+                if changes.is_empty() {
+                    println!("{} {}", label, "unchanged".muted());
+                    continue;
+                }
+                edited += 1;
+                println!("{} {}", label.bold(), path.display().to_string().muted());
+                for (file, change) in changes {
+                    println!("  {} {}", change.symbol(), file.display());
+                }
+            }
+        }
+    }
+
+    println!();
+    if edited > 0 {
+        println!(
+            "{} {} skill install(s) edited locally; `ai-cli skills update` would overwrite them",
+            "!".warning(),
+            edited
+        );
+        return Ok(Outcome::Failure);
+    }
+    if failed > 0 {
+        return Ok(Outcome::PartialFailure);
+    }
+    println!("{} No local edits", "✓".success());
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_added_removed_and_modified_files() {
+        let files = |entries: &[(&str, &str)]| -> BTreeMap<PathBuf, Vec<u8>> {
+            entries
+                .iter()
+                .map(|(path, body)| (PathBuf::from(path), body.as_bytes().to_vec()))
+                .collect()
+        };
+        let source = files(&[
+            ("SKILL.md", "v1"),
+            ("scripts/fill.py", "print()"),
+            ("forms.md", "x"),
+        ]);
+        let installed = files(&[
+            ("SKILL.md", "v1 edited"),
+            ("forms.md", "x"),
+            ("notes.md", "mine"),
+        ]);
+
+        assert_eq!(
+            compare(&source, &installed),
+            [
+                (PathBuf::from("SKILL.md"), Change::Modified),
+                (PathBuf::from("notes.md"), Change::Added),
+                (PathBuf::from("scripts/fill.py"), Change::Removed),
+            ]
+        );
+        assert!(compare(&source, &source).is_empty());
+    }
+}
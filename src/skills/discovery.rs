@@ -1,17 +1,39 @@
 use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_norway::{Mapping, Value};
 use std::path::{Path, PathBuf};
 
 /// Represents a skill found in a repository
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Skill {
     /// Skill name from frontmatter
     pub name: String,
     /// Description from frontmatter
     pub description: Option<String>,
+    /// `version` from frontmatter, numbers included (`1.0` → "1.0")
+    pub version: Option<String>,
+    pub license: Option<String>,
+    /// `allowed-tools`, given as a list or a comma- or space-separated string
+    pub allowed_tools: Vec<String>,
+    /// `metadata` mapping, as written
+    pub metadata: Mapping,
     /// Path to the skill directory (containing SKILL.md)
     pub path: PathBuf,
 }
 
+/// The SKILL.md frontmatter fields ai-cli understands; others are ignored
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Frontmatter {
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<Value>,
+    license: Option<String>,
+    allowed_tools: Option<Value>,
+    #[serde(default)]
+    metadata: Mapping,
+}
+
 /// Discovery priority order for finding SKILL.md files
 const DISCOVERY_PATHS: &[&str] = &[
     "",                     // Root directory
@@ -106,6 +128,11 @@ fn find_skills_recursive(
     Ok(())
 }
 
+/// Check that a directory holds a readable SKILL.md with valid frontmatter
+pub fn check_skill_dir(dir: &Path) -> Result<Skill> {
+    parse_skill(&dir.join("SKILL.md"), dir)
+}
+
 /// Parse a SKILL.md file and extract frontmatter
 fn parse_skill(skill_file: &Path, skill_dir: &Path) -> Result<Skill> {
     if !skill_file.exists() {
@@ -115,61 +142,75 @@ fn parse_skill(skill_file: &Path, skill_dir: &Path) -> Result<Skill> {
     let content = std::fs::read_to_string(skill_file)
         .with_context(|| format!("Failed to read {}", skill_file.display()))?;
 
-    // Parse YAML frontmatter (between --- markers)
-    let (name, description) = parse_frontmatter(&content)?;
+    let mut skill = parse_frontmatter(&content)?;
+    skill.path = skill_dir.to_path_buf();
+    Ok(skill)
+}
+
+/// The frontmatter of a SKILL.md (between `---` markers) as a YAML mapping
+pub fn frontmatter_yaml(content: &str) -> Result<Mapping> {
+    let rest = content
+        .trim_start()
+        .strip_prefix("---")
+        .context("SKILL.md must start with YAML frontmatter (---)")?;
+    let end = rest
+        .find("\n---")
+        .context("SKILL.md frontmatter not properly closed with ---")?;
+    match serde_norway::from_str(&rest[..end]).context("Frontmatter is not valid YAML")? {
+        Value::Mapping(map) => Ok(map),
+        Value::Null => Ok(Mapping::new()),
+        _ => anyhow::bail!("Frontmatter must be a mapping of fields"),
+    }
+}
+
+/// The skill described by a SKILL.md's frontmatter, without its path
+fn parse_frontmatter(content: &str) -> Result<Skill> {
+    let fields = frontmatter_yaml(content)?;
+    let frontmatter: Frontmatter = serde_norway::from_value(Value::Mapping(fields))
+        .context("SKILL.md frontmatter has a field of the wrong type")?;
+    let name = frontmatter
+        .name
+        .context("SKILL.md must have a 'name' field in frontmatter")?;
 
     Ok(Skill {
         name,
-        description,
-        path: skill_dir.to_path_buf(),
+        // Block scalars (`>`, `|`) end in a newline
+        description: frontmatter.description.map(|d| d.trim().to_string()),
+        version: frontmatter.version.as_ref().and_then(scalar),
+        license: frontmatter.license,
+        allowed_tools: match &frontmatter.allowed_tools {
+            Some(Value::Sequence(tools)) => tools.iter().filter_map(scalar).collect(),
+            Some(Value::String(tools)) => split_tools(tools),
+            _ => Vec::new(),
+        },
+        metadata: frontmatter.metadata,
+        path: PathBuf::new(),
     })
 }
 
-fn parse_frontmatter(content: &str) -> Result<(String, Option<String>)> {
-    let content = content.trim();
-
-    if !content.starts_with("---") {
-        anyhow::bail!("SKILL.md must start with YAML frontmatter (---)");
+/// A YAML string, number or boolean as text
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
     }
+}
 
-    let rest = &content[3..];
-    let end_idx = rest
-        .find("---")
-        .context("SKILL.md frontmatter not properly closed with ---")?;
-
-    let yaml_content = &rest[..end_idx];
-
-    // Simple YAML parsing for name, description
-    let mut name = None;
-    let mut description = None;
-
-    for line in yaml_content.lines() {
-        let trimmed = line.trim();
-
-        if trimmed.starts_with("name:") {
-            name = Some(
-                trimmed
-                    .strip_prefix("name:")
-                    .unwrap()
-                    .trim()
-                    .trim_matches('"')
-                    .to_string(),
-            );
-        } else if trimmed.starts_with("description:") {
-            description = Some(
-                trimmed
-                    .strip_prefix("description:")
-                    .unwrap()
-                    .trim()
-                    .trim_matches('"')
-                    .to_string(),
-            );
-        }
-    }
-
-    let name = name.context("SKILL.md must have a 'name' field in frontmatter")?;
-
-    Ok((name, description))
+/// `Read, Grep` or `Read Grep`; commas win so `Bash(git log:*)` stays whole
+fn split_tools(tools: &str) -> Vec<String> {
+    let parts: Vec<&str> = if tools.contains(',') {
+        tools.split(',').collect()
+    } else {
+        tools.split_whitespace().collect()
+    };
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 /// List installed skills for an agent
@@ -196,3 +237,37 @@ pub fn list_installed_skills(skills_path: &Path) -> Result<Vec<Skill>> {
 
     Ok(skills)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_frontmatter_as_yaml() {
+        let content = "---\nname: pdf\ndescription: >\n  Fill PDF forms.\n  Use when: a form is attached\n\
+                       version: 1.0\nlicense: \"MIT\"\nallowed-tools:\n  - Read\n  - Bash(git log:*)\n\
+                       metadata:\n  owner: docs\n---\n# PDF\n";
+        let skill = parse_frontmatter(content).unwrap();
+        assert_eq!(skill.name, "pdf");
+        assert_eq!(
+            skill.description.as_deref(),
+            Some("Fill PDF forms. Use when: a form is attached")
+        );
+        assert_eq!(skill.version.as_deref(), Some("1.0"));
+        assert_eq!(skill.license.as_deref(), Some("MIT"));
+        assert_eq!(skill.allowed_tools, ["Read", "Bash(git log:*)"]);
+        assert_eq!(
+            skill.metadata.get("owner").and_then(Value::as_str),
+            Some("docs")
+        );
+
+        let quoted = "---\nname: \"xlsx\"\ndescription: \"Sheets: read and write\"\n\
+                      allowed-tools: Read Grep\n---\n";
+        let skill = parse_frontmatter(quoted).unwrap();
+        assert_eq!(skill.description.as_deref(), Some("Sheets: read and write"));
+        assert_eq!(skill.allowed_tools, ["Read", "Grep"]);
+
+        assert!(parse_frontmatter("---\ndescription: no name\n---\n").is_err());
+        assert!(parse_frontmatter("---\nname: [pdf\n---\n").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! `skills doctor`: find broken skill installs.
+//!
+//! Looks through each agent's skills directory for links whose target is
+//! gone, directories without a SKILL.md, files that can't be read, frontmatter
+//! that doesn't parse or names a different skill than its directory, and two
+//! directories defining the same skill. Each problem comes with a fix.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use colored::Colorize;
+
+use super::agents::{self, SkillAgent};
+use super::discovery;
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::theme::Themed;
+
+/// A broken skill install and how to fix it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// Directory name in the skills directory
+    pub skill: String,
+    pub message: String,
+    pub fix: String,
+}
+
+impl Problem {
+    fn new(skill: &str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Problem {
+            skill: skill.to_string(),
+            message: message.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+/// Files below `dir` that can't be opened, or directories that can't be listed
+fn unreadable(dir: &Path, found: &mut Vec<(PathBuf, String)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            found.push((dir.to_path_buf(), e.to_string()));
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            unreadable(&path, found);
+        } else if path.exists()
+            && let Err(e) = fs::File::open(&path)
+        {
+            found.push((path, e.to_string()));
+        }
+    }
+}
+
+/// Problems with the skills in `skills_path`; `.disabled/` and other hidden
+/// entries are left alone
+pub fn inspect(skills_path: &Path) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let entries = match fs::read_dir(skills_path) {
+        Ok(entries) => entries,
+        Err(e) if skills_path.exists() => {
+            problems.push(Problem::new(
+                &skills_path.display().to_string(),
+                format!("Can't read the skills directory: {}", e),
+                format!("Fix the permissions: chmod u+rx {}", skills_path.display()),
+            ));
+            return problems;
+        }
+        Err(_) => return problems,
+    };
+    let mut entries: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+
+    // Frontmatter name → directories defining it
+    let mut names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for path in entries {
+        let dir_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if dir_name.starts_with('.') {
+            continue;
+        }
+        let remove = format!("remove it with `ai-cli skills remove {}`", dir_name);
+
+        let is_link = fs::symlink_metadata(&path).is_ok_and(|m| m.is_symlink());
+        if is_link && !path.exists() {
+            let target = fs::read_link(&path).unwrap_or_default();
+            problems.push(Problem::new(
+                &dir_name,
+                format!("Link to {} that no longer exists", target.display()),
+                format!("Reinstall the skill, or {}", remove),
+            ));
+            continue;
+        }
+        if !path.is_dir() {
+            continue;
+        }
+
+        let skill_file = path.join("SKILL.md");
+        if !skill_file.exists() {
+            if path.join("skill.md").is_file() {
+                problems.push(Problem::new(
+                    &dir_name,
+                    "Has skill.md but agents look for SKILL.md",
+                    "Rename skill.md to SKILL.md",
+                ));
+            } else {
+                problems.push(Problem::new(
+                    &dir_name,
+                    "No SKILL.md, so agents ignore this directory",
+                    format!("Add a SKILL.md or {}", remove),
+                ));
+            }
+            continue;
+        }
+
+        let mut unreadable_files = Vec::new();
+        unreadable(&path, &mut unreadable_files);
+        for (file, error) in &unreadable_files {
+            let relative = file.strip_prefix(&path).unwrap_or(file);
+            problems.push(Problem::new(
+                &dir_name,
+                format!("Can't read {}: {}", relative.display(), error),
+                format!("Fix the permissions: chmod -R u+rX {}", path.display()),
+            ));
+        }
+        if unreadable_files.iter().any(|(file, _)| file == &skill_file) {
+            continue;
+        }
+
+        match discovery::check_skill_dir(&path) {
+            Ok(skill) => {
+                if skill.name != dir_name {
+                    problems.push(Problem::new(
+                        &dir_name,
+                        format!("SKILL.md names the skill '{}'", skill.name),
+                        format!(
+                            "Rename the directory to '{}' or set `name: {}` in SKILL.md",
+                            skill.name, dir_name
+                        ),
+                    ));
+                }
+                names.entry(skill.name).or_default().push(dir_name);
+            }
+            Err(e) => problems.push(Problem::new(
+                &dir_name,
+                format!("{:#}", e),
+                format!(
+                    "Run `ai-cli skills validate {}` for details",
+                    path.display()
+                ),
+            )),
+        }
+    }
+
+    for (name, dirs) in names.into_iter().filter(|(_, dirs)| dirs.len() > 1) {
+        problems.push(Problem::new(
+            &dirs[0],
+            format!("'{}' is defined by {}", name, dirs.join(", ")),
+            "Keep one of them; agents load only one skill per name",
+        ));
+    }
+    problems
+}
+
+/// Handle `skills doctor [--agent] [--project]` command; `Failure` when
+/// anything is broken
+pub fn handle_doctor(agent_filter: Option<&str>, scope: &Scope) -> Result<Outcome> {
+    let agents: Vec<SkillAgent> = match agent_filter {
+        Some(id) => vec![
+            agents::find_in(scope, id)
+                .ok_or_else(|| exit::usage(format!("Unknown agent: {}", id)))?,
+        ],
+        None => agents::catalog_for(scope)
+            .into_iter()
+            .filter(|a| a.is_installed())
+            .collect(),
+    };
+
+    let mut total = 0;
+    for agent in &agents {
+        if !agent.skills_path.is_dir() {
+            continue;
+        }
+        let problems = inspect(&agent.skills_path);
+        if problems.is_empty() {
+            println!(
+                "{} {} {}",
+                "✓".success(),
+                agent.name.bold(),
+                agent.skills_path.display().to_string().muted()
+            );
+            continue;
+        }
+        total += problems.len();
+        println!(
+            "{} {} {}",
+            "✗".error(),
+            agent.name.bold(),
+            agent.skills_path.display().to_string().muted()
+        );
+        for problem in &problems {
+            println!("  {}: {}", problem.skill.bold(), problem.message);
+            println!("    {} {}", "→".info(), problem.fix.muted());
+        }
+    }
+
+    println!();
+    if total > 0 {
+        println!("{} Found {} problem(s)", "✗".error(), total);
+        return Ok(Outcome::Failure);
+    }
+    println!("{} No broken skills", "✓".success());
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(dir: &Path, name: &str, frontmatter_name: &str) {
+        fs::create_dir_all(dir.join(name)).unwrap();
+        fs::write(
+            dir.join(name).join("SKILL.md"),
+            format!("---\nname: {}\ndescription: x\n---\n", frontmatter_name),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn finds_broken_installs() {
+        let dir = TempDir::new().unwrap();
+        let skills = dir.path();
+        write_skill(skills, "pdf", "pdf");
+        write_skill(skills, "pdf-old", "pdf");
+        write_skill(skills, "xlsx", "sheets");
+        fs::create_dir_all(skills.join("empty")).unwrap();
+        fs::create_dir_all(skills.join("lower")).unwrap();
+        fs::write(skills.join("lower/skill.md"), "---\nname: lower\n---\n").unwrap();
+        write_skill(&skills.join(".disabled"), "off", "other");
+        fs::create_dir_all(skills.join("broken")).unwrap();
+        fs::write(skills.join("broken/SKILL.md"), "# no frontmatter").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(dir.path().join("gone"), skills.join("linked")).unwrap();
+
+        let problems = inspect(skills);
+        let found: Vec<(&str, &str)> = problems
+            .iter()
+            .map(|p| (p.skill.as_str(), p.message.as_str()))
+            .collect();
+        assert!(found.contains(&("empty", "No SKILL.md, so agents ignore this directory")));
+        assert!(found.contains(&("lower", "Has skill.md but agents look for SKILL.md")));
+        assert!(found.contains(&("xlsx", "SKILL.md names the skill 'sheets'")));
+        assert!(found.contains(&("pdf", "'pdf' is defined by pdf, pdf-old")));
+        assert!(found.iter().any(|(skill, _)| *skill == "broken"));
+        #[cfg(unix)]
+        assert!(
+            found
+                .iter()
+                .any(|(skill, message)| *skill == "linked" && message.contains("no longer exists"))
+        );
+        assert!(!found.iter().any(|(skill, _)| *skill == "off"));
+    }
+}
@@ -0,0 +1,138 @@
+//! `skills grep`: full-text search across every agent's installed skills.
+
+use std::fs;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use super::agents;
+use super::discovery::{self, Skill};
+use crate::exit::{self, Outcome};
+use crate::theme::Themed;
+
+/// Matching lines shown per skill before the rest are counted
+const LINES_SHOWN: usize = 3;
+
+/// One place a query occurs in a skill
+#[derive(Debug, Clone, PartialEq)]
+struct Hit {
+    /// `name`, `description`, or the SKILL.md line number
+    label: String,
+    text: String,
+}
+
+fn contains(haystack: &str, query: &str) -> bool {
+    haystack.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// Where `query` occurs in `skill`'s name, description and the body of its
+/// SKILL.md (`content`, frontmatter excluded)
+fn hits(skill: &Skill, content: &str, query: &str) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    if contains(&skill.name, query) {
+        hits.push(Hit {
+            label: "name".to_string(),
+            text: skill.name.clone(),
+        });
+    }
+    if let Some(description) = skill.description.as_deref().filter(|d| contains(d, query)) {
+        hits.push(Hit {
+            label: "description".to_string(),
+            text: description.to_string(),
+        });
+    }
+
+    let mut lines = content.lines().enumerate().peekable();
+    if lines.peek().is_some_and(|(_, line)| line.trim() == "---") {
+        lines.next();
+        for (_, line) in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+        }
+    }
+    for (index, line) in lines {
+        if contains(line, query) {
+            hits.push(Hit {
+                label: (index + 1).to_string(),
+                text: line.trim().to_string(),
+            });
+        }
+    }
+    hits
+}
+
+/// Handle `skills grep <query>` command
+pub fn handle_grep(query: &str, agent_filter: Option<&str>) -> Result<Outcome> {
+    let agents = match agent_filter {
+        Some(agent_id) => vec![
+            agents::find(agent_id)
+                .ok_or_else(|| exit::usage(format!("Unknown agent: {}", agent_id)))?,
+        ],
+        None => agents::catalog(),
+    };
+
+    let mut matched = 0;
+    for agent in &agents {
+        let mut skills = discovery::list_installed_skills(&agent.skills_path)?;
+        skills.sort_by(|a, b| a.name.cmp(&b.name));
+        for skill in skills {
+            let file = skill.path.join("SKILL.md");
+            let content = fs::read_to_string(&file).unwrap_or_default();
+            let hits = hits(&skill, &content, query);
+            if hits.is_empty() {
+                continue;
+            }
+
+            matched += 1;
+            println!(
+                "{} {} {}",
+                skill.name.info().bold(),
+                format!("({})", agent.id).muted(),
+                file.display().to_string().muted()
+            );
+            for hit in hits.iter().take(LINES_SHOWN) {
+                let text: String = hit.text.chars().take(100).collect();
+                println!("  {} {}", format!("{}:", hit.label).muted(), text);
+            }
+            if hits.len() > LINES_SHOWN {
+                println!(
+                    "  {}",
+                    format!("... and {} more", hits.len() - LINES_SHOWN).muted()
+                );
+            }
+        }
+    }
+
+    if matched == 0 {
+        println!("No installed skill mentions '{}'", query);
+        return Ok(Outcome::NothingToDo);
+    }
+    println!();
+    println!("{} skill install(s) match '{}'", matched, query);
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn searches_name_description_and_body() {
+        let skill = Skill {
+            name: "release-notes".to_string(),
+            description: Some("Draft Release notes from merged PRs".to_string()),
+            path: PathBuf::from("/skills/release-notes"),
+            ..Default::default()
+        };
+        let content = "---\nname: release-notes\ndescription: Draft Release notes\n---\n\
+                       # Release notes\n\nRun `git log` since the last tag.\n";
+
+        let found = hits(&skill, content, "RELEASE");
+        let labels: Vec<&str> = found.iter().map(|h| h.label.as_str()).collect();
+        assert_eq!(labels, ["name", "description", "5"]);
+        assert_eq!(hits(&skill, content, "git log")[0].label, "7");
+        assert!(hits(&skill, content, "frontmatter").is_empty());
+    }
+}
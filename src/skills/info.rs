@@ -0,0 +1,136 @@
+//! `skills info`: everything known about one installed skill.
+//!
+//! Prints the whole SKILL.md frontmatter rather than the name and description
+//! `skills list` shows, where the skill is installed and how big each copy is,
+//! and the repository and commit it was installed from.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_norway::Value;
+
+use super::agents;
+use super::discovery;
+use super::manifest::Manifest;
+use crate::exit::{self, Outcome};
+use crate::prune::{format_size, measure};
+use crate::theme::Themed;
+
+/// Frontmatter keys shown first, in this order; the rest follow as written
+const KNOWN_FIELDS: &[&str] = &["name", "description", "version", "license", "allowed-tools"];
+
+fn field_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Sequence(items) => items.iter().map(field_value).collect::<Vec<_>>().join(", "),
+        Value::Mapping(map) => map
+            .iter()
+            .map(|(k, v)| format!("{}={}", field_value(k), field_value(v)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Tagged(tagged) => field_value(&tagged.value),
+    }
+}
+
+/// Every frontmatter field of a SKILL.md, known fields first
+fn frontmatter_fields(content: &str) -> Result<Vec<(String, String)>> {
+    let map = discovery::frontmatter_yaml(content)?;
+    let mut fields: Vec<(String, String)> = map
+        .iter()
+        .map(|(key, value)| (field_value(key), field_value(value)))
+        .collect();
+    fields.sort_by_key(|(key, _)| {
+        KNOWN_FIELDS
+            .iter()
+            .position(|known| known == key)
+            .unwrap_or(KNOWN_FIELDS.len())
+    });
+    Ok(fields)
+}
+
+/// Handle `skills info <skill>` command
+pub fn handle_info(skill_name: &str) -> Result<Outcome> {
+    let installed: Vec<_> = agents::catalog()
+        .into_iter()
+        .filter_map(|agent| {
+            let path = agent.skills_path.join(skill_name);
+            discovery::check_skill_dir(&path).ok()?;
+            Some((agent, path))
+        })
+        .collect();
+    let Some((_, first)) = installed.first() else {
+        return Err(exit::usage(format!(
+            "Skill '{}' is not installed (see `ai-cli skills list`)",
+            skill_name
+        )));
+    };
+
+    let skill_file = first.join("SKILL.md");
+    let content = fs::read_to_string(&skill_file)
+        .with_context(|| format!("Failed to read {}", skill_file.display()))?;
+    println!("{}", skill_name.bold());
+    for (key, value) in frontmatter_fields(&content)? {
+        println!("  {:<16} {}", key, value);
+    }
+    println!();
+
+    let manifest = Manifest::load()?;
+    let source = match manifest.skills.get(skill_name) {
+        Some(record) => match &record.commit {
+            Some(commit) => format!("{} @ {}", record.source, commit.get(..7).unwrap_or(commit)),
+            None => record.source.clone(),
+        },
+        None => "unknown (not installed by ai-cli)".muted().to_string(),
+    };
+    println!("  {:<16} {}", "source", source);
+
+    println!("  {}", "installed in".bold());
+    for (agent, path) in &installed {
+        // Linked skills are measured in the store they point to
+        let (size, _) = measure(&fs::canonicalize(path).unwrap_or_else(|_| path.clone()));
+        let location = match fs::read_link(path) {
+            Ok(target) => format!("{} → {}", path.display(), target.display()),
+            Err(_) => path.display().to_string(),
+        };
+        println!(
+            "    {:<14} {}  {}",
+            agent.id,
+            location,
+            format_size(size).muted()
+        );
+    }
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_every_frontmatter_field_known_ones_first() {
+        let content = "---\nlicense: MIT\nallowed-tools:\n  - Read\n  - Bash(git:*)\n\
+                       metadata:\n  owner: docs\nname: pdf\ndescription: \"Fill forms: PDFs\"\n\
+                       ---\n# PDF\n";
+        let fields = frontmatter_fields(content).unwrap();
+        let keys: Vec<&str> = fields.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "name",
+                "description",
+                "license",
+                "allowed-tools",
+                "metadata"
+            ]
+        );
+        assert_eq!(fields[1].1, "Fill forms: PDFs");
+        assert_eq!(fields[3].1, "Read, Bash(git:*)");
+        assert_eq!(fields[4].1, "owner=docs");
+
+        assert!(frontmatter_fields("# No frontmatter").is_err());
+    }
+}
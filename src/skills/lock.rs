@@ -0,0 +1,275 @@
+//! `~/.ai-cli/skills.lock`: the exact version of every installed skill.
+//!
+//! Each skill installed from a repository is locked to the commit it came
+//! from and a checksum of its files. `skills install --locked` installs
+//! exactly those versions again, on another machine or for a teammate, and
+//! refuses a skill whose files no longer match its checksum.
+//!
+//! ```json
+//! { "version": 1,
+//!   "skills": { "pdf": { "source": "anthropics/skills#v1.2.0",
+//!                        "commit": "3f2a9c1…", "checksum": "sha256:9b1e…" } } }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::actions::{clone_skills, copy_by_default, files_in, install_skills, split_ref};
+use super::agents::{self, SkillAgent};
+use super::discovery::{self, Skill};
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::journal;
+use crate::paths;
+use crate::theme::Themed;
+
+const LOCK_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Repository, with `#ref` when installed from a branch, tag or commit
+    pub source: String,
+    /// Full commit the skill was installed from
+    pub commit: String,
+    /// `sha256:` over the skill's files, see [`checksum`]
+    pub checksum: String,
+}
+
+pub fn lock_path() -> PathBuf {
+    paths::home().join(".ai-cli").join("skills.lock")
+}
+
+/// `sha256:<hex>` over every file of a skill directory: relative paths with
+/// `/` separators and contents, in path order
+pub fn checksum(dir: &Path) -> String {
+    let mut hasher = Sha256::new();
+    for (path, content) in files_in(dir) {
+        let path: Vec<String> = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        hasher.update(path.join("/").as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+    format!("sha256:{:x}", hasher.finalize())
+}
+
+impl Lockfile {
+    pub fn load() -> Result<Self> {
+        let path = lock_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let lock: Lockfile = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON in {}", path.display()))?;
+        if lock.version > LOCK_VERSION {
+            anyhow::bail!(
+                "{} was written by a newer ai-cli (format version {})",
+                path.display(),
+                lock.version
+            );
+        }
+        Ok(lock)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = lock_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        let lock = Lockfile {
+            version: LOCK_VERSION,
+            skills: self.skills.clone(),
+        };
+        let content = serde_json::to_string_pretty(&lock)?;
+        journal::record_file(&path);
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Lock `skill`, as found in a clone of `source` at `commit`
+    pub fn record(&mut self, skill: &Skill, source: &str, commit: &str) {
+        self.skills.insert(
+            skill.name.clone(),
+            LockedSkill {
+                source: source.to_string(),
+                commit: commit.to_string(),
+                checksum: checksum(&skill.path),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, skill: &str) {
+        self.skills.remove(skill);
+    }
+
+    /// Locked skill names per source pinned to its commit, optionally only
+    /// those from `repo`
+    fn by_commit(&self, repo: Option<&str>) -> BTreeMap<(String, String), Vec<String>> {
+        let mut groups: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+        for (name, locked) in &self.skills {
+            let base = split_ref(&locked.source).0;
+            if repo.is_some_and(|repo| split_ref(repo).0 != base) {
+                continue;
+            }
+            groups
+                .entry((locked.source.clone(), format!("{}#{}", base, locked.commit)))
+                .or_default()
+                .push(name.clone());
+        }
+        groups
+    }
+}
+
+/// Handle `skills install --locked [repo]` command: reinstall the locked
+/// versions, checking each skill's files against its checksum
+pub fn handle_install_locked(
+    repo: Option<&str>,
+    agent_filter: Option<&str>,
+    copy: bool,
+) -> Result<Outcome> {
+    let lock = Lockfile::load()?;
+    let groups = lock.by_commit(repo);
+    if groups.is_empty() {
+        let path = lock_path();
+        match repo {
+            Some(repo) => println!("No skills from {} in {}", repo, path.display()),
+            None => println!("No skills locked in {}", path.display()),
+        }
+        return Ok(Outcome::NothingToDo);
+    }
+    let agents: Vec<SkillAgent> = match agent_filter {
+        Some(id) => {
+            vec![agents::find(id).ok_or_else(|| exit::usage(format!("Unknown agent: {}", id)))?]
+        }
+        None => agents::catalog(),
+    };
+
+    let copy = copy || copy_by_default();
+
+    let mut failed = 0;
+    for ((source, pinned), names) in &groups {
+        let clone = match clone_skills(pinned) {
+            Ok(clone) => clone,
+            Err(e) => {
+                println!("  {} {}: {:#}", "[FAIL]".error(), pinned, e);
+                failed += names.len();
+                continue;
+            }
+        };
+        let found = discovery::discover_skills(clone.path())?;
+
+        let mut verified = Vec::new();
+        for name in names {
+            let locked = &lock.skills[name];
+            match found.iter().find(|s| &s.name == name) {
+                None => {
+                    println!("  {} {}: not in {}", "[FAIL]".error(), name, pinned);
+                    failed += 1;
+                }
+                Some(skill) if checksum(&skill.path) != locked.checksum => {
+                    println!(
+                        "  {} {}: files don't match the locked checksum",
+                        "[FAIL]".error(),
+                        name
+                    );
+                    failed += 1;
+                }
+                Some(skill) => verified.push(skill.clone()),
+            }
+        }
+        if verified.is_empty() {
+            continue;
+        }
+        let commit = split_ref(pinned).1;
+        if let Err(e) = install_skills(source, &verified, &agents, commit, copy, &Scope::Global) {
+            println!("  {} {}: {:#}", "[FAIL]".error(), pinned, e);
+            failed += verified.len();
+        }
+        println!();
+    }
+
+    if failed == 0 {
+        println!("{}", "Locked skills installed successfully!".success());
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn checksums_cover_paths_and_contents() {
+        let dir = TempDir::new().unwrap();
+        let skill = dir.path().join("pdf");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(skill.join("SKILL.md"), "v1").unwrap();
+        fs::write(skill.join("scripts/fill.py"), "print()").unwrap();
+        let original = checksum(&skill);
+        assert!(original.starts_with("sha256:"));
+        assert_eq!(original.len(), "sha256:".len() + 64);
+
+        let copy = dir.path().join("copy");
+        fs::create_dir_all(copy.join("scripts")).unwrap();
+        fs::write(copy.join("SKILL.md"), "v1").unwrap();
+        fs::write(copy.join("scripts/fill.py"), "print()").unwrap();
+        assert_eq!(checksum(&copy), original);
+
+        fs::rename(copy.join("scripts/fill.py"), copy.join("scripts/run.py")).unwrap();
+        assert_ne!(checksum(&copy), original);
+        fs::write(skill.join("SKILL.md"), "v2").unwrap();
+        assert_ne!(checksum(&skill), original);
+    }
+
+    #[test]
+    fn groups_locked_skills_by_pinned_commit() {
+        let locked = |source: &str, commit: &str| LockedSkill {
+            source: source.to_string(),
+            commit: commit.to_string(),
+            checksum: String::new(),
+        };
+        let lock = Lockfile {
+            version: LOCK_VERSION,
+            skills: BTreeMap::from([
+                ("pdf".to_string(), locked("anthropics/skills#v1", "aaa1111")),
+                (
+                    "xlsx".to_string(),
+                    locked("anthropics/skills#v1", "aaa1111"),
+                ),
+                ("notes".to_string(), locked("acme/skills", "bbb2222")),
+            ]),
+        };
+
+        let groups = lock.by_commit(None);
+        assert_eq!(
+            groups[&(
+                "anthropics/skills#v1".to_string(),
+                "anthropics/skills#aaa1111".to_string()
+            )],
+            ["pdf", "xlsx"]
+        );
+        assert_eq!(groups.len(), 2);
+
+        let acme = lock.by_commit(Some("acme/skills#main"));
+        assert_eq!(acme.into_values().collect::<Vec<_>>(), [["notes"]]);
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::journal;
+use crate::paths;
+
+/// Where installed skills came from, stored in `~/.ai-cli/skills.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub skills: BTreeMap<String, SkillRecord>,
+    /// Repositories whose newly added skills `skills update` installs
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub subscriptions: BTreeMap<String, Subscription>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Agent ids new skills go to; empty for every installed agent
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// Skills the repository had at the last check
+    #[serde(default)]
+    pub seen: Vec<String>,
+    /// New skills are copied into the agents rather than linked
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub copy: bool,
+}
+
+impl Subscription {
+    /// Skills in `available` that weren't there at the last check, which
+    /// count as seen from now on
+    pub fn take_new(&mut self, available: &[String]) -> Vec<String> {
+        let new: Vec<String> = available
+            .iter()
+            .filter(|name| !self.seen.contains(name))
+            .cloned()
+            .collect();
+        self.seen.extend(new.iter().cloned());
+        self.seen.sort();
+        new
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillRecord {
+    /// Repository the skill was installed from (owner/repo or URL)
+    pub source: String,
+    /// Agent ids the skill is installed into
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// Commit of the source repository the installed copy is from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+pub fn manifest_path() -> PathBuf {
+    paths::home().join(".ai-cli").join("skills.json")
+}
+
+impl Manifest {
+    pub fn load() -> Result<Self> {
+        let path = manifest_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON in {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = manifest_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        journal::record_file(&path);
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Source repository recorded for a skill, if any
+    pub fn source_of(&self, skill: &str) -> Option<&str> {
+        self.skills.get(skill).map(|r| r.source.as_str())
+    }
+
+    /// Whether the manifest records `skill` as installed into `agent_id`
+    pub fn is_recorded(&self, skill: &str, agent_id: &str) -> bool {
+        self.skills
+            .get(skill)
+            .is_some_and(|r| r.agents.iter().any(|a| a == agent_id))
+    }
+
+    /// Whether skills from `source` are recorded and every one of them is
+    /// installed into each of `agent_ids`
+    pub fn has_source(&self, source: &str, agent_ids: &[&str]) -> bool {
+        let mut records = self
+            .skills
+            .values()
+            .filter(|r| r.source == source)
+            .peekable();
+        records.peek().is_some()
+            && records.all(|r| agent_ids.iter().all(|id| r.agents.iter().any(|a| a == id)))
+    }
+
+    pub fn record_install(&mut self, skill: &str, source: &str, agent_id: &str) {
+        let record = self
+            .skills
+            .entry(skill.to_string())
+            .or_insert_with(|| SkillRecord {
+                source: source.to_string(),
+                agents: Vec::new(),
+                commit: None,
+            });
+        record.source = source.to_string();
+        if !record.agents.iter().any(|a| a == agent_id) {
+            record.agents.push(agent_id.to_string());
+            record.agents.sort();
+        }
+    }
+
+    /// Remember the source commit `skill` was last copied from
+    pub fn record_commit(&mut self, skill: &str, commit: Option<&str>) {
+        if let Some(record) = self.skills.get_mut(skill) {
+            record.commit = commit.map(str::to_string);
+        }
+    }
+
+    pub fn record_removal(&mut self, skill: &str, agent_id: &str) {
+        if let Some(record) = self.skills.get_mut(skill) {
+            record.agents.retain(|a| a != agent_id);
+            if record.agents.is_empty() {
+                self.skills.remove(skill);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_agents_per_skill() {
+        let mut manifest = Manifest::default();
+        manifest.record_install("pdf", "anthropics/skills", "claude");
+        manifest.record_install("pdf", "anthropics/skills", "codex");
+        manifest.record_install("pdf", "anthropics/skills", "claude");
+        manifest.record_commit("pdf", Some("3f2a9c1"));
+
+        assert_eq!(manifest.skills["pdf"].agents, vec!["claude", "codex"]);
+        assert_eq!(manifest.skills["pdf"].commit.as_deref(), Some("3f2a9c1"));
+        assert_eq!(manifest.source_of("pdf"), Some("anthropics/skills"));
+
+        assert!(manifest.has_source("anthropics/skills", &["claude", "codex"]));
+        assert!(!manifest.has_source("anthropics/skills", &["claude", "gemini"]));
+        assert!(!manifest.has_source("acme/skills", &["claude"]));
+
+        manifest.record_removal("pdf", "claude");
+        assert_eq!(manifest.skills["pdf"].agents, vec!["codex"]);
+        assert!(manifest.is_recorded("pdf", "codex"));
+        assert!(!manifest.is_recorded("pdf", "claude"));
+
+        manifest.record_removal("pdf", "codex");
+        assert!(manifest.source_of("pdf").is_none());
+    }
+
+    #[test]
+    fn subscriptions_report_each_new_skill_once() {
+        let mut subscription = Subscription {
+            agents: Vec::new(),
+            seen: vec!["pdf".to_string()],
+            ..Default::default()
+        };
+        let available = ["pdf".to_string(), "xlsx".to_string()];
+        assert_eq!(subscription.take_new(&available), ["xlsx"]);
+        assert!(subscription.take_new(&available).is_empty());
+    }
+}
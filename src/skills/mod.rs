@@ -1,5 +1,38 @@
 pub mod actions;
 pub mod agents;
+pub mod auth;
+pub mod bundle;
+pub mod conflict;
+pub mod convert;
+pub mod dev;
+pub mod diff;
 pub mod discovery;
+pub mod doctor;
+pub mod grep;
+pub mod info;
+pub mod lock;
+pub mod manifest;
+pub mod outdated;
+pub mod search;
+pub mod skillignore;
+pub mod sync;
+pub mod toggle;
+pub mod validate;
 
-pub use actions::{handle_install, handle_list, handle_remove};
+pub use actions::{
+    SkillSelection, handle_install, handle_list, handle_prune, handle_remove, handle_remove_many,
+    handle_subscribe, handle_unsubscribe, handle_update,
+};
+pub use bundle::{handle_export, handle_import};
+pub use convert::handle_convert;
+pub use dev::handle_dev;
+pub use diff::handle_diff;
+pub use doctor::handle_doctor;
+pub use grep::handle_grep;
+pub use info::handle_info;
+pub use lock::handle_install_locked;
+pub use outdated::handle_outdated;
+pub use search::handle_search;
+pub use sync::handle_sync;
+pub use toggle::{handle_disable, handle_enable};
+pub use validate::handle_validate;
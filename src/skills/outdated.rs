@@ -0,0 +1,160 @@
+//! `skills outdated`: installed skills whose source has new commits.
+//!
+//! The tip of each recorded source's ref is read with `git ls-remote`, without
+//! cloning, and compared with the commit the skills were installed from.
+//! Skills pinned to a commit are never outdated. A new commit doesn't
+//! necessarily touch a given skill; `skills update` only re-copies the skills
+//! whose files changed.
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::actions::{commit_range, is_commit, parse_repo_url, split_ref};
+use super::auth;
+use super::manifest::{Manifest, SkillRecord};
+use crate::exit::Outcome;
+use crate::logging;
+use crate::theme::Themed;
+
+/// The commit `git_ref` (the default branch when `None`) points to in
+/// `git ls-remote` output; annotated tags resolve to their commit
+fn remote_commit(listing: &str, git_ref: Option<&str>) -> Option<String> {
+    let refs: BTreeMap<&str, &str> = listing
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(commit, name)| (name.trim(), commit.trim()))
+        .collect();
+    let candidates = match git_ref {
+        None => vec!["HEAD".to_string()],
+        Some(git_ref) => vec![
+            format!("refs/tags/{}^{{}}", git_ref),
+            format!("refs/tags/{}", git_ref),
+            format!("refs/heads/{}", git_ref),
+            git_ref.to_string(),
+        ],
+    };
+    candidates
+        .iter()
+        .find_map(|name| refs.get(name.as_str()))
+        .map(|commit| commit.to_string())
+}
+
+/// Current commit of the branch or tag `source` follows
+fn remote_tip(source: &str) -> Result<String> {
+    let (repo, git_ref) = split_ref(source);
+    let url = parse_repo_url(repo)?;
+    let mut ls_remote = Command::new("git");
+    ls_remote
+        .args(["ls-remote", &url])
+        .arg(git_ref.unwrap_or("HEAD"));
+    auth::configure(&mut ls_remote, &url);
+    logging::line(&format!(
+        "$ git ls-remote {} {}",
+        url,
+        git_ref.unwrap_or("HEAD")
+    ));
+    let output = ls_remote.output().context("Failed to run git ls-remote")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match auth::explain(&stderr, &url) {
+            Some(hint) => anyhow::bail!("{}", hint),
+            None => anyhow::bail!("git ls-remote failed: {}", stderr.trim()),
+        }
+    }
+    remote_commit(&String::from_utf8_lossy(&output.stdout), git_ref)
+        .with_context(|| format!("{} has no ref {}", repo, git_ref.unwrap_or("HEAD")))
+}
+
+/// Handle `skills outdated` command; `UpdatesAvailable` when any skill is behind
+pub fn handle_outdated() -> Result<Outcome> {
+    let manifest = Manifest::load()?;
+    if manifest.skills.is_empty() {
+        println!("No skills installed by ai-cli");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut by_source: BTreeMap<&str, Vec<(&String, &SkillRecord)>> = BTreeMap::new();
+    for (name, record) in &manifest.skills {
+        by_source
+            .entry(record.source.as_str())
+            .or_default()
+            .push((name, record));
+    }
+    let width = manifest.skills.keys().map(|n| n.len()).max().unwrap_or(0);
+
+    let mut outdated = 0;
+    let mut failed = 0;
+    for (source, skills) in &by_source {
+        if split_ref(source).1.is_some_and(is_commit) {
+            for (name, _) in skills {
+                println!(
+                    "  {} {:<width$}  {}",
+                    "=".muted(),
+                    name,
+                    format!("{} (pinned)", source).muted()
+                );
+            }
+            continue;
+        }
+        let tip = match remote_tip(source) {
+            Ok(tip) => tip,
+            Err(e) => {
+                println!("  {} {}: {:#}", "[FAIL]".error(), source, e);
+                failed += 1;
+                continue;
+            }
+        };
+        for (name, record) in skills {
+            if record.commit.as_deref() == Some(tip.as_str()) {
+                println!("  {} {:<width$}  {}", "✓".success(), name, source.muted());
+                continue;
+            }
+            outdated += 1;
+            println!(
+                "  {} {:<width$}  {}  {}",
+                "↑".warning(),
+                name,
+                source,
+                commit_range(record.commit.as_deref(), Some(&tip)).muted()
+            );
+        }
+    }
+
+    println!();
+    if outdated > 0 {
+        println!(
+            "{} {} skill(s) have upstream changes; run `ai-cli skills update`",
+            "→".info(),
+            outdated
+        );
+        return Ok(Outcome::UpdatesAvailable);
+    }
+    if failed > 0 {
+        return Ok(Outcome::PartialFailure);
+    }
+    println!("{} All skills are up to date", "✓".success());
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_refs_in_ls_remote_output() {
+        let listing = "1111111111111111111111111111111111111111\tHEAD\n\
+                       2222222222222222222222222222222222222222\trefs/heads/main\n\
+                       3333333333333333333333333333333333333333\trefs/tags/v1.2.0\n\
+                       4444444444444444444444444444444444444444\trefs/tags/v1.2.0^{}\n\
+                       5555555555555555555555555555555555555555\trefs/heads/next\n";
+        let commit = |git_ref| remote_commit(listing, git_ref).unwrap();
+        assert!(commit(None).starts_with('1'));
+        // The commit an annotated tag points to, not the tag object
+        assert!(commit(Some("v1.2.0")).starts_with('4'));
+        assert!(commit(Some("next")).starts_with('5'));
+        assert!(commit(Some("refs/heads/main")).starts_with('2'));
+        assert_eq!(remote_commit(listing, Some("v9")), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! `skills search <query>`: find skill repositories without leaving the terminal.
+//!
+//! By default GitHub is searched for repositories tagged `claude-skills` or
+//! `agent-skills`. Setting `registry` under `[skills]` in config.toml searches
+//! an index instead, given as a URL or a file path:
+//!
+//! ```json
+//! { "repositories": [
+//!     { "repo": "acme/skills", "description": "Internal skills", "stars": 12,
+//!       "topics": ["release", "docs"] } ] }
+//! ```
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::thousands;
+use crate::config;
+use crate::exit::Outcome;
+use crate::http;
+use crate::output;
+use crate::search::{self, SkillRepo};
+use crate::theme::Themed;
+
+/// A registry index is refetched after this long
+const INDEX_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct RegistryIndex {
+    #[serde(default)]
+    repositories: Vec<SkillRepo>,
+}
+
+async fn load_index(location: &str) -> Result<RegistryIndex> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return http::get_json(location, INDEX_TTL).await;
+    }
+    let content = std::fs::read_to_string(location)
+        .with_context(|| format!("Failed to read skills registry {}", location))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse skills registry {}", location))
+}
+
+/// Index entries whose name, description or topics contain `query`, most
+/// starred first
+fn matching(index: RegistryIndex, query: &str) -> Vec<SkillRepo> {
+    let query = query.to_lowercase();
+    let mut found: Vec<SkillRepo> = index
+        .repositories
+        .into_iter()
+        .filter(|repo| {
+            repo.repo.to_lowercase().contains(&query)
+                || repo.description.to_lowercase().contains(&query)
+                || repo
+                    .topics
+                    .iter()
+                    .any(|t| t.to_lowercase().contains(&query))
+        })
+        .collect();
+    found.sort_by_key(|r| std::cmp::Reverse(r.stars));
+    found
+}
+
+/// Handle `skills search <query>` command
+pub async fn handle_search(query: &str) -> Result<Outcome> {
+    let registry = config::load()?.skills.registry;
+    let source = registry.as_deref().unwrap_or("GitHub");
+    let spinner = output::spinner(&format!("Searching {} for '{}'...", source, query));
+    let result = match &registry {
+        Some(location) => load_index(location)
+            .await
+            .map(|index| matching(index, query)),
+        None => search::skill_repos(search::GITHUB_SEARCH_URL, query)
+            .await
+            .context("Failed to search GitHub (set GITHUB_TOKEN for a higher rate limit)"),
+    };
+    spinner.finish_and_clear();
+    let repos = result?;
+
+    if repos.is_empty() {
+        println!("No skill repositories match '{}'", query);
+        return Ok(Outcome::NothingToDo);
+    }
+    for repo in &repos {
+        println!(
+            "{}  {}",
+            repo.repo.info().bold(),
+            format!("★ {}", thousands(repo.stars)).muted()
+        );
+        if !repo.description.is_empty() {
+            println!("  {}", repo.description);
+        }
+        println!("  {} ai-cli skills install {}", "→".info(), repo.repo);
+        println!();
+    }
+    println!(
+        "{}",
+        format!("{} repositories from {}", repos.len(), source).muted()
+    );
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_index_entries_by_name_description_or_topic() {
+        let index: RegistryIndex = serde_json::from_str(
+            r#"{"repositories": [
+                {"repo": "acme/skills", "description": "Release notes and docs", "stars": 3},
+                {"repo": "acme/pdf-tools", "stars": 40},
+                {"repo": "acme/office", "description": "Spreadsheets", "topics": ["PDF"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let names: Vec<String> = matching(index, "pdf").into_iter().map(|r| r.repo).collect();
+        assert_eq!(names, ["acme/pdf-tools", "acme/office"]);
+    }
+}
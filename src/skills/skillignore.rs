@@ -0,0 +1,130 @@
+//! `.skillignore`: files left out when a skill is copied.
+//!
+//! Dependencies, caches, test fixtures, videos and archives aren't copied
+//! into the store or an agent's skills directory. A `.skillignore` at the
+//! skill's root, in gitignore syntax, adds patterns, and `!pattern` brings a
+//! default back. Comparisons with the source apply the same rules, so ignored
+//! files never count as changes.
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Left out of every skill unless its `.skillignore` says otherwise
+const DEFAULT_IGNORES: &[&str] = &[
+    ".git/",
+    "node_modules/",
+    ".venv/",
+    "__pycache__/",
+    ".DS_Store",
+    "**/test/fixtures/",
+    "**/tests/fixtures/",
+    "__fixtures__/",
+    "*.mp4",
+    "*.mov",
+    "*.zip",
+    "*.tar.gz",
+];
+
+/// Warn about skills larger than this without `max_size_mb` in `[skills]`
+pub(super) const DEFAULT_MAX_SIZE_MB: u64 = 10;
+
+/// The defaults plus the skill's own `.skillignore`; unreadable or invalid
+/// lines are skipped rather than failing the install
+fn rules(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in DEFAULT_IGNORES {
+        let _ = builder.add_line(None, pattern);
+    }
+    let skillignore = root.join(".skillignore");
+    if skillignore.is_file() {
+        let _ = builder.add(skillignore);
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Files of the skill at `dir` that a copy keeps, relative to it and sorted
+pub(super) fn skill_files(dir: &Path) -> Vec<PathBuf> {
+    let rules = rules(dir);
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            if rules.matched(relative, is_dir).is_ignore() {
+                continue;
+            }
+            if is_dir {
+                pending.push(path);
+            } else {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Bytes a copy of the skill at `dir` takes
+pub(super) fn copied_size(dir: &Path) -> u64 {
+    skill_files(dir)
+        .iter()
+        .filter_map(|file| std::fs::metadata(dir.join(file)).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn leaves_out_defaults_and_skillignore_patterns() {
+        let dir = TempDir::new().unwrap();
+        let skill = dir.path();
+        for file in [
+            "SKILL.md",
+            "scripts/fill.py",
+            "scripts/node_modules/pdf-lib/index.js",
+            "scripts/tests/fixtures/form.pdf",
+            "assets/demo.mp4",
+            "assets/template.zip",
+            "drafts/notes.md",
+            ".git/HEAD",
+        ] {
+            let path = skill.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        fs::write(
+            skill.join(".skillignore"),
+            "# not for agents\ndrafts/\n!template.zip\n",
+        )
+        .unwrap();
+
+        let files: Vec<String> = skill_files(skill)
+            .iter()
+            .map(|f| f.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(
+            files,
+            [
+                ".skillignore",
+                "SKILL.md",
+                "assets/template.zip",
+                "scripts/fill.py"
+            ]
+        );
+        let skillignore = fs::metadata(skill.join(".skillignore")).unwrap().len();
+        assert_eq!(copied_size(skill), 3 + skillignore);
+    }
+}
@@ -0,0 +1,154 @@
+//! `skills sync`: bring skills installed in one agent to others.
+//!
+//! Skills missing from a target agent are added; skills it already has with
+//! the same files are left alone, and differing ones are replaced only after
+//! asking. A skill the source links to `~/.ai-cli/skills/` gets a link in the
+//! target too, a copied one gets a copy.
+
+use std::path::Path;
+
+use anyhow::Result;
+use colored::Colorize;
+
+use super::actions::{is_linked, place_skill, same_contents};
+use super::agents::{self, SkillAgent};
+use super::discovery;
+use super::manifest::Manifest;
+use crate::exit::{self, Outcome};
+use crate::prompt;
+use crate::theme::Themed;
+
+/// What a target agent has of a skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Missing,
+    Same,
+    Different,
+}
+
+fn state(source: &Path, dest: &Path) -> State {
+    if !dest.exists() {
+        State::Missing
+    } else if same_contents(source, dest) {
+        State::Same
+    } else {
+        State::Different
+    }
+}
+
+fn installed_agent(id: &str) -> Result<SkillAgent> {
+    let agent = agents::find(id).ok_or_else(|| exit::usage(format!("Unknown agent: {}", id)))?;
+    if !agent.is_installed() {
+        return Err(exit::usage(format!("{} is not installed", agent.name)));
+    }
+    Ok(agent)
+}
+
+/// Handle `skills sync --from <agent> --to <agent|all>` command
+pub fn handle_sync(from: &str, to: &str) -> Result<Outcome> {
+    let source = installed_agent(from)?;
+    let targets: Vec<SkillAgent> = if to == "all" {
+        agents::catalog()
+            .into_iter()
+            .filter(|a| a.id != source.id && a.is_installed())
+            .collect()
+    } else {
+        vec![installed_agent(to)?]
+    };
+    if targets.iter().any(|t| t.id == source.id) {
+        return Err(exit::usage("--from and --to name the same agent"));
+    }
+    if targets.is_empty() {
+        println!("No other agent installed to sync to");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let skills = discovery::list_installed_skills(&source.skills_path)?;
+    if skills.is_empty() {
+        println!("{} has no skills installed", source.name);
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut manifest = Manifest::load()?;
+    let mut synced = 0;
+    let mut failed = 0;
+    for target in &targets {
+        println!("{}", format!("{} → {}", source.name, target.name).bold());
+        for skill in &skills {
+            let dest = target.skills_path.join(&skill.name);
+            match state(&skill.path, &dest) {
+                State::Same => {
+                    println!("  {} {}", "=".muted(), skill.name.muted());
+                    continue;
+                }
+                State::Different => {
+                    if !prompt::confirm(&format!(
+                        "{} already has a different '{}'. Replace it?",
+                        target.name, skill.name
+                    ))? {
+                        println!("  {} {} (kept)", "-".muted(), skill.name);
+                        continue;
+                    }
+                }
+                State::Missing => {}
+            }
+
+            let copy = !is_linked(&source, &skill.name);
+            let placed = target
+                .ensure_skills_dir()
+                .map_err(anyhow::Error::from)
+                .and_then(|()| place_skill(skill, target, copy));
+            match placed {
+                Ok(_) => {
+                    println!("  {} {}", "+".success(), skill.name);
+                    if let Some(repo) = manifest.source_of(&skill.name).map(str::to_string) {
+                        manifest.record_install(&skill.name, &repo, target.id);
+                    }
+                    synced += 1;
+                }
+                Err(e) => {
+                    println!("  {} {}: {:#}", "✗".error(), skill.name, e);
+                    failed += 1;
+                }
+            }
+        }
+        println!();
+    }
+    manifest.save()?;
+
+    if synced == 0 && failed == 0 {
+        println!("{} Every skill is already in sync", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+    println!(
+        "{}",
+        format!("Synced {} skill install(s)", synced).success()
+    );
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn classifies_target_copies() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("claude/pdf");
+        let same = dir.path().join("codex/pdf");
+        let different = dir.path().join("gemini/pdf");
+        for (path, body) in [(&source, "v2"), (&same, "v2"), (&different, "v1")] {
+            fs::create_dir_all(path).unwrap();
+            fs::write(path.join("SKILL.md"), body).unwrap();
+        }
+
+        assert_eq!(state(&source, &same), State::Same);
+        assert_eq!(state(&source, &different), State::Different);
+        assert_eq!(
+            state(&source, &dir.path().join("opencode/pdf")),
+            State::Missing
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! `skills disable` / `skills enable`: turn a skill off without deleting it.
+//!
+//! Disabling moves the skill out of the agent's skills directory into
+//! `.disabled/` inside it, where agents don't look for skills; enabling moves
+//! it back. A link into `~/.ai-cli/skills/` moves as a link, and the manifest
+//! keeps its record, so nothing has to be downloaded again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use super::agents::{self, SkillAgent};
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::logging;
+use crate::theme::Themed;
+
+/// Where an agent's disabled skill `name` is kept
+pub(super) fn disabled_path(agent: &SkillAgent, name: &str) -> PathBuf {
+    agent.skills_path.join(".disabled").join(name)
+}
+
+/// Whether the agent has `name` disabled
+pub(super) fn is_disabled(agent: &SkillAgent, name: &str) -> bool {
+    fs::symlink_metadata(disabled_path(agent, name)).is_ok()
+}
+
+/// Handle `skills disable <skill> [--agent] [--project]` command
+pub fn handle_disable(skill: &str, agent_filter: Option<&str>, scope: &Scope) -> Result<Outcome> {
+    toggle(skill, agent_filter, scope, false)
+}
+
+/// Handle `skills enable <skill> [--agent] [--project]` command
+pub fn handle_enable(skill: &str, agent_filter: Option<&str>, scope: &Scope) -> Result<Outcome> {
+    toggle(skill, agent_filter, scope, true)
+}
+
+/// Move a skill directory or link from `from` to `to`
+fn relocate(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::rename(from, to)
+        .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))
+}
+
+fn toggle(
+    skill_name: &str,
+    agent_filter: Option<&str>,
+    scope: &Scope,
+    enable: bool,
+) -> Result<Outcome> {
+    let agents = match agent_filter {
+        Some(id) => vec![
+            agents::find_in(scope, id)
+                .ok_or_else(|| exit::usage(format!("Unknown agent: {}", id)))?,
+        ],
+        None => agents::catalog_for(scope),
+    };
+    let (verb, done) = if enable {
+        ("Enabling", "enabled")
+    } else {
+        ("Disabling", "disabled")
+    };
+    println!("{}", format!("{} skill '{}':", verb, skill_name).bold());
+
+    let mut moved = 0;
+    let mut already = 0;
+    let mut failed = 0;
+    for agent in agents.iter().filter(|a| a.is_installed()) {
+        let active = agent.skills_path.join(skill_name);
+        let disabled = disabled_path(agent, skill_name);
+        let (from, to) = if enable {
+            (&disabled, &active)
+        } else {
+            (&active, &disabled)
+        };
+        // symlink_metadata so a link into the store counts, whatever it points to
+        if fs::symlink_metadata(from).is_err() {
+            if fs::symlink_metadata(to).is_ok() {
+                already += 1;
+                println!(
+                    "  {:<16}{}",
+                    agent.name,
+                    format!("[SKIP] Already {}", done).muted()
+                );
+            }
+            continue;
+        }
+
+        print!("  {:<16}", agent.name);
+        if fs::symlink_metadata(to).is_ok() {
+            println!(
+                "{} both {} and {} exist; remove one",
+                "[FAIL]".error(),
+                active.display(),
+                disabled.display()
+            );
+            failed += 1;
+            continue;
+        }
+        match relocate(from, to) {
+            Ok(()) => {
+                logging::line(&format!("{} skill {}", done, active.display()));
+                println!("{}", "[OK]".success());
+                moved += 1;
+            }
+            Err(e) => {
+                println!("{} {:#}", "[FAIL]".error(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    if moved == 0 && failed == 0 {
+        if already > 0 {
+            println!("Skill '{}' is already {}", skill_name, done);
+            return Ok(Outcome::NothingToDo);
+        }
+        return Err(exit::usage(format!(
+            "Skill '{}' not found in any agent",
+            skill_name
+        )));
+    }
+    if moved > 0 {
+        println!(
+            "{}",
+            format!("Skill {} for {} agent(s)", done, moved).success()
+        );
+    }
+    Ok(Outcome::from_failures(failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn moves_links_as_links() {
+        let dir = TempDir::new().unwrap();
+        let stored = dir.path().join("store/pdf");
+        fs::create_dir_all(&stored).unwrap();
+        fs::write(stored.join("SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+        let active = dir.path().join("skills/pdf");
+        fs::create_dir_all(active.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(&stored, &active).unwrap();
+
+        let disabled = dir.path().join("skills/.disabled/pdf");
+        relocate(&active, &disabled).unwrap();
+        assert!(fs::symlink_metadata(&active).is_err());
+        assert_eq!(fs::read_link(&disabled).unwrap(), stored);
+
+        relocate(&disabled, &active).unwrap();
+        assert!(active.join("SKILL.md").is_file());
+        assert!(stored.is_dir());
+    }
+}
@@ -0,0 +1,256 @@
+//! `skills validate`: lint SKILL.md files against the Agent Skills spec.
+//!
+//! Checks that the frontmatter is valid YAML, that `name` is a lowercase,
+//! hyphenated identifier matching the skill's directory, that `description`
+//! is present and within its length limit, and that no fields outside the
+//! spec are used. Given a directory it checks every skill below it, so a
+//! skill repository can run it in CI; problems exit with code 1.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_norway::Value;
+
+use super::agents;
+use super::discovery;
+use crate::exit::{self, Outcome};
+use crate::theme::Themed;
+
+/// Frontmatter fields the spec allows
+const ALLOWED_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "license",
+    "allowed-tools",
+    "metadata",
+    "compatibility",
+];
+const MAX_NAME: usize = 64;
+const MAX_DESCRIPTION: usize = 1024;
+const MAX_COMPATIBILITY: usize = 500;
+/// Directories never searched for skills
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Problems with the SKILL.md `content` of the skill in directory `dir_name`
+pub(super) fn check(dir_name: &str, content: &str) -> Vec<String> {
+    let fields = match discovery::frontmatter_yaml(content) {
+        Ok(fields) => fields,
+        Err(e) => return vec![format!("{:#}", e)],
+    };
+    let mut problems = Vec::new();
+
+    match fields.get("name") {
+        None => problems.push("Missing required field 'name'".to_string()),
+        Some(Value::String(name)) => {
+            if name.is_empty() || name.chars().count() > MAX_NAME {
+                problems.push(format!("name must be 1-{} characters", MAX_NAME));
+            }
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            {
+                problems.push(format!(
+                    "name '{}' may only contain lowercase letters, digits and hyphens",
+                    name
+                ));
+            }
+            if name.starts_with('-') || name.ends_with('-') || name.contains("--") {
+                problems.push(format!(
+                    "name '{}' can't start or end with a hyphen or contain '--'",
+                    name
+                ));
+            }
+            if name != dir_name {
+                problems.push(format!(
+                    "name '{}' doesn't match its directory '{}'; rename one of them",
+                    name, dir_name
+                ));
+            }
+        }
+        Some(_) => problems.push("name must be a string".to_string()),
+    }
+
+    match fields.get("description") {
+        None => problems.push(
+            "Missing required field 'description' (what the skill does and when to use it)"
+                .to_string(),
+        ),
+        Some(Value::String(description)) if description.trim().is_empty() => {
+            problems.push("description is empty".to_string())
+        }
+        Some(Value::String(description)) if description.chars().count() > MAX_DESCRIPTION => {
+            problems.push(format!(
+                "description is {} characters; the limit is {}",
+                description.chars().count(),
+                MAX_DESCRIPTION
+            ))
+        }
+        Some(Value::String(_)) => {}
+        Some(_) => problems.push("description must be a string".to_string()),
+    }
+
+    if let Some(compatibility) = fields.get("compatibility") {
+        match compatibility.as_str() {
+            Some(text) if text.chars().count() > MAX_COMPATIBILITY => problems.push(format!(
+                "compatibility is {} characters; the limit is {}",
+                text.chars().count(),
+                MAX_COMPATIBILITY
+            )),
+            Some(_) => {}
+            None => problems.push("compatibility must be a string".to_string()),
+        }
+    }
+    if fields.get("metadata").is_some_and(|m| !m.is_mapping()) {
+        problems.push("metadata must be a mapping of keys to values".to_string());
+    }
+    if fields
+        .get("allowed-tools")
+        .is_some_and(|t| !t.is_string() && !t.is_sequence())
+    {
+        problems.push("allowed-tools must be a string or a list of tools".to_string());
+    }
+
+    for key in fields.keys() {
+        let key = key.as_str().unwrap_or_default();
+        if !ALLOWED_FIELDS.contains(&key) {
+            problems.push(format!(
+                "Field '{}' isn't part of the spec; move it under 'metadata' (allowed: {})",
+                key,
+                ALLOWED_FIELDS.join(", ")
+            ));
+        }
+    }
+    problems
+}
+
+/// Directories holding a SKILL.md at or below `dir`
+fn find_skill_dirs(dir: &Path, found: &mut Vec<PathBuf>) {
+    if dir.join("SKILL.md").is_file() {
+        found.push(dir.to_path_buf());
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut children: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| !SKIPPED_DIRS.contains(&n))
+        })
+        .collect();
+    children.sort();
+    for child in children {
+        find_skill_dirs(&child, found);
+    }
+}
+
+/// Skill directories `target` refers to: a SKILL.md, a directory to search,
+/// or the name of an installed skill
+fn resolve(target: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(target);
+    if path.is_file() {
+        let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+        return Ok(vec![dir.unwrap_or(Path::new(".")).to_path_buf()]);
+    }
+    if path.is_dir() {
+        let mut found = Vec::new();
+        find_skill_dirs(path, &mut found);
+        if found.is_empty() {
+            return Err(exit::usage(format!("No SKILL.md found in {}", target)));
+        }
+        return Ok(found);
+    }
+    let installed: Vec<PathBuf> = agents::catalog()
+        .iter()
+        .map(|agent| agent.skills_path.join(target))
+        .filter(|dir| dir.join("SKILL.md").is_file())
+        .collect();
+    if installed.is_empty() {
+        return Err(exit::usage(format!(
+            "'{}' is neither a path nor an installed skill",
+            target
+        )));
+    }
+    Ok(installed)
+}
+
+/// Handle `skills validate [path|skill]` command
+pub fn handle_validate(target: Option<&str>) -> Result<Outcome> {
+    let dirs = resolve(target.unwrap_or("."))?;
+    let mut invalid = 0;
+    for dir in &dirs {
+        let skill_file = dir.join("SKILL.md");
+        let content = fs::read_to_string(&skill_file)
+            .with_context(|| format!("Failed to read {}", skill_file.display()))?;
+        // `.` has no name of its own; use the directory it stands for
+        let dir_name = fs::canonicalize(dir)
+            .unwrap_or_else(|_| dir.clone())
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let problems = check(&dir_name, &content);
+        if problems.is_empty() {
+            println!("{} {}", "✓".success(), skill_file.display());
+            continue;
+        }
+        invalid += 1;
+        println!("{} {}", "✗".error(), skill_file.display());
+        for problem in problems {
+            println!("    {}", problem);
+        }
+    }
+
+    println!();
+    if invalid > 0 {
+        println!(
+            "{} {} of {} skill(s) have problems",
+            "✗".error(),
+            invalid,
+            dirs.len()
+        );
+        return Ok(Outcome::Failure);
+    }
+    println!("{} {} skill(s) valid", "✓".success(), dirs.len());
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_spec_violations() {
+        let valid = "---\nname: pdf-tools\ndescription: Fill PDF forms\nlicense: MIT\n\
+                     metadata:\n  version: \"1.0\"\n---\n# PDF\n";
+        assert!(check("pdf-tools", valid).is_empty());
+
+        let problems = check(
+            "pdf",
+            "---\nname: PDF--Tools\nversion: 1.0\nmetadata: plain\n---\n",
+        );
+        assert!(problems.iter().any(|p| p.contains("lowercase")));
+        assert!(problems.iter().any(|p| p.contains("'--'")));
+        assert!(
+            problems
+                .iter()
+                .any(|p| p.contains("doesn't match its directory 'pdf'"))
+        );
+        assert!(problems.iter().any(|p| p.contains("'description'")));
+        assert!(problems.iter().any(|p| p.contains("Field 'version'")));
+        assert!(problems.iter().any(|p| p.starts_with("metadata")));
+
+        let long = format!("---\nname: pdf\ndescription: {}\n---\n", "x".repeat(1025));
+        assert_eq!(
+            check("pdf", &long),
+            ["description is 1025 characters; the limit is 1024"]
+        );
+        let broken = check("pdf", "---\nname: pdf\ndescription: [unclosed\n---\n");
+        assert_eq!(broken.len(), 1);
+        assert!(broken[0].starts_with("Frontmatter is not valid YAML"));
+    }
+}
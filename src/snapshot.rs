@@ -0,0 +1,421 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{find_tool, install_tool};
+use crate::exit::Outcome;
+use crate::managed::install_pinned;
+use crate::mcp::{self, servers, targets};
+use crate::prompt;
+use crate::skills::actions::install_from_repo;
+use crate::skills::{agents, discovery, manifest::Manifest};
+use crate::theme::Themed;
+use crate::tools;
+
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Everything ai-cli manages on this machine
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    #[serde(default)]
+    pub tools: Vec<ToolEntry>,
+    /// Enabled MCP server ids keyed by target name
+    #[serde(default)]
+    pub mcp: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub skills: Vec<SkillEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolEntry {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillEntry {
+    pub name: String,
+    /// Repository the skill was installed from, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub agents: Vec<String>,
+}
+
+/// Collect the current state of installed tools, MCP servers and skills
+pub fn capture() -> Result<Snapshot> {
+    let tools = tools::installed_versions()
+        .into_iter()
+        .filter_map(|v| {
+            v.installed.map(|version| ToolEntry {
+                name: v.name,
+                version: Some(version),
+            })
+        })
+        .collect();
+
+    let servers = servers::catalog();
+    let mut mcp = BTreeMap::new();
+    for target in targets::catalog() {
+        if !target.is_installed() {
+            continue;
+        }
+        let enabled: Vec<String> = servers
+            .iter()
+            .filter(|s| target.is_server_enabled(s).unwrap_or(false))
+            .map(|s| s.id.to_string())
+            .collect();
+        mcp.insert(target.name.to_string(), enabled);
+    }
+
+    let manifest = Manifest::load()?;
+    let mut skills: BTreeMap<String, SkillEntry> = BTreeMap::new();
+    for agent in agents::catalog() {
+        if !agent.is_installed() {
+            continue;
+        }
+        for skill in discovery::list_installed_skills(&agent.skills_path)? {
+            let entry = skills
+                .entry(skill.name.clone())
+                .or_insert_with(|| SkillEntry {
+                    source: manifest.source_of(&skill.name).map(str::to_string),
+                    name: skill.name,
+                    agents: Vec::new(),
+                });
+            entry.agents.push(agent.id.to_string());
+        }
+    }
+
+    Ok(Snapshot {
+        version: SNAPSHOT_VERSION,
+        tools,
+        mcp,
+        skills: skills.into_values().collect(),
+    })
+}
+
+impl Snapshot {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let snapshot: Snapshot = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON in {}", path.display()))?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            anyhow::bail!(
+                "{} was written by a newer ai-cli (format version {})",
+                path.display(),
+                snapshot.version
+            );
+        }
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Handle `export <file>` command
+pub fn handle_export(path: &Path) -> Result<()> {
+    let snapshot = capture()?;
+    snapshot.save(path)?;
+
+    let enabled: usize = snapshot.mcp.values().map(Vec::len).sum();
+    println!(
+        "{} Exported {} tool(s), {} MCP server entries and {} skill(s) to {}",
+        "✓".success(),
+        snapshot.tools.len(),
+        enabled,
+        snapshot.skills.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Handle `import <file>` command
+pub async fn handle_import(path: &Path) -> Result<Outcome> {
+    let snapshot = Snapshot::load(path)?;
+    apply(&snapshot).await
+}
+
+/// "2.0.14 (Claude Code)" or "v2.0.14" -> "2.0.14"
+fn plain_version(version: &str) -> &str {
+    version
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('v')
+}
+
+/// An installed tool in another version than the snapshot's
+#[derive(Debug, PartialEq)]
+struct VersionChange {
+    tool: String,
+    wanted: String,
+    installed: String,
+}
+
+/// Installed tools whose version isn't the one in the snapshot
+fn version_changes(wanted: &[ToolEntry], installed: &[tools::ToolVersion]) -> Vec<VersionChange> {
+    wanted
+        .iter()
+        .filter_map(|entry| {
+            let want = plain_version(entry.version.as_deref()?);
+            let have = installed
+                .iter()
+                .find(|v| v.name == entry.name)?
+                .installed
+                .as_deref()
+                .map(plain_version)?;
+            (!want.is_empty() && want != have).then(|| VersionChange {
+                tool: entry.name.clone(),
+                wanted: want.to_string(),
+                installed: have.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Converge this machine towards `snapshot`
+///
+/// Tools installed in another version get the snapshot's version where it can
+/// be pinned (npm installs); the others count as failures to fix by hand.
+pub async fn apply(snapshot: &Snapshot) -> Result<Outcome> {
+    let catalog = tools::catalog();
+    let missing_tools: Vec<_> = snapshot
+        .tools
+        .iter()
+        .filter_map(|entry| find_tool(&catalog, &entry.name))
+        .filter(|tool| !tool.is_installed())
+        .collect();
+    let version_changes = version_changes(&snapshot.tools, &tools::installed_versions());
+
+    let all_servers = servers::catalog();
+    let mcp_targets = targets::catalog();
+    let mut mcp_changes = Vec::new();
+    for (target_name, server_ids) in &snapshot.mcp {
+        let Some(target) = mcp_targets.iter().find(|t| t.name == target_name) else {
+            continue;
+        };
+        for id in server_ids {
+            if let Some(server) = all_servers.iter().find(|s| s.id == *id)
+                && !target.is_server_enabled(server).unwrap_or(false)
+            {
+                mcp_changes.push((target, server));
+            }
+        }
+    }
+
+    let installed_skills: Vec<(String, String)> = agents::catalog()
+        .iter()
+        .flat_map(|agent| {
+            discovery::list_installed_skills(&agent.skills_path)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| (s.name, agent.id.to_string()))
+        })
+        .collect();
+
+    // Group missing skills by source repo and agent so each repo is cloned once per agent set
+    let mut skill_installs: BTreeMap<(String, Vec<String>), Vec<String>> = BTreeMap::new();
+    let mut unsourced = Vec::new();
+    for skill in &snapshot.skills {
+        let missing_agents: Vec<String> = skill
+            .agents
+            .iter()
+            .filter(|a| !installed_skills.contains(&(skill.name.clone(), a.to_string())))
+            .cloned()
+            .collect();
+        if missing_agents.is_empty() {
+            continue;
+        }
+        match &skill.source {
+            Some(source) => skill_installs
+                .entry((source.clone(), missing_agents))
+                .or_default()
+                .push(skill.name.clone()),
+            None => unsourced.push(skill.name.clone()),
+        }
+    }
+
+    if missing_tools.is_empty()
+        && version_changes.is_empty()
+        && mcp_changes.is_empty()
+        && skill_installs.is_empty()
+    {
+        println!("{} Already matches {}", "✓".success(), "snapshot".bold());
+        report_unsourced(&unsourced);
+        return Ok(Outcome::NothingToDo);
+    }
+
+    println!("{}", "Planned changes:".bold());
+    for tool in &missing_tools {
+        println!("  {} install {}", "+".success(), tool.name);
+    }
+    for change in &version_changes {
+        println!(
+            "  {} install {} {} ({} is installed)",
+            "~".info(),
+            change.tool,
+            change.wanted,
+            change.installed
+        );
+    }
+    for (target, server) in &mcp_changes {
+        println!(
+            "  {} enable {} in {}",
+            "+".success(),
+            server.id,
+            target.name
+        );
+    }
+    for ((source, agent_ids), names) in &skill_installs {
+        println!(
+            "  {} install skill(s) {} from {} for {}",
+            "+".success(),
+            names.join(", "),
+            source,
+            agent_ids.join(", ")
+        );
+    }
+    println!();
+
+    if !prompt::confirm("Apply these changes?")? {
+        println!("Cancelled.");
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let mut failed = 0;
+
+    for tool in missing_tools {
+        if let Err(e) = install_tool(tool).await {
+            println!("{} Failed to install {}: {}", "✗".error(), tool.name, e);
+            failed += 1;
+        }
+    }
+    for change in &version_changes {
+        let result = match find_tool(&catalog, &change.tool) {
+            Some(tool) => install_pinned(tool, &change.wanted),
+            None => Err(anyhow!("unknown tool")),
+        };
+        match result {
+            Ok(()) => println!(
+                "{} Installed {} {}",
+                "✓".success(),
+                change.tool,
+                change.wanted
+            ),
+            Err(e) => {
+                println!(
+                    "{} {} {} is installed, the snapshot has {}: {:#}",
+                    "✗".error(),
+                    change.tool,
+                    change.installed,
+                    change.wanted,
+                    e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    let configured = mcp::configure_all(mcp_changes.iter().map(|(_, server)| *server));
+    for (target, server) in mcp_changes {
+        let result = match configured.get(&server.id) {
+            Some(server) => target.enable_server(server),
+            None => Err(anyhow!("not configured")),
+        };
+        match result {
+            Ok(_) => println!("{} Enabled {} in {}", "✓".success(), server.id, target.name),
+            Err(e) => {
+                println!(
+                    "{} Failed to enable {} in {}: {}",
+                    "✗".error(),
+                    server.id,
+                    target.name,
+                    e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    for ((source, agent_ids), names) in skill_installs {
+        let agents: Vec<_> = agent_ids.iter().filter_map(|id| agents::find(id)).collect();
+        if let Err(e) = install_from_repo(&source, &agents, Some(&names)) {
+            println!(
+                "{} Failed to install skills from {}: {}",
+                "✗".error(),
+                source,
+                e
+            );
+            failed += 1;
+        }
+    }
+
+    report_unsourced(&unsourced);
+
+    println!();
+    if failed == 0 {
+        println!("{}", "Import complete!".success().bold());
+    } else {
+        println!(
+            "{}",
+            format!("Import finished with {} failure(s)", failed).warning()
+        );
+    }
+
+    Ok(Outcome::from_failures(failed))
+}
+
+fn report_unsourced(names: &[String]) {
+    if names.is_empty() {
+        return;
+    }
+    println!(
+        "{} Skipped skill(s) with no recorded source: {}",
+        "!".warning(),
+        names.join(", ")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_tools_in_other_versions() {
+        let entry = |name: &str, version: Option<&str>| ToolEntry {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        };
+        let installed = |name: &str, version: &str| tools::ToolVersion {
+            installed: Some(version.to_string()),
+            ..tools::ToolVersion::new(name)
+        };
+        let wanted = [
+            entry("Claude Code", Some("2.0.14 (Claude Code)")),
+            entry("Codex CLI", Some("v0.50.0")),
+            entry("Amp", None),
+            entry("Gemini CLI", Some("0.9.0")),
+        ];
+        let have = [
+            installed("Claude Code", "2.0.10 (Claude Code)"),
+            installed("Codex CLI", "0.50.0"),
+            installed("Amp", "0.1"),
+        ];
+        assert_eq!(
+            version_changes(&wanted, &have),
+            [VersionChange {
+                tool: "Claude Code".to_string(),
+                wanted: "2.0.14".to_string(),
+                installed: "2.0.10".to_string(),
+            }]
+        );
+    }
+}
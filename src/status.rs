@@ -0,0 +1,301 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use serde::Deserialize;
+
+use crate::http;
+use crate::logging;
+use crate::output;
+use crate::theme::Themed;
+
+/// Status pages are asked again after a minute
+const STATUS_TTL: Duration = Duration::from_secs(60);
+
+/// Where a provider publishes its status
+#[derive(Debug, Clone)]
+pub enum StatusApi {
+    /// An Atlassian Statuspage `summary.json` endpoint
+    Statuspage { url: String },
+    /// Google Cloud's `incidents.json`, filtered to products matching one of the names
+    GoogleCloud {
+        url: String,
+        products: &'static [&'static str],
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusSource {
+    pub provider: &'static str,
+    /// Page to open for details
+    pub page: &'static str,
+    pub api: StatusApi,
+}
+
+/// Returns the status sources of all supported providers
+pub fn sources() -> Vec<StatusSource> {
+    vec![
+        StatusSource {
+            provider: "Anthropic",
+            page: "https://status.anthropic.com",
+            api: StatusApi::Statuspage {
+                url: "https://status.anthropic.com/api/v2/summary.json".to_string(),
+            },
+        },
+        StatusSource {
+            provider: "OpenAI",
+            page: "https://status.openai.com",
+            api: StatusApi::Statuspage {
+                url: "https://status.openai.com/api/v2/summary.json".to_string(),
+            },
+        },
+        StatusSource {
+            provider: "Google",
+            page: "https://status.cloud.google.com",
+            api: StatusApi::GoogleCloud {
+                url: "https://status.cloud.google.com/incidents.json".to_string(),
+                products: &["Gemini", "Vertex AI"],
+            },
+        },
+        StatusSource {
+            provider: "GitHub",
+            page: "https://www.githubstatus.com",
+            api: StatusApi::Statuspage {
+                url: "https://www.githubstatus.com/api/v2/summary.json".to_string(),
+            },
+        },
+    ]
+}
+
+/// An ongoing incident
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incident {
+    pub name: String,
+    /// Impact or current state as reported by the provider
+    pub impact: String,
+    pub url: Option<String>,
+}
+
+/// A provider's current status
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderStatus {
+    /// The provider's own summary, e.g. "All Systems Operational"
+    pub description: String,
+    pub incidents: Vec<Incident>,
+}
+
+#[derive(Deserialize)]
+struct Summary {
+    status: SummaryStatus,
+    #[serde(default)]
+    incidents: Vec<SummaryIncident>,
+}
+
+#[derive(Deserialize)]
+struct SummaryStatus {
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct SummaryIncident {
+    name: String,
+    #[serde(default)]
+    impact: String,
+    #[serde(default)]
+    status: String,
+    shortlink: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CloudIncident {
+    external_desc: String,
+    end: Option<String>,
+    #[serde(default)]
+    severity: String,
+    uri: Option<String>,
+    #[serde(default)]
+    affected_products: Vec<CloudProduct>,
+}
+
+#[derive(Deserialize)]
+struct CloudProduct {
+    title: String,
+}
+
+fn parse_statuspage(body: &str) -> Result<ProviderStatus> {
+    let summary: Summary = serde_json::from_str(body).context("Unexpected status response")?;
+    Ok(ProviderStatus {
+        description: summary.status.description,
+        incidents: summary
+            .incidents
+            .into_iter()
+            .map(|i| Incident {
+                name: i.name,
+                impact: format!("{}, {}", i.impact, i.status),
+                url: i.shortlink,
+            })
+            .collect(),
+    })
+}
+
+fn parse_google_cloud(body: &str, products: &[&str]) -> Result<ProviderStatus> {
+    let incidents: Vec<CloudIncident> =
+        serde_json::from_str(body).context("Unexpected status response")?;
+    let ongoing: Vec<Incident> = incidents
+        .into_iter()
+        .filter(|i| i.end.is_none())
+        .filter(|i| {
+            i.affected_products
+                .iter()
+                .any(|p| products.iter().any(|name| p.title.contains(name)))
+        })
+        .map(|i| Incident {
+            name: i
+                .external_desc
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            impact: i.severity,
+            url: i
+                .uri
+                .map(|uri| format!("https://status.cloud.google.com/{}", uri)),
+        })
+        .collect();
+    Ok(ProviderStatus {
+        description: if ongoing.is_empty() {
+            format!("No incidents affecting {}", products.join(" or "))
+        } else {
+            format!("Incidents affecting {}", products.join(" or "))
+        },
+        incidents: ongoing,
+    })
+}
+
+async fn fetch(api: &StatusApi) -> Result<ProviderStatus> {
+    let url = match api {
+        StatusApi::Statuspage { url } | StatusApi::GoogleCloud { url, .. } => url,
+    };
+    let body = http::get_text(url, STATUS_TTL).await?;
+    match api {
+        StatusApi::Statuspage { .. } => parse_statuspage(&body),
+        StatusApi::GoogleCloud { products, .. } => parse_google_cloud(&body, products),
+    }
+}
+
+/// Handle `status --providers` command. Returns `false` when a provider reports an incident.
+pub async fn handle_providers() -> Result<bool> {
+    let spinner = output::spinner("Checking provider status...");
+    let sources = sources();
+    let results = join_all(sources.iter().map(|s| fetch(&s.api))).await;
+    spinner.finish_and_clear();
+
+    let mut affected = 0;
+    for (source, result) in sources.iter().zip(results) {
+        match result {
+            Ok(status) if status.incidents.is_empty() => {
+                println!(
+                    "{} {:<10} {}",
+                    "✓".success(),
+                    source.provider,
+                    status.description.muted()
+                );
+            }
+            Ok(status) => {
+                affected += 1;
+                println!(
+                    "{} {:<10} {}",
+                    "!".warning(),
+                    source.provider,
+                    status.description
+                );
+                for incident in &status.incidents {
+                    println!(
+                        "    {} {}",
+                        incident.name,
+                        format!("({})", incident.impact).muted()
+                    );
+                    if let Some(url) = &incident.url {
+                        println!("      {}", url.muted());
+                    }
+                }
+            }
+            Err(e) => {
+                logging::line(&format!("status {}: {:#}", source.provider, e));
+                println!(
+                    "{} {:<10} {}",
+                    "?".muted(),
+                    source.provider,
+                    format!("status unavailable, see {}", source.page).muted()
+                );
+            }
+        }
+    }
+
+    if affected > 0 {
+        println!();
+        println!(
+            "{} {} provider(s) report incidents; errors may not be your config",
+            "→".info(),
+            affected
+        );
+    }
+    Ok(affected == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn reports_statuspage_incidents() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/api/v2/summary.json");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        r#"{"status":{"indicator":"minor","description":"Minor Service Outage"},
+                            "components":[],
+                            "incidents":[{"name":"Elevated errors on Claude Opus","impact":"minor",
+                              "status":"investigating","shortlink":"https://stspg.io/abc"}]}"#,
+                    );
+            })
+            .await;
+
+        let api = StatusApi::Statuspage {
+            url: format!("{}/api/v2/summary.json", server.base_url()),
+        };
+        let status = fetch(&api).await.unwrap();
+        assert_eq!(status.description, "Minor Service Outage");
+        assert_eq!(
+            status.incidents,
+            vec![Incident {
+                name: "Elevated errors on Claude Opus".to_string(),
+                impact: "minor, investigating".to_string(),
+                url: Some("https://stspg.io/abc".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn keeps_only_ongoing_google_incidents_for_ai_products() {
+        let body = r#"[
+            {"external_desc":"Vertex AI Gemini API errors\nMore detail","end":null,"severity":"medium",
+             "uri":"incidents/abc","affected_products":[{"title":"Vertex Gemini API"},{"title":"Vertex AI Online Prediction"}]},
+            {"external_desc":"Old Vertex AI issue","end":"2026-01-01T00:00:00+00:00","severity":"low",
+             "affected_products":[{"title":"Vertex AI Online Prediction"}]},
+            {"external_desc":"Cloud SQL latency","end":null,"severity":"high",
+             "affected_products":[{"title":"Cloud SQL"}]}
+        ]"#;
+        let status = parse_google_cloud(body, &["Gemini", "Vertex AI"]).unwrap();
+        assert_eq!(status.incidents.len(), 1);
+        assert_eq!(status.incidents[0].name, "Vertex AI Gemini API errors");
+        assert_eq!(
+            status.incidents[0].url.as_deref(),
+            Some("https://status.cloud.google.com/incidents/abc")
+        );
+    }
+}
@@ -0,0 +1,731 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::exit::Outcome;
+use crate::journal;
+use crate::logging;
+use crate::managed;
+use crate::notify::{self, Event};
+use crate::paths;
+use crate::prompt;
+use crate::skills::manifest;
+use crate::snapshot::{self, Snapshot};
+use crate::theme::Themed;
+use crate::tools::command_output;
+
+/// Desired-state file kept in the sync repository
+const STATE_FILE: &str = "state.json";
+/// Copy of the skills source manifest
+const SKILLS_FILE: &str = "skills.json";
+/// Marks a sync directory backed by S3 instead of git; holds the bucket URL
+const S3_REMOTE_FILE: &str = "s3-remote";
+
+/// Where the synced state is kept
+#[derive(Debug, Clone, PartialEq)]
+enum Remote {
+    /// The sync directory is a git checkout with an `origin` remote
+    Git,
+    /// `s3://bucket/prefix`, read and written with the AWS CLI
+    S3(String),
+}
+
+fn remote_of(dir: &Path) -> Option<Remote> {
+    if dir.join(".git").exists() {
+        return Some(Remote::Git);
+    }
+    let url = std::fs::read_to_string(dir.join(S3_REMOTE_FILE)).ok()?;
+    Some(Remote::S3(url.trim().trim_end_matches('/').to_string()))
+}
+
+/// Local checkout of the sync repository
+pub fn repo_dir() -> PathBuf {
+    paths::data_home().join("ai-cli").join("sync")
+}
+
+/// Handle `sync init <remote>` command
+pub fn handle_init(remote: &str) -> Result<()> {
+    let dir = repo_dir();
+    if remote_of(&dir).is_some() {
+        anyhow::bail!(
+            "Sync repository already exists at {} (remove it to re-initialize)",
+            dir.display()
+        );
+    }
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    if remote.starts_with("s3://") {
+        return init_s3(&dir, remote.trim_end_matches('/'));
+    }
+
+    println!("{} Cloning {}...", "→".info(), remote);
+    let cloned = logging::run(Command::new("git").arg("clone").arg(remote).arg(&dir))
+        .context("Failed to run git clone")?
+        .success();
+
+    if !cloned {
+        // An empty remote can't be cloned; start a fresh repository pointing at it
+        println!("{} Starting a new sync repository", "→".info());
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        git(&dir, &["init"])?;
+        git(&dir, &["remote", "add", "origin", remote])?;
+    }
+
+    println!(
+        "{} Sync repository ready at {}",
+        "✓".success(),
+        dir.display()
+    );
+    println!(
+        "{} Run `ai-cli sync push` to publish this machine's state",
+        "→".info()
+    );
+    Ok(())
+}
+
+fn init_s3(dir: &Path, url: &str) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    println!("{} Fetching {}...", "→".info(), url);
+    let found = s3_get(url, STATE_FILE, &dir.join(STATE_FILE))?;
+    s3_get(url, SKILLS_FILE, &dir.join(SKILLS_FILE))?;
+    std::fs::write(dir.join(S3_REMOTE_FILE), url)
+        .with_context(|| format!("Failed to write {}", dir.join(S3_REMOTE_FILE).display()))?;
+
+    println!(
+        "{} Sync directory ready at {}",
+        "✓".success(),
+        dir.display()
+    );
+    if found {
+        println!(
+            "{} Run `ai-cli sync pull` to apply the synced state",
+            "→".info()
+        );
+    } else {
+        println!(
+            "{} Run `ai-cli sync push` to publish this machine's state",
+            "→".info()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `sync push` command
+pub fn handle_push() -> Result<Outcome> {
+    let (dir, remote) = require_repo()?;
+    if let Remote::S3(url) = remote {
+        return push_s3(&dir, &url);
+    }
+
+    write_local_state(&dir)?;
+    git(&dir, &["add", "-A"])?;
+
+    let has_changes = !git_output(&dir, &["status", "--porcelain"])?.is_empty();
+    if has_changes {
+        let host = command_output("hostname", &[]).unwrap_or_else(|| "unknown host".into());
+        git(&dir, &["commit", "-m", &format!("Sync from {}", host)])?;
+    }
+
+    if git_output(&dir, &["rev-parse", "--verify", "HEAD"]).is_err() {
+        println!("{} Nothing to push", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+
+    if has_upstream(&dir) {
+        let rebased = logging::run(
+            Command::new("git")
+                .current_dir(&dir)
+                .args(["pull", "--rebase", "origin", "HEAD"]),
+        )
+        .context("Failed to run git pull")?
+        .success();
+
+        if !rebased {
+            let conflicts = git_output(&dir, &["diff", "--name-only", "--diff-filter=U"])?;
+            let _ = git(&dir, &["rebase", "--abort"]);
+            anyhow::bail!(
+                "Remote changes conflict with this machine's state ({}). \
+                 Run `ai-cli sync pull --force` to take the remote state, then push again.",
+                conflicts.lines().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+
+    git(&dir, &["push", "-u", "origin", "HEAD"])?;
+    println!("{} Pushed state to sync repository", "✓".success());
+    Ok(if has_changes {
+        Outcome::Success
+    } else {
+        Outcome::NothingToDo
+    })
+}
+
+fn push_s3(dir: &Path, url: &str) -> Result<Outcome> {
+    let state = dir.join(STATE_FILE);
+    let synced = std::fs::read_to_string(&state).ok();
+    let fetched = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let remote_state = fetched.path().join(STATE_FILE);
+    if s3_get(url, STATE_FILE, &remote_state)?
+        && std::fs::read_to_string(&remote_state).ok() != synced
+    {
+        anyhow::bail!(
+            "The remote state changed since the last sync. \
+             Run `ai-cli sync pull --force` to take the remote state, then push again."
+        );
+    }
+
+    if !local_state_differs(dir, &Remote::S3(url.to_string()))? {
+        println!("{} Nothing to push", "✓".success());
+        return Ok(Outcome::NothingToDo);
+    }
+    write_local_state(dir)?;
+    s3_put(&state, url, STATE_FILE)?;
+    if dir.join(SKILLS_FILE).exists() {
+        s3_put(&dir.join(SKILLS_FILE), url, SKILLS_FILE)?;
+    }
+    println!("{} Pushed state to {}", "✓".success(), url);
+    Ok(Outcome::Success)
+}
+
+/// Fetch the remote state into the sync directory. Returns whether it changed,
+/// or `None` when the remote has no state yet.
+fn fetch_remote(
+    dir: &Path,
+    remote: &Remote,
+    force: bool,
+    local_changed: bool,
+) -> Result<Option<bool>> {
+    let conflict = || {
+        anyhow::anyhow!(
+            "Both this machine and the remote changed since the last sync. \
+             Push first to merge, or use `ai-cli sync pull --force` to take the remote state."
+        )
+    };
+    match remote {
+        Remote::Git => {
+            if !has_upstream(dir) {
+                return Ok(None);
+            }
+            git(dir, &["fetch", "origin", "HEAD"])?;
+            let behind = git_output(dir, &["rev-list", "--count", "HEAD..FETCH_HEAD"])
+                .unwrap_or_else(|_| "1".into());
+            let remote_changed = behind.trim() != "0";
+            if local_changed && remote_changed && !force {
+                return Err(conflict());
+            }
+            if remote_changed {
+                git(dir, &["reset", "--hard", "FETCH_HEAD"])?;
+            }
+            Ok(Some(remote_changed))
+        }
+        Remote::S3(url) => {
+            let fetched = tempfile::tempdir().context("Failed to create temporary directory")?;
+            if !s3_get(url, STATE_FILE, &fetched.path().join(STATE_FILE))? {
+                return Ok(None);
+            }
+            s3_get(url, SKILLS_FILE, &fetched.path().join(SKILLS_FILE))?;
+            let read = |dir: &Path| std::fs::read_to_string(dir.join(STATE_FILE)).ok();
+            let remote_changed = read(fetched.path()) != read(dir);
+            if local_changed && remote_changed && !force {
+                return Err(conflict());
+            }
+            for name in [STATE_FILE, SKILLS_FILE] {
+                let from = fetched.path().join(name);
+                if from.exists() {
+                    std::fs::copy(&from, dir.join(name)).with_context(|| {
+                        format!("Failed to update {}", dir.join(name).display())
+                    })?;
+                }
+            }
+            Ok(Some(remote_changed))
+        }
+    }
+}
+
+/// Handle `sync pull` command
+pub async fn handle_pull(force: bool) -> Result<Outcome> {
+    let (dir, remote) = require_repo()?;
+
+    let local_changed = local_state_differs(&dir, &remote)?;
+
+    if fetch_remote(&dir, &remote, force, local_changed)?.is_none() {
+        println!(
+            "{} Remote has no state yet; run `ai-cli sync push`",
+            "!".warning()
+        );
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let state_path = dir.join(STATE_FILE);
+    if !state_path.exists() {
+        println!(
+            "{} Remote has no state yet; run `ai-cli sync push`",
+            "!".warning()
+        );
+        return Ok(Outcome::NothingToDo);
+    }
+
+    let desired = Snapshot::load(&state_path)?;
+    merge_skills_manifest(&dir)?;
+    let outcome = snapshot::apply(&desired).await?;
+
+    // The organization's policy wins over the synced state
+    let Some(policy) = managed::load().await? else {
+        return Ok(outcome);
+    };
+    println!();
+    Ok(match (outcome, managed::remediate(&policy).await?) {
+        (Outcome::NothingToDo, other) | (other, Outcome::NothingToDo) => other,
+        (Outcome::Success, other) | (other, _) => other,
+    })
+}
+
+/// What on this machine differs from the synced state
+///
+/// Missing tools, servers and skills are what `sync pull` installs; versions
+/// and extras are left alone and reported as conflicts.
+fn differences(desired: &Snapshot, current: &Snapshot) -> Vec<String> {
+    let mut found = Vec::new();
+    for want in &desired.tools {
+        match current.tools.iter().find(|t| t.name == want.name) {
+            None => found.push(format!("{} is not installed", want.name)),
+            Some(have) => {
+                if let (Some(wanted), Some(installed)) =
+                    (short_version(&want.version), short_version(&have.version))
+                    && wanted != installed
+                {
+                    found.push(format!(
+                        "{} {} is installed, the synced state has {}",
+                        want.name, installed, wanted
+                    ));
+                }
+            }
+        }
+    }
+    for have in &current.tools {
+        if !desired.tools.iter().any(|t| t.name == have.name) {
+            found.push(format!(
+                "{} is installed but not in the synced state",
+                have.name
+            ));
+        }
+    }
+
+    // Targets missing on either side are covered by the tool checks
+    for (target, wanted) in &desired.mcp {
+        let Some(enabled) = current.mcp.get(target) else {
+            continue;
+        };
+        for id in wanted.iter().filter(|id| !enabled.contains(id)) {
+            found.push(format!("MCP server {} is not enabled in {}", id, target));
+        }
+        for id in enabled.iter().filter(|id| !wanted.contains(id)) {
+            found.push(format!(
+                "MCP server {} is enabled in {} but not in the synced state",
+                id, target
+            ));
+        }
+    }
+
+    let pairs = |snapshot: &Snapshot| -> BTreeSet<(String, String)> {
+        snapshot
+            .skills
+            .iter()
+            .flat_map(|s| s.agents.iter().map(|a| (s.name.clone(), a.clone())))
+            .collect()
+    };
+    let (wanted, installed) = (pairs(desired), pairs(current));
+    for (name, agent) in wanted.difference(&installed) {
+        found.push(format!("Skill {} is not installed for {}", name, agent));
+    }
+    for (name, agent) in installed.difference(&wanted) {
+        found.push(format!(
+            "Skill {} for {} is not in the synced state",
+            name, agent
+        ));
+    }
+    found
+}
+
+/// "2.0.14 (Claude Code)" -> "2.0.14"
+fn short_version(version: &Option<String>) -> Option<&str> {
+    version.as_deref()?.split_whitespace().next()
+}
+
+/// One daemon round: take the remote state, apply it, and report what's left
+async fn converge() -> Result<Vec<String>> {
+    handle_pull(true).await?;
+    let (dir, remote) = require_repo()?;
+    let Some(desired) = synced_state(&dir, &remote)? else {
+        return Ok(Vec::new());
+    };
+    Ok(differences(&desired, &snapshot::capture()?))
+}
+
+/// Handle `sync daemon` command: converge with the synced state every `interval` minutes
+///
+/// The remote state wins; nothing is pushed. Runs until interrupted.
+pub async fn handle_daemon(interval: u64) -> Result<Outcome> {
+    require_repo()?;
+    // Nobody is there to answer prompts
+    prompt::set_non_interactive(true);
+    // Each round is an operation of its own for `undo`, not one that never ends
+    journal::finish();
+
+    loop {
+        let now = chrono::Local::now();
+        println!(
+            "{} {} Converging with the synced state",
+            "→".info(),
+            now.format("%Y-%m-%d %H:%M")
+        );
+        journal::begin("sync daemon");
+        let converged = converge().await;
+        journal::finish();
+        match converged {
+            Ok(conflicts) => {
+                if conflicts.is_empty() {
+                    println!("{} Matches the synced state", "✓".success());
+                } else {
+                    println!(
+                        "{} {} difference(s) the sync doesn't resolve:",
+                        "!".warning(),
+                        conflicts.len()
+                    );
+                    for conflict in &conflicts {
+                        println!("  - {}", conflict);
+                    }
+                }
+                notify::send(Event::ConfigDrift, "sync daemon", &conflicts).await;
+            }
+            // Offline or a broken remote: try again next round
+            Err(e) => {
+                println!("{} Sync failed: {:#}", "✗".error(), e);
+                logging::line(&format!("sync daemon: {:#}", e));
+            }
+        }
+
+        let next = now + chrono::Duration::minutes(interval as i64);
+        println!(
+            "{}",
+            format!("Next check at {}", next.format("%H:%M")).muted()
+        );
+        println!();
+        tokio::time::sleep(std::time::Duration::from_secs(interval * 60)).await;
+    }
+}
+
+/// Handle `sync status` command
+pub fn handle_status() -> Result<()> {
+    let dir = repo_dir();
+    let Some(remote) = remote_of(&dir) else {
+        println!(
+            "{} Sync not initialized (run `ai-cli sync init <remote>`)",
+            "!".warning()
+        );
+        return Ok(());
+    };
+
+    let (url, last) = match &remote {
+        Remote::Git => (
+            git_output(&dir, &["remote", "get-url", "origin"]).unwrap_or_default(),
+            git_output(&dir, &["log", "-1", "--format=%cr (%s)"]).unwrap_or_default(),
+        ),
+        Remote::S3(url) => {
+            let synced = std::fs::metadata(dir.join(STATE_FILE))
+                .and_then(|m| m.modified())
+                .map(|t| {
+                    chrono::DateTime::<chrono::Local>::from(t)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            (url.clone(), synced)
+        }
+    };
+    println!("{:<12} {}", "Repository:".bold(), dir.display());
+    println!("{:<12} {}", "Remote:".bold(), url.trim());
+    println!(
+        "{:<12} {}",
+        "Last sync:".bold(),
+        if last.is_empty() {
+            "never"
+        } else {
+            last.trim()
+        }
+    );
+    if local_state_differs(&dir, &remote)? {
+        println!("{} This machine has changes not pushed yet", "→".info());
+    } else {
+        println!(
+            "{} This machine matches the last synced state",
+            "✓".success()
+        );
+    }
+    Ok(())
+}
+
+fn require_repo() -> Result<(PathBuf, Remote)> {
+    let dir = repo_dir();
+    let Some(remote) = remote_of(&dir) else {
+        anyhow::bail!(
+            "Sync not initialized. Run `ai-cli sync init <git-remote or s3://bucket/prefix>` first"
+        );
+    };
+    Ok((dir, remote))
+}
+
+fn write_local_state(dir: &Path) -> Result<()> {
+    snapshot::capture()?.save(&dir.join(STATE_FILE))?;
+
+    let manifest = manifest::manifest_path();
+    if manifest.exists() {
+        std::fs::copy(&manifest, dir.join(SKILLS_FILE))
+            .with_context(|| format!("Failed to copy {}", manifest.display()))?;
+    }
+    Ok(())
+}
+
+/// The state as of the last sync
+fn synced_state(dir: &Path, remote: &Remote) -> Result<Option<Snapshot>> {
+    let committed = match remote {
+        Remote::Git => git_output(dir, &["show", &format!("HEAD:{}", STATE_FILE)]).ok(),
+        Remote::S3(_) => std::fs::read_to_string(dir.join(STATE_FILE)).ok(),
+    };
+    let Some(committed) = committed else {
+        return Ok(None);
+    };
+    let committed = serde_json::from_str(&committed)
+        .with_context(|| format!("Failed to parse {} in sync repository", STATE_FILE))?;
+    Ok(Some(committed))
+}
+
+/// Whether this machine's state differs from the last synced state
+fn local_state_differs(dir: &Path, remote: &Remote) -> Result<bool> {
+    let Some(committed) = synced_state(dir, remote)? else {
+        return Ok(true);
+    };
+    let current = snapshot::capture()?;
+    Ok(serde_json::to_value(&committed)? != serde_json::to_value(&current)?)
+}
+
+/// Take skill sources from the synced manifest for skills we don't know yet
+fn merge_skills_manifest(dir: &Path) -> Result<()> {
+    let synced = dir.join(SKILLS_FILE);
+    if !synced.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&synced)
+        .with_context(|| format!("Failed to read {}", synced.display()))?;
+    let remote: manifest::Manifest = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse JSON in {}", synced.display()))?;
+
+    let mut local = manifest::Manifest::load()?;
+    merge_manifests(&mut local, remote);
+    local.save()
+}
+
+/// Add the skills `remote` has a source for and `local` doesn't know; what
+/// `local` records about a skill wins
+fn merge_manifests(local: &mut manifest::Manifest, remote: manifest::Manifest) {
+    for (name, record) in remote.skills {
+        local.skills.entry(name).or_insert(record);
+    }
+}
+
+fn has_upstream(dir: &Path) -> bool {
+    git_output(dir, &["ls-remote", "--exit-code", "--heads", "origin"]).is_ok()
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let status = logging::run(Command::new("git").current_dir(dir).args(args))
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !status.success() {
+        anyhow::bail!("git {} failed", args.join(" "));
+    }
+    Ok(())
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!("git {} failed", args.join(" "));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Download `url/name` to `dest`. Returns false when the object doesn't exist.
+fn s3_get(url: &str, name: &str, dest: &Path) -> Result<bool> {
+    let object = format!("{}/{}", url, name);
+    let listed = Command::new("aws")
+        .args(["s3", "ls", &object])
+        .output()
+        .context("Failed to run aws; install the AWS CLI to sync through S3")?;
+    if !listed.status.success() {
+        return Ok(false);
+    }
+    let status = logging::run(
+        Command::new("aws")
+            .args(["s3", "cp", "--only-show-errors", &object])
+            .arg(dest),
+    )
+    .context("Failed to run aws s3 cp")?;
+    if !status.success() {
+        anyhow::bail!("Failed to download {}", object);
+    }
+    Ok(true)
+}
+
+fn s3_put(src: &Path, url: &str, name: &str) -> Result<()> {
+    let object = format!("{}/{}", url, name);
+    let status = logging::run(
+        Command::new("aws")
+            .args(["s3", "cp", "--only-show-errors"])
+            .arg(src)
+            .arg(&object),
+    )
+    .context("Failed to run aws s3 cp")?;
+    if !status.success() {
+        anyhow::bail!("Failed to upload {}", object);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{SkillEntry, ToolEntry};
+
+    #[test]
+    fn reports_what_sync_does_not_resolve() {
+        let tool = |name: &str, version: &str| ToolEntry {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+        };
+        let skill = |name: &str, agents: &[&str]| SkillEntry {
+            name: name.to_string(),
+            source: None,
+            agents: agents.iter().map(|a| a.to_string()).collect(),
+        };
+        let desired = Snapshot {
+            version: 1,
+            tools: vec![
+                tool("Claude Code", "2.0.14 (Claude Code)"),
+                tool("Codex CLI", "0.50.0"),
+            ],
+            mcp: [("Claude Code".to_string(), vec!["linear".to_string()])].into(),
+            skills: vec![skill("pdf", &["claude", "codex"])],
+        };
+        let current = Snapshot {
+            version: 1,
+            tools: vec![
+                tool("Claude Code", "2.0.10 (Claude Code)"),
+                tool("Amp", "0.1"),
+            ],
+            mcp: [(
+                "Claude Code".to_string(),
+                vec!["linear".to_string(), "github".to_string()],
+            )]
+            .into(),
+            skills: vec![skill("pdf", &["claude"])],
+        };
+        assert_eq!(
+            differences(&desired, &current),
+            vec![
+                "Claude Code 2.0.10 is installed, the synced state has 2.0.14",
+                "Codex CLI is not installed",
+                "Amp is installed but not in the synced state",
+                "MCP server github is enabled in Claude Code but not in the synced state",
+                "Skill pdf is not installed for codex",
+            ]
+        );
+        assert!(differences(&desired, &desired).is_empty());
+    }
+
+    #[test]
+    fn merges_skill_sources_from_the_remote() {
+        let mut local = manifest::Manifest::default();
+        local.record_install("pdf", "acme/skills", "claude");
+        let mut remote = manifest::Manifest::default();
+        remote.record_install("pdf", "anthropics/skills", "codex");
+        remote.record_install("docx", "anthropics/skills", "codex");
+
+        merge_manifests(&mut local, remote);
+        assert_eq!(local.source_of("pdf"), Some("acme/skills"));
+        assert_eq!(local.skills["pdf"].agents, ["claude"]);
+        assert_eq!(local.source_of("docx"), Some("anthropics/skills"));
+    }
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Sync", "-c", "user.email=sync@example.com"])
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {}", args.join(" "));
+    }
+
+    /// Commit `state` on the other machine and push it
+    fn push_state(other: &Path, state: &str) {
+        std::fs::write(other.join(STATE_FILE), state).unwrap();
+        run_git(other, &["add", "-A"]);
+        run_git(other, &["commit", "-qm", state]);
+        run_git(other, &["push", "-q", "origin", "HEAD"]);
+    }
+
+    #[test]
+    fn detects_conflicts_with_the_remote() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = dir.path().join("remote.git");
+        std::fs::create_dir(&remote).unwrap();
+        run_git(&remote, &["init", "-q", "--bare"]);
+        let url = remote.to_str().unwrap();
+        let other = dir.path().join("other");
+        let here = dir.path().join("here");
+        run_git(dir.path(), &["clone", "-q", url, "other"]);
+
+        // An empty remote has no state to fetch
+        run_git(dir.path(), &["clone", "-q", url, "here"]);
+        assert_eq!(
+            fetch_remote(&here, &Remote::Git, false, true).unwrap(),
+            None
+        );
+
+        push_state(&other, "v1");
+        assert_eq!(
+            fetch_remote(&here, &Remote::Git, false, false).unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            fetch_remote(&here, &Remote::Git, false, true).unwrap(),
+            Some(false)
+        );
+
+        // Both sides changed: refuse unless forced, and keep the local state
+        push_state(&other, "v2");
+        let err = fetch_remote(&here, &Remote::Git, false, true).unwrap_err();
+        assert!(err.to_string().contains("Both this machine and the remote"));
+        let state = || std::fs::read_to_string(here.join(STATE_FILE)).unwrap();
+        assert_eq!(state(), "v1");
+
+        assert_eq!(
+            fetch_remote(&here, &Remote::Git, true, true).unwrap(),
+            Some(true)
+        );
+        assert_eq!(state(), "v2");
+    }
+}
@@ -0,0 +1,290 @@
+//! Opt-in anonymous usage statistics.
+//!
+//! Each run appends one event (command name, exit category, OS, version and
+//! date) to a local queue; the queue is sent in batches. Nothing is recorded
+//! unless `[telemetry] enabled = true`, and `DO_NOT_TRACK` or
+//! `AI_CLI_NO_TELEMETRY` turn it off regardless of the config.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::cli;
+use crate::config;
+use crate::exit::{self, Outcome};
+use crate::http;
+use crate::logging;
+use crate::paths;
+use crate::theme::Themed;
+
+const DEFAULT_ENDPOINT: &str = "https://telemetry.ai-cli.dev/v1/events";
+/// Send once this many events are queued, or when the oldest is from an earlier day
+const BATCH_SIZE: usize = 20;
+/// How long a run waits for the collector before keeping the queue for later
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// One ai-cli run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Subcommand names only, e.g. `mcp enable`; never arguments
+    pub command: String,
+    /// `success`, `usage`, `network`, ...
+    pub result: String,
+    pub os: String,
+    pub arch: String,
+    pub version: String,
+    /// Day of the run, `YYYY-MM-DD`
+    pub date: String,
+}
+
+fn state_dir() -> PathBuf {
+    paths::state_home().join("ai-cli").join("telemetry")
+}
+
+fn queue_path() -> PathBuf {
+    state_dir().join("queue.jsonl")
+}
+
+/// Written when the collector asks clients to stop sending
+fn kill_switch_path() -> PathBuf {
+    state_dir().join("disabled-by-server")
+}
+
+/// Why telemetry is off even though it is enabled in the config
+fn override_reason() -> Option<String> {
+    for var in ["DO_NOT_TRACK", "AI_CLI_NO_TELEMETRY"] {
+        if env::var_os(var).is_some_and(|v| !v.is_empty() && v != "0") {
+            return Some(format!("${} is set", var));
+        }
+    }
+    kill_switch_path()
+        .exists()
+        .then(|| "switched off by the collector".to_string())
+}
+
+/// Whether this run should be recorded
+pub fn enabled() -> bool {
+    config::load().is_ok_and(|c| c.telemetry.enabled) && override_reason().is_none()
+}
+
+fn endpoint() -> String {
+    config::load()
+        .ok()
+        .and_then(|c| c.telemetry.endpoint)
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
+}
+
+/// The subcommand names `args` resolve to, with aliases and prefixes expanded
+pub fn command_path(args: &[String]) -> String {
+    let Ok(matches) = cli::command().try_get_matches_from(args) else {
+        return "invalid".to_string();
+    };
+    let mut names = Vec::new();
+    let mut current = &matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name.to_string());
+        current = sub;
+    }
+    if names.is_empty() {
+        "(none)".to_string()
+    } else {
+        names.join(" ")
+    }
+}
+
+fn result_category(code: u8) -> &'static str {
+    match code {
+        exit::SUCCESS => "success",
+        exit::USAGE => "usage",
+        exit::NETWORK => "network",
+        exit::PARTIAL_FAILURE => "partial",
+        exit::NOTHING_TO_DO => "nothing-to-do",
+        exit::UPDATES_AVAILABLE => "updates-available",
+        _ => "failure",
+    }
+}
+
+fn event(args: &[String], code: u8) -> Event {
+    Event {
+        command: command_path(args),
+        result: result_category(code).to_string(),
+        os: env::consts::OS.to_string(),
+        arch: env::consts::ARCH.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+    }
+}
+
+fn read_queue(path: &Path) -> Vec<Event> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn append(path: &Path, event: &Event) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(event)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Whether the queue should be sent now
+fn is_due(events: &[Event], today: &str) -> bool {
+    events.len() >= BATCH_SIZE || events.first().is_some_and(|e| e.date.as_str() < today)
+}
+
+/// What happened to a batch
+#[derive(Debug, PartialEq)]
+enum Sent {
+    Accepted,
+    /// The collector no longer wants events from this version
+    KillSwitch,
+}
+
+async fn upload(url: &str, events: &[Event]) -> Result<Sent> {
+    let request = http::client()
+        .post(url)
+        .timeout(UPLOAD_TIMEOUT)
+        .json(&serde_json::json!({ "events": events }));
+    let response = http::send(request).await?;
+    if response.status() == reqwest::StatusCode::GONE {
+        return Ok(Sent::KillSwitch);
+    }
+    response
+        .error_for_status()
+        .with_context(|| format!("Failed to send usage statistics to {}", url))?;
+    Ok(Sent::Accepted)
+}
+
+/// Queue an event for this run and send the queue when a batch is due
+///
+/// Failures are logged and never affect the command's exit code.
+pub async fn record(args: &[String], code: u8) {
+    let path = queue_path();
+    if let Err(e) = append(&path, &event(args, code)) {
+        logging::line(&format!("telemetry: {:#}", e));
+        return;
+    }
+    let events = read_queue(&path);
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    if !is_due(&events, &today) {
+        return;
+    }
+    match upload(&endpoint(), &events).await {
+        Ok(Sent::Accepted) => {
+            let _ = fs::remove_file(&path);
+        }
+        Ok(Sent::KillSwitch) => {
+            let _ = fs::remove_file(&path);
+            let _ = fs::write(kill_switch_path(), "");
+            logging::line("telemetry: switched off by the collector");
+        }
+        // Kept for the next run
+        Err(e) => logging::line(&format!("telemetry: {:#}", e)),
+    }
+}
+
+fn print_collected() {
+    println!("  Collected: command name (e.g. `mcp enable`), exit category, OS, architecture,");
+    println!("  ai-cli version and date. Never arguments, paths, keys or config contents.");
+}
+
+/// Handle `telemetry on` command
+pub fn handle_on() -> Result<Outcome> {
+    config::set_flag("telemetry", "enabled", true)?;
+    let _ = fs::remove_file(kill_switch_path());
+    println!(
+        "{} Anonymous usage statistics turned on, thank you",
+        "✓".success()
+    );
+    print_collected();
+    if let Some(reason) = override_reason() {
+        println!("{} Nothing is sent while {}", "!".warning(), reason);
+    }
+    Ok(Outcome::Success)
+}
+
+/// Handle `telemetry off` command
+pub fn handle_off() -> Result<Outcome> {
+    config::set_flag("telemetry", "enabled", false)?;
+    let path = queue_path();
+    let queued = read_queue(&path).len();
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    println!("{} Anonymous usage statistics turned off", "✓".success());
+    if queued > 0 {
+        println!(
+            "  {}",
+            format!("Deleted {} unsent event(s)", queued).muted()
+        );
+    }
+    Ok(Outcome::Success)
+}
+
+/// Handle `telemetry status` command
+pub fn handle_status() -> Result<()> {
+    let enabled_in_config = config::load()?.telemetry.enabled;
+    let state = match (enabled_in_config, override_reason()) {
+        (false, _) => "off".muted().to_string(),
+        (true, Some(reason)) => format!("{} ({})", "paused".warning(), reason),
+        (true, None) => "on".success().to_string(),
+    };
+    println!("{} {}", "Usage statistics:".bold(), state);
+    print_collected();
+    if enabled_in_config {
+        println!();
+        println!("  Endpoint: {}", endpoint());
+        println!("  Queued:   {} event(s)", read_queue(&queue_path()).len());
+    } else {
+        println!();
+        println!("{} Turn on with `ai-cli telemetry on`", "→".info());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn records_subcommand_names_without_arguments() {
+        assert_eq!(command_path(&args("ai-cli mcp en linear")), "mcp enable");
+        assert_eq!(command_path(&args("ai-cli --yes skills ls")), "skills list");
+        assert_eq!(command_path(&args("ai-cli bogus")), "invalid");
+        assert_eq!(result_category(exit::NETWORK), "network");
+    }
+
+    #[test]
+    fn batches_by_size_or_age() {
+        let queue = tempfile::tempdir().unwrap();
+        let path = queue.path().join("queue.jsonl");
+        let mut today = event(&args("ai-cli doctor"), 0);
+        today.date = "2026-10-16".to_string();
+        append(&path, &today).unwrap();
+        let events = read_queue(&path);
+        assert_eq!(events, vec![today.clone()]);
+        assert!(!is_due(&events, "2026-10-16"));
+        assert!(is_due(&events, "2026-10-17"));
+        assert!(is_due(&vec![today; BATCH_SIZE], "2026-10-16"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Colors used for statuses, markers and headers.
+//!
+//! Output code styles text by role (`"✓".success()`, `path.muted()`) instead
+//! of naming colors, so the palette can be switched with a preset or
+//! overridden per role in the `[theme]` table of the config file.
+
+use std::sync::RwLock;
+
+use anyhow::{Result, bail};
+use colored::{Color, ColoredString, Colorize};
+use serde::Deserialize;
+
+use crate::config;
+
+/// How a role is rendered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Paint {
+    Color(Color),
+    Dimmed,
+    Plain,
+}
+
+impl Paint {
+    fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("dimmed") {
+            return Ok(Paint::Dimmed);
+        }
+        if value.eq_ignore_ascii_case("none") || value.eq_ignore_ascii_case("plain") {
+            return Ok(Paint::Plain);
+        }
+        if let Some(hex) = value.strip_prefix('#')
+            && hex.len() == 6
+            && let Ok(rgb) = u32::from_str_radix(hex, 16)
+        {
+            return Ok(Paint::Color(Color::TrueColor {
+                r: (rgb >> 16) as u8,
+                g: (rgb >> 8) as u8,
+                b: rgb as u8,
+            }));
+        }
+        match value.parse::<Color>() {
+            Ok(color) => Ok(Paint::Color(color)),
+            Err(()) => bail!(
+                "Unknown theme color '{}' (use a color name like 'bright blue', '#rrggbb', 'dimmed' or 'none')",
+                value
+            ),
+        }
+    }
+
+    fn apply<T: Colorize>(self, text: T) -> ColoredString {
+        match self {
+            Paint::Color(color) => text.color(color),
+            Paint::Dimmed => text.dimmed(),
+            Paint::Plain => text.normal(),
+        }
+    }
+}
+
+/// Palette of roles used throughout the output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Installed, enabled, up to date
+    pub success: Paint,
+    /// Outdated, skipped, needs attention
+    pub warning: Paint,
+    /// Failed, not installed
+    pub error: Paint,
+    /// Progress arrows and hints
+    pub info: Paint,
+    /// Paths, descriptions and other secondary text
+    pub muted: Paint,
+    /// Section headers
+    pub accent: Paint,
+    /// Versions and other values worth spotting
+    pub highlight: Paint,
+}
+
+impl Theme {
+    /// The default palette, tuned for dark terminals
+    pub const DARK: Theme = Theme {
+        success: Paint::Color(Color::Green),
+        warning: Paint::Color(Color::Yellow),
+        error: Paint::Color(Color::Red),
+        info: Paint::Color(Color::Cyan),
+        muted: Paint::Dimmed,
+        accent: Paint::Color(Color::BrightCyan),
+        highlight: Paint::Color(Color::BrightBlue),
+    };
+
+    /// Darker colors that stay readable on light backgrounds
+    pub const LIGHT: Theme = Theme {
+        success: Paint::Color(Color::Green),
+        warning: Paint::Color(Color::Magenta),
+        error: Paint::Color(Color::Red),
+        info: Paint::Color(Color::Blue),
+        muted: Paint::Plain,
+        accent: Paint::Color(Color::Blue),
+        highlight: Paint::Color(Color::Magenta),
+    };
+
+    /// Color only for errors and warnings
+    pub const MINIMAL: Theme = Theme {
+        success: Paint::Plain,
+        warning: Paint::Color(Color::Yellow),
+        error: Paint::Color(Color::Red),
+        info: Paint::Plain,
+        muted: Paint::Plain,
+        accent: Paint::Plain,
+        highlight: Paint::Plain,
+    };
+
+    /// Build a theme from the `[theme]` config table
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let mut theme = match config.preset.as_deref() {
+            None | Some("dark") => Theme::DARK,
+            Some("light") => Theme::LIGHT,
+            Some("minimal") => Theme::MINIMAL,
+            Some(other) => bail!(
+                "Unknown theme preset '{}' (available: dark, light, minimal)",
+                other
+            ),
+        };
+
+        let overrides = [
+            (&config.success, &mut theme.success),
+            (&config.warning, &mut theme.warning),
+            (&config.error, &mut theme.error),
+            (&config.info, &mut theme.info),
+            (&config.muted, &mut theme.muted),
+            (&config.accent, &mut theme.accent),
+            (&config.highlight, &mut theme.highlight),
+        ];
+        for (value, paint) in overrides {
+            if let Some(value) = value {
+                *paint = Paint::parse(value)?;
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+/// `[theme]` table in the config file
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct ThemeConfig {
+    /// `dark` (default), `light` or `minimal`
+    pub preset: Option<String>,
+    pub success: Option<String>,
+    pub warning: Option<String>,
+    pub error: Option<String>,
+    pub info: Option<String>,
+    pub muted: Option<String>,
+    pub accent: Option<String>,
+    pub highlight: Option<String>,
+}
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+/// Load the theme from the config file; falls back to the default on errors
+pub fn init_from_config() -> Result<()> {
+    let theme = Theme::from_config(&config::load()?.theme)?;
+    set(theme);
+    Ok(())
+}
+
+/// Use `theme` for all following output
+pub fn set(theme: Theme) {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+pub fn current() -> Theme {
+    *THEME.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Style text by role using the current theme
+pub trait Themed: Colorize + Sized {
+    fn success(self) -> ColoredString {
+        current().success.apply(self)
+    }
+    fn warning(self) -> ColoredString {
+        current().warning.apply(self)
+    }
+    fn error(self) -> ColoredString {
+        current().error.apply(self)
+    }
+    fn info(self) -> ColoredString {
+        current().info.apply(self)
+    }
+    fn muted(self) -> ColoredString {
+        current().muted.apply(self)
+    }
+    fn accent(self) -> ColoredString {
+        current().accent.apply(self)
+    }
+    fn highlight(self) -> ColoredString {
+        current().highlight.apply(self)
+    }
+}
+
+impl<T: Colorize + Sized> Themed for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_preset_and_overrides() {
+        let config = ThemeConfig {
+            preset: Some("light".into()),
+            warning: Some("#ff8800".into()),
+            muted: Some("bright black".into()),
+            ..Default::default()
+        };
+        let theme = Theme::from_config(&config).unwrap();
+
+        assert_eq!(theme.success, Theme::LIGHT.success);
+        assert_eq!(
+            theme.warning,
+            Paint::Color(Color::TrueColor {
+                r: 0xff,
+                g: 0x88,
+                b: 0x00
+            })
+        );
+        assert_eq!(theme.muted, Paint::Color(Color::BrightBlack));
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        let bad_preset = ThemeConfig {
+            preset: Some("solarized".into()),
+            ..Default::default()
+        };
+        assert!(Theme::from_config(&bad_preset).is_err());
+
+        let bad_color = ThemeConfig {
+            error: Some("reddish".into()),
+            ..Default::default()
+        };
+        assert!(Theme::from_config(&bad_color).is_err());
+    }
+}
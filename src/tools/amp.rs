@@ -7,6 +7,7 @@ pub fn definition() -> Tool {
         vec!["amp".to_string(), "--version".to_string()],
     )
     .with_binary_name("amp")
+    .with_legacy_npm_package("@sourcegraph/amp")
 }
 
 pub fn installed_version() -> ToolVersion {
@@ -7,6 +7,8 @@ pub fn definition() -> Tool {
         vec!["claude".to_string(), "--version".to_string()],
     )
     .with_binary_name("claude")
+    .with_legacy_npm_package("@anthropic-ai/claude-code")
+    .with_release_repo("anthropics/claude-code")
 }
 
 pub fn installed_version() -> ToolVersion {
@@ -7,6 +7,7 @@ pub fn definition() -> Tool {
         vec!["codex".to_string(), "--version".to_string()],
     )
     .with_binary_name("codex")
+    .with_release_repo("openai/codex")
 }
 
 pub fn installed_version() -> ToolVersion {
@@ -7,6 +7,7 @@ pub fn definition() -> Tool {
         vec!["copilot".to_string(), "--version".to_string()],
     )
     .with_binary_name("copilot")
+    .with_release_repo("github/copilot-cli")
 }
 
 pub fn installed_version() -> ToolVersion {
@@ -7,6 +7,7 @@ pub fn definition() -> Tool {
         vec!["gemini".to_string(), "--version".to_string()],
     )
     .with_binary_name("gemini")
+    .with_release_repo("google-gemini/gemini-cli")
 }
 
 pub fn installed_version() -> ToolVersion {
@@ -0,0 +1,124 @@
+//! Editors and editor extensions with built-in agents.
+//!
+//! ai-cli doesn't install these, but `apps list` reports their versions next
+//! to the CLI tools: apps from their macOS bundle or their launcher command,
+//! extensions from `code --list-extensions --show-versions`.
+
+use std::path::PathBuf;
+
+use super::{ToolVersion, command_output};
+use crate::paths;
+
+/// Editor apps: name, identifier, macOS bundle and launcher command
+const APPS: &[(&str, &str, &str, &str)] = &[
+    ("Cursor", "cursor", "Cursor.app", "cursor"),
+    ("Windsurf", "windsurf", "Windsurf.app", "windsurf"),
+];
+
+/// VS Code extensions: name and extension id
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("GitHub Copilot", "github.copilot"),
+    ("GitHub Copilot Chat", "github.copilot-chat"),
+    ("Amp (VS Code)", "sourcegraph.amp"),
+];
+
+/// The `<string>` following `<key>{key}</key>` in an XML property list
+fn plist_string(content: &str, key: &str) -> Option<String> {
+    let rest = content.split_once(&format!("<key>{}</key>", key))?.1;
+    let value = rest.trim_start().strip_prefix("<string>")?;
+    Some(value.split_once("</string>")?.0.trim().to_string())
+}
+
+/// `id@version` lines of `code --list-extensions --show-versions`, by lowercase id
+fn parse_extensions(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().split_once('@'))
+        .map(|(id, version)| (id.to_lowercase(), version.to_string()))
+        .collect()
+}
+
+fn bundle_version(bundle: &str) -> Option<String> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    [
+        PathBuf::from("/Applications"),
+        paths::home().join("Applications"),
+    ]
+    .iter()
+    .find_map(|dir| {
+        let plist = dir.join(bundle).join("Contents/Info.plist");
+        let content = std::fs::read_to_string(plist).ok()?;
+        plist_string(&content, "CFBundleShortVersionString")
+    })
+}
+
+fn app_version(bundle: &str, command: &str) -> Option<String> {
+    bundle_version(bundle).or_else(|| {
+        // Editor launchers print version, commit and architecture on separate lines
+        command_output(command, &["--version"])
+            .and_then(|output| output.lines().next().map(|line| line.trim().to_string()))
+    })
+}
+
+/// Versions of the editors and extensions found on this machine
+pub fn installed_versions() -> Vec<ToolVersion> {
+    let mut found: Vec<ToolVersion> = APPS
+        .iter()
+        .filter_map(|(name, id, bundle, command)| {
+            let version = app_version(bundle, command)?;
+            Some(
+                ToolVersion::new(name)
+                    .with_installed(Some(version))
+                    .with_identifier(id),
+            )
+        })
+        .collect();
+
+    let extensions = command_output("code", &["--list-extensions", "--show-versions"])
+        .map(|output| parse_extensions(&output))
+        .unwrap_or_default();
+    for (name, id) in EXTENSIONS {
+        if let Some((_, version)) = extensions.iter().find(|(ext, _)| ext == id) {
+            found.push(
+                ToolVersion::new(name)
+                    .with_installed(Some(version.clone()))
+                    .with_identifier(id),
+            );
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_bundle_and_extension_versions() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+	<key>CFBundleName</key>
+	<string>Cursor</string>
+	<key>CFBundleShortVersionString</key>
+	<string>1.7.44</string>
+</dict>
+</plist>"#;
+        assert_eq!(
+            plist_string(plist, "CFBundleShortVersionString").as_deref(),
+            Some("1.7.44")
+        );
+        assert_eq!(plist_string(plist, "CFBundleVersion"), None);
+
+        let extensions = parse_extensions("GitHub.copilot@1.372.0\ngithub.copilot-chat@0.31.5\n");
+        assert_eq!(
+            extensions,
+            [
+                ("github.copilot".to_string(), "1.372.0".to_string()),
+                ("github.copilot-chat".to_string(), "0.31.5".to_string()),
+            ]
+        );
+    }
+}
@@ -8,6 +8,7 @@ pub fn definition() -> Tool {
     )
     .with_binary_name("vibe")
     .with_config_dir(".vibe")
+    .with_release_repo("mistralai/mistral-vibe")
 }
 
 pub fn installed_version() -> ToolVersion {
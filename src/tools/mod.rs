@@ -6,12 +6,15 @@ mod copilot;
 mod cursor_agent;
 mod factory;
 mod gemini;
+pub mod ide;
 mod kilo;
 mod mistral_vibe;
 mod opencode;
 
 use std::process::Command;
 
+use crate::paths;
+
 pub use amp::{definition as amp_tool, installed_version as amp_installed_version};
 pub use claude::{definition as claude_tool, installed_version as claude_installed_version};
 pub use cline::{definition as cline_tool, installed_version as cline_installed_version};
@@ -45,6 +48,10 @@ pub struct Tool {
     pub binary_name: Option<String>,
     pub config_dirs: Vec<String>,
     pub extra_binary_paths: Vec<String>,
+    /// npm packages the tool was distributed as before its current installer
+    pub legacy_npm_packages: Vec<String>,
+    /// GitHub repository (`owner/name`) whose releases carry the tool's release notes
+    pub release_repo: Option<String>,
 }
 
 impl Tool {
@@ -56,6 +63,8 @@ impl Tool {
             binary_name: None,
             config_dirs: Vec::new(),
             extra_binary_paths: Vec::new(),
+            legacy_npm_packages: Vec::new(),
+            release_repo: None,
         }
     }
 
@@ -74,6 +83,44 @@ impl Tool {
         self
     }
 
+    pub fn with_legacy_npm_package(mut self, package: &str) -> Self {
+        self.legacy_npm_packages.push(package.to_string());
+        self
+    }
+
+    pub fn with_release_repo(mut self, repo: &str) -> Self {
+        self.release_repo = Some(repo.to_string());
+        self
+    }
+
+    /// Whether the tool is installed the way ai-cli installs it now: through
+    /// npm for npm tools, otherwise as a binary outside npm's prefix
+    pub fn has_current_install(&self) -> bool {
+        match &self.install_method {
+            InstallMethod::Npm(_) => self.is_installed(),
+            InstallMethod::Bootstrap(_) | InstallMethod::Amp(_) => {
+                let home = paths::home();
+                self.binary_name
+                    .iter()
+                    .map(|binary| paths::local_bin().join(binary))
+                    .chain(self.extra_binary_paths.iter().map(|p| home.join(p)))
+                    .any(|path| path.exists())
+            }
+        }
+    }
+
+    /// Every npm package the tool is or was distributed as
+    pub fn npm_packages(&self) -> Vec<&str> {
+        let current = match &self.install_method {
+            InstallMethod::Npm(package) => Some(package.as_str()),
+            _ => None,
+        };
+        current
+            .into_iter()
+            .chain(self.legacy_npm_packages.iter().map(String::as_str))
+            .collect()
+    }
+
     pub fn is_installed(&self) -> bool {
         if self.check_command.is_empty() {
             return false;
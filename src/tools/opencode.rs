@@ -8,6 +8,7 @@ pub fn definition() -> Tool {
     )
     .with_binary_name("opencode")
     .with_config_dir(".opencode")
+    .with_release_repo("sst/opencode")
 }
 
 pub fn installed_version() -> ToolVersion {
@@ -0,0 +1,153 @@
+use std::env;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::config;
+use crate::notify::{self, Event};
+use crate::paths;
+use crate::theme::Themed;
+use crate::tools::installed_versions;
+use crate::versions::{fetch_latest_versions, get_ai_cli_latest, is_newer_version};
+
+/// Check at most once a day
+const CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+/// How long to wait for a running check before falling back to the cached result
+const FINISH_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Result of a background update check, cached between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateStatus {
+    /// Unix timestamp of the check
+    pub checked_at: i64,
+    /// Outdated tools as "Name installed → latest"
+    pub outdated: Vec<String>,
+    /// Newer ai-cli release, if any
+    pub ai_cli: Option<String>,
+}
+
+fn cache_path() -> PathBuf {
+    paths::state_home().join("ai-cli").join("update-check.json")
+}
+
+fn load_cache() -> Option<UpdateStatus> {
+    let content = std::fs::read_to_string(cache_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Result of the last background check, if one ran
+pub fn last_check() -> Option<UpdateStatus> {
+    load_cache()
+}
+
+fn save_cache(cache: &UpdateStatus) -> Result<()> {
+    let path = cache_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(&path, serde_json::to_string(cache)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Forget the last result, e.g. after tools were installed or updated
+pub fn invalidate() {
+    let _ = std::fs::remove_file(cache_path());
+}
+
+/// Whether update hints are wanted: on a terminal, not disabled in config or env
+pub fn enabled() -> bool {
+    if env::var_os("AI_CLI_NO_UPDATE_CHECK").is_some_and(|v| !v.is_empty() && v != "0") {
+        return false;
+    }
+    if !std::io::stderr().is_terminal() {
+        return false;
+    }
+    config::load()
+        .ok()
+        .and_then(|c| c.update_check)
+        .unwrap_or(true)
+}
+
+/// Start a background check when the cached result is older than a day
+pub fn spawn() -> Option<JoinHandle<Option<UpdateStatus>>> {
+    let now = chrono::Utc::now().timestamp();
+    let previous = load_cache();
+    if previous
+        .as_ref()
+        .is_some_and(|c| now - c.checked_at < CHECK_INTERVAL_SECS)
+    {
+        return None;
+    }
+    // The check counts even when the run ends before it does, as quick
+    // commands do; the previous result stays until a check completes
+    let _ = save_cache(&UpdateStatus {
+        checked_at: now,
+        ..previous.unwrap_or_default()
+    });
+
+    Some(tokio::spawn(async move {
+        let installed = tokio::task::spawn_blocking(installed_versions).await.ok()?;
+        let (latest, ai_cli_latest) = tokio::join!(fetch_latest_versions(), get_ai_cli_latest());
+        if latest.values().all(Option::is_none) && ai_cli_latest.is_none() {
+            // Offline: keep the old result
+            return None;
+        }
+
+        let outdated = installed
+            .iter()
+            .filter_map(|tool| {
+                let current = tool.installed.as_deref()?;
+                let newest = latest.get(tool.name.as_str())?.as_deref()?;
+                is_newer_version(newest, current)
+                    .then(|| format!("{} {} → {}", tool.name, current, newest))
+            })
+            .collect();
+        let cache = UpdateStatus {
+            checked_at: now,
+            outdated,
+            ai_cli: ai_cli_latest
+                .filter(|latest| is_newer_version(latest, env!("CARGO_PKG_VERSION"))),
+        };
+        let _ = save_cache(&cache);
+        if latest.values().any(Option::is_some) {
+            notify::send(Event::ToolOutdated, "update check", &cache.outdated).await;
+        }
+        Some(cache)
+    }))
+}
+
+/// Print a one-line hint from a just-finished check, or from the cached result
+pub async fn finish(check: Option<JoinHandle<Option<UpdateStatus>>>) {
+    let fresh = match check {
+        Some(handle) => tokio::time::timeout(FINISH_TIMEOUT, handle)
+            .await
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten(),
+        None => None,
+    };
+    let Some(cache) = fresh.or_else(load_cache) else {
+        return;
+    };
+
+    if !cache.outdated.is_empty() {
+        eprintln!(
+            "\n{} Updates available: {} (run `ai-cli apps update`)",
+            "→".info(),
+            cache.outdated.join(", ")
+        );
+    }
+    if let Some(latest) = &cache.ai_cli {
+        eprintln!(
+            "{} ai-cli {} is available (you have {})",
+            "→".info(),
+            latest,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+}
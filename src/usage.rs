@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
+use colored::*;
+use serde_json::Value;
+
+use crate::paths;
+use crate::theme::Themed;
+
+/// Token counts and estimated cost for one model call or an aggregate of calls
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Usage {
+    pub input: u64,
+    pub output: u64,
+    pub cache_write: u64,
+    pub cache_read: u64,
+    pub cost: f64,
+    /// Some calls used a model without a known price
+    pub unpriced: bool,
+}
+
+impl Usage {
+    fn add(&mut self, other: &Usage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+        self.cost += other.cost;
+        self.unpriced |= other.unpriced;
+    }
+
+    fn priced(mut self, model: &str) -> Self {
+        match price_for(model) {
+            Some(price) => {
+                self.cost = (self.input as f64 * price.input
+                    + self.output as f64 * price.output
+                    + self.cache_write as f64 * price.cache_write
+                    + self.cache_read as f64 * price.cache_read)
+                    / 1_000_000.0;
+            }
+            None => self.unpriced = true,
+        }
+        self
+    }
+}
+
+/// One model call read from a tool's local session data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub date: NaiveDate,
+    pub model: String,
+    pub usage: Usage,
+}
+
+/// USD per million tokens
+struct Price {
+    input: f64,
+    output: f64,
+    cache_write: f64,
+    cache_read: f64,
+}
+
+/// List prices by model family; more specific names first
+const PRICES: &[(&str, Price)] = &[
+    ("opus-4-5", price(5.0, 25.0, 6.25, 0.5)),
+    ("opus", price(15.0, 75.0, 18.75, 1.5)),
+    ("sonnet", price(3.0, 15.0, 3.75, 0.3)),
+    ("haiku-4-5", price(1.0, 5.0, 1.25, 0.1)),
+    ("haiku", price(0.8, 4.0, 1.0, 0.08)),
+    ("gpt-5-mini", price(0.25, 2.0, 0.0, 0.025)),
+    ("gpt-5-nano", price(0.05, 0.4, 0.0, 0.005)),
+    ("gpt-5", price(1.25, 10.0, 0.0, 0.125)),
+    ("codex-mini", price(1.5, 6.0, 0.0, 0.375)),
+    ("o3", price(2.0, 8.0, 0.0, 0.5)),
+    ("o4-mini", price(1.1, 4.4, 0.0, 0.275)),
+    ("gpt-4.1", price(2.0, 8.0, 0.0, 0.5)),
+];
+
+const fn price(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Price {
+    Price {
+        input,
+        output,
+        cache_write,
+        cache_read,
+    }
+}
+
+fn price_for(model: &str) -> Option<&'static Price> {
+    PRICES
+        .iter()
+        .find(|(family, _)| model.contains(family))
+        .map(|(_, price)| price)
+}
+
+fn local_date(timestamp: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Local).date_naive())
+}
+
+fn tokens(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Parse a Claude Code transcript (`~/.claude/projects/*/*.jsonl`)
+///
+/// Claude writes one line per content block of a response, each carrying the
+/// same usage, so lines are de-duplicated by message and request id.
+pub fn parse_claude_transcript(content: &str, seen: &mut HashSet<String>) -> Vec<Record> {
+    let mut records = Vec::new();
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let message = &entry["message"];
+        let Some(usage) = message.get("usage") else {
+            continue;
+        };
+        let Some(date) = entry["timestamp"].as_str().and_then(local_date) else {
+            continue;
+        };
+        if let (Some(id), Some(request)) = (message["id"].as_str(), entry["requestId"].as_str())
+            && !seen.insert(format!("{}:{}", id, request))
+        {
+            continue;
+        }
+
+        let model = message["model"].as_str().unwrap_or("unknown").to_string();
+        let usage = Usage {
+            input: tokens(usage, "input_tokens"),
+            output: tokens(usage, "output_tokens"),
+            cache_write: tokens(usage, "cache_creation_input_tokens"),
+            cache_read: tokens(usage, "cache_read_input_tokens"),
+            ..Usage::default()
+        }
+        .priced(&model);
+        records.push(Record { date, model, usage });
+    }
+    records
+}
+
+/// Parse a Codex CLI session log (`~/.codex/sessions/YYYY/MM/DD/*.jsonl`)
+///
+/// Token counts come from `token_count` events; the model from the preceding
+/// `turn_context` entry.
+pub fn parse_codex_session(content: &str) -> Vec<Record> {
+    let mut records = Vec::new();
+    let mut model = "unknown".to_string();
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let payload = &entry["payload"];
+        if let Some(m) = payload["model"].as_str() {
+            model = m.to_string();
+        }
+        if payload["type"] != "token_count" {
+            continue;
+        }
+        let Some(last) = payload["info"].get("last_token_usage") else {
+            continue;
+        };
+        let Some(date) = entry["timestamp"].as_str().and_then(local_date) else {
+            continue;
+        };
+
+        // Codex counts cached tokens as part of the input
+        let cached = tokens(last, "cached_input_tokens");
+        let usage = Usage {
+            input: tokens(last, "input_tokens").saturating_sub(cached),
+            output: tokens(last, "output_tokens"),
+            cache_read: cached,
+            ..Usage::default()
+        }
+        .priced(&model);
+        records.push(Record {
+            date,
+            model: model.clone(),
+            usage,
+        });
+    }
+    records
+}
+
+/// `*.jsonl` files under `dir` modified after `since`
+fn jsonl_files(dir: &Path, since: SystemTime) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(jsonl_files(&path, since));
+        } else if path.extension().is_some_and(|e| e == "jsonl")
+            && entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified >= since)
+        {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Usage of every tool since `since`, keyed by tool name
+pub fn collect(since: SystemTime) -> BTreeMap<&'static str, Vec<Record>> {
+    let home = paths::home();
+    let mut by_tool = BTreeMap::new();
+
+    let mut seen = HashSet::new();
+    let claude: Vec<Record> = jsonl_files(&home.join(".claude/projects"), since)
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| parse_claude_transcript(&content, &mut seen))
+        .collect();
+    by_tool.insert("Claude Code", claude);
+
+    let codex: Vec<Record> = jsonl_files(&home.join(".codex/sessions"), since)
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| parse_codex_session(&content))
+        .collect();
+    by_tool.insert("Codex CLI", codex);
+
+    by_tool
+}
+
+fn format_tokens(n: u64) -> String {
+    match n {
+        n if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1_000_000.0),
+        n if n >= 1_000 => format!("{:.1}k", n as f64 / 1_000.0),
+        n => n.to_string(),
+    }
+}
+
+fn format_cost(usage: &Usage) -> String {
+    if usage.unpriced {
+        format!("${:.2}+", usage.cost)
+    } else {
+        format!("${:.2}", usage.cost)
+    }
+}
+
+fn print_row(label: &str, usage: &Usage) {
+    println!(
+        "  {:<12} {:>9} {:>9} {:>9} {:>9} {:>10}",
+        label,
+        format_tokens(usage.input),
+        format_tokens(usage.output),
+        format_tokens(usage.cache_write),
+        format_tokens(usage.cache_read),
+        format_cost(usage)
+    );
+}
+
+/// Handle `usage` command: tokens and estimated cost per tool per day
+pub fn handle_usage(days: u32) -> Result<()> {
+    let today = Local::now().date_naive();
+    let first_day = today - chrono::Days::new(u64::from(days.saturating_sub(1)));
+    // Files are filtered by mtime; a session may span midnight, so look a day further back
+    let since = SystemTime::now() - Duration::from_secs(86_400 * (u64::from(days) + 1));
+
+    let mut total = Usage::default();
+    let mut any = false;
+    for (tool, records) in collect(since) {
+        let mut by_day: BTreeMap<NaiveDate, Usage> = BTreeMap::new();
+        for record in records.iter().filter(|r| r.date >= first_day) {
+            by_day.entry(record.date).or_default().add(&record.usage);
+        }
+        if by_day.is_empty() {
+            println!("{} {}", tool.bold(), "no usage recorded".muted());
+            println!();
+            continue;
+        }
+        any = true;
+
+        println!("{}", tool.bold());
+        println!(
+            "{}",
+            format!(
+                "  {:<12} {:>9} {:>9} {:>9} {:>9} {:>10}",
+                "Date", "Input", "Output", "Cache W", "Cache R", "Cost"
+            )
+            .muted()
+        );
+        let mut tool_total = Usage::default();
+        for (date, usage) in &by_day {
+            print_row(&date.to_string(), usage);
+            tool_total.add(usage);
+        }
+        print_row("Total", &tool_total);
+        println!();
+        total.add(&tool_total);
+    }
+
+    if any {
+        println!(
+            "{} {} over the last {} day(s) (estimated from list prices)",
+            "→".info(),
+            format_cost(&total).bold(),
+            days
+        );
+        if total.unpriced {
+            println!(
+                "  {}",
+                "+ some models have no known price and are not included in the cost".muted()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_transcripts_are_deduplicated_and_priced() {
+        let line = r#"{"type":"assistant","requestId":"req_1","timestamp":"2025-11-20T12:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-5","usage":{"input_tokens":1000000,"output_tokens":100000,"cache_creation_input_tokens":0,"cache_read_input_tokens":0}}}"#;
+        let content = format!("{line}\n{line}\n{{\"type\":\"user\"}}\n");
+
+        let records = parse_claude_transcript(&content, &mut HashSet::new());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].model, "claude-sonnet-4-5");
+        assert_eq!(records[0].usage.input, 1_000_000);
+        assert!((records[0].usage.cost - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn codex_sessions_split_cached_input() {
+        let content = [
+            r#"{"timestamp":"2025-11-20T12:00:00Z","type":"turn_context","payload":{"model":"gpt-5-codex"}}"#,
+            r#"{"timestamp":"2025-11-20T12:00:01Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":1200,"cached_input_tokens":200,"output_tokens":300}}}}"#,
+            r#"{"timestamp":"2025-11-20T12:00:02Z","type":"event_msg","payload":{"type":"token_count","info":null}}"#,
+        ]
+        .join("\n");
+
+        let records = parse_codex_session(&content);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].model, "gpt-5-codex");
+        assert_eq!(records[0].usage.input, 1000);
+        assert_eq!(records[0].usage.cache_read, 200);
+        assert!(!records[0].usage.unpriced);
+    }
+}
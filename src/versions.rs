@@ -1,28 +1,33 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
+use anyhow::Result;
 use colored::*;
 use futures::future::join_all;
-use indicatif::{ProgressBar, ProgressStyle};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::exit::Outcome;
+use crate::http;
+use crate::output;
+use crate::theme::Themed;
 use crate::tools::ToolVersion;
 
-#[derive(Deserialize)]
+/// How long a looked-up latest version is reused
+const VERSION_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Deserialize, Serialize)]
 struct NpmPackageInfo {
     #[serde(rename = "dist-tags")]
     dist_tags: NpmDistTags,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 struct NpmDistTags {
     latest: String,
 }
 
 async fn get_factory_cli_latest() -> Option<String> {
-    let script = reqwest::get("https://app.factory.ai/cli")
-        .await
-        .ok()?
-        .text()
+    let script = http::get_text("https://app.factory.ai/cli", VERSION_TTL)
         .await
         .ok()?;
 
@@ -33,25 +38,23 @@ async fn get_factory_cli_latest() -> Option<String> {
 }
 
 async fn fetch_npm_latest(url: &str) -> Option<String> {
-    let response = reqwest::get(url).await.ok()?;
-    let info: NpmPackageInfo = response.json().await.ok()?;
+    let info: NpmPackageInfo = http::get_json(url, VERSION_TTL).await.ok()?;
     Some(info.dist_tags.latest)
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 struct PypiPackageInfo {
     info: PypiInfo,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 struct PypiInfo {
     version: String,
 }
 
 async fn get_pypi_latest(package: &str) -> Option<String> {
     let url = format!("https://pypi.org/pypi/{}/json", package);
-    let response = reqwest::get(&url).await.ok()?;
-    let info: PypiPackageInfo = response.json().await.ok()?;
+    let info: PypiPackageInfo = http::get_json(&url, VERSION_TTL).await.ok()?;
     Some(info.info.version)
 }
 
@@ -60,6 +63,82 @@ async fn get_npm_latest(package: &str) -> Option<String> {
     fetch_npm_latest(&url).await
 }
 
+/// `registry.npmjs.org/<package>/latest`: the latest version's manifest
+#[derive(Deserialize, Serialize)]
+struct NpmLatestManifest {
+    version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct NpmSearch {
+    objects: Vec<NpmSearchObject>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct NpmSearchObject {
+    package: NpmSearchPackage,
+}
+
+#[derive(Deserialize, Serialize)]
+struct NpmSearchPackage {
+    name: String,
+    /// When the latest version was published
+    date: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct NpmDownloads {
+    downloads: u64,
+}
+
+/// How maintained an npm package looks
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpmHealth {
+    pub latest: String,
+    /// When the latest version was published (RFC 3339)
+    pub published: Option<String>,
+    /// Deprecation message of the latest version
+    pub deprecated: Option<String>,
+    pub weekly_downloads: Option<u64>,
+}
+
+async fn fetch_npm_health(registry: &str, downloads_api: &str, package: &str) -> Result<NpmHealth> {
+    let latest_url = format!("{}/{}/latest", registry, package);
+    let search_url = format!("{}/-/v1/search?text={}&size=5", registry, package);
+    let downloads_url = format!("{}/downloads/point/last-week/{}", downloads_api, package);
+    let (manifest, search, downloads) = tokio::join!(
+        http::get_json::<NpmLatestManifest>(&latest_url, VERSION_TTL),
+        http::get_json::<NpmSearch>(&search_url, VERSION_TTL),
+        http::get_json::<NpmDownloads>(&downloads_url, VERSION_TTL),
+    );
+    let manifest = manifest?;
+    let published = search.ok().and_then(|search| {
+        search
+            .objects
+            .into_iter()
+            .find(|o| o.package.name == package)
+            .and_then(|o| o.package.date)
+    });
+    Ok(NpmHealth {
+        latest: manifest.version,
+        published,
+        deprecated: manifest.deprecated,
+        weekly_downloads: downloads.ok().map(|d| d.downloads),
+    })
+}
+
+/// Latest version, publish date, deprecation and weekly downloads of `package`
+pub async fn get_npm_health(package: &str) -> Result<NpmHealth> {
+    fetch_npm_health(
+        "https://registry.npmjs.org",
+        "https://api.npmjs.org",
+        package,
+    )
+    .await
+}
+
 pub fn is_newer_version(latest: &str, installed: &str) -> bool {
     // Extract numeric parts from version strings
     let parse_version = |v: &str| -> Vec<u32> {
@@ -87,16 +166,8 @@ pub fn is_newer_version(latest: &str, installed: &str) -> bool {
     false
 }
 
-pub async fn check_latest_versions(tools: &mut [ToolVersion]) {
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.cyan} {msg}")
-            .unwrap(),
-    );
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-
-    spinner.set_message("Fetching versions...");
+/// Latest published version of every tool, keyed by tool name
+pub async fn fetch_latest_versions() -> HashMap<&'static str, Option<String>> {
     let sources = vec![
         (
             "Claude Code",
@@ -132,7 +203,12 @@ pub async fn check_latest_versions(tools: &mut [ToolVersion]) {
     )
     .await;
 
-    let latest_map: HashMap<_, _> = resolved.into_iter().collect();
+    resolved.into_iter().collect()
+}
+
+pub async fn check_latest_versions(tools: &mut [ToolVersion]) {
+    let spinner = output::spinner("Fetching versions...");
+    let latest_map = fetch_latest_versions().await;
 
     for tool in tools.iter_mut() {
         if let Some(latest) = latest_map.get(tool.name.as_str()) {
@@ -143,6 +219,83 @@ pub async fn check_latest_versions(tools: &mut [ToolVersion]) {
     spinner.finish_and_clear();
 }
 
+#[derive(Deserialize, Serialize)]
+struct GithubRelease {
+    tag_name: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CratesIoInfo {
+    #[serde(rename = "crate")]
+    krate: CratesIoCrate,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CratesIoCrate {
+    max_stable_version: String,
+}
+
+async fn fetch_crates_io_latest(url: &str) -> Option<String> {
+    let info: CratesIoInfo = http::get_json(url, VERSION_TTL).await.ok()?;
+    Some(info.krate.max_stable_version)
+}
+
+/// Latest ai-cli release published on GitHub, or on crates.io when GitHub has none
+pub async fn get_ai_cli_latest() -> Option<String> {
+    match fetch_github_release("https://api.github.com/repos/mikko-kohtala/ai-cli/releases/latest")
+        .await
+    {
+        Some(latest) => Some(latest),
+        None => fetch_crates_io_latest("https://crates.io/api/v1/crates/ai-cli").await,
+    }
+}
+
+/// The running ai-cli as a row next to the tools it manages
+pub async fn ai_cli_version() -> ToolVersion {
+    ToolVersion {
+        installed: Some(env!("CARGO_PKG_VERSION").to_string()),
+        latest: get_ai_cli_latest().await,
+        ..ToolVersion::new("ai-cli")
+    }
+}
+
+/// Handle `--check-update`
+pub async fn handle_check_update() -> Outcome {
+    let current = env!("CARGO_PKG_VERSION");
+    let spinner = output::spinner("Checking for a newer ai-cli...");
+    let latest = get_ai_cli_latest().await;
+    spinner.finish_and_clear();
+
+    match latest {
+        Some(latest) if is_newer_version(&latest, current) => {
+            println!(
+                "{} ai-cli {} is available (you have {})",
+                "→".info(),
+                latest.highlight(),
+                current.warning()
+            );
+            Outcome::UpdatesAvailable
+        }
+        Some(_) => {
+            println!("{} ai-cli {} is the latest version", "✓".success(), current);
+            Outcome::Success
+        }
+        None => {
+            println!(
+                "{} Could not look up the latest ai-cli release (you have {})",
+                "?".muted(),
+                current
+            );
+            Outcome::Failure
+        }
+    }
+}
+
+async fn fetch_github_release(url: &str) -> Option<String> {
+    let release: GithubRelease = http::get_json(url, VERSION_TTL).await.ok()?;
+    Some(release.tag_name.trim_start_matches('v').to_string())
+}
+
 pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize, id_width: usize) {
     let status = match &tool.installed {
         Some(version) => {
@@ -150,34 +303,29 @@ pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize,
             if check_latest {
                 if let Some(latest) = &tool.latest {
                     if version.contains(latest) || latest.contains(version) {
-                        version_str.green().to_string()
+                        version_str.success().to_string()
                     } else if is_newer_version(latest, version) {
                         format!(
                             "{} → {} available",
-                            version_str.yellow(),
-                            latest.bright_blue()
+                            version_str.warning(),
+                            latest.highlight()
                         )
                     } else {
-                        version_str.green().to_string()
+                        version_str.success().to_string()
                     }
                 } else {
-                    version_str.green().to_string()
+                    version_str.success().to_string()
                 }
             } else {
-                version_str.green().to_string()
+                version_str.success().to_string()
             }
         }
-        None => {
-            if check_latest && tool.latest.is_some() {
-                format!(
-                    "{} ({})",
-                    "not installed".red(),
-                    tool.latest.as_ref().unwrap().bright_blue()
-                )
-            } else {
-                "not installed".red().to_string()
+        None => match &tool.latest {
+            Some(latest) if check_latest => {
+                format!("{} ({})", "not installed".error(), latest.highlight())
             }
-        }
+            _ => "not installed".error().to_string(),
+        },
     };
 
     let name_padding = label_width.saturating_sub(tool.name.len());
@@ -190,7 +338,7 @@ pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize,
         "{}{}{}{}{}",
         format!("{}:", tool.name).bold(),
         name_spacer,
-        identifier.bright_black(),
+        identifier.muted(),
         id_spacer,
         status
     );
@@ -198,7 +346,9 @@ pub fn print_version(tool: &ToolVersion, check_latest: bool, label_width: usize,
 
 #[cfg(test)]
 mod tests {
-    use super::fetch_npm_latest;
+    use super::{
+        NpmHealth, fetch_crates_io_latest, fetch_github_release, fetch_npm_health, fetch_npm_latest,
+    };
     use httpmock::prelude::*;
 
     #[tokio::test]
@@ -216,4 +366,85 @@ mod tests {
         let latest = fetch_npm_latest(&format!("{}/@github/copilot", server.base_url())).await;
         assert_eq!(latest.as_deref(), Some("0.0.357"));
     }
+
+    #[tokio::test]
+    async fn it_fetches_latest_github_release_without_v_prefix() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/releases/latest");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"tag_name":"v0.3.1"}"#);
+            })
+            .await;
+
+        let latest = fetch_github_release(&format!("{}/releases/latest", server.base_url())).await;
+        assert_eq!(latest.as_deref(), Some("0.3.1"));
+    }
+
+    #[tokio::test]
+    async fn it_fetches_max_stable_version_from_crates_io() {
+        let server = MockServer::start_async().await;
+        let _mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/api/v1/crates/ai-cli");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"crate":{"name":"ai-cli","max_version":"0.5.0-rc.1","max_stable_version":"0.4.2"}}"#);
+            })
+            .await;
+
+        let latest =
+            fetch_crates_io_latest(&format!("{}/api/v1/crates/ai-cli", server.base_url())).await;
+        assert_eq!(latest.as_deref(), Some("0.4.2"));
+    }
+
+    #[tokio::test]
+    async fn it_fetches_npm_health_of_the_latest_version() {
+        let server = MockServer::start_async().await;
+        let _latest = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/old-cli/latest");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        r#"{"name":"old-cli","version":"2.1.0","deprecated":"Moved to new-cli"}"#,
+                    );
+            })
+            .await;
+        let _search = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/-/v1/search");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        r#"{"objects":[
+                            {"package":{"name":"old-cli-plugin","date":"2024-01-01T00:00:00.000Z"}},
+                            {"package":{"name":"old-cli","date":"2025-03-04T10:00:00.000Z"}}]}"#,
+                    );
+            })
+            .await;
+        let _downloads = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/downloads/point/last-week/old-cli");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(r#"{"downloads":1234,"package":"old-cli"}"#);
+            })
+            .await;
+
+        let health = fetch_npm_health(&server.base_url(), &server.base_url(), "old-cli")
+            .await
+            .unwrap();
+        assert_eq!(
+            health,
+            NpmHealth {
+                latest: "2.1.0".to_string(),
+                published: Some("2025-03-04T10:00:00.000Z".to_string()),
+                deprecated: Some("Moved to new-cli".to_string()),
+                weekly_downloads: Some(1234),
+            }
+        );
+    }
 }
@@ -0,0 +1,290 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use colored::*;
+
+use crate::exit::{self, Outcome};
+use crate::instructions::Scope;
+use crate::logging;
+use crate::theme::Themed;
+
+/// Project files agents read that are usually untracked, relative to the repo root
+const AGENT_FILES: &[&str] = &[
+    ".mcp.json",
+    ".cursor/mcp.json",
+    ".vscode/mcp.json",
+    ".gemini/settings.json",
+    ".claude/settings.json",
+    ".claude/settings.local.json",
+    ".claude/skills",
+    ".claude/commands",
+    ".claude/agents",
+    ".cursor/rules",
+    ".windsurf/rules",
+    "CLAUDE.local.md",
+    ".ai-cli",
+];
+
+/// A git worktree of the current repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct Worktree {
+    pub path: PathBuf,
+    /// Checked out branch, `None` when detached
+    pub branch: Option<String>,
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let status = logging::run(Command::new("git").current_dir(dir).args(args))
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !status.success() {
+        bail!("git {} failed", args.join(" "));
+    }
+    Ok(())
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!("git {} failed", args.join(" "));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Root of the repository containing the current directory
+fn repo_root() -> Result<PathBuf> {
+    let cwd = env::current_dir().context("Failed to get current directory")?;
+    let root = git_output(&cwd, &["rev-parse", "--show-toplevel"])
+        .map_err(|_| exit::usage("Not inside a git repository"))?;
+    Ok(PathBuf::from(root.trim()))
+}
+
+/// Parse `git worktree list --porcelain`
+fn parse_worktrees(output: &str) -> Vec<Worktree> {
+    output
+        .split("\n\n")
+        .filter_map(|block| {
+            let path = block.lines().find_map(|l| l.strip_prefix("worktree "))?;
+            let branch = block
+                .lines()
+                .find_map(|l| l.strip_prefix("branch "))
+                .map(|b| b.trim_start_matches("refs/heads/").to_string());
+            Some(Worktree {
+                path: PathBuf::from(path),
+                branch,
+            })
+        })
+        .collect()
+}
+
+fn worktrees(root: &Path) -> Result<Vec<Worktree>> {
+    Ok(parse_worktrees(&git_output(
+        root,
+        &["worktree", "list", "--porcelain"],
+    )?))
+}
+
+/// Default location: a sibling of the repository named `<repo>-<branch>`
+fn default_path(root: &Path, branch: &str) -> PathBuf {
+    let repo = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "workspace".to_string());
+    let name = format!("{}-{}", repo, branch.replace('/', "-"));
+    root.parent().unwrap_or(root).join(name)
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)
+            .with_context(|| format!("Failed to create directory {}", to.display()))?;
+        for entry in fs::read_dir(from)
+            .with_context(|| format!("Failed to read {}", from.display()))?
+            .flatten()
+        {
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::copy(from, to)
+        .map(|_| ())
+        .with_context(|| format!("Failed to copy {}", from.display()))
+}
+
+/// Copy MCP configs, skills, settings and instruction files that exist in
+/// `root` but not in `workspace` (untracked files git didn't check out)
+///
+/// Returns the copied paths, relative to the roots.
+pub fn copy_agent_files(root: &Path, workspace: &Path) -> Result<Vec<PathBuf>> {
+    let instructions = Scope::Project(root.to_path_buf());
+    let mut files: Vec<PathBuf> = AGENT_FILES.iter().map(PathBuf::from).collect();
+    files.extend(
+        instructions
+            .targets()
+            .into_iter()
+            .filter_map(|t| t.path.strip_prefix(root).ok().map(Path::to_path_buf)),
+    );
+
+    let mut copied = Vec::new();
+    for relative in files {
+        let source = root.join(&relative);
+        let target = workspace.join(&relative);
+        if !source.exists() || target.exists() {
+            continue;
+        }
+        copy_recursive(&source, &target)?;
+        copied.push(relative);
+    }
+    Ok(copied)
+}
+
+/// Handle `workspace new <branch>` command
+pub fn handle_new(branch: &str, path: Option<&Path>, base: Option<&str>) -> Result<Outcome> {
+    let root = repo_root()?;
+    let path = path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_path(&root, branch));
+    if path.exists() {
+        return Err(exit::usage(format!("{} already exists", path.display())));
+    }
+
+    let path_arg = path.to_string_lossy().to_string();
+    let branch_exists = git_output(
+        &root,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch),
+        ],
+    )
+    .is_ok();
+    if branch_exists {
+        git(&root, &["worktree", "add", &path_arg, branch])?;
+    } else {
+        let mut args = vec!["worktree", "add", "-b", branch, &path_arg];
+        args.extend(base);
+        git(&root, &args)?;
+    }
+    println!(
+        "{} Created worktree for {} at {}",
+        "✓".success(),
+        branch.bold(),
+        path.display()
+    );
+
+    let copied = copy_agent_files(&root, &path)?;
+    for relative in &copied {
+        println!("  {} {}", "+".success(), relative.display());
+    }
+    if copied.is_empty() {
+        println!("  {}", "(no untracked agent files to copy)".muted());
+    }
+    println!();
+    println!(
+        "{} cd {} and start an agent there",
+        "→".info(),
+        path.display()
+    );
+    Ok(Outcome::Success)
+}
+
+/// Handle `workspace list` command
+pub fn handle_list() -> Result<()> {
+    let root = repo_root()?;
+    for worktree in worktrees(&root)? {
+        let branch = worktree.branch.as_deref().unwrap_or("(detached)");
+        let marker = if worktree.path == root {
+            "*".accent()
+        } else {
+            " ".normal()
+        };
+        println!(
+            "{} {:<30} {}",
+            marker,
+            branch,
+            worktree.path.display().to_string().muted()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `workspace remove <branch>` command
+pub fn handle_remove(name: &str, force: bool) -> Result<Outcome> {
+    let root = repo_root()?;
+    let worktree = worktrees(&root)?
+        .into_iter()
+        .filter(|w| w.path != root)
+        .find(|w| w.branch.as_deref() == Some(name) || w.path == Path::new(name))
+        .ok_or_else(|| exit::usage(format!("No workspace for '{}'", name)))?;
+
+    let path_arg = worktree.path.to_string_lossy().to_string();
+    let mut args = vec!["worktree", "remove", &path_arg];
+    if force {
+        args.push("--force");
+    }
+    git(&root, &args)?;
+    println!(
+        "{} Removed {} (the branch is kept)",
+        "✓".success(),
+        worktree.path.display()
+    );
+    Ok(Outcome::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_porcelain_worktree_list() {
+        let output = "worktree /src/app\nHEAD 1234\nbranch refs/heads/main\n\n\
+                      worktree /src/app-feature-x\nHEAD 5678\nbranch refs/heads/feature/x\n\n\
+                      worktree /src/app-review\nHEAD 9abc\ndetached\n";
+        let worktrees = parse_worktrees(output);
+        assert_eq!(worktrees.len(), 3);
+        assert_eq!(worktrees[1].branch.as_deref(), Some("feature/x"));
+        assert_eq!(worktrees[2].branch, None);
+        assert_eq!(
+            default_path(Path::new("/src/app"), "feature/x"),
+            PathBuf::from("/src/app-feature-x")
+        );
+    }
+
+    #[test]
+    fn copies_only_missing_agent_files() {
+        let root = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        fs::write(root.path().join(".mcp.json"), "{}").unwrap();
+        fs::create_dir_all(root.path().join(".claude/skills/pdf")).unwrap();
+        fs::write(root.path().join(".claude/skills/pdf/SKILL.md"), "# pdf").unwrap();
+        fs::write(root.path().join("CLAUDE.md"), "root").unwrap();
+        fs::write(workspace.path().join("CLAUDE.md"), "tracked").unwrap();
+
+        let copied = copy_agent_files(root.path(), workspace.path()).unwrap();
+        assert_eq!(
+            copied,
+            vec![PathBuf::from(".mcp.json"), PathBuf::from(".claude/skills")]
+        );
+        assert!(
+            workspace
+                .path()
+                .join(".claude/skills/pdf/SKILL.md")
+                .exists()
+        );
+        assert_eq!(
+            fs::read_to_string(workspace.path().join("CLAUDE.md")).unwrap(),
+            "tracked"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use ai_cli::mcp::{servers, targets};
+use ai_cli::{config, paths};
+
+#[test]
+fn config_root_sandboxes_managed_paths() {
+    let root = tempfile::tempdir().unwrap();
+    paths::set_config_root(Some(root.path().to_path_buf())).unwrap();
+
+    for target in targets::catalog() {
+        assert!(
+            target.config_path().starts_with(root.path()),
+            "{} config escapes the config root",
+            target.name
+        );
+    }
+    assert!(config::config_path().starts_with(root.path()));
+
+    let claude = targets::catalog()
+        .into_iter()
+        .find(|t| t.name == "Claude Code")
+        .unwrap();
+    let linear = servers::find("linear").unwrap();
+    claude.enable_server(&linear).unwrap();
+
+    let written = std::fs::read_to_string(root.path().join(".claude.json")).unwrap();
+    assert!(written.contains("\"linear\""));
+    assert!(claude.is_server_enabled(&linear).unwrap());
+}
@@ -0,0 +1,22 @@
+use std::process::Command;
+
+#[test]
+fn global_flags_alone_print_help() {
+    let home = tempfile::tempdir().unwrap();
+    for flags in [
+        &["--yes"][..],
+        &["--plain"],
+        &["--config-root", home.path().to_str().unwrap()],
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_ai-cli"))
+            .args(flags)
+            .env("HOME", home.path())
+            .env("AI_CLI_NO_UPDATE_CHECK", "1")
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(2), "{:?}: {}", flags, stderr);
+        assert!(stderr.contains("Usage:"), "{:?}: {}", flags, stderr);
+        assert!(!stderr.contains("panicked"), "{:?}: {}", flags, stderr);
+    }
+}